@@ -0,0 +1,47 @@
+//! Connecting to the service: the credentials, the access token and its refresh.
+//!
+//! ```sh
+//! cargo run --example auth --features fixtures
+//! ```
+mod harness;
+
+use toornament::*;
+
+fn auth(service: &harness::MockService) -> Result<()> {
+    // The client asks for an access token with the application's credentials, read here from
+    // the `TOORNAMENT_*` environment variables, as soon as it is created.
+    let toornament = service.client()?;
+    let token = toornament.token_info()?;
+    println!("Scopes of the token: {:?}", token.scopes);
+    println!(
+        "The token expires in {} seconds",
+        token.expires_in().num_seconds()
+    );
+
+    // The account the credentials belong to.
+    let account = toornament.me()?;
+    println!("Signed in as {} ({})", account.name, account.id.0);
+
+    // The token is refreshed when it expires, or on demand.
+    assert!(toornament.refresh());
+
+    // Checking that the service answers.
+    let ping = toornament.ping()?;
+    println!("The service answered in {:?}", ping.latency);
+
+    // Rotating the application's keys: the new ones replace the old ones once they are used
+    // to get a new token.
+    toornament.rotate_credentials("NEW_CLIENT_ID", "NEW_CLIENT_SECRET")?;
+    println!("Credentials rotated");
+    Ok(())
+}
+
+fn main() {
+    let service = harness::MockService::start();
+    let outcome = auth(&service);
+    service.print_requests();
+    if let Err(e) = outcome {
+        eprintln!("The example failed: {:?}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,377 @@
+//! The harness of the examples: a mocked service, listening on the local host, answers the
+//! requests of the examples with the payloads of `toornament::fixtures`, so that the examples
+//! run offline, without an account of the service.
+//!
+//! The mocked service answers:
+//!
+//! * a list with its fixture, split into `PAGES` pages by the `page` parameter and a
+//!   `Content-Range`;
+//! * a single resource with its fixture, whose `id` is the one of the address;
+//! * a write with the fixture of the resource, updated with the fields sent;
+//! * a deletion or a sent invitation with no content;
+//! * a request for an access token with a `401` if its client id was revoked.
+//!
+//! A test can replace the fixture of an address with `MockService::replace`, to answer a
+//! tournament in another status for example.
+#![allow(dead_code)]
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+
+use serde_json::Value;
+use toornament::*;
+
+/// Number of pages of every list of the mocked service.
+pub const PAGES: usize = 3;
+
+/// The access token returned by the mocked service.
+const TOKEN: &str = r#"{
+    "access_token": "ACCESS_TOKEN",
+    "expires_in": 3600,
+    "token_type": "bearer",
+    "scope": "organizer:view organizer:result organizer:participant organizer:permission"
+}"#;
+
+/// A response of the mocked service.
+struct Response {
+    status: u16,
+    content_range: Option<String>,
+    body: String,
+}
+impl Response {
+    fn json(value: &Value) -> Response {
+        Response {
+            status: 200,
+            content_range: None,
+            body: value.to_string(),
+        }
+    }
+
+    fn empty(status: u16) -> Response {
+        Response {
+            status,
+            content_range: None,
+            body: String::new(),
+        }
+    }
+}
+
+/// The mocked service, answering the requests until the example exits.
+pub struct MockService {
+    address: SocketAddr,
+    requests: Arc<Mutex<Vec<Request>>>,
+    revoked: Arc<Mutex<Vec<String>>>,
+    replaced: Arc<Mutex<Vec<(String, String)>>>,
+}
+
+/// A request received by the mocked service.
+struct Request {
+    /// The method and the target, like "GET /v1/tournaments".
+    line: String,
+    /// The headers, by lowercase name.
+    headers: Vec<(String, String)>,
+    /// The body.
+    body: String,
+}
+impl MockService {
+    /// Starts the mocked service on a free port of the local host.
+    pub fn start() -> MockService {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Could not start the service");
+        let address = listener.local_addr().expect("Could not start the service");
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let revoked = Arc::new(Mutex::new(Vec::new()));
+        let replaced = Arc::new(Mutex::new(Vec::new()));
+        let (log, refused, fixtures) = (requests.clone(), revoked.clone(), replaced.clone());
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let (log, refused, fixtures) = (log.clone(), refused.clone(), fixtures.clone());
+                std::thread::spawn(move || serve(stream, &log, &refused, &fixtures));
+            }
+        });
+        MockService {
+            address,
+            requests,
+            revoked,
+            replaced,
+        }
+    }
+
+    /// Answers the requests to the `path`, like "/v1/tournaments/1", with the `fixture` from
+    /// now on instead of the one of `toornament::fixtures`.
+    pub fn replace(&self, path: &str, fixture: &str) {
+        if let Ok(mut replaced) = self.replaced.lock() {
+            replaced.push((path.to_owned(), fixture.to_owned()));
+        }
+    }
+
+    /// Refuses the requests for an access token with the client id from now on.
+    pub fn revoke(&self, client_id: &str) {
+        if let Ok(mut revoked) = self.revoked.lock() {
+            revoked.push(client_id.to_owned());
+        }
+    }
+
+    /// Returns the base address of the mocked service.
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// Creates a client of the mocked service, reading the credentials like the command line
+    /// client: from the `TOORNAMENT_*` environment variables.
+    pub fn client(&self) -> Result<Toornament> {
+        // The services of the tests running at the same time set the variables in turn.
+        static ENVIRONMENT: Mutex<()> = Mutex::new(());
+        let _environment = ENVIRONMENT.lock().unwrap_or_else(|e| e.into_inner());
+        std::env::set_var("TOORNAMENT_API_TOKEN", "API_TOKEN");
+        std::env::set_var("TOORNAMENT_CLIENT_ID", "CLIENT_ID");
+        std::env::set_var("TOORNAMENT_CLIENT_SECRET", "CLIENT_SECRET");
+        std::env::set_var("TOORNAMENT_BASE_URL", self.base_url());
+        Toornament::from_env()
+    }
+
+    /// Returns the requests received so far, like "GET /v1/tournaments".
+    pub fn requests(&self) -> Vec<String> {
+        self.requests
+            .lock()
+            .map(|r| r.iter().map(|request| request.line.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the requests received so far with their value of the header `name`, if any.
+    pub fn requests_with_header(&self, name: &str) -> Vec<(String, Option<String>)> {
+        let name = name.to_ascii_lowercase();
+        self.requests
+            .lock()
+            .map(|r| {
+                r.iter()
+                    .map(|request| {
+                        let value = request
+                            .headers
+                            .iter()
+                            .find(|(n, _)| *n == name)
+                            .map(|(_, v)| v.clone());
+                        (request.line.clone(), value)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the requests received so far with their bodies.
+    pub fn requests_with_body(&self) -> Vec<(String, String)> {
+        self.requests
+            .lock()
+            .map(|r| {
+                r.iter()
+                    .map(|request| (request.line.clone(), request.body.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Prints the requests received so far.
+    pub fn print_requests(&self) {
+        println!("\nRequests to the service:");
+        for request in self.requests() {
+            println!("    {}", request);
+        }
+    }
+}
+
+/// Runs the `example` with a client of a new mocked service, then prints the requests it made.
+/// Exits with an error status if the example failed.
+pub fn run(example: fn(&Toornament) -> Result<()>) {
+    let service = MockService::start();
+    let outcome = service.client().and_then(|toornament| example(&toornament));
+    service.print_requests();
+    if let Err(e) = outcome {
+        eprintln!("The example failed: {:?}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Reads the requests from the `stream` and writes their responses, keeping the connection
+/// open until the client closes it.
+fn serve(
+    stream: TcpStream,
+    log: &Mutex<Vec<Request>>,
+    revoked: &Mutex<Vec<String>>,
+    replaced: &Mutex<Vec<(String, String)>>,
+) {
+    let mut reader = BufReader::new(match stream.try_clone() {
+        Ok(stream) => stream,
+        Err(_) => return,
+    });
+    let mut stream = stream;
+    while serve_one(&mut reader, &mut stream, log, revoked, replaced) {}
+}
+
+/// Reads a request and writes the response. Returns `false` once the connection is closed.
+fn serve_one(
+    reader: &mut BufReader<TcpStream>,
+    stream: &mut TcpStream,
+    log: &Mutex<Vec<Request>>,
+    revoked: &Mutex<Vec<String>>,
+    replaced: &Mutex<Vec<(String, String)>>,
+) -> bool {
+    let mut line = String::new();
+    if reader.read_line(&mut line).unwrap_or(0) == 0 {
+        return false;
+    }
+    let mut words = line.split_whitespace();
+    let (method, target) = match (words.next(), words.next()) {
+        (Some(method), Some(target)) => (method.to_owned(), target.to_owned()),
+        _ => return false,
+    };
+    let mut length = 0;
+    let mut headers = Vec::new();
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).is_err() || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().unwrap_or(0);
+            }
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_owned()));
+        }
+    }
+    let mut body = vec![0; length];
+    if reader.read_exact(&mut body).is_err() {
+        return false;
+    }
+    let body = String::from_utf8_lossy(&body);
+    if let Ok(mut requests) = log.lock() {
+        requests.push(Request {
+            line: format!("{} {}", method, target),
+            headers,
+            body: body.clone().into_owned(),
+        });
+    }
+
+    let refused = revoked.lock().map(|revoked| {
+        revoked.iter().any(|id| {
+            body.split('&')
+                .any(|field| field == format!("client_id={}", id))
+        })
+    });
+    let path = target.split('?').next().unwrap_or_default();
+    let fixture = replaced.lock().ok().and_then(|replaced| {
+        replaced
+            .iter()
+            .rev()
+            .find(|(p, _)| p == path)
+            .map(|(_, fixture)| fixture.clone())
+    });
+    let response = if target == "/oauth/v2/token" && refused.unwrap_or(false) {
+        Response::empty(401)
+    } else {
+        answer(&method, &target, &body, fixture)
+    };
+    let mut head = format!(
+        "HTTP/1.1 {} Mocked\r\nContent-Type: application/json\r\nContent-Length: {}\r\n",
+        response.status,
+        response.body.len()
+    );
+    if let Some(range) = response.content_range {
+        head.push_str(&format!("Content-Range: {}\r\n", range));
+    }
+    write!(stream, "{}\r\n{}", head, response.body).is_ok()
+}
+
+/// Returns the response of the mocked service to a request.
+fn answer(method: &str, target: &str, body: &str, replaced: Option<String>) -> Response {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    if path == "/oauth/v2/token" {
+        return Response::json(&serde_json::from_str(TOKEN).unwrap());
+    }
+    if method == "DELETE" {
+        return Response::empty(204);
+    }
+    let segments = path
+        .trim_start_matches("/v1/")
+        .split('/')
+        .collect::<Vec<&str>>();
+    let first = |list: &str| serde_json::from_str::<Value>(list).unwrap()[0].to_string();
+    let fixture = match segments.as_slice() {
+        _ if replaced.is_some() => replaced.unwrap_or_default(),
+        [.., "result"] => "{}".to_owned(),
+        [.., "invitation"] => return Response::empty(204),
+        ["me"] => fixtures::ACCOUNT.to_owned(),
+        ["disciplines"] => fixtures::DISCIPLINES.to_owned(),
+        ["disciplines", _] => fixtures::DISCIPLINE.to_owned(),
+        ["tournaments"] | ["me", "tournaments"] => fixtures::TOURNAMENTS.to_owned(),
+        ["tournaments", _] => fixtures::TOURNAMENT.to_owned(),
+        ["tournaments", _, "settings"] => fixtures::TOURNAMENT_SETTINGS.to_owned(),
+        [.., "matches"] => fixtures::MATCHES.to_owned(),
+        [.., "matches", _] => fixtures::MATCH.to_owned(),
+        [.., "games"] => fixtures::GAMES.to_owned(),
+        [.., "games", _] => first(fixtures::GAMES),
+        [.., "participants"] => fixtures::PARTICIPANTS.to_owned(),
+        [.., "participants", _] => fixtures::PARTICIPANT.to_owned(),
+        [.., "permissions"] => fixtures::PERMISSIONS.to_owned(),
+        [.., "permissions", _] => first(fixtures::PERMISSIONS),
+        [.., "stages"] => fixtures::STAGES.to_owned(),
+        [.., "custom-fields"] => fixtures::CUSTOM_FIELDS.to_owned(),
+        [.., "videos"] => fixtures::VIDEOS.to_owned(),
+        [.., "final-standings"] => fixtures::FINAL_STANDINGS.to_owned(),
+        _ => return Response::empty(404),
+    };
+    let fixture: Value = serde_json::from_str(&fixture).unwrap();
+    let sent: Option<Value> = serde_json::from_str(body).ok();
+
+    match (fixture, method) {
+        (Value::Array(items), "GET") => page(items, query),
+        // A new resource of a list.
+        (Value::Array(items), "POST") => {
+            let mut created = items.into_iter().next().unwrap_or_default();
+            update(&mut created, sent);
+            Response::json(&created)
+        }
+        // A replaced list.
+        (Value::Array(items), _) => Response::json(&sent.unwrap_or(Value::Array(items))),
+        (mut resource, _) => {
+            if let (true, Some(id), Some(Value::String(_))) = (
+                segments.len() > 1,
+                segments.last(),
+                resource.get("id").cloned(),
+            ) {
+                resource["id"] = Value::String((*id).to_owned());
+            }
+            update(&mut resource, sent);
+            Response::json(&resource)
+        }
+    }
+}
+
+/// Returns the page of the list asked for by the `page` parameter of the `query`.
+fn page(items: Vec<Value>, query: &str) -> Response {
+    let number = query
+        .split('&')
+        .filter_map(|p| p.strip_prefix("page="))
+        .find_map(|p| p.parse::<usize>().ok())
+        .unwrap_or(1);
+    if number == 0 || number > PAGES || items.is_empty() {
+        return Response::json(&Value::Array(Vec::new()));
+    }
+    let start = (number - 1) * items.len();
+    let range = format!(
+        "items {}-{}/{}",
+        start,
+        start + items.len() - 1,
+        items.len() * PAGES
+    );
+    Response {
+        content_range: Some(range),
+        ..Response::json(&Value::Array(items))
+    }
+}
+
+/// Writes the fields `sent` by the client into the `resource`.
+fn update(resource: &mut Value, sent: Option<Value>) {
+    if let (Value::Object(resource), Some(Value::Object(sent))) = (resource, sent) {
+        resource.extend(sent);
+    }
+}
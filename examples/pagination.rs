@@ -0,0 +1,36 @@
+//! Fetching the large collections page by page, one at a time or concurrently.
+//!
+//! ```sh
+//! cargo run --example pagination --features fixtures
+//! ```
+mod harness;
+
+use toornament::*;
+
+fn pagination(toornament: &Toornament) -> Result<()> {
+    let tournament_id = TournamentId("5608fd12140ba061298b4569".to_owned());
+
+    // A page tells where it is in the collection.
+    let pager = toornament
+        .tournament_participants_pager(tournament_id, TournamentParticipantsFilter::default());
+    println!("The second page: {:?}", pager.page(2)?.paging());
+
+    // A pager fetches the pages until the last one.
+    let first = pager.page(1)?;
+    println!("{} pages of participants", first.pages().unwrap_or(1));
+    let all = pager.fetch_all()?;
+    println!("{} participants fetched one page at a time", all.len());
+
+    // Once the total is known, the remaining pages may be fetched concurrently.
+    let parallel = pager.fetch_all_parallel(4)?;
+    assert_eq!(parallel, all);
+    println!(
+        "{} participants fetched four pages at a time",
+        parallel.len()
+    );
+    Ok(())
+}
+
+fn main() {
+    harness::run(pagination);
+}
@@ -0,0 +1,62 @@
+//! Sharing the organization of a tournament: the permissions of the other organizers.
+//!
+//! ```sh
+//! cargo run --example permissions --features fixtures
+//! ```
+mod harness;
+
+use toornament::*;
+
+fn permissions(toornament: &Toornament) -> Result<()> {
+    let tournament_id = TournamentId("5608fd12140ba061298b4569".to_owned());
+
+    let permissions = toornament.tournament_permissions(tournament_id.clone())?;
+    for permission in &permissions.0 {
+        println!(
+            "{} may {:?} ({:?})",
+            permission.email, permission.attributes.0, permission.status
+        );
+    }
+    // Reminding the organizers who have not accepted their invitation yet.
+    for permission in permissions.pending() {
+        if let Some(ref permission_id) = permission.id {
+            toornament
+                .resend_permission_invitation(tournament_id.clone(), permission_id.clone())?;
+            println!("Invitation sent again to {}", permission.email);
+        }
+    }
+
+    // A referee reporting the results.
+    let referee = Permission::create(
+        "referee@example.com",
+        PermissionAttributes([PermissionAttribute::Report].iter().cloned().collect()),
+    );
+    let referee = toornament.create_tournament_permission(tournament_id.clone(), referee)?;
+    let referee_id = referee
+        .id
+        .clone()
+        .ok_or(Error::Rest("The permission has no id"))?;
+    println!("Added {} with the id {}", referee.email, referee_id.0);
+
+    // The referee also checks the participants in.
+    let attributes = PermissionAttributes(
+        [PermissionAttribute::Report, PermissionAttribute::Register]
+            .iter()
+            .cloned()
+            .collect(),
+    );
+    let referee = toornament.update_tournament_permission_attributes(
+        tournament_id.clone(),
+        referee_id.clone(),
+        attributes,
+    )?;
+    println!("The referee may now {:?}", referee.attributes.0);
+
+    toornament.delete_tournament_permission(tournament_id, referee_id)?;
+    println!("Removed the referee");
+    Ok(())
+}
+
+fn main() {
+    harness::run(permissions);
+}
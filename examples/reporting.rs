@@ -0,0 +1,58 @@
+//! Reporting the results of the matches: one by one, or in a batch read from a score file.
+//!
+//! ```sh
+//! cargo run --example reporting --features fixtures
+//! ```
+mod harness;
+
+use toornament::*;
+
+/// The score file of the scorekeepers, see the `import` module.
+const SCORES: &str = "\
+match_id,score_1,score_2
+5617bb3af3df95f2318b4567,2,1
+5617bb3af3df95f2318b4568,0,2
+";
+
+fn reporting(toornament: &Toornament) -> Result<()> {
+    let tournament_id = TournamentId("5608fd12140ba061298b4569".to_owned());
+
+    // The result of a single match, built from the match to keep its opponents.
+    let m = toornament
+        .matches(tournament_id.clone(), None, Includes::none())?
+        .0
+        .remove(1);
+    let mut opponents = m.opponents.clone();
+    for (opponent, (score, result)) in opponents
+        .0
+        .iter_mut()
+        .zip([(2, MatchResultSimple::Win), (0, MatchResultSimple::Loss)])
+    {
+        opponent.score = Some(score);
+        opponent.result = Some(result);
+    }
+    let result = MatchResult {
+        status: MatchStatus::Completed,
+        opponents,
+    };
+    let result = toornament.set_match_result(tournament_id.clone(), m.id.clone(), result)?;
+    println!("Reported match {}: {:?}", m.id.0, result.score_line());
+
+    // The results of a round, applied in a batch which stops at the first failure.
+    let updates = import::results_from_csv(SCORES.as_bytes())?
+        .into_iter()
+        .map(|(id, result)| (id, MatchPatch::from(result)))
+        .collect();
+    let options = batch::BatchOptions::default();
+    for (id, outcome) in toornament.update_matches(tournament_id, updates, options, None) {
+        match outcome {
+            batch::UpdateOutcome::Updated(m) => println!("Reported match {}: {:?}", id.0, m.status),
+            outcome => println!("Could not report match {}: {:?}", id.0, outcome),
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    harness::run(reporting);
+}
@@ -3,7 +3,7 @@ fn list_markdown_files(dir_path: &str) -> Vec<String> {
 
     let mut out = Vec::new();
 
-    let dir = match read_dir(&dir_path) {
+    let dir = match read_dir(dir_path) {
         Ok(dir) => dir,
         Err(e) => panic!("Could not read mdbook directory: {:?}", e),
     };
@@ -0,0 +1,61 @@
+/// An account identity.
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct AccountId(pub String);
+
+/// The account the credentials of a client belong to, as returned by `Toornament::me`.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
+pub struct Account {
+    /// The account identifier.
+    /// Example: "378426939508809728"
+    pub id: AccountId,
+    /// The name of the account.
+    /// Example: "Avery Bullock"
+    pub name: String,
+    /// The email address of the account, if the credentials allow to read it.
+    /// Example: "organizer@oxent.net"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// The scopes granted to the credentials for the account.
+    /// Example: ["organizer:view", "organizer:result"]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<String>,
+}
+impl Account {
+    /// Returns `true` if the `scope`, like "organizer:result", is granted for the account.
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_account() {
+        let json = r#"{ "id": "378426939508809728", "name": "Avery Bullock" }"#;
+        let account: Account = serde_json::from_str(json).unwrap();
+        assert_eq!(account.id, AccountId("378426939508809728".to_owned()));
+        assert!(account.email.is_none());
+        assert!(!account.has_scope("organizer:view"));
+
+        let account = Account {
+            scopes: vec!["organizer:view".to_owned()],
+            ..account
+        };
+        assert!(account.has_scope("organizer:view"));
+        assert!(!account.has_scope("organizer:result"));
+    }
+}
@@ -0,0 +1,379 @@
+//! This module archives everything about a tournament into a bundle which can be stored
+//! beyond the retention of the service and restored later as a new tournament, for example in
+//! a staging environment.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use toornament::*;
+//!
+//! let toornament = Toornament::with_application("API_TOKEN",
+//!                                               "CLIENT_ID",
+//!                                               "CLIENT_SECRET").unwrap();
+//! let bundle = archive::snapshot(&toornament, TournamentId("1".to_owned()), None).unwrap();
+//! std::fs::write("tournament-1.json", serde_json::to_string(&bundle).unwrap()).unwrap();
+//!
+//! let staging = Toornament::from_env().unwrap();
+//! let restored = archive::restore(&staging, bundle, archive::RestoreOptions::default()).unwrap();
+//! println!("Restored as tournament {}, without {} videos",
+//!          restored.tournament_id.0, restored.videos.len());
+//! ```
+//!
+//! Two bundles (or snapshots) of the same tournament can be compared with `diff` to report
+//! what has changed between them:
+//!
+//! ```rust,no_run
+//! # use toornament::*;
+//! # let toornament = Toornament::from_env().unwrap();
+//! let last_week: archive::ArchiveBundle =
+//!     serde_json::from_str(&std::fs::read_to_string("tournament-1.json").unwrap()).unwrap();
+//! let now = archive::snapshot(&toornament, TournamentId("1".to_owned()), None).unwrap();
+//! for change in archive::diff(&last_week.snapshot, &now.snapshot).changes {
+//!     println!("{:?}", change);
+//! }
+//! ```
+use chrono::{DateTime, FixedOffset, Utc};
+
+use crate::endpoints::Endpoint;
+use crate::matches::{Match, MatchId, MatchStatus};
+use crate::opponents::{Opponent, Opponents};
+use crate::participants::Participant;
+use crate::permissions::Permissions;
+use crate::progress::{Progress, Steps};
+use crate::stages::Stages;
+use crate::tournaments::TournamentId;
+use crate::videos::Videos;
+use crate::{Difference, Error, Result, Toornament, TournamentSnapshot, TournamentVideosFilter};
+
+/// Version of the format of the bundles created by this version of the library.
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// Everything about a tournament at some moment.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ArchiveBundle {
+    /// Version of the format of the bundle.
+    pub version: u32,
+    /// When the bundle was created.
+    pub archived_at: DateTime<Utc>,
+    /// The tournament with its participants, stages and matches (with their games).
+    pub snapshot: TournamentSnapshot,
+    /// Permissions of the users on the tournament.
+    pub permissions: Permissions,
+    /// Videos of the tournament.
+    pub videos: Videos,
+}
+
+/// Fetches everything about a tournament into a bundle, telling the `progress`, if any, about
+/// every request.
+pub fn snapshot(
+    client: &Toornament,
+    id: TournamentId,
+    progress: Option<&dyn Progress>,
+) -> Result<ArchiveBundle> {
+    log::debug!("Archiving tournament: {:?}", id);
+    let mut steps = Steps::new(progress, Some(TournamentSnapshot::FETCH_STEPS + 2));
+    let snapshot = TournamentSnapshot::fetch(client, id.clone(), &mut steps, || ())?;
+    if snapshot.tournament.is_none() {
+        return Err(Error::Rest("The tournament was not found"));
+    }
+    steps.start(Endpoint::Permissions(id.clone()).resource());
+    let permissions = client.tournament_permissions(id.clone())?;
+    let filter = TournamentVideosFilter::default();
+    steps.start(
+        Endpoint::Videos {
+            tournament_id: id.clone(),
+            filter: filter.clone(),
+        }
+        .resource(),
+    );
+    let videos = client.tournament_videos(id, filter)?;
+    steps.finish();
+    Ok(ArchiveBundle {
+        version: ARCHIVE_VERSION,
+        archived_at: Utc::now(),
+        snapshot,
+        permissions,
+        videos,
+    })
+}
+
+/// How a bundle is restored, see `restore`.
+#[derive(Clone, Debug, Default)]
+pub struct RestoreOptions {
+    /// Whether to recreate the permissions of the bundle. The service sends an invitation to
+    /// the user of every created permission, so they are not recreated by default.
+    pub permissions: bool,
+}
+impl RestoreOptions {
+    builder!(permissions, bool);
+}
+
+/// What `restore` has recreated, and what of the bundle it has not.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Restored {
+    /// Id of the new tournament, created with the participants of the bundle.
+    pub tournament_id: TournamentId,
+    /// The permissions of the bundle which were not recreated, see
+    /// `RestoreOptions::permissions`.
+    pub permissions: Permissions,
+    /// The stages of the bundle: the API used by this crate has no endpoint to create them,
+    /// they are created by the service when the new tournament is configured.
+    pub stages: Stages,
+    /// The videos of the bundle: the API used by this crate has no endpoint to create them.
+    pub videos: Videos,
+}
+
+/// Recreates the tournament of a bundle as a new tournament with its participants, in the
+/// same way as `Toornament::undo` recreates a deleted tournament, and with its permissions if
+/// the `options` say so. The API used by this crate can't create the stages, the matches and the videos, so those
+/// of the bundle are kept for the history only: the stages and the videos are returned to be
+/// recreated by hand.
+pub fn restore(
+    client: &Toornament,
+    bundle: ArchiveBundle,
+    options: RestoreOptions,
+) -> Result<Restored> {
+    if bundle.version > ARCHIVE_VERSION {
+        return Err(Error::Rest("The archive was created by a newer version"));
+    }
+    let snapshot = bundle.snapshot;
+    let tournament = snapshot
+        .tournament
+        .ok_or(Error::Rest("The archive has no tournament"))?;
+    log::debug!("Restoring tournament: {:?}", tournament.id);
+    let tournament_id = crate::undo::recreate(client, tournament, snapshot.participants)?;
+
+    let mut permissions = bundle.permissions;
+    if options.permissions {
+        for mut permission in permissions.0.drain(..) {
+            permission.id = None;
+            let _ = client.create_tournament_permission(tournament_id.clone(), permission)?;
+        }
+    }
+    Ok(Restored {
+        tournament_id,
+        permissions,
+        stages: snapshot.stages,
+        videos: bundle.videos,
+    })
+}
+
+/// A difference between two snapshots of a tournament.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case", tag = "change")]
+pub enum Change {
+    /// A participant was added.
+    ParticipantAdded {
+        /// The added participant.
+        participant: Participant,
+    },
+    /// A participant was removed.
+    ParticipantRemoved {
+        /// The removed participant.
+        participant: Participant,
+    },
+    /// The status of a match has changed.
+    StatusChanged {
+        /// The match.
+        match_id: MatchId,
+        /// The previous status.
+        before: MatchStatus,
+        /// The new status.
+        after: MatchStatus,
+    },
+    /// The results, the scores or the forfeits of the opponents of a match have changed.
+    ResultChanged {
+        /// The match.
+        match_id: MatchId,
+        /// The previous opponents.
+        before: Opponents,
+        /// The new opponents.
+        after: Opponents,
+    },
+    /// A match was moved to another date.
+    ScheduleMoved {
+        /// The match.
+        match_id: MatchId,
+        /// The previous date.
+        before: DateTime<FixedOffset>,
+        /// The new date.
+        after: DateTime<FixedOffset>,
+    },
+}
+
+/// The differences between two snapshots of a tournament. See `diff`.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ChangeSet {
+    /// The differences: the participants first, then the matches in the order of the new
+    /// snapshot.
+    pub changes: Vec<Change>,
+}
+impl ChangeSet {
+    /// Returns whether the snapshots are the same.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Returns the opponents of a match without their participants, ordered by their numbers.
+fn results(m: &Match) -> Vec<Opponent> {
+    let mut results = m
+        .opponents
+        .0
+        .iter()
+        .map(|o| Opponent {
+            participant: None,
+            ..o.clone()
+        })
+        .collect::<Vec<_>>();
+    results.sort_by_key(|o| o.number);
+    results
+}
+
+/// Returns the differences which turn the `old` snapshot into the `new` one: the participants
+/// added and removed, and the statuses, results and dates of the matches changed. The
+/// resources are told apart as by `TournamentSnapshot::diff`. The matches only existing in one
+/// of the snapshots are not reported.
+pub fn diff(old: &TournamentSnapshot, new: &TournamentSnapshot) -> ChangeSet {
+    let diff = old.diff(new);
+    let mut changes = Vec::new();
+    for difference in diff.participants {
+        match difference {
+            Difference::Added(p) => changes.push(Change::ParticipantAdded {
+                participant: p.clone(),
+            }),
+            Difference::Removed(p) => changes.push(Change::ParticipantRemoved {
+                participant: p.clone(),
+            }),
+            Difference::Changed(..) => {}
+        }
+    }
+    for difference in diff.matches {
+        let (o, m) = match difference {
+            Difference::Changed(o, m) => (o, m),
+            _ => continue,
+        };
+        if o.status != m.status {
+            changes.push(Change::StatusChanged {
+                match_id: m.id.clone(),
+                before: o.status.clone(),
+                after: m.status.clone(),
+            });
+        }
+        if results(o) != results(m) {
+            changes.push(Change::ResultChanged {
+                match_id: m.id.clone(),
+                before: o.opponents.clone(),
+                after: m.opponents.clone(),
+            });
+        }
+        if o.date != m.date {
+            changes.push(Change::ScheduleMoved {
+                match_id: m.id.clone(),
+                before: o.date,
+                after: m.date,
+            });
+        }
+    }
+    ChangeSet { changes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::participants::{ParticipantId, Participants};
+    use crate::tournaments::Tournament;
+
+    #[test]
+    fn test_archive_bundle_serialization() {
+        let string = r#"
+        {
+            "id": "1",
+            "discipline": "my_discipline",
+            "name": "My Weekly Tournament",
+            "status": "completed",
+            "online": true,
+            "public": true,
+            "size": 16
+        }"#;
+        let tournament: Tournament = serde_json::from_str(string).unwrap();
+        let bundle = ArchiveBundle {
+            version: ARCHIVE_VERSION,
+            archived_at: Utc::now(),
+            snapshot: TournamentSnapshot {
+                tournament: Some(tournament),
+                ..Default::default()
+            },
+            permissions: Permissions(Vec::new()),
+            videos: Videos::from(Vec::new()),
+        };
+        let json = serde_json::to_string(&bundle).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ArchiveBundle>(&json).unwrap(),
+            bundle
+        );
+    }
+
+    #[test]
+    fn test_diff() {
+        let string = r#"
+        [{
+            "id": "2",
+            "type": "duel",
+            "discipline": "my_discipline",
+            "status": "pending",
+            "tournament_id": "1",
+            "number": 1,
+            "stage_number": 1,
+            "group_number": 1,
+            "round_number": 1,
+            "date": "2015-09-06T00:10:00-06:00",
+            "opponents": [
+                { "number": 1, "participant": { "name": "Evil Geniuses" }, "forfeit": false },
+                { "number": 2, "forfeit": false }
+            ]
+        }]"#;
+        let old = TournamentSnapshot {
+            participants: Participants::from(vec![
+                Participant::create("Evil Geniuses"),
+                Participant::create("Fnatic"),
+            ]),
+            matches: serde_json::from_str(string).unwrap(),
+            ..Default::default()
+        };
+        assert!(diff(&old, &old).is_empty());
+
+        let mut new = old.clone();
+        new.participants.0[1] = Participant::create("Natus Vincere");
+        let m = &mut new.matches.0[0];
+        m.status = MatchStatus::Completed;
+        m.opponents.0[0].score = Some(2);
+        m.opponents.0[0].participant = None;
+        m.date += chrono::Duration::hours(1);
+        let changes = diff(&old, &new).changes;
+        assert_eq!(changes.len(), 5);
+        assert_eq!(
+            changes[0],
+            Change::ParticipantAdded {
+                participant: Participant::create("Natus Vincere")
+            }
+        );
+        assert_eq!(
+            changes[1],
+            Change::ParticipantRemoved {
+                participant: Participant::create("Fnatic")
+            }
+        );
+        assert!(matches!(changes[2], Change::StatusChanged { .. }));
+        assert!(matches!(changes[3], Change::ResultChanged { .. }));
+        assert!(matches!(changes[4], Change::ScheduleMoved { .. }));
+
+        // A participant with an id is the same one after a rename, as for the watcher.
+        let id = ParticipantId("1".to_owned());
+        let mut named = old.clone();
+        named.participants.0[0] = Participant::create("Evil Geniuses").id(id.clone());
+        let mut renamed = named.clone();
+        renamed.participants.0[0] = Participant::create("EG").id(id);
+        assert!(diff(&named, &renamed).is_empty());
+    }
+}
@@ -0,0 +1,396 @@
+//! This module contains the asynchronous client, `AsyncToornament`, for the applications
+//! running on an asynchronous runtime. It covers the reading methods of the tournaments, the
+//! matches, the participants and the stages.
+//!
+//! It shares with the blocking `Toornament` the description of the requests, the retry
+//! policy, the circuit breaker, the access token store and the parsing of the responses. The
+//! other features of the blocking client are not applied by it: the audit, the confirmation
+//! of destructive operations, the write policy, the metrics, the lenient parsing and the
+//! detection of schema drift. The refreshes of an expired token are not merged either: each
+//! task finding the token expired gets a new one.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use toornament::*;
+//! use toornament::asynchronous::AsyncToornament;
+//!
+//! async fn print_matches() -> Result<()> {
+//!     let toornament = AsyncToornament::with_application("API_TOKEN",
+//!                                                        "CLIENT_ID",
+//!                                                        "CLIENT_SECRET").await?;
+//!     let matches = toornament
+//!         .matches(TournamentId("1".to_owned()), None, Include::Games)
+//!         .await?;
+//!     println!("{} matches", matches.0.len());
+//!     Ok(())
+//! }
+//! ```
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Instant;
+
+use serde::de::DeserializeOwned;
+
+use crate::endpoints::{Endpoint, API_BASE};
+use crate::protocol::{self, Attempt, Decoder};
+use crate::*;
+
+/// The asynchronous client. Cloning is cheap: the clones share the connection pool and the
+/// access token.
+#[derive(Debug, Clone)]
+pub struct AsyncToornament {
+    client: reqwest::Client,
+    api_token: Secret,
+    credentials: Arc<(String, Secret)>,
+    scopes: Arc<BTreeSet<Scope>>,
+    base_url: String,
+    retry: RetryPolicy,
+    timeouts: Timeouts,
+    options: Arc<RequestOptions>,
+    breaker: Option<Arc<breaker::Breaker>>,
+    tokens: Arc<TokenStore>,
+}
+impl AsyncToornament {
+    /// Creates new `AsyncToornament` object with client credentials, like
+    /// `Toornament::with_application`, and gets the access token.
+    pub async fn with_application<S: Into<String>>(
+        api_token: S,
+        client_id: S,
+        client_secret: S,
+    ) -> Result<AsyncToornament> {
+        AsyncToornament::with_application_scopes(api_token, client_id, client_secret, Vec::new())
+            .await
+    }
+
+    /// Creates new `AsyncToornament` object with client credentials whose access token is
+    /// granted the `scopes` only, like `Toornament::with_application_scopes`, and gets the
+    /// access token.
+    pub async fn with_application_scopes<S: Into<String>, I: IntoIterator<Item = Scope>>(
+        api_token: S,
+        client_id: S,
+        client_secret: S,
+        scopes: I,
+    ) -> Result<AsyncToornament> {
+        let mut toornament = AsyncToornament::with_token_store(
+            Arc::new(TokenStore::new()),
+            api_token,
+            client_id,
+            client_secret,
+        );
+        toornament.scopes = Arc::new(scopes.into_iter().collect());
+        toornament.fresh_token().await?;
+        Ok(toornament)
+    }
+
+    /// Creates new `AsyncToornament` object sharing the access token with the other clients
+    /// using the same `tokens` store, like `Toornament::with_token_store`. The token is got
+    /// by the first request if the store has no valid one.
+    pub fn with_token_store<S: Into<String>>(
+        tokens: Arc<TokenStore>,
+        api_token: S,
+        client_id: S,
+        client_secret: S,
+    ) -> AsyncToornament {
+        AsyncToornament {
+            client: reqwest::Client::new(),
+            api_token: Secret::from(api_token.into()),
+            credentials: Arc::new((client_id.into(), Secret::from(client_secret.into()))),
+            scopes: Arc::default(),
+            base_url: API_BASE.to_owned(),
+            retry: RetryPolicy::default(),
+            timeouts: Timeouts::default(),
+            options: Arc::default(),
+            breaker: None,
+            tokens,
+        }
+    }
+
+    /// Consumes `AsyncToornament` object and sets the retry policy of the requests, see
+    /// `Toornament::retry`.
+    pub fn retry(mut self, policy: RetryPolicy) -> AsyncToornament {
+        self.retry = policy;
+        self
+    }
+
+    /// Consumes `AsyncToornament` object and sets the timeouts of its requests, see
+    /// `Toornament::timeouts`.
+    pub fn timeouts(mut self, timeouts: Timeouts) -> Result<AsyncToornament> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(timeout) = timeouts.connect_timeout() {
+            builder = builder.connect_timeout(timeout);
+        }
+        self.client = builder.build()?;
+        self.timeouts = timeouts;
+        Ok(self)
+    }
+
+    /// Consumes `AsyncToornament` object and sets the `User-Agent` of its requests, see
+    /// `Toornament::user_agent`.
+    pub fn user_agent<S: Into<String>>(self, user_agent: S) -> Result<AsyncToornament> {
+        self.header("User-Agent", user_agent)
+    }
+
+    /// Consumes `AsyncToornament` object and adds the header to all its requests, see
+    /// `Toornament::header`. The access tokens are requested by the requests needing them, so
+    /// the first one is requested with the header too.
+    pub fn header<N: Into<String>, V: Into<String>>(
+        self,
+        name: N,
+        value: V,
+    ) -> Result<AsyncToornament> {
+        self.with_request_options(RequestOptions::new().header(name, value))
+    }
+
+    /// Consumes `AsyncToornament` object and sets the language of the translated labels of the
+    /// responses, see `Toornament::locale`.
+    pub fn locale<S: Into<String>>(self, locale: S) -> Result<AsyncToornament> {
+        self.with_request_options(RequestOptions::new().locale(locale))
+    }
+
+    /// Returns a clone of the client adding the headers and the query parameters of the
+    /// `options` to its requests, see `Toornament::with_request_options`.
+    pub fn with_request_options(&self, options: RequestOptions) -> Result<AsyncToornament> {
+        protocol::validate(&options)?;
+        let mut client = self.clone();
+        client.options = Arc::new(self.options.merge(&options));
+        Ok(client)
+    }
+
+    /// Consumes `AsyncToornament` object and sets the circuit breaker of the requests, see
+    /// `Toornament::circuit_breaker`.
+    pub fn circuit_breaker(mut self, policy: CircuitBreakerPolicy) -> AsyncToornament {
+        self.breaker = Some(Arc::new(breaker::Breaker::new(policy)));
+        self
+    }
+
+    /// Returns a valid access token, getting a new one if the stored token is expired.
+    async fn fresh_token(&self) -> Result<String> {
+        {
+            let g = self.tokens.lock()?;
+            if !protocol::expired(&g) {
+                return Ok(g.access_token.expose().to_owned());
+            }
+        }
+        let (client_id, client_secret) = &*self.credentials;
+        let (address, form) = protocol::token_request(
+            &self.base_url,
+            client_id,
+            client_secret.expose(),
+            &self.scopes,
+        );
+        // The token request goes through the circuit breaker like the other requests.
+        let permit = match self.breaker {
+            Some(ref breaker) => Some(breaker.allow(Instant::now())?),
+            None => None,
+        };
+        let result = async {
+            let mut builder = self.client.post(address).form(&form);
+            for (name, value) in &self.options.headers {
+                builder = builder.header(name.as_str(), value.as_str());
+            }
+            let response = builder.send().await?;
+            let status = response.status();
+            let content_type = protocol::content_type(response.headers()).map(str::to_owned);
+            let body = response.bytes().await?;
+            protocol::check(status, content_type.as_deref(), &body)?;
+            parse_token(&body[..])
+        }
+        .await;
+        if let Some(permit) = permit {
+            permit.record(Attempt::of(&result).failure(), Instant::now());
+        }
+        let token = result?;
+        let access_token = token.access_token.expose().to_owned();
+        *self.tokens.lock()? = token;
+        Ok(access_token)
+    }
+
+    /// Sends the GET request to the endpoint, retrying it according to the retry policy, and
+    /// parses the response.
+    async fn get<T: DeserializeOwned>(&self, endpoint: &Endpoint) -> Result<T> {
+        Ok(self.get_ranged(endpoint).await?.0)
+    }
+
+    async fn get_ranged<T: DeserializeOwned>(
+        &self,
+        endpoint: &Endpoint,
+    ) -> Result<(T, Option<ContentRange>)> {
+        let request = protocol::Request::new("get", endpoint, &self.base_url, None);
+        let context = request.context.clone();
+        endpoint
+            .validate()
+            .map_err(|e| e.context(context.clone()))?;
+        self.send(request).await.map_err(|e| e.context(context))
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        request: protocol::Request,
+    ) -> Result<(T, Option<ContentRange>)> {
+        let deadline = self.timeouts.total_timeout().map(|t| Instant::now() + t);
+        let mut attempt = 1;
+        let response = loop {
+            let request = request
+                .clone()
+                .authorized(&self.api_token, &self.fresh_token().await?)
+                .with_options(&self.options);
+            let mut builder = self.client.get(&request.url);
+            let timeout = protocol::attempt_timeout(
+                self.timeouts.attempt_timeout(),
+                deadline,
+                Instant::now(),
+            )?;
+            if let Some(timeout) = timeout {
+                builder = builder.timeout(timeout);
+            }
+            for (name, value) in request.headers {
+                builder = builder.header(name, value);
+            }
+            let permit = match self.breaker {
+                Some(ref breaker) => Some(breaker.allow(Instant::now())?),
+                None => None,
+            };
+            let result = builder.send().await;
+            let outcome = match result {
+                Ok(ref r) => Attempt::Status(r.status().as_u16()),
+                Err(ref e) if e.is_connect() || e.is_timeout() => Attempt::Transient,
+                Err(_) => Attempt::Failed,
+            };
+            if let Some(permit) = permit {
+                permit.record(outcome.failure(), Instant::now());
+            }
+            match protocol::retry_wait(&self.retry, "get", attempt, outcome) {
+                Some(wait) if deadline.is_none_or(|d| Instant::now() + wait < d) => {
+                    tokio::time::sleep(wait).await
+                }
+                _ => break result?,
+            }
+            attempt += 1;
+        };
+        let status = response.status();
+        let path = response.url().path().to_owned();
+        let range = response
+            .headers()
+            .get(reqwest::header::CONTENT_RANGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok());
+        let content_type = protocol::content_type(response.headers()).map(str::to_owned);
+        let body = response.bytes().await?;
+        protocol::check(status, content_type.as_deref(), &body)?;
+        Ok((Decoder::default().decode(&path, &body)?, range))
+    }
+
+    /// See `Toornament::disciplines`.
+    pub async fn disciplines(&self, id: Option<DisciplineId>) -> Result<Disciplines> {
+        match id {
+            Some(id) => Ok(Disciplines::from(vec![
+                self.get(&Endpoint::DisciplineById(id)).await?,
+            ])),
+            None => self.get(&Endpoint::AllDisciplines).await,
+        }
+    }
+
+    /// See `Toornament::tournaments`.
+    pub async fn tournaments<I: Into<Includes>>(
+        &self,
+        tournament_id: Option<TournamentId>,
+        include: I,
+    ) -> Result<Tournaments> {
+        let with_streams = include.into().contains(Include::Streams);
+        match tournament_id {
+            Some(tournament_id) => {
+                let endpoint = Endpoint::TournamentByIdGet {
+                    tournament_id,
+                    with_streams,
+                };
+                Ok(Tournaments::from(vec![self.get(&endpoint).await?]))
+            }
+            None => {
+                let endpoint = Endpoint::AllTournaments {
+                    with_streams,
+                    filter: TournamentFilter::default(),
+                };
+                self.get(&endpoint).await
+            }
+        }
+    }
+
+    /// See `Toornament::matches`.
+    pub async fn matches<I: Into<Includes>>(
+        &self,
+        tournament_id: TournamentId,
+        match_id: Option<MatchId>,
+        include: I,
+    ) -> Result<Matches> {
+        let with_games = include.into().contains(Include::Games);
+        let mut matches = match match_id {
+            Some(match_id) => {
+                let endpoint = Endpoint::MatchByIdGet {
+                    tournament_id,
+                    match_id,
+                    with_games,
+                };
+                self.get(&endpoint).await?
+            }
+            None => {
+                let endpoint = Endpoint::MatchesByTournament {
+                    tournament_id,
+                    with_games,
+                    page: None,
+                    per_page: None,
+                };
+                self.get::<Matches>(&endpoint).await?
+            }
+        };
+        matches.0 = matches.0.into_iter().map(Match::link_games).collect();
+        Ok(matches)
+    }
+
+    /// See `Toornament::tournament_participants`.
+    pub async fn tournament_participants(
+        &self,
+        tournament_id: TournamentId,
+        filter: TournamentParticipantsFilter,
+    ) -> Result<Participants> {
+        let endpoint = Endpoint::Participants {
+            tournament_id,
+            filter,
+        };
+        self.get(&endpoint).await
+    }
+
+    /// See `Toornament::tournament_stages`.
+    pub async fn tournament_stages(&self, tournament_id: TournamentId) -> Result<Stages> {
+        self.get(&Endpoint::Stages(tournament_id)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_async_request_errors_have_context() {
+        let tokens = Arc::new(TokenStore::new());
+        let json = r#"{ "access_token": "secret", "expires_in": 3600 }"#;
+        *tokens.lock().unwrap() = crate::parse_token(json.as_bytes()).unwrap();
+        let mut client =
+            AsyncToornament::with_token_store(tokens, "API_TOKEN", "CLIENT_ID", "CLIENT_SECRET");
+        // Nothing listens there, so the request fails.
+        client.base_url = crate::closed_address();
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let error = runtime
+            .block_on(client.tournament_stages(TournamentId("1".to_owned())))
+            .unwrap_err();
+        assert!(matches!(error.root(), Error::Reqwest(_)));
+        assert_eq!(
+            error.to_string(),
+            "Error while fetching the stages of tournament 1"
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! This module records the mutating calls of a client (creations, updates and deletions) into
+//! a pluggable sink, to keep a trail of the changes made by the automation.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use toornament::*;
+//! use toornament::audit::JsonLinesSink;
+//!
+//! let toornament = Toornament::with_application("API_TOKEN",
+//!                                               "CLIENT_ID",
+//!                                               "CLIENT_SECRET").unwrap()
+//!                             .audit(JsonLinesSink::create("audit.jsonl").unwrap());
+//! // Every update is now appended to `audit.jsonl` as a JSON object per line.
+//! ```
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+#[cfg(feature = "client-blocking")]
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+
+use crate::error::Result;
+
+/// Maximum length of the payload summary in characters.
+#[cfg(feature = "client-blocking")]
+const PAYLOAD_SUMMARY_LENGTH: usize = 256;
+
+/// A mutating call made by a client.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AuditRecord {
+    /// When the call was finished.
+    pub timestamp: DateTime<Utc>,
+    /// HTTP method of the call: `POST`, `PUT`, `PATCH` or `DELETE`.
+    pub method: String,
+    /// The address of the endpoint.
+    pub endpoint: String,
+    /// The beginning of the body sent, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload: Option<String>,
+    /// Status of the response, if it was received.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// The error of the call, if it failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+impl AuditRecord {
+    #[cfg(feature = "client-blocking")]
+    pub(crate) fn new(method: &str, endpoint: String, payload: Option<&str>) -> AuditRecord {
+        AuditRecord {
+            timestamp: Utc::now(),
+            method: method.to_uppercase(),
+            endpoint,
+            payload: payload.map(|p| {
+                if p.chars().count() > PAYLOAD_SUMMARY_LENGTH {
+                    format!(
+                        "{}...",
+                        p.chars().take(PAYLOAD_SUMMARY_LENGTH).collect::<String>()
+                    )
+                } else {
+                    p.to_owned()
+                }
+            }),
+            status: None,
+            error: None,
+        }
+    }
+
+    /// Returns whether the call succeeded.
+    pub fn is_success(&self) -> bool {
+        self.error.is_none() && self.status.is_some_and(|s| (200..300).contains(&s))
+    }
+}
+
+/// A destination of the audit records. The sink is shared by the clones of the client, so the
+/// records may come from several threads.
+pub trait AuditSink: Send + Sync {
+    /// Stores a record. The failures of the sink must not fail the call, so they are to be
+    /// handled (or logged) by the sink itself.
+    fn record(&self, record: &AuditRecord);
+}
+
+/// A sink appending the records to a file as a JSON object per line.
+#[derive(Debug)]
+pub struct JsonLinesSink {
+    file: Mutex<File>,
+}
+impl JsonLinesSink {
+    /// Opens the file for appending, creating it if it does not exist.
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<JsonLinesSink> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        Ok(JsonLinesSink {
+            file: Mutex::new(file),
+        })
+    }
+}
+impl AuditSink for JsonLinesSink {
+    fn record(&self, record: &AuditRecord) {
+        let line = match serde_json::to_string(record) {
+            Ok(line) => line,
+            Err(e) => return log::error!("Unable to serialize the audit record: {}", e),
+        };
+        let result = match self.file.lock() {
+            Ok(mut file) => writeln!(file, "{}", line),
+            Err(_) => return log::error!("Unable to lock the audit log"),
+        };
+        if let Err(e) = result {
+            log::error!("Unable to write the audit record: {}", e);
+        }
+    }
+}
+
+/// The sink of a client.
+#[cfg(feature = "client-blocking")]
+#[derive(Clone)]
+pub(crate) struct Auditor(pub(crate) Arc<dyn AuditSink>);
+#[cfg(feature = "client-blocking")]
+impl std::fmt::Debug for Auditor {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("Auditor")
+    }
+}
+
+#[cfg(all(test, feature = "client-blocking"))]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Memory(Mutex<Vec<AuditRecord>>);
+    impl AuditSink for Memory {
+        fn record(&self, record: &AuditRecord) {
+            self.0.lock().unwrap().push(record.clone());
+        }
+    }
+
+    #[test]
+    fn test_audit_record() {
+        let long = "x".repeat(300);
+        let mut record = AuditRecord::new("patch", "/v1/tournaments/1".to_owned(), Some(&long));
+        assert_eq!(record.method, "PATCH");
+        assert_eq!(
+            record.payload.as_ref().map(|p| p.len()),
+            Some(PAYLOAD_SUMMARY_LENGTH + 3)
+        );
+        assert!(!record.is_success());
+        record.status = Some(200);
+        assert!(record.is_success());
+
+        let sink = Memory::default();
+        sink.record(&record);
+        assert_eq!(sink.0.lock().unwrap().as_slice(), &[record]);
+    }
+
+    #[test]
+    fn test_json_lines_sink() {
+        let path = std::env::temp_dir().join("toornament_test_json_lines_sink.jsonl");
+        let _ = std::fs::remove_file(&path);
+        let sink = JsonLinesSink::create(&path).unwrap();
+        let mut record = AuditRecord::new("delete", "/v1/tournaments/1".to_owned(), None);
+        record.status = Some(204);
+        sink.record(&record);
+        sink.record(&record);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let records = content
+            .lines()
+            .map(|l| serde_json::from_str::<AuditRecord>(l).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(records, vec![record.clone(), record]);
+        assert!(!content.contains("payload"));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,239 @@
+//! This module defines how `Toornament::update_matches` applies a batch of match updates, for
+//! example when importing the scores of a whole round.
+//!
+//! The updates are sent with up to `BatchOptions::concurrency` requests at a time. When one of
+//! them fails, the batch continues, stops or rolls back the updates already applied according
+//! to `BatchOptions::on_failure`. The service has no transactions, so a rollback is only a
+//! best effort: the matches are fetched before being updated and patched back to their
+//! previous state, which may fail too.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use toornament::*;
+//! use toornament::batch::{BatchOptions, OnFailure, UpdateOutcome};
+//!
+//! let toornament = Toornament::with_application("API_TOKEN",
+//!                                               "CLIENT_ID",
+//!                                               "CLIENT_SECRET").unwrap();
+//! let updates = vec![
+//!     (MatchId("2".to_owned()), MatchPatch::default().status(MatchStatus::Completed)),
+//!     (MatchId("3".to_owned()), MatchPatch::default().status(MatchStatus::Completed)),
+//! ];
+//! let options = BatchOptions::default()
+//!     .concurrency(4)
+//!     .on_failure(OnFailure::Rollback);
+//! let outcomes = toornament.update_matches(TournamentId("1".to_owned()), updates, options, None);
+//! for (id, outcome) in outcomes {
+//!     if let UpdateOutcome::Failed(e) = outcome {
+//!         println!("Could not update match {}: {}", id.0, e);
+//!     }
+//! }
+//! ```
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::endpoints::Endpoint;
+use crate::matches::{Match, MatchId, MatchPatch};
+use crate::progress::{report, Progress};
+use crate::tournaments::TournamentId;
+use crate::{Error, Includes, Result, Toornament};
+
+/// What a batch does when an update fails.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OnFailure {
+    /// Applies the other updates anyway.
+    Continue,
+    /// Does not start the remaining updates.
+    Stop,
+    /// Does not start the remaining updates and restores the matches already updated.
+    Rollback,
+}
+
+/// How a batch of updates is applied.
+#[derive(Clone, Debug)]
+pub struct BatchOptions {
+    /// Maximum number of requests at a time.
+    pub concurrency: usize,
+    /// What to do when an update fails.
+    pub on_failure: OnFailure,
+}
+impl Default for BatchOptions {
+    fn default() -> BatchOptions {
+        BatchOptions {
+            concurrency: 1,
+            on_failure: OnFailure::Stop,
+        }
+    }
+}
+impl BatchOptions {
+    builder!(concurrency, usize);
+    builder!(on_failure, OnFailure);
+}
+
+/// What happened to an update of a batch.
+#[derive(Debug)]
+pub enum UpdateOutcome {
+    /// The match was updated.
+    Updated(Match),
+    /// The update failed.
+    Failed(Error),
+    /// The update was not started because another one failed before.
+    NotAttempted,
+    /// The match was updated, then restored because another update failed.
+    RolledBack,
+    /// The match was updated, but could not be restored after another update failed.
+    RollbackFailed(Error),
+}
+
+/// Applies the updates. See `Toornament::update_matches`.
+pub(crate) fn update_matches(
+    client: &Toornament,
+    tournament_id: TournamentId,
+    updates: Vec<(MatchId, MatchPatch)>,
+    options: BatchOptions,
+    progress: Option<&dyn Progress>,
+) -> Vec<(MatchId, UpdateOutcome)> {
+    let rollback = options.on_failure == OnFailure::Rollback;
+    let next = AtomicUsize::new(0);
+    let done = AtomicUsize::new(0);
+    let total = Some(updates.len());
+    let failed = AtomicBool::new(false);
+    let outcomes = updates
+        .iter()
+        .map(|_| Mutex::new((UpdateOutcome::NotAttempted, None)))
+        .collect::<Vec<_>>();
+
+    let apply = |(id, patch): &(MatchId, MatchPatch)| -> Result<(Match, Option<Match>)> {
+        let previous = if rollback {
+            let m = client
+                .matches(tournament_id.clone(), Some(id.clone()), Includes::none())?
+                .0
+                .pop()
+                .ok_or(Error::Rest("The match to update was not found"))?;
+            Some(m)
+        } else {
+            None
+        };
+        let updated = client.patch_match(tournament_id.clone(), id.clone(), patch)?;
+        Ok((updated, previous))
+    };
+    std::thread::scope(|scope| {
+        for _ in 0..options.concurrency.clamp(1, updates.len().max(1)) {
+            scope.spawn(|| loop {
+                if failed.load(Ordering::SeqCst) && options.on_failure != OnFailure::Continue {
+                    return;
+                }
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                let update = match updates.get(i) {
+                    Some(update) => update,
+                    None => return,
+                };
+                let endpoint = Endpoint::MatchByIdUpdate {
+                    tournament_id: tournament_id.clone(),
+                    match_id: update.0.clone(),
+                };
+                report(
+                    progress,
+                    done.load(Ordering::SeqCst),
+                    total,
+                    Some(endpoint.resource()),
+                );
+                let applied = apply(update);
+                done.fetch_add(1, Ordering::SeqCst);
+                let outcome = match applied {
+                    Ok((updated, previous)) => (UpdateOutcome::Updated(updated), previous),
+                    Err(e) => {
+                        failed.store(true, Ordering::SeqCst);
+                        (UpdateOutcome::Failed(e), None)
+                    }
+                };
+                if let Ok(mut slot) = outcomes[i].lock() {
+                    *slot = outcome;
+                }
+            });
+        }
+    });
+
+    let failed = failed.into_inner();
+    let outcomes = updates
+        .into_iter()
+        .zip(outcomes)
+        .map(|((id, _), outcome)| {
+            let (outcome, previous) = outcome
+                .into_inner()
+                .unwrap_or((UpdateOutcome::NotAttempted, None));
+            let outcome = match (outcome, previous) {
+                (UpdateOutcome::Updated(_), Some(previous)) if failed => {
+                    log::debug!("Rolling back the update of match {:?}", id);
+                    let patch = MatchPatch::restoring(&previous);
+                    match client.patch_match(tournament_id.clone(), id.clone(), &patch) {
+                        Ok(_) => UpdateOutcome::RolledBack,
+                        Err(e) => UpdateOutcome::RollbackFailed(e),
+                    }
+                }
+                (outcome, _) => outcome,
+            };
+            (id, outcome)
+        })
+        .collect();
+    report(progress, done.into_inner(), total, None);
+    outcomes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MatchStatus, TokenStore};
+
+    #[test]
+    fn test_update_matches_on_failure() {
+        let tokens = std::sync::Arc::new(TokenStore::new());
+        let json = r#"{ "access_token": "secret", "expires_in": 3600 }"#;
+        *tokens.lock().unwrap() = crate::parse_token(json.as_bytes()).unwrap();
+        let mut client =
+            Toornament::with_token_store(tokens, "API_TOKEN", "CLIENT_ID", "CLIENT_SECRET")
+                .unwrap();
+        // Nothing listens there, so every update fails.
+        client.base_url = crate::closed_address();
+
+        let updates = (1..4)
+            .map(|i| {
+                let patch = MatchPatch::default().status(MatchStatus::Completed);
+                (MatchId(i.to_string()), patch)
+            })
+            .collect::<Vec<_>>();
+        let id = TournamentId("1".to_owned());
+        let outcomes =
+            client.update_matches(id.clone(), updates.clone(), BatchOptions::default(), None);
+        assert_eq!(outcomes.len(), 3);
+        assert_eq!(outcomes[2].0, MatchId("3".to_owned()));
+        assert!(matches!(outcomes[0].1, UpdateOutcome::Failed(_)));
+        assert!(matches!(outcomes[1].1, UpdateOutcome::NotAttempted));
+        assert!(matches!(outcomes[2].1, UpdateOutcome::NotAttempted));
+
+        let options = BatchOptions::default()
+            .concurrency(2)
+            .on_failure(OnFailure::Continue);
+        let outcomes = client.update_matches(id.clone(), updates.clone(), options, None);
+        assert!(outcomes
+            .iter()
+            .all(|(_, o)| matches!(o, UpdateOutcome::Failed(_))));
+
+        let received = Mutex::new(Vec::new());
+        let progress = |update: &crate::ProgressUpdate| {
+            received.lock().unwrap().push(update.clone());
+        };
+        client.update_matches(id, updates, BatchOptions::default(), Some(&progress));
+        let progress = received.lock().unwrap();
+        assert_eq!(progress.len(), 2);
+        assert_eq!(progress[0].done, 0);
+        assert_eq!(progress[0].total, Some(3));
+        assert_eq!(
+            progress[0].endpoint.as_deref(),
+            Some("match 1 of tournament 1")
+        );
+        assert_eq!(progress[1].done, 1);
+        assert!(progress[1].endpoint.is_none());
+    }
+}
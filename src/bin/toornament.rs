@@ -0,0 +1,212 @@
+//! The command line client of the Toornament API, built on the crate with the `cli` feature.
+//!
+//! The credentials are read from the configuration file given with `--config` (see
+//! `Toornament::from_config`) or from the `TOORNAMENT_*` environment variables (see
+//! `Toornament::from_env`).
+use std::error::Error as StdError;
+use std::process;
+
+use toornament::*;
+
+const USAGE: &str = "\
+Usage: toornament [--config <file>] <command>
+
+Commands:
+    tournaments list [--name <name>] [--discipline <id>] [--featured] [--archived]
+        Lists the tournaments: their ids, statuses and names. The archived tournaments are
+        included with --archived.
+    matches report <tournament_id> <results.csv>
+        Reports the results of the matches read from a CSV file, see `toornament::import`.
+    participants import <tournament_id> <participants.json>
+        Adds the participants read from a JSON array to the tournament.
+
+The credentials are read from the configuration file or from the TOORNAMENT_API_TOKEN,
+TOORNAMENT_CLIENT_ID and TOORNAMENT_CLIENT_SECRET environment variables.";
+
+/// A command of the client with its arguments.
+#[derive(Debug)]
+enum Command {
+    ListTournaments(TournamentFilter),
+    ReportMatches(TournamentId, String),
+    ImportParticipants(TournamentId, String),
+}
+
+/// The parsed command line.
+#[derive(Debug)]
+struct Arguments {
+    config: Option<String>,
+    command: Command,
+}
+
+/// Parses the arguments following the name of the program.
+fn parse<I: IntoIterator<Item = String>>(args: I) -> std::result::Result<Arguments, String> {
+    let mut args = args.into_iter();
+    let mut config = None;
+    let mut words = Vec::new();
+    let mut filter = TournamentFilter::default();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("{} requires a value", name))
+        };
+        match arg.as_str() {
+            "--config" => config = Some(value("--config")?),
+            "--name" => filter.name = Some(value("--name")?),
+            "--discipline" => filter.discipline_id = Some(DisciplineId(value("--discipline")?)),
+            "--featured" => filter.featured = Some(true),
+            "--archived" => filter.archived = Some(true),
+            "-h" | "--help" => return Err(String::new()),
+            flag if flag.starts_with('-') => return Err(format!("unknown option {}", flag)),
+            _ => words.push(arg),
+        }
+    }
+    let words = words.iter().map(String::as_str).collect::<Vec<_>>();
+    let command = match words.as_slice() {
+        ["tournaments", "list"] => Command::ListTournaments(filter),
+        ["matches", "report", id, file] => {
+            Command::ReportMatches(TournamentId((*id).to_owned()), (*file).to_owned())
+        }
+        ["participants", "import", id, file] => {
+            Command::ImportParticipants(TournamentId((*id).to_owned()), (*file).to_owned())
+        }
+        [] => return Err("no command given".to_owned()),
+        _ => return Err(format!("unknown command: {}", words.join(" "))),
+    };
+    Ok(Arguments { config, command })
+}
+
+/// Runs the command, returning `false` if a part of it failed.
+fn run(arguments: Arguments) -> Result<bool> {
+    let toornament = match arguments.config {
+        Some(path) => Toornament::from_config(path)?,
+        None => Toornament::from_env()?,
+    };
+    match arguments.command {
+        Command::ListTournaments(filter) => {
+            for t in toornament.filtered_tournaments(filter, Includes::none())?.0 {
+                let archived = if t.is_archived() { "\tarchived" } else { "" };
+                let id = t.id.map(|id| id.0).unwrap_or_default();
+                println!("{}\t{:?}\t{}{}", id, t.status, t.name, archived);
+            }
+            Ok(true)
+        }
+        Command::ReportMatches(tournament_id, file) => {
+            let updates = import::results_from_csv(std::fs::File::open(file)?)?
+                .into_iter()
+                .map(|(id, result)| (id, MatchPatch::from(result)))
+                .collect();
+            let outcomes = toornament.update_matches(
+                tournament_id,
+                updates,
+                batch::BatchOptions::default(),
+                None,
+            );
+            let mut reported = true;
+            for (id, outcome) in outcomes {
+                match outcome {
+                    batch::UpdateOutcome::Updated(_) => println!("{}\treported", id.0),
+                    outcome => {
+                        reported = false;
+                        println!("{}\t{:?}", id.0, outcome);
+                    }
+                }
+            }
+            Ok(reported)
+        }
+        Command::ImportParticipants(tournament_id, file) => {
+            let participants: Participants = serde_json::from_reader(std::fs::File::open(file)?)?;
+            let mut imported = true;
+            for participant in participants.0 {
+                let name = participant.name.clone();
+                match toornament.create_tournament_participant(tournament_id.clone(), participant) {
+                    Ok(p) => println!("{}\t{}", p.id.map(|id| id.0).unwrap_or_default(), name),
+                    Err(e) => {
+                        imported = false;
+                        eprintln!("{}: {}", name, report(&e));
+                    }
+                }
+            }
+            Ok(imported)
+        }
+    }
+}
+
+/// Returns the message of the error followed by the messages of its causes.
+fn report(error: &Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+fn main() {
+    let arguments = match parse(std::env::args().skip(1)) {
+        Ok(arguments) => arguments,
+        Err(message) => {
+            if !message.is_empty() {
+                eprintln!("toornament: {}\n", message);
+            }
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+    match run(arguments) {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("toornament: {}", report(&e));
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> std::result::Result<Arguments, String> {
+        parse(line.split_whitespace().map(str::to_owned))
+    }
+
+    #[test]
+    fn test_parse_arguments() {
+        let parsed =
+            args("--config t.toml tournaments list --featured --archived --discipline dota2")
+                .unwrap();
+        assert_eq!(parsed.config.as_deref(), Some("t.toml"));
+        match parsed.command {
+            Command::ListTournaments(filter) => {
+                assert_eq!(filter.featured, Some(true));
+                assert_eq!(filter.archived, Some(true));
+                assert_eq!(filter.discipline_id, Some(DisciplineId::from("dota2")));
+                assert!(filter.name.is_none());
+            }
+            command => panic!("Unexpected command: {:?}", command),
+        }
+        match args("matches report 1 round-1.csv").unwrap().command {
+            Command::ReportMatches(id, file) => {
+                assert_eq!(id.0, "1");
+                assert_eq!(file, "round-1.csv");
+            }
+            command => panic!("Unexpected command: {:?}", command),
+        }
+        assert!(args("participants import 1").is_err());
+        assert!(args("tournaments list --name").is_err());
+        assert!(args("tournaments list --verbose").is_err());
+        assert_eq!(args("--help").unwrap_err(), "");
+        assert_eq!(args("").unwrap_err(), "no command given");
+    }
+
+    #[test]
+    fn test_report_causes() {
+        let error = Error::Denied("read only".to_owned()).context("deleting tournament 1");
+        assert_eq!(
+            report(&error),
+            format!("Error while deleting tournament 1: {}", error.root())
+        );
+    }
+}
@@ -0,0 +1,219 @@
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+use std::sync::Mutex;
+use std::time::Duration;
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+use std::time::Instant;
+
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+use crate::{Error, Result};
+
+/// When a client stops sending requests to a struggling service: after `failures` consecutive
+/// server errors or timeouts the circuit opens and the requests fail at once with
+/// `Error::CircuitOpen`. After the cool-down a single probe request is let through: the circuit
+/// closes if it succeeds and opens again if it fails.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CircuitBreakerPolicy {
+    /// Number of consecutive failures opening the circuit.
+    pub failures: u32,
+    /// Time the circuit stays open before a probe, in milliseconds.
+    pub cool_down_ms: u64,
+}
+impl Default for CircuitBreakerPolicy {
+    fn default() -> CircuitBreakerPolicy {
+        CircuitBreakerPolicy {
+            failures: 5,
+            cool_down_ms: 30_000,
+        }
+    }
+}
+impl CircuitBreakerPolicy {
+    /// Creates new circuit breaker policy.
+    pub fn new(failures: u32, cool_down: Duration) -> CircuitBreakerPolicy {
+        CircuitBreakerPolicy {
+            failures,
+            cool_down_ms: cool_down.as_millis() as u64,
+        }
+    }
+
+    /// Returns the time the circuit stays open before a probe.
+    pub fn cool_down(&self) -> Duration {
+        Duration::from_millis(self.cool_down_ms)
+    }
+}
+
+/// A state of the circuit breaker of a client.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BreakerState {
+    /// The requests are sent.
+    Closed,
+    /// The requests fail at once until the cool-down passes.
+    Open,
+    /// The cool-down has passed: a probe request decides whether the circuit closes.
+    HalfOpen,
+}
+
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+#[derive(Debug)]
+struct Circuit {
+    state: BreakerState,
+    failures: u32,
+    opened: Option<Instant>,
+    probing: bool,
+}
+
+/// The circuit breaker shared by the clones of a client.
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+#[derive(Debug)]
+pub(crate) struct Breaker {
+    policy: CircuitBreakerPolicy,
+    circuit: Mutex<Circuit>,
+}
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+impl Breaker {
+    pub(crate) fn new(policy: CircuitBreakerPolicy) -> Breaker {
+        Breaker {
+            policy,
+            circuit: Mutex::new(Circuit {
+                state: BreakerState::Closed,
+                failures: 0,
+                opened: None,
+                probing: false,
+            }),
+        }
+    }
+
+    /// Returns the state of the circuit at `now`.
+    pub(crate) fn state(&self, now: Instant) -> BreakerState {
+        let circuit = self.circuit.lock().unwrap_or_else(|e| e.into_inner());
+        match (circuit.state, circuit.opened) {
+            (BreakerState::Open, Some(opened)) if now >= opened + self.policy.cool_down() => {
+                BreakerState::HalfOpen
+            }
+            (state, _) => state,
+        }
+    }
+
+    /// Fails with `Error::CircuitOpen` if a request may not be sent at `now`, or returns the
+    /// permit to send it, whose outcome is recorded with `Permit::record`. A request allowed
+    /// while the circuit is half-open is the probe: if its permit is dropped without recording
+    /// the outcome, for example when the future sending it is dropped, the next request becomes
+    /// the probe.
+    pub(crate) fn allow(&self, now: Instant) -> Result<Permit<'_>> {
+        let state = self.state(now);
+        let mut circuit = self.circuit.lock().unwrap_or_else(|e| e.into_inner());
+        let probe = match state {
+            BreakerState::Closed => false,
+            BreakerState::HalfOpen if !circuit.probing => {
+                circuit.state = BreakerState::HalfOpen;
+                circuit.probing = true;
+                true
+            }
+            _ => return Err(Error::CircuitOpen),
+        };
+        Ok(Permit {
+            breaker: self,
+            probe,
+            recorded: false,
+        })
+    }
+
+    fn record(&self, probe: bool, failure: Option<bool>, now: Instant) -> bool {
+        let mut circuit = self.circuit.lock().unwrap_or_else(|e| e.into_inner());
+        if probe {
+            circuit.probing = false;
+        }
+        match failure {
+            Some(false) => {
+                circuit.state = BreakerState::Closed;
+                circuit.failures = 0;
+                circuit.opened = None;
+                false
+            }
+            Some(true) => {
+                circuit.failures = circuit.failures.saturating_add(1);
+                let opens = probe
+                    || circuit.state == BreakerState::Closed
+                        && circuit.failures >= self.policy.failures.max(1);
+                if opens {
+                    log::warn!(
+                        "The circuit breaker opens after {} failures",
+                        circuit.failures
+                    );
+                    circuit.state = BreakerState::Open;
+                    circuit.opened = Some(now);
+                    return true;
+                }
+                false
+            }
+            None => false,
+        }
+    }
+}
+
+/// The permission to send a request given by `Breaker::allow`.
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+#[derive(Debug)]
+pub(crate) struct Permit<'a> {
+    breaker: &'a Breaker,
+    probe: bool,
+    recorded: bool,
+}
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+impl Permit<'_> {
+    /// Records the outcome of the request: `Some(true)` for a server error or a timeout,
+    /// `Some(false)` for any other response and `None` if the service was not reached for
+    /// another reason. Returns `true` if the circuit has just opened.
+    pub(crate) fn record(mut self, failure: Option<bool>, now: Instant) -> bool {
+        self.recorded = true;
+        self.breaker.record(self.probe, failure, now)
+    }
+}
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        if !self.recorded && self.probe {
+            let mut circuit = self
+                .breaker
+                .circuit
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            circuit.probing = false;
+        }
+    }
+}
+
+#[cfg(all(test, any(feature = "client-blocking", feature = "client-async")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circuit_breaker() {
+        let breaker = Breaker::new(CircuitBreakerPolicy::new(2, Duration::from_secs(10)));
+        let start = Instant::now();
+        let send = |failure, now| breaker.allow(now).unwrap().record(failure, now);
+        assert!(!send(Some(true), start));
+        assert!(!send(Some(false), start));
+        assert!(!send(Some(true), start));
+        assert!(!send(None, start));
+        assert!(send(Some(true), start));
+        assert_eq!(breaker.state(start), BreakerState::Open);
+        assert!(matches!(breaker.allow(start), Err(Error::CircuitOpen)));
+
+        // A single probe is let through after the cool-down, and its failure opens the
+        // circuit again.
+        let later = start + Duration::from_secs(10);
+        assert_eq!(breaker.state(later), BreakerState::HalfOpen);
+        let probe = breaker.allow(later).unwrap();
+        assert!(breaker.allow(later).is_err());
+        assert!(probe.record(Some(true), later));
+        assert!(breaker.allow(later).is_err());
+
+        // A probe dropped without an outcome lets the next request probe.
+        let later = later + Duration::from_secs(10);
+        drop(breaker.allow(later).unwrap());
+        assert_eq!(breaker.state(later), BreakerState::HalfOpen);
+        assert!(!send(Some(false), later));
+        assert_eq!(breaker.state(later), BreakerState::Closed);
+        assert!(breaker.allow(later).is_ok());
+    }
+}
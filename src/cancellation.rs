@@ -0,0 +1,129 @@
+use std::any::Any;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::{Error, Result};
+
+/// A handle to cancel a long operation, such as fetching many pages, from another thread or
+/// once a deadline has passed. The operation checks the handle between the requests and
+/// returns `Error::Cancelled` as soon as it is cancelled. Clones share the same state.
+#[derive(Clone, Debug, Default)]
+pub struct Cancellation {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+impl Cancellation {
+    /// Creates new handle which is cancelled only by calling `cancel`.
+    pub fn new() -> Cancellation {
+        Cancellation::default()
+    }
+
+    /// Creates new handle which is also cancelled once the `deadline` has passed.
+    pub fn with_deadline(deadline: Instant) -> Cancellation {
+        Cancellation {
+            cancelled: Arc::default(),
+            deadline: Some(deadline),
+        }
+    }
+
+    /// Creates new handle which is also cancelled after the `timeout` from now.
+    pub fn with_timeout(timeout: Duration) -> Cancellation {
+        Cancellation::with_deadline(Instant::now() + timeout)
+    }
+
+    /// Cancels the operations using this handle or its clones.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns `true` if the handle was cancelled or its deadline has passed.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+            || self.deadline.map(|d| Instant::now() >= d).unwrap_or(false)
+    }
+
+    /// Returns `Error::Cancelled` if the handle was cancelled.
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            Err(Error::Cancelled(Partial::default()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// The results a cancelled operation got before it was cancelled, held by `Error::Cancelled`.
+/// Their type is told by the operation, for example a `Pager` returns the `Vec` of the items of
+/// the pages it fetched. It is empty if the operation has no results to return.
+#[derive(Default)]
+pub struct Partial(Option<Box<dyn Any + Send + Sync>>);
+impl Partial {
+    /// Creates new partial results.
+    #[cfg(feature = "client-blocking")]
+    pub(crate) fn new<T: Any + Send + Sync>(results: T) -> Partial {
+        Partial(Some(Box::new(results)))
+    }
+
+    /// Returns `true` if there are no results.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Returns the results if there are some of the type `T`.
+    pub fn results<T: Any>(&self) -> Option<&T> {
+        self.0.as_ref().and_then(|r| r.downcast_ref())
+    }
+
+    /// Returns the results if there are some of the type `T`.
+    pub fn into_results<T: Any>(self) -> Option<T> {
+        self.0.and_then(|r| r.downcast().ok()).map(|r| *r)
+    }
+}
+impl std::fmt::Debug for Partial {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.debug_struct("Partial")
+            .field("empty", &self.is_empty())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancellation() {
+        let cancellation = Cancellation::new();
+        let clone = cancellation.clone();
+        assert!(cancellation.check().is_ok());
+        clone.cancel();
+        assert!(cancellation.is_cancelled());
+        match cancellation.check() {
+            Err(Error::Cancelled(partial)) => assert!(partial.is_empty()),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        assert!(Cancellation::with_timeout(Duration::from_secs(0)).is_cancelled());
+        assert!(!Cancellation::with_timeout(Duration::from_secs(60)).is_cancelled());
+    }
+
+    #[cfg(feature = "client-blocking")]
+    #[test]
+    fn test_partial_results() {
+        let error = Error::Cancelled(Partial::default())
+            .context("getting page 3")
+            .with_partial(vec![1, 2]);
+        match error.root() {
+            Error::Cancelled(partial) => {
+                assert_eq!(partial.results::<Vec<i32>>(), Some(&vec![1, 2]));
+                assert_eq!(partial.results::<Vec<u8>>(), None);
+            }
+            other => panic!("Unexpected error: {:?}", other),
+        }
+        assert!(matches!(
+            Error::Unconfirmed.with_partial(vec![1]),
+            Error::Unconfirmed
+        ));
+    }
+}
@@ -59,6 +59,7 @@ macro_rules! enum_number {
 #[derive(
     Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
 )]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct TeamSize {
     /// Minimum team size
     pub min: i64,
@@ -71,3 +72,105 @@ enum_number!(MatchResultSimple {
     Draw = 2,
     Loss = 3,
 });
+
+/// An optional part of the responses, which the service adds when it is requested.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Include {
+    /// The streams of the tournaments
+    Streams,
+    /// The games of the matches
+    Games,
+    /// The statistics of the games
+    Stats,
+}
+impl Include {
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// A set of the optional parts of the responses to request, like
+/// `Include::Games | Include::Stats`. Each method uses the parts its endpoint supports and
+/// ignores the others.
+///
+/// The set may also be converted from a `bool`, which stands for all the parts or none of
+/// them, as the methods took a `bool` before. This form is deprecated and will be removed in
+/// the next major version: prefer `Include`, which tells which parts are requested. The
+/// compiler does not warn about it, as a conversion cannot be marked as deprecated.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Includes(u8);
+impl Includes {
+    /// Returns the empty set.
+    pub fn none() -> Includes {
+        Includes(0)
+    }
+
+    /// Returns the set of all the parts.
+    pub fn all() -> Includes {
+        Include::Streams | Include::Games | Include::Stats
+    }
+
+    /// Returns the set of the part if it is `requested`, the empty set otherwise.
+    #[cfg(feature = "client-blocking")]
+    pub(crate) fn when(requested: bool, include: Include) -> Includes {
+        if requested {
+            include.into()
+        } else {
+            Includes::none()
+        }
+    }
+
+    /// Returns `true` if the part is in the set.
+    pub fn contains(self, include: Include) -> bool {
+        self.0 & include.bit() != 0
+    }
+}
+impl From<Include> for Includes {
+    fn from(include: Include) -> Includes {
+        Includes(include.bit())
+    }
+}
+impl From<bool> for Includes {
+    fn from(all: bool) -> Includes {
+        if all {
+            Includes::all()
+        } else {
+            Includes::none()
+        }
+    }
+}
+impl std::ops::BitOr<Include> for Includes {
+    type Output = Includes;
+
+    fn bitor(self, include: Include) -> Includes {
+        Includes(self.0 | include.bit())
+    }
+}
+impl std::ops::BitOr for Include {
+    type Output = Includes;
+
+    fn bitor(self, include: Include) -> Includes {
+        Includes::from(self) | include
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_includes() {
+        let includes = Include::Games | Include::Stats;
+        assert!(includes.contains(Include::Games));
+        assert!(includes.contains(Include::Stats));
+        assert!(!includes.contains(Include::Streams));
+        assert!(!Includes::none().contains(Include::Games));
+        assert_eq!(
+            Includes::from(Include::Streams) | Include::Streams,
+            Include::Streams.into()
+        );
+        // The deprecated calls passing a `bool` keep compiling until the next major version.
+        assert_eq!(Includes::from(true), Includes::all());
+        assert_eq!(Includes::from(false), Includes::none());
+    }
+}
@@ -0,0 +1,433 @@
+use std::time::Duration;
+
+#[cfg(feature = "client-blocking")]
+use crate::secret::Secret;
+#[cfg(feature = "client-blocking")]
+use crate::{ConfigError, Error, Result};
+
+/// How failed requests are retried: on connection errors, timeouts, rate limiting and server
+/// errors the request is sent again after the backoff, which doubles after every attempt.
+/// Only the idempotent requests (`GET`, `PUT` and `DELETE`) are retried: a failed `POST` or
+/// `PATCH` may have been processed anyway, and sending it again could create duplicates.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one.
+    pub attempts: u32,
+    /// Delay before the first retry, in milliseconds.
+    pub backoff_ms: u64,
+}
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            attempts: 1,
+            backoff_ms: 500,
+        }
+    }
+}
+impl RetryPolicy {
+    /// Creates new retry policy.
+    pub fn new(attempts: u32, backoff: Duration) -> RetryPolicy {
+        RetryPolicy {
+            attempts,
+            backoff_ms: backoff.as_millis() as u64,
+        }
+    }
+
+    /// Returns the delay before the retry following the given attempt, starting from one.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u64
+            .checked_shl(attempt.saturating_sub(1))
+            .unwrap_or(u64::MAX);
+        Duration::from_millis(self.backoff_ms.saturating_mul(factor))
+    }
+}
+
+/// The timeouts of the requests, each optional: the connect timeout bounds establishing a
+/// connection, the attempt timeout bounds an attempt from sending the request to reading the
+/// whole response, and the total deadline bounds a call with all its retries and their backoff.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Timeouts {
+    /// Timeout of establishing a connection, in milliseconds.
+    pub connect_ms: Option<u64>,
+    /// Timeout of an attempt, in milliseconds. Also read from `read_ms`.
+    #[serde(alias = "read_ms")]
+    pub attempt_ms: Option<u64>,
+    /// Deadline of a call including its retries, in milliseconds.
+    pub total_ms: Option<u64>,
+}
+impl Timeouts {
+    /// Creates new timeouts, none of which is set.
+    pub fn new() -> Timeouts {
+        Timeouts::default()
+    }
+
+    /// Sets the timeout of establishing a connection.
+    pub fn connect(mut self, timeout: Duration) -> Timeouts {
+        self.connect_ms = Some(timeout.as_millis() as u64);
+        self
+    }
+
+    /// Sets the timeout of an attempt, from sending the request to reading the whole response.
+    pub fn attempt(mut self, timeout: Duration) -> Timeouts {
+        self.attempt_ms = Some(timeout.as_millis() as u64);
+        self
+    }
+
+    /// Sets the timeout of an attempt. It bounds the whole attempt and not only reading the
+    /// response, as the name suggested.
+    #[deprecated(since = "3.0.0", note = "use `Timeouts::attempt` instead")]
+    pub fn read(self, timeout: Duration) -> Timeouts {
+        self.attempt(timeout)
+    }
+
+    /// Sets the deadline of a call including its retries and their backoff.
+    pub fn total(mut self, timeout: Duration) -> Timeouts {
+        self.total_ms = Some(timeout.as_millis() as u64);
+        self
+    }
+
+    /// Returns the timeout of establishing a connection.
+    pub fn connect_timeout(&self) -> Option<Duration> {
+        self.connect_ms.map(Duration::from_millis)
+    }
+
+    /// Returns the timeout of an attempt.
+    pub fn attempt_timeout(&self) -> Option<Duration> {
+        self.attempt_ms.map(Duration::from_millis)
+    }
+
+    /// Returns the timeout of an attempt.
+    #[deprecated(since = "3.0.0", note = "use `Timeouts::attempt_timeout` instead")]
+    pub fn read_timeout(&self) -> Option<Duration> {
+        self.attempt_timeout()
+    }
+
+    /// Returns the deadline of a call.
+    pub fn total_timeout(&self) -> Option<Duration> {
+        self.total_ms.map(Duration::from_millis)
+    }
+}
+
+/// How the connections to the service are kept open to be reused by the next requests, each
+/// setting optional: the defaults of `reqwest` are used for the ones which are not set.
+/// See `Toornament::connection_pool`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConnectionPool {
+    /// Time an idle connection is kept open, in milliseconds.
+    pub idle_timeout_ms: Option<u64>,
+    /// Maximum number of the idle connections kept open to a host.
+    pub max_idle_per_host: Option<usize>,
+    /// Interval of the TCP keep-alive probes of the connections, in milliseconds.
+    pub tcp_keepalive_ms: Option<u64>,
+}
+impl ConnectionPool {
+    /// Creates new settings of the pool, none of which is set.
+    pub fn new() -> ConnectionPool {
+        ConnectionPool::default()
+    }
+
+    /// Sets the time an idle connection is kept open.
+    pub fn idle(mut self, timeout: Duration) -> ConnectionPool {
+        self.idle_timeout_ms = Some(timeout.as_millis() as u64);
+        self
+    }
+
+    /// Sets the maximum number of the idle connections kept open to a host. Zero disables
+    /// the reuse of the connections.
+    pub fn max_idle(mut self, connections: usize) -> ConnectionPool {
+        self.max_idle_per_host = Some(connections);
+        self
+    }
+
+    /// Sets the interval of the TCP keep-alive probes, which keep the idle connections from
+    /// being closed by the proxies and the load balancers.
+    pub fn keepalive(mut self, interval: Duration) -> ConnectionPool {
+        self.tcp_keepalive_ms = Some(interval.as_millis() as u64);
+        self
+    }
+
+    /// Returns the time an idle connection is kept open.
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        self.idle_timeout_ms.map(Duration::from_millis)
+    }
+
+    /// Returns the interval of the TCP keep-alive probes.
+    pub fn tcp_keepalive(&self) -> Option<Duration> {
+        self.tcp_keepalive_ms.map(Duration::from_millis)
+    }
+}
+
+/// Extra headers and query parameters of the requests, like a beta flag asked by the support
+/// of the service. See `Toornament::with_request_options`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RequestOptions {
+    /// The headers, by name.
+    pub headers: Vec<(String, String)>,
+    /// The query parameters, appended to the ones of the endpoints.
+    pub query: Vec<(String, String)>,
+    /// The language of the translated labels, like "fr" or "pt-BR", sent as the `_locale`
+    /// parameter of the endpoints accepting it.
+    pub locale: Option<String>,
+}
+impl RequestOptions {
+    /// Creates new options, adding nothing to the requests.
+    pub fn new() -> RequestOptions {
+        RequestOptions::default()
+    }
+
+    /// Adds the header, replacing the one with the same name.
+    pub fn header<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        let name = name.into();
+        self.headers.retain(|(n, _)| !n.eq_ignore_ascii_case(&name));
+        self.headers.push((name, value.into()));
+        self
+    }
+
+    /// Adds the query parameter.
+    pub fn query<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.query.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets the language of the translated labels, replacing the one of the client.
+    pub fn locale<S: Into<String>>(mut self, locale: S) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Returns the options with the `other` ones added.
+    #[cfg(any(feature = "client-blocking", feature = "client-async"))]
+    pub(crate) fn merge(&self, other: &RequestOptions) -> RequestOptions {
+        let mut merged = self.clone();
+        for (name, value) in &other.headers {
+            merged = merged.header(name.clone(), value.clone());
+        }
+        merged.query.extend(other.query.iter().cloned());
+        if other.locale.is_some() {
+            merged.locale = other.locale.clone();
+        }
+        merged
+    }
+}
+
+/// Returns whether the locale looks like a language tag, like "fr" or "pt-BR".
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+pub(crate) fn valid_locale(locale: &str) -> bool {
+    !locale.is_empty()
+        && locale
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Settings of a `Toornament` client.
+#[cfg(feature = "client-blocking")]
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Settings {
+    pub api_token: Secret,
+    pub client_id: String,
+    pub client_secret: Secret,
+    pub base_url: Option<String>,
+    pub timeout: Option<u64>,
+    pub proxy: Option<String>,
+    pub retry: Option<RetryPolicy>,
+    pub timeouts: Option<Timeouts>,
+    pub pool: Option<ConnectionPool>,
+    pub locale: Option<String>,
+}
+#[cfg(feature = "client-blocking")]
+impl Settings {
+    /// Reads the settings from the `TOORNAMENT_*` variables returned by `var`.
+    pub fn from_vars<F: Fn(&str) -> Option<String>>(var: F) -> Result<Settings> {
+        let required = |name: &str| {
+            var(name)
+                .filter(|v| !v.is_empty())
+                .ok_or_else(|| Error::Config(ConfigError::Missing(name.to_owned())))
+        };
+        let timeout = match var("TOORNAMENT_TIMEOUT") {
+            Some(value) => Some(value.parse().map_err(|_| {
+                Error::Config(ConfigError::Invalid("TOORNAMENT_TIMEOUT".to_owned(), value))
+            })?),
+            None => None,
+        };
+        Settings {
+            api_token: Secret::from(required("TOORNAMENT_API_TOKEN")?),
+            client_id: required("TOORNAMENT_CLIENT_ID")?,
+            client_secret: Secret::from(required("TOORNAMENT_CLIENT_SECRET")?),
+            base_url: var("TOORNAMENT_BASE_URL").filter(|v| !v.is_empty()),
+            timeout,
+            locale: var("TOORNAMENT_LOCALE").filter(|v| !v.is_empty()),
+            ..Default::default()
+        }
+        .validate()
+    }
+
+    /// Reads the settings from a TOML document.
+    #[cfg(feature = "config")]
+    pub fn from_toml(document: &str) -> Result<Settings> {
+        toml::from_str::<Settings>(document)
+            .map_err(|e| Error::Config(ConfigError::Parse(e.message().to_owned())))?
+            .validate()
+    }
+
+    fn validate(self) -> Result<Settings> {
+        let invalid = |name: &str, value: String| {
+            Err(Error::Config(ConfigError::Invalid(name.to_owned(), value)))
+        };
+        for (name, value) in &[
+            ("api_token", self.api_token.expose()),
+            ("client_id", &self.client_id),
+            ("client_secret", self.client_secret.expose()),
+        ] {
+            if value.is_empty() {
+                return Err(Error::Config(ConfigError::Missing((*name).to_owned())));
+            }
+        }
+        for (name, url) in &[("base_url", &self.base_url), ("proxy", &self.proxy)] {
+            if let Some(url) = url {
+                if !url.starts_with("http://") && !url.starts_with("https://") {
+                    return invalid(name, url.clone());
+                }
+            }
+        }
+        if self.timeout == Some(0) {
+            return invalid("timeout", "0".to_owned());
+        }
+        if let Some(retry) = self.retry {
+            if retry.attempts == 0 {
+                return invalid("retry.attempts", "0".to_owned());
+            }
+        }
+        if let Some(timeouts) = self.timeouts {
+            for (name, value) in &[
+                ("timeouts.connect_ms", timeouts.connect_ms),
+                ("timeouts.attempt_ms", timeouts.attempt_ms),
+                ("timeouts.total_ms", timeouts.total_ms),
+            ] {
+                if *value == Some(0) {
+                    return invalid(name, "0".to_owned());
+                }
+            }
+        }
+        if let Some(pool) = self.pool {
+            if pool.tcp_keepalive_ms == Some(0) {
+                return invalid("pool.tcp_keepalive_ms", "0".to_owned());
+            }
+        }
+        if let Some(ref locale) = self.locale {
+            if !valid_locale(locale) {
+                return invalid("locale", locale.clone());
+            }
+        }
+        Ok(self)
+    }
+}
+
+#[cfg(all(test, feature = "client-blocking"))]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_settings_from_vars() {
+        let mut vars = HashMap::new();
+        vars.insert("TOORNAMENT_API_TOKEN", "token");
+        vars.insert("TOORNAMENT_CLIENT_ID", "id");
+        let var = |vars: &HashMap<&str, &str>, name: &str| vars.get(name).map(|v| v.to_string());
+
+        match Settings::from_vars(|name| var(&vars, name)) {
+            Err(Error::Config(ConfigError::Missing(name))) => {
+                assert_eq!(name, "TOORNAMENT_CLIENT_SECRET")
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        vars.insert("TOORNAMENT_CLIENT_SECRET", "secret");
+        vars.insert("TOORNAMENT_TIMEOUT", "five");
+        assert!(matches!(
+            Settings::from_vars(|name| var(&vars, name)),
+            Err(Error::Config(ConfigError::Invalid(_, _)))
+        ));
+
+        vars.insert("TOORNAMENT_TIMEOUT", "5");
+        vars.insert("TOORNAMENT_BASE_URL", "http://localhost:8080");
+        vars.insert("TOORNAMENT_LOCALE", "fr FR");
+        assert!(matches!(
+            Settings::from_vars(|name| var(&vars, name)),
+            Err(Error::Config(ConfigError::Invalid(_, _)))
+        ));
+
+        vars.insert("TOORNAMENT_LOCALE", "fr");
+        let settings = Settings::from_vars(|name| var(&vars, name)).unwrap();
+        assert_eq!(settings.locale.as_deref(), Some("fr"));
+        assert_eq!(settings.client_secret.expose(), "secret");
+        assert!(!format!("{:?}", settings).contains("secret\""));
+        assert_eq!(settings.timeout, Some(5));
+        assert_eq!(settings.base_url.as_deref(), Some("http://localhost:8080"));
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_settings_from_toml() {
+        let settings = Settings::from_toml(
+            r#"
+            api_token = "token"
+            client_id = "id"
+            client_secret = "secret"
+            timeout = 10
+            proxy = "http://proxy:3128"
+
+            [retry]
+            attempts = 3
+
+            [timeouts]
+            connect_ms = 2000
+            total_ms = 60000
+
+            [pool]
+            idle_timeout_ms = 90000
+            max_idle_per_host = 4
+            "#,
+        )
+        .unwrap();
+        assert_eq!(settings.timeout, Some(10));
+        assert_eq!(settings.proxy.as_deref(), Some("http://proxy:3128"));
+        let retry = settings.retry.unwrap();
+        assert_eq!(retry, RetryPolicy::new(3, Duration::from_millis(500)));
+        assert_eq!(retry.backoff(3), Duration::from_secs(2));
+        let timeouts = settings.timeouts.unwrap();
+        assert_eq!(timeouts.connect_timeout(), Some(Duration::from_secs(2)));
+        assert_eq!(timeouts.attempt_timeout(), None);
+        assert_eq!(timeouts.total_timeout(), Some(Duration::from_secs(60)));
+        let pool = settings.pool.unwrap();
+        assert_eq!(pool.idle_timeout(), Some(Duration::from_secs(90)));
+        assert_eq!(pool.max_idle_per_host, Some(4));
+        assert_eq!(pool.tcp_keepalive(), None);
+
+        let missing = Settings::from_toml("api_token = \"token\"\nclient_id = \"id\"");
+        assert!(matches!(missing, Err(Error::Config(ConfigError::Parse(_)))));
+        let unknown = Settings::from_toml(
+            "api_token = \"t\"\nclient_id = \"i\"\nclient_secret = \"s\"\ntimeuot = 5",
+        );
+        assert!(matches!(unknown, Err(Error::Config(ConfigError::Parse(_)))));
+        let invalid = Settings::from_toml(
+            "api_token = \"t\"\nclient_id = \"i\"\nclient_secret = \"s\"\nbase_url = \"ftp://x\"",
+        );
+        match invalid {
+            Err(Error::Config(ConfigError::Invalid(name, _))) => assert_eq!(name, "base_url"),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        let invalid = Settings::from_toml(
+            "api_token = \"t\"\nclient_id = \"i\"\nclient_secret = \"s\"\n[timeouts]\nread_ms = 0",
+        );
+        match invalid {
+            Err(Error::Config(ConfigError::Invalid(name, _))) => {
+                assert_eq!(name, "timeouts.attempt_ms")
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+}
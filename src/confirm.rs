@@ -0,0 +1,93 @@
+use crate::participants::{ParticipantId, Participants};
+use crate::permissions::PermissionId;
+use crate::tournaments::TournamentId;
+use crate::{Result, Toornament, UndoToken};
+
+/// A handle allowing the destructive calls of a client requiring confirmation: the deletions
+/// and the replacements of whole lists. See `Toornament::require_confirmation`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use toornament::*;
+/// let t = Toornament::with_application("API_TOKEN",
+///                                      "CLIENT_ID",
+///                                      "CLIENT_SECRET").unwrap()
+///                    .require_confirmation();
+/// // Fails with `Error::Unconfirmed`
+/// assert!(t.delete_tournament(TournamentId("1".to_owned())).is_err());
+/// // Deletes the tournament
+/// t.confirm_destructive().delete_tournament(TournamentId("1".to_owned())).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Confirmed {
+    client: Toornament,
+}
+impl Confirmed {
+    pub(crate) fn new(client: Toornament) -> Confirmed {
+        Confirmed { client }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn client(&self) -> &Toornament {
+        &self.client
+    }
+
+    /// See `Toornament::delete_tournament`.
+    pub fn delete_tournament(&self, id: TournamentId) -> Result<()> {
+        self.client.delete_tournament(id)
+    }
+
+    /// See `Toornament::delete_tournament_with_undo`.
+    pub fn delete_tournament_with_undo(&self, id: TournamentId) -> Result<UndoToken> {
+        self.client.delete_tournament_with_undo(id)
+    }
+
+    /// See `Toornament::update_tournament_participants`.
+    pub fn update_tournament_participants(
+        &self,
+        id: TournamentId,
+        participants: Participants,
+    ) -> Result<Participants> {
+        self.client.update_tournament_participants(id, participants)
+    }
+
+    /// See `Toornament::update_tournament_participants_with_undo`.
+    pub fn update_tournament_participants_with_undo(
+        &self,
+        id: TournamentId,
+        participants: Participants,
+    ) -> Result<(Participants, UndoToken)> {
+        self.client
+            .update_tournament_participants_with_undo(id, participants)
+    }
+
+    /// See `Toornament::delete_tournament_participant`.
+    pub fn delete_tournament_participant(
+        &self,
+        id: TournamentId,
+        participant_id: ParticipantId,
+    ) -> Result<()> {
+        self.client
+            .delete_tournament_participant(id, participant_id)
+    }
+
+    /// See `Toornament::delete_tournament_participant_with_undo`.
+    pub fn delete_tournament_participant_with_undo(
+        &self,
+        id: TournamentId,
+        participant_id: ParticipantId,
+    ) -> Result<UndoToken> {
+        self.client
+            .delete_tournament_participant_with_undo(id, participant_id)
+    }
+
+    /// See `Toornament::delete_tournament_permission`.
+    pub fn delete_tournament_permission(
+        &self,
+        id: TournamentId,
+        permission_id: PermissionId,
+    ) -> Result<()> {
+        self.client.delete_tournament_permission(id, permission_id)
+    }
+}
@@ -1,3 +1,4 @@
+//! This module describes the game disciplines and lists the ids of the well-known ones.
 use std::collections::HashMap;
 
 use crate::common::TeamSize;
@@ -11,9 +12,74 @@ pub struct AdditionalFields(pub HashMap<String, HashMap<String, String>>);
     Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
 )]
 pub struct DisciplineId(pub String);
+impl DisciplineId {
+    /// Returns `true` if the id is one of the `ids` constants.
+    pub fn is_known(&self) -> bool {
+        ids::ALL.contains(&self.0.as_str())
+    }
+}
+impl<'a> From<&'a str> for DisciplineId {
+    fn from(id: &'a str) -> DisciplineId {
+        DisciplineId(id.to_owned())
+    }
+}
+
+/// Ids of the well-known disciplines, to avoid typos when filtering by a discipline. Any other
+/// discipline id can still be used as `DisciplineId::from("some_id")`.
+///
+/// # Example
+///
+/// ```rust
+/// use toornament::disciplines::ids;
+/// use toornament::DisciplineId;
+///
+/// let id = DisciplineId::from(ids::COUNTERSTRIKE_GO);
+/// assert_eq!(id.0, "counterstrike_go");
+/// assert!(id.is_known());
+/// ```
+pub mod ids {
+    /// Counter-Strike: Global Offensive
+    pub const COUNTERSTRIKE_GO: &str = "counterstrike_go";
+    /// League of Legends
+    pub const LEAGUE_OF_LEGENDS: &str = "leagueoflegends";
+    /// Dota 2
+    pub const DOTA2: &str = "dota2";
+    /// Hearthstone
+    pub const HEARTHSTONE: &str = "hearthstone";
+    /// Overwatch
+    pub const OVERWATCH: &str = "overwatch";
+    /// Rocket League
+    pub const ROCKET_LEAGUE: &str = "rocketleague";
+    /// StarCraft II
+    pub const STARCRAFT2: &str = "starcraft2";
+    /// Heroes of the Storm
+    pub const HEROES_OF_THE_STORM: &str = "heroesofthestorm";
+    /// Call of Duty 4: Modern Warfare
+    pub const COD4: &str = "cod4";
+    /// Quake Live
+    pub const QUAKE_LIVE: &str = "quakelive";
+    /// WWE 2K17
+    pub const WWE2K17: &str = "wwe2k17";
+
+    /// All the known discipline ids.
+    pub const ALL: &[&str] = &[
+        COUNTERSTRIKE_GO,
+        LEAGUE_OF_LEGENDS,
+        DOTA2,
+        HEARTHSTONE,
+        OVERWATCH,
+        ROCKET_LEAGUE,
+        STARCRAFT2,
+        HEROES_OF_THE_STORM,
+        COD4,
+        QUAKE_LIVE,
+        WWE2K17,
+    ];
+}
 
 /// A game discipline object.
 #[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct Discipline {
     /// An identifier for a discipline, can be used in others APIs.
     /// Example: "counterstrike_go"
@@ -71,6 +137,7 @@ impl Discipline {
     builder!(additional_fields, Option<AdditionalFields>);
 }
 
+#[cfg(feature = "client-blocking")]
 impl Discipline {
     /// Returns iter for the discipline
     pub fn iter<'a>(&self, client: &'a crate::Toornament) -> crate::DisciplineIter<'a> {
@@ -86,6 +153,7 @@ impl Discipline {
 /// A list of `Discipline` objects.
 #[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct Disciplines(pub Vec<Discipline>);
+list_wrapper!(Disciplines, Discipline);
 
 #[cfg(test)]
 mod tests {
@@ -170,7 +238,7 @@ mod tests {
         let ds: Disciplines = serde_json::from_str(string).unwrap();
 
         assert_eq!(ds.0.len(), 2);
-        let correct_disciplines = vec![
+        let correct_disciplines = [
             Discipline::new(
                 DisciplineId("counterstrike_go".to_owned()),
                 "Counter-Strike: GO",
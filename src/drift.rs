@@ -0,0 +1,186 @@
+//! This module detects the schema drift: the differences between the responses of the service
+//! and the models of the crate, which appear when the service changes its schema. The fields
+//! the models do not know are silently ignored while parsing, and a null where the models do
+//! not expect one fails the whole call, so a client detecting the drift with
+//! `Toornament::detect_schema_drift` records them to report precise issues.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use toornament::*;
+//!
+//! let toornament = Toornament::with_application("API_TOKEN",
+//!                                               "CLIENT_ID",
+//!                                               "CLIENT_SECRET").unwrap()
+//!                             .detect_schema_drift();
+//! let _ = toornament.tournaments(None, Include::Streams);
+//! for drift in toornament.schema_drift() {
+//!     println!("{}", drift);
+//! }
+//! ```
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// A kind of difference between a response and the models.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum DriftKind {
+    /// The field is not known by the model and was ignored.
+    UnknownField,
+    /// The field is null where the model does not expect it.
+    UnexpectedNull,
+    /// The field does not fit the model, with the reason.
+    Mismatch(String),
+}
+
+/// A difference between a response and the models.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Drift {
+    /// The address of the response.
+    pub endpoint: String,
+    /// Path to the field in the response, with `[]` standing for any element of a list, like
+    /// `opponents[].participant.nickname`.
+    pub path: String,
+    /// The difference.
+    pub kind: DriftKind,
+}
+impl ::std::fmt::Display for Drift {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        match self.kind {
+            DriftKind::UnknownField => write!(f, "{}: unknown field {}", self.endpoint, self.path),
+            DriftKind::UnexpectedNull => {
+                write!(f, "{}: unexpected null at {}", self.endpoint, self.path)
+            }
+            DriftKind::Mismatch(ref reason) => {
+                write!(
+                    f,
+                    "{}: mismatch at {}: {}",
+                    self.endpoint, self.path, reason
+                )
+            }
+        }
+    }
+}
+
+/// The drift found by a client and its clones.
+pub(crate) type Drifts = Arc<Mutex<BTreeSet<Drift>>>;
+
+/// Appends the path of the `serde_ignored` path to `out`.
+fn ignored_path(path: &serde_ignored::Path<'_>, out: &mut String) {
+    match *path {
+        serde_ignored::Path::Root => {}
+        serde_ignored::Path::Seq { parent, .. } => {
+            ignored_path(parent, out);
+            out.push_str("[]");
+        }
+        serde_ignored::Path::Map { parent, ref key } => {
+            ignored_path(parent, out);
+            if !out.is_empty() {
+                out.push('.');
+            }
+            out.push_str(key);
+        }
+        serde_ignored::Path::Some { parent }
+        | serde_ignored::Path::NewtypeStruct { parent }
+        | serde_ignored::Path::NewtypeVariant { parent } => ignored_path(parent, out),
+    }
+}
+
+/// Parses the value as the model, recording the fields it ignores and the null or the
+/// mismatch it fails on.
+pub(crate) fn inspect<T: DeserializeOwned>(value: &Value, endpoint: &str, drifts: &Drifts) {
+    let mut found = BTreeSet::new();
+    let mut unknown = |path: serde_ignored::Path<'_>| {
+        let mut out = String::new();
+        ignored_path(&path, &mut out);
+        found.insert(Drift {
+            endpoint: endpoint.to_owned(),
+            path: out,
+            kind: DriftKind::UnknownField,
+        });
+    };
+    let deserializer = serde_ignored::Deserializer::new(value, &mut unknown);
+    let result: Result<T, _> = serde_path_to_error::deserialize(deserializer);
+    if let Err(e) = result {
+        let mut path = String::new();
+        let mut field = Some(value);
+        for segment in e.path().iter() {
+            match *segment {
+                serde_path_to_error::Segment::Seq { index } => {
+                    path.push_str("[]");
+                    field = field.and_then(|f| f.get(index));
+                }
+                serde_path_to_error::Segment::Map { ref key }
+                | serde_path_to_error::Segment::Enum { variant: ref key } => {
+                    if !path.is_empty() {
+                        path.push('.');
+                    }
+                    path.push_str(key);
+                    field = field.and_then(|f| f.get(key));
+                }
+                serde_path_to_error::Segment::Unknown => field = None,
+            }
+        }
+        let kind = match field {
+            Some(Value::Null) => DriftKind::UnexpectedNull,
+            _ => DriftKind::Mismatch(e.inner().to_string()),
+        };
+        found.insert(Drift {
+            endpoint: endpoint.to_owned(),
+            path,
+            kind,
+        });
+    }
+    for drift in &found {
+        log::debug!("Schema drift: {}", drift);
+    }
+    if let Ok(mut drifts) = drifts.lock() {
+        drifts.extend(found);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Matches, Participants};
+
+    #[test]
+    #[cfg_attr(
+        feature = "strict-serde",
+        ignore = "the unknown fields fail the parsing"
+    )]
+    fn test_inspect() {
+        let drifts = Drifts::default();
+        let value = serde_json::json!([
+            { "name": "Evil Geniuses", "nickname": "EG",
+              "lineup": [{ "name": "Arteezy", "nickname": "rtz" }] },
+            { "name": "Fnatic", "nickname": "FNC" }
+        ]);
+        inspect::<Participants>(&value, "/v1/participants", &drifts);
+        let value = serde_json::json!([{ "name": null }]);
+        inspect::<Participants>(&value, "/v1/participants", &drifts);
+        let value = serde_json::json!([{ "id": "1", "status": "paused" }]);
+        inspect::<Matches>(&value, "/v1/matches", &drifts);
+
+        let drifts = drifts.lock().unwrap().iter().cloned().collect::<Vec<_>>();
+        let paths = drifts.iter().map(|d| d.path.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                "[].status",
+                "[].lineup[].nickname",
+                "[].name",
+                "[].nickname"
+            ]
+        );
+        assert!(matches!(drifts[0].kind, DriftKind::Mismatch(_)));
+        assert_eq!(drifts[1].kind, DriftKind::UnknownField);
+        assert_eq!(drifts[2].kind, DriftKind::UnexpectedNull);
+        assert_eq!(
+            drifts[2].to_string(),
+            "/v1/participants: unexpected null at [].name"
+        );
+    }
+}
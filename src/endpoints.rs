@@ -1,30 +1,57 @@
+use crate::filters::{flag, QueryParams};
 use crate::*;
 
-const API_BASE: &str = "https://api.toornament.com/organizer/v2";
+/// The default base address of the API.
+pub(crate) const API_BASE: &str = "https://api.toornament.com/organizer/v2";
 
+// The asynchronous client sends a part of the requests only.
+#[cfg_attr(not(feature = "client-blocking"), allow(dead_code))]
 #[derive(Debug, Clone)]
 pub enum Endpoint {
     OauthToken,
     AllDisciplines,
+    DisciplinesPage {
+        per_page: i64,
+    },
     DisciplineById(DisciplineId),
     AllTournaments {
         with_streams: bool,
+        filter: TournamentFilter,
     },
+    Me,
     MyTournaments,
     TournamentByIdGet {
         tournament_id: TournamentId,
         with_streams: bool,
     },
     TournamentByIdUpdate(TournamentId),
+    TournamentSettings(TournamentId),
     TournamentCreate,
     MatchesByTournament {
         tournament_id: TournamentId,
         with_games: bool,
+        page: Option<i64>,
+        per_page: Option<i64>,
     },
     MatchesByDiscipline {
         discipline_id: DisciplineId,
         filter: MatchFilter,
     },
+    MatchesByStage {
+        tournament_id: TournamentId,
+        stage_number: StageNumber,
+        group_number: Option<u64>,
+        with_games: bool,
+        page: Option<i64>,
+        per_page: Option<i64>,
+    },
+    MatchesByParticipant {
+        tournament_id: TournamentId,
+        participant_id: ParticipantId,
+        with_games: bool,
+        page: Option<i64>,
+        per_page: Option<i64>,
+    },
     MatchByIdGet {
         tournament_id: TournamentId,
         match_id: MatchId,
@@ -71,265 +98,538 @@ pub enum Endpoint {
     ParticipantById(TournamentId, ParticipantId),
     Permissions(TournamentId),
     PermissionById(TournamentId, PermissionId),
+    PermissionInvitation(TournamentId, PermissionId),
     Stages(TournamentId),
+    FinalStandings(TournamentId),
+    CustomFields(TournamentId),
     Videos {
         tournament_id: TournamentId,
         filter: TournamentVideosFilter,
     },
 }
 
-impl ::std::fmt::Display for Endpoint {
-    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
-        let address;
+impl Endpoint {
+    /// Returns the address template of the endpoint without the identifiers and the query, for
+    /// example "/v1/tournaments/{tournament_id}/matches".
+    #[cfg(feature = "metrics")]
+    pub fn route(&self) -> &'static str {
         match *self {
-            Endpoint::OauthToken => address = "/oauth/v2/token".to_owned(),
-            Endpoint::AllDisciplines => address = "/v1/disciplines".to_owned(),
-            Endpoint::DisciplineById(ref id) => address = format!("/v1/disciplines/{}", id.0),
-            Endpoint::AllTournaments { with_streams } => {
-                address = format!(
-                    "/v1/tournaments?with_streams={}",
-                    if with_streams { "1" } else { "0" }
-                )
-            }
-            Endpoint::MyTournaments => address = "/v1/me/tournaments".to_owned(),
+            Endpoint::OauthToken => "/oauth/v2/token",
+            Endpoint::AllDisciplines | Endpoint::DisciplinesPage { .. } => "/v1/disciplines",
+            Endpoint::DisciplineById(_) => "/v1/disciplines/{discipline_id}",
+            Endpoint::AllTournaments { .. } | Endpoint::TournamentCreate => "/v1/tournaments",
+            Endpoint::Me => "/v1/me",
+            Endpoint::MyTournaments => "/v1/me/tournaments",
+            Endpoint::TournamentByIdGet { .. } | Endpoint::TournamentByIdUpdate(_) => {
+                "/v1/tournaments/{tournament_id}"
+            }
+            Endpoint::TournamentSettings(_) => "/v1/tournaments/{tournament_id}/settings",
+            Endpoint::MatchesByTournament { .. }
+            | Endpoint::MatchesByStage { .. }
+            | Endpoint::MatchesByParticipant { .. } => "/v1/tournaments/{tournament_id}/matches",
+            Endpoint::MatchesByDiscipline { .. } => "/v1/disciplines/{discipline_id}/matches",
+            Endpoint::MatchByIdGet { .. } | Endpoint::MatchByIdUpdate { .. } => {
+                "/v1/tournaments/{tournament_id}/matches/{match_id}"
+            }
+            Endpoint::MatchResult(..) => {
+                "/v1/tournaments/{tournament_id}/matches/{match_id}/result"
+            }
+            Endpoint::MatchGames { .. } => {
+                "/v1/tournaments/{tournament_id}/matches/{match_id}/games"
+            }
+            Endpoint::MatchGameByNumberGet { .. } | Endpoint::MatchGameByNumberUpdate { .. } => {
+                "/v1/tournaments/{tournament_id}/matches/{match_id}/games/{game_number}"
+            }
+            Endpoint::MatchGameResultGet { .. } | Endpoint::MatchGameResultUpdate { .. } => {
+                "/v1/tournaments/{tournament_id}/matches/{match_id}/games/{game_number}/result"
+            }
+            Endpoint::Participants { .. }
+            | Endpoint::ParticipantCreate(_)
+            | Endpoint::ParticipantsUpdate(_) => "/v1/tournaments/{tournament_id}/participants",
+            Endpoint::ParticipantById(..) => {
+                "/v1/tournaments/{tournament_id}/participants/{participant_id}"
+            }
+            Endpoint::Permissions(_) => "/v1/tournaments/{tournament_id}/permissions",
+            Endpoint::PermissionById(..) => {
+                "/v1/tournaments/{tournament_id}/permissions/{permission_id}"
+            }
+            Endpoint::PermissionInvitation(..) => {
+                "/v1/tournaments/{tournament_id}/permissions/{permission_id}/invitation"
+            }
+            Endpoint::Stages(_) => "/v1/tournaments/{tournament_id}/stages",
+            Endpoint::FinalStandings(_) => "/v1/tournaments/{tournament_id}/final-standings",
+            Endpoint::CustomFields(_) => "/v1/tournaments/{tournament_id}/custom-fields",
+            Endpoint::Videos { .. } => "/v1/tournaments/{tournament_id}/videos",
+        }
+    }
+
+    /// Returns whether the endpoint accepts the `_locale` parameter translating its labels, like
+    /// the names of the disciplines and of the custom fields.
+    pub fn localized(&self) -> bool {
+        matches!(
+            *self,
+            Endpoint::AllDisciplines
+                | Endpoint::DisciplinesPage { .. }
+                | Endpoint::DisciplineById(_)
+                | Endpoint::AllTournaments { .. }
+                | Endpoint::MyTournaments
+                | Endpoint::TournamentByIdGet { .. }
+                | Endpoint::CustomFields(_)
+        )
+    }
+
+    /// Checks the filter of the endpoint before the request is sent.
+    pub(crate) fn validate(&self) -> Result<()> {
+        match *self {
+            Endpoint::MatchesByDiscipline { ref filter, .. } => filter.validate(),
+            Endpoint::AllTournaments { ref filter, .. } => filter.validate(),
+            _ => Ok(()),
+        }
+    }
+
+    /// Describes the resource of the endpoint, for example "match 2 of tournament 1".
+    pub fn resource(&self) -> String {
+        match *self {
+            Endpoint::OauthToken => "the access token".to_owned(),
+            Endpoint::AllDisciplines | Endpoint::DisciplinesPage { .. } => {
+                "the disciplines".to_owned()
+            }
+            Endpoint::DisciplineById(ref id) => format!("discipline {}", id.0),
+            Endpoint::AllTournaments { .. } => "the tournaments".to_owned(),
+            Endpoint::Me => "the account".to_owned(),
+            Endpoint::MyTournaments => "my tournaments".to_owned(),
             Endpoint::TournamentByIdGet {
-                ref tournament_id,
-                with_streams,
-            } => {
-                address = format!(
-                    "/v1/tournaments/{}?with_streams={}",
-                    tournament_id.0,
-                    if with_streams { "1" } else { "0" }
-                )
+                ref tournament_id, ..
+            }
+            | Endpoint::TournamentByIdUpdate(ref tournament_id) => {
+                format!("tournament {}", tournament_id.0)
             }
-            Endpoint::TournamentByIdUpdate(ref tournament_id) => {
-                address = format!("/v1/tournaments/{}", tournament_id.0)
+            Endpoint::TournamentSettings(ref tournament_id) => {
+                format!("the settings of tournament {}", tournament_id.0)
             }
-            Endpoint::TournamentCreate => address = "/v1/tournaments".to_owned(),
+            Endpoint::TournamentCreate => "a tournament".to_owned(),
             Endpoint::MatchesByTournament {
+                ref tournament_id, ..
+            } => format!("the matches of tournament {}", tournament_id.0),
+            Endpoint::MatchesByDiscipline {
+                ref discipline_id, ..
+            } => format!("the matches of discipline {}", discipline_id.0),
+            Endpoint::MatchesByStage {
                 ref tournament_id,
-                with_games,
-            } => {
-                address = format!(
-                    "/v1/tournaments/{}/matches?with_games={}",
-                    tournament_id.0,
-                    if with_games { "1" } else { "0" }
-                )
-            }
+                ref stage_number,
+                group_number,
+                ..
+            } => match group_number {
+                Some(group_number) => format!(
+                    "the matches of group {} of stage {} of tournament {}",
+                    group_number, stage_number.0, tournament_id.0
+                ),
+                None => format!(
+                    "the matches of stage {} of tournament {}",
+                    stage_number.0, tournament_id.0
+                ),
+            },
+            Endpoint::MatchesByParticipant {
+                ref tournament_id,
+                ref participant_id,
+                ..
+            } => format!(
+                "the matches of participant {} of tournament {}",
+                participant_id.0, tournament_id.0
+            ),
             Endpoint::MatchByIdGet {
                 ref tournament_id,
                 ref match_id,
-                with_games,
-            } => {
-                address = format!(
-                    "/v1/tournaments/{}/matches/{}?with_games={}",
-                    tournament_id.0,
-                    match_id.0,
-                    if with_games { "1" } else { "0" }
-                )
-            }
-            Endpoint::MatchByIdUpdate {
+                ..
+            }
+            | Endpoint::MatchByIdUpdate {
                 ref tournament_id,
                 ref match_id,
-            } => address = format!("/v1/tournaments/{}/matches/{}", tournament_id.0, match_id.0),
-            Endpoint::MatchesByDiscipline {
-                ref discipline_id,
-                ref filter,
-            } => {
-                address = format!(
-                    "/v1/disciplines/{}/matches?{}",
-                    discipline_id.0,
-                    match_filter(filter.clone())
-                )
-            }
-            Endpoint::MatchResult(ref tournament_id, ref match_id) => {
-                address = format!(
-                    "/v1/tournaments/{}/matches/{}/result",
-                    tournament_id.0, match_id.0
-                )
-            }
+            } => format!("match {} of tournament {}", match_id.0, tournament_id.0),
+            Endpoint::MatchResult(ref tournament_id, ref match_id) => format!(
+                "the result of match {} of tournament {}",
+                match_id.0, tournament_id.0
+            ),
             Endpoint::MatchGames {
                 ref tournament_id,
                 ref match_id,
-                with_stats,
-            } => {
-                address = format!(
-                    "/v1/tournaments/{}/matches/{}/games?with_stats={}",
-                    tournament_id.0,
-                    match_id.0,
-                    if with_stats { "1" } else { "0" }
-                )
-            }
+                ..
+            } => format!(
+                "the games of match {} of tournament {}",
+                match_id.0, tournament_id.0
+            ),
             Endpoint::MatchGameByNumberGet {
                 ref tournament_id,
                 ref match_id,
                 ref game_number,
-                with_stats,
-            } => {
-                address = format!(
-                    "/v1/tournaments/{}/matches/{}/games/{}?with_stats={}",
-                    tournament_id.0,
-                    match_id.0,
-                    game_number.0,
-                    if with_stats { "1" } else { "0" }
-                )
-            }
-            Endpoint::MatchGameByNumberUpdate {
+                ..
+            }
+            | Endpoint::MatchGameByNumberUpdate {
                 ref tournament_id,
                 ref match_id,
                 ref game_number,
-            } => {
-                address = format!(
-                    "/v1/tournaments/{}/matches/{}/games/{}",
-                    tournament_id.0, match_id.0, game_number.0
-                )
-            }
+            } => format!(
+                "game {} of match {} of tournament {}",
+                game_number.0, match_id.0, tournament_id.0
+            ),
             Endpoint::MatchGameResultGet {
                 ref tournament_id,
                 ref match_id,
                 ref game_number,
-            } => {
-                address = format!(
-                    "/v1/tournaments/{}/matches/{}/games/{}/result",
-                    tournament_id.0, match_id.0, game_number.0
-                )
             }
-            Endpoint::MatchGameResultUpdate {
+            | Endpoint::MatchGameResultUpdate {
                 ref tournament_id,
                 ref match_id,
                 ref game_number,
-                update_match,
-            } => {
-                address = format!(
-                    "/v1/tournaments/{}/matches/{}/games/{}/result?update_match={}",
-                    tournament_id.0,
-                    match_id.0,
-                    game_number.0,
-                    if update_match { "1" } else { "0" }
-                )
-            }
+                ..
+            } => format!(
+                "the result of game {} of match {} of tournament {}",
+                game_number.0, match_id.0, tournament_id.0
+            ),
             Endpoint::Participants {
-                ref tournament_id,
-                ref filter,
-            } => {
-                address = format!(
-                    "/v1/tournaments/{}/participants?{}",
-                    tournament_id.0,
-                    tournament_participants(filter.clone())
-                )
+                ref tournament_id, ..
+            }
+            | Endpoint::ParticipantsUpdate(ref tournament_id) => {
+                format!("the participants of tournament {}", tournament_id.0)
             }
             Endpoint::ParticipantCreate(ref tournament_id) => {
-                address = format!("/v1/tournaments/{}/participants", tournament_id.0)
+                format!("a participant of tournament {}", tournament_id.0)
             }
-            Endpoint::ParticipantsUpdate(ref tournament_id) => {
-                address = format!("/v1/tournaments/{}/participants", tournament_id.0)
+            Endpoint::ParticipantById(ref tournament_id, ref participant_id) => format!(
+                "participant {} of tournament {}",
+                participant_id.0, tournament_id.0
+            ),
+            Endpoint::Permissions(ref tournament_id) => {
+                format!("the permissions of tournament {}", tournament_id.0)
             }
-            Endpoint::ParticipantById(ref tournament_id, ref participant_id) => {
-                address = format!(
-                    "/v1/tournaments/{}/participants/{}",
-                    tournament_id.0, participant_id.0
-                )
+            Endpoint::PermissionById(ref tournament_id, ref permission_id) => format!(
+                "permission {} of tournament {}",
+                permission_id.0, tournament_id.0
+            ),
+            Endpoint::PermissionInvitation(ref tournament_id, ref permission_id) => format!(
+                "the invitation of permission {} of tournament {}",
+                permission_id.0, tournament_id.0
+            ),
+            Endpoint::Stages(ref tournament_id) => {
+                format!("the stages of tournament {}", tournament_id.0)
             }
-            Endpoint::Permissions(ref tournament_id) => {
-                address = format!("/v1/tournaments/{}/permissions", tournament_id.0)
+            Endpoint::FinalStandings(ref tournament_id) => {
+                format!("the final standings of tournament {}", tournament_id.0)
             }
-            Endpoint::PermissionById(ref tournament_id, ref permission_id) => {
-                address = format!(
-                    "/v1/tournaments/{}/permissions/{}",
-                    tournament_id.0, permission_id.0
-                )
+            Endpoint::CustomFields(ref tournament_id) => {
+                format!("the custom fields of tournament {}", tournament_id.0)
             }
+            Endpoint::Videos {
+                ref tournament_id, ..
+            } => format!("the videos of tournament {}", tournament_id.0),
+        }
+    }
+}
+
+impl Endpoint {
+    /// Returns the address of the endpoint without the base and the query.
+    fn path(&self) -> String {
+        match *self {
+            Endpoint::OauthToken => "/oauth/v2/token".to_owned(),
+            Endpoint::AllDisciplines | Endpoint::DisciplinesPage { .. } => {
+                "/v1/disciplines".to_owned()
+            }
+            Endpoint::DisciplineById(ref id) => format!("/v1/disciplines/{}", id.0),
+            Endpoint::AllTournaments { .. } | Endpoint::TournamentCreate => {
+                "/v1/tournaments".to_owned()
+            }
+            Endpoint::Me => "/v1/me".to_owned(),
+            Endpoint::MyTournaments => "/v1/me/tournaments".to_owned(),
+            Endpoint::TournamentByIdGet {
+                ref tournament_id, ..
+            }
+            | Endpoint::TournamentByIdUpdate(ref tournament_id) => {
+                format!("/v1/tournaments/{}", tournament_id.0)
+            }
+            Endpoint::TournamentSettings(ref tournament_id) => {
+                format!("/v1/tournaments/{}/settings", tournament_id.0)
+            }
+            Endpoint::MatchesByTournament {
+                ref tournament_id, ..
+            }
+            | Endpoint::MatchesByStage {
+                ref tournament_id, ..
+            }
+            | Endpoint::MatchesByParticipant {
+                ref tournament_id, ..
+            } => format!("/v1/tournaments/{}/matches", tournament_id.0),
+            Endpoint::MatchByIdGet {
+                ref tournament_id,
+                ref match_id,
+                ..
+            }
+            | Endpoint::MatchByIdUpdate {
+                ref tournament_id,
+                ref match_id,
+            } => format!("/v1/tournaments/{}/matches/{}", tournament_id.0, match_id.0),
+            Endpoint::MatchesByDiscipline {
+                ref discipline_id, ..
+            } => format!("/v1/disciplines/{}/matches", discipline_id.0),
+            Endpoint::MatchResult(ref tournament_id, ref match_id) => format!(
+                "/v1/tournaments/{}/matches/{}/result",
+                tournament_id.0, match_id.0
+            ),
+            Endpoint::MatchGames {
+                ref tournament_id,
+                ref match_id,
+                ..
+            } => format!(
+                "/v1/tournaments/{}/matches/{}/games",
+                tournament_id.0, match_id.0
+            ),
+            Endpoint::MatchGameByNumberGet {
+                ref tournament_id,
+                ref match_id,
+                ref game_number,
+                ..
+            }
+            | Endpoint::MatchGameByNumberUpdate {
+                ref tournament_id,
+                ref match_id,
+                ref game_number,
+            } => format!(
+                "/v1/tournaments/{}/matches/{}/games/{}",
+                tournament_id.0, match_id.0, game_number.0
+            ),
+            Endpoint::MatchGameResultGet {
+                ref tournament_id,
+                ref match_id,
+                ref game_number,
+            }
+            | Endpoint::MatchGameResultUpdate {
+                ref tournament_id,
+                ref match_id,
+                ref game_number,
+                ..
+            } => format!(
+                "/v1/tournaments/{}/matches/{}/games/{}/result",
+                tournament_id.0, match_id.0, game_number.0
+            ),
+            Endpoint::Participants {
+                ref tournament_id, ..
+            }
+            | Endpoint::ParticipantCreate(ref tournament_id)
+            | Endpoint::ParticipantsUpdate(ref tournament_id) => {
+                format!("/v1/tournaments/{}/participants", tournament_id.0)
+            }
+            Endpoint::ParticipantById(ref tournament_id, ref participant_id) => format!(
+                "/v1/tournaments/{}/participants/{}",
+                tournament_id.0, participant_id.0
+            ),
+            Endpoint::Permissions(ref tournament_id) => {
+                format!("/v1/tournaments/{}/permissions", tournament_id.0)
+            }
+            Endpoint::PermissionById(ref tournament_id, ref permission_id) => format!(
+                "/v1/tournaments/{}/permissions/{}",
+                tournament_id.0, permission_id.0
+            ),
+            Endpoint::PermissionInvitation(ref tournament_id, ref permission_id) => format!(
+                "/v1/tournaments/{}/permissions/{}/invitation",
+                tournament_id.0, permission_id.0
+            ),
             Endpoint::Stages(ref tournament_id) => {
-                address = format!("/v1/tournaments/{}/stages", tournament_id.0)
+                format!("/v1/tournaments/{}/stages", tournament_id.0)
+            }
+            Endpoint::FinalStandings(ref tournament_id) => {
+                format!("/v1/tournaments/{}/final-standings", tournament_id.0)
+            }
+            Endpoint::CustomFields(ref tournament_id) => {
+                format!("/v1/tournaments/{}/custom-fields", tournament_id.0)
             }
             Endpoint::Videos {
-                ref tournament_id,
+                ref tournament_id, ..
+            } => format!("/v1/tournaments/{}/videos", tournament_id.0),
+        }
+    }
+}
+
+impl QueryParams for Endpoint {
+    fn query_params(&self) -> Vec<(String, String)> {
+        let param = |name: &str, value: String| vec![(name.to_owned(), value)];
+        let paging = |page: Option<i64>, per_page: Option<i64>| {
+            let page = page.map(|p| ("page".to_owned(), p.to_string()));
+            let per_page = per_page.map(|p| ("per_page".to_owned(), p.to_string()));
+            page.into_iter().chain(per_page).collect::<Vec<_>>()
+        };
+        match *self {
+            Endpoint::DisciplinesPage { per_page } => paging(None, Some(per_page)),
+            Endpoint::AllTournaments {
+                with_streams,
                 ref filter,
             } => {
-                address = format!(
-                    "/v1/tournaments/{}/videos?{}",
-                    tournament_id.0,
-                    tournament_videos(filter.clone())
-                )
+                let mut params = param("with_streams", flag(with_streams));
+                params.extend(filter.query_params());
+                params
             }
-        };
-
-        fmt.write_str(&format!("{}{}", API_BASE, address))
+            Endpoint::TournamentByIdGet { with_streams, .. } => {
+                param("with_streams", flag(with_streams))
+            }
+            Endpoint::MatchesByTournament {
+                with_games,
+                page,
+                per_page,
+                ..
+            } => {
+                let mut params = param("with_games", flag(with_games));
+                params.extend(paging(page, per_page));
+                params
+            }
+            Endpoint::MatchByIdGet { with_games, .. } => param("with_games", flag(with_games)),
+            Endpoint::MatchesByDiscipline { ref filter, .. } => filter.query_params(),
+            Endpoint::MatchesByStage {
+                ref stage_number,
+                group_number,
+                with_games,
+                page,
+                per_page,
+                ..
+            } => {
+                let mut params = param("stage_number", stage_number.0.to_string());
+                if let Some(group_number) = group_number {
+                    params.extend(param("group_number", group_number.to_string()));
+                }
+                params.extend(param("with_games", flag(with_games)));
+                params.extend(paging(page, per_page));
+                params
+            }
+            Endpoint::MatchesByParticipant {
+                ref participant_id,
+                with_games,
+                page,
+                per_page,
+                ..
+            } => {
+                let mut params = param("participant_id", participant_id.0.clone());
+                params.extend(param("with_games", flag(with_games)));
+                params.extend(paging(page, per_page));
+                params
+            }
+            Endpoint::MatchGames { with_stats, .. }
+            | Endpoint::MatchGameByNumberGet { with_stats, .. } => {
+                param("with_stats", flag(with_stats))
+            }
+            Endpoint::MatchGameResultUpdate { update_match, .. } => {
+                param("update_match", flag(update_match))
+            }
+            Endpoint::Participants { ref filter, .. } => filter.query_params(),
+            Endpoint::Videos { ref filter, .. } => filter.query_params(),
+            _ => Vec::new(),
+        }
     }
 }
 
-fn match_filter(f: MatchFilter) -> String {
-    let mut out = Vec::new();
-    if let Some(f) = f.featured {
-        out.push(format!("featured={}", if f { 1 } else { 0 }));
-    }
-    if let Some(r) = f.has_result {
-        out.push(format!("has_result={}", if r { 1 } else { 0 }));
-    }
-    if let Some(s) = f.sort {
-        out.push(format!("sort={}", s.to_string()));
-    }
-    if let Some(i) = f.participant_id {
-        out.push(format!("participant_id={}", i.0));
-    }
-    if let Some(ref i) = f.tournament_ids {
-        out.push(format!(
-            "tournament_ids={}",
-            i.iter()
-                .map(|i| i.0.as_str())
-                .collect::<Vec<&str>>()
-                .join(",")
-        ));
-    }
-    out.push(format!("with_games={}", if f.with_games { 1 } else { 0 }));
-    if let Some(d) = f.before_date {
-        out.push(format!("before_date={}", d));
-    }
-    if let Some(d) = f.after_date {
-        out.push(format!("after_date={}", d));
-    }
-    if let Some(p) = f.page {
-        out.push(format!("page={}", p));
+impl ::std::fmt::Display for Endpoint {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let query = self.to_query_string();
+        if query.is_empty() {
+            write!(fmt, "{}{}", API_BASE, self.path())
+        } else {
+            write!(fmt, "{}{}?{}", API_BASE, self.path(), query)
+        }
     }
-    out.join("&")
 }
 
-fn tournament_participants(f: TournamentParticipantsFilter) -> String {
-    format!(
-        "with_lineup={}&with_custom_fields={}&sort={}&page={}",
-        f.with_lineup as u64,
-        f.with_custom_fields as u64,
-        f.sort.to_string(),
-        f.page
-    )
-}
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_matches_by_stage_address() {
+        use crate::{endpoints::Endpoint, StageNumber, TournamentId};
 
-fn tournament_videos(f: TournamentVideosFilter) -> String {
-    let mut out = Vec::new();
-    if let Some(c) = f.category {
-        out.push(format!("category={}", c.to_string()));
+        let endpoint = Endpoint::MatchesByStage {
+            tournament_id: TournamentId("1".to_owned()),
+            stage_number: StageNumber(2),
+            group_number: Some(1),
+            with_games: false,
+            page: None,
+            per_page: None,
+        };
+        assert!(endpoint
+            .to_string()
+            .ends_with("/v1/tournaments/1/matches?stage_number=2&group_number=1&with_games=0"));
+        assert_eq!(
+            endpoint.resource(),
+            "the matches of group 1 of stage 2 of tournament 1"
+        );
     }
-    out.push(format!("sort={}", f.sort.to_string()));
-    if let Some(p) = f.page {
-        out.push(format!("page={}", p));
+
+    #[test]
+    fn test_matches_by_participant_address() {
+        use crate::{endpoints::Endpoint, ParticipantId, TournamentId};
+
+        let endpoint = Endpoint::MatchesByParticipant {
+            tournament_id: TournamentId("1".to_owned()),
+            participant_id: ParticipantId("2".to_owned()),
+            with_games: true,
+            page: None,
+            per_page: None,
+        };
+        assert!(endpoint
+            .to_string()
+            .ends_with("/v1/tournaments/1/matches?participant_id=2&with_games=1"));
+        let endpoint = Endpoint::MatchesByParticipant {
+            tournament_id: TournamentId("1".to_owned()),
+            participant_id: ParticipantId("2".to_owned()),
+            with_games: true,
+            page: Some(2),
+            per_page: Some(10),
+        };
+        assert!(endpoint.to_string().ends_with(
+            "/v1/tournaments/1/matches?participant_id=2&with_games=1&page=2&per_page=10"
+        ));
     }
-    out.join("&")
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::endpoints::match_filter;
-    use crate::filters::MatchFilter;
+    #[test]
+    fn test_filtered_addresses() {
+        use crate::{endpoints::Endpoint, DisciplineId, MatchFilter, TournamentFilter};
+
+        let endpoint = Endpoint::AllTournaments {
+            with_streams: false,
+            filter: TournamentFilter::default().name("Weekly Cup"),
+        };
+        assert!(endpoint
+            .to_string()
+            .ends_with("/v1/tournaments?with_streams=0&name=Weekly%20Cup"));
+        let endpoint = Endpoint::AllTournaments {
+            with_streams: false,
+            filter: TournamentFilter::default().page(3i64).per_page(20i64),
+        };
+        assert!(endpoint
+            .to_string()
+            .ends_with("/v1/tournaments?with_streams=0&page=3&per_page=20"));
+        let endpoint = Endpoint::MatchesByDiscipline {
+            discipline_id: DisciplineId("counterstrike_go".to_owned()),
+            filter: MatchFilter::default().page(2i64),
+        };
+        assert!(endpoint.to_string().ends_with(
+            "/v1/disciplines/counterstrike_go/matches?sort=date_asc&with_games=0&page=2"
+        ));
+        assert!(Endpoint::MyTournaments
+            .to_string()
+            .ends_with("/v1/me/tournaments"));
+    }
 
     #[test]
-    fn test_match_filter_to_get_string() {
-        let f = MatchFilter::default()
-            .featured(true)
-            .has_result(true)
-            .page(2i64);
+    fn test_endpoint_resource_in_error_context() {
+        use crate::{endpoints::Endpoint, Error, MatchId, TournamentId};
+        use std::error::Error as StdError;
+
+        let endpoint = Endpoint::MatchByIdGet {
+            tournament_id: TournamentId("1".to_owned()),
+            match_id: MatchId("2".to_owned()),
+            with_games: false,
+        };
+        let error =
+            Error::Rest("Can't get the token").context(format!("fetching {}", endpoint.resource()));
         assert_eq!(
-            match_filter(f),
-            "featured=1&has_result=1&sort=date_asc&with_games=0&page=2"
+            error.to_string(),
+            "Error while fetching match 2 of tournament 1"
         );
+        assert!(matches!(error.root(), Error::Rest(_)));
+        assert_eq!(error.source().unwrap().to_string(), "Can't get the token");
     }
 }
@@ -1,5 +1,4 @@
 use chrono::format::ParseError;
-use reqwest::Error as ReqwestError;
 use serde_json::Error as JsonError;
 use std::error::Error as StdError;
 use std::fmt::Display;
@@ -10,12 +9,60 @@ pub type Result<T> = ::std::result::Result<T, Error>;
 
 /// A toornament service error type
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "snake_case")]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
 pub enum ToornamentErrorType {
     /// Duplicate email error type
     EmailDuplicate,
     /// Match integrity error type
     MatchIntegrity,
+    /// Duplicate participant error type
+    ParticipantDuplicate,
+    /// The registrations of the tournament are closed
+    RegistrationClosed,
+    /// The tournament has reached its size
+    TournamentFull,
+    /// An error type not known by this crate
+    Other(String),
+}
+
+impl ToornamentErrorType {
+    /// Returns the name of the error type used by the service.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            ToornamentErrorType::EmailDuplicate => "email_duplicate",
+            ToornamentErrorType::MatchIntegrity => "match_integrity",
+            ToornamentErrorType::ParticipantDuplicate => "participant_duplicate",
+            ToornamentErrorType::RegistrationClosed => "registration_closed",
+            ToornamentErrorType::TournamentFull => "tournament_full",
+            ToornamentErrorType::Other(ref name) => name,
+        }
+    }
+}
+
+impl From<String> for ToornamentErrorType {
+    fn from(name: String) -> ToornamentErrorType {
+        match name.as_str() {
+            "email_duplicate" => ToornamentErrorType::EmailDuplicate,
+            "match_integrity" => ToornamentErrorType::MatchIntegrity,
+            "participant_duplicate" => ToornamentErrorType::ParticipantDuplicate,
+            "registration_closed" => ToornamentErrorType::RegistrationClosed,
+            "tournament_full" => ToornamentErrorType::TournamentFull,
+            _ => ToornamentErrorType::Other(name),
+        }
+    }
+}
+
+impl From<ToornamentErrorType> for String {
+    fn from(error_type: ToornamentErrorType) -> String {
+        error_type.as_str().to_owned()
+    }
+}
+
+impl Display for ToornamentErrorType {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        fmt.write_str(self.as_str())
+    }
 }
 
 /// A toornament service error scope
@@ -30,6 +77,7 @@ pub enum ToornamentErrorScope {
 
 /// A list of toornament service errors
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct ToornamentError {
     /// Error message.
     pub message: String,
@@ -53,11 +101,54 @@ pub struct ToornamentErrors(pub Vec<ToornamentError>);
 
 /// Toornament service error
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct ToornamentServiceError {
     /// A list of toornament service errors
     pub errors: ToornamentErrors,
 }
 
+impl Display for ToornamentErrorScope {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        fmt.write_str(match *self {
+            ToornamentErrorScope::Query => "query",
+            ToornamentErrorScope::Body => "body",
+        })
+    }
+}
+
+impl Display for ToornamentError {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(fmt, "{} (in the {}", self.message, self.scope)?;
+        if let Some(ref path) = self.property_path {
+            write!(fmt, ", property \"{}\"", path)?;
+        }
+        if let Some(ref value) = self.invalid_value {
+            write!(fmt, ", invalid value \"{}\"", value)?;
+        }
+        if let Some(ref error_type) = self.error_type {
+            write!(fmt, ", type {}", error_type)?;
+        }
+        fmt.write_str(")")
+    }
+}
+
+impl Display for ToornamentErrors {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let errors = self
+            .0
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<String>>();
+        fmt.write_str(&errors.join("; "))
+    }
+}
+
+impl Display for ToornamentServiceError {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        self.errors.fmt(fmt)
+    }
+}
+
 /// Iter errors
 #[derive(Debug, Clone)]
 pub enum IterError {
@@ -71,40 +162,182 @@ pub enum IterError {
     NoPermissionId,
     /// A discipline with such id does not exist
     NoSuchDiscipline(crate::DisciplineId),
+    /// A stage with such number does not exist in the tournament
+    NoSuchStage(crate::TournamentId, crate::StageNumber),
 }
 
 impl Display for IterError {
     fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
-        let s;
-        match *self {
+        let s = match *self {
             IterError::NoSuchTournament(ref id) => {
-                s = format!("A tournament with id ({}) does not exist", id.0);
+                format!("A tournament with id ({}) does not exist", id.0)
             }
-            IterError::NoTournamentId(_) => {
-                s = "A tournament does not have an id set.".to_owned();
+            IterError::NoTournamentId(_) => "A tournament does not have an id set.".to_owned(),
+            IterError::NoSuchMatch(ref t_id, ref m_id) => format!(
+                "A match does not exist (tournament id = {}, match id = {})",
+                t_id.0, m_id.0
+            ),
+            IterError::NoPermissionId => "A permission does not have an id set.".to_owned(),
+            IterError::NoSuchDiscipline(ref id) => {
+                format!("A permission with id ({}) does not exist.", id.0)
             }
-            IterError::NoSuchMatch(ref t_id, ref m_id) => {
-                s = format!(
-                    "A match does not exist (tournament id = {}, match id = {})",
-                    t_id.0, m_id.0
-                );
+            IterError::NoSuchStage(ref id, ref number) => format!(
+                "A stage does not exist (tournament id = {}, stage number = {})",
+                id.0, number.0
+            ),
+        };
+        fmt.write_str(&s)
+    }
+}
+
+/// Organizer errors
+#[derive(Debug, Clone)]
+pub enum OrganizerError {
+    /// A tournament with such id does not exist
+    NoSuchTournament(crate::TournamentId),
+    /// A tournament is in a status which does not allow the operation
+    InvalidStatus(crate::TournamentId, crate::TournamentStatus),
+    /// A stage with such number does not exist in the tournament
+    NoSuchStage(crate::TournamentId, crate::StageNumber),
+    /// A tournament has less participants than required to be seeded
+    NotEnoughParticipants(crate::TournamentId, usize),
+    /// A tournament has more participants than its size allows
+    TooManyParticipants(crate::TournamentId, usize),
+    /// A tournament still has matches which are not completed
+    UnfinishedMatches(crate::TournamentId, Vec<crate::MatchId>),
+    /// A tournament does not have any stage configured
+    NoStages(crate::TournamentId),
+}
+
+impl Display for OrganizerError {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let s = match *self {
+            OrganizerError::NoSuchTournament(ref id) => {
+                format!("A tournament with id ({}) does not exist", id.0)
             }
-            IterError::NoPermissionId => {
-                s = "A permission does not have an id set.".to_owned();
+            OrganizerError::InvalidStatus(ref id, ref status) => format!(
+                "The operation is not allowed for the tournament with id ({}) in status {:?}",
+                id.0, status
+            ),
+            OrganizerError::NoSuchStage(ref id, ref number) => format!(
+                "A stage does not exist (tournament id = {}, stage number = {})",
+                id.0, number.0
+            ),
+            OrganizerError::NotEnoughParticipants(ref id, count) => format!(
+                "The tournament with id ({}) has not enough participants: {}",
+                id.0, count
+            ),
+            OrganizerError::TooManyParticipants(ref id, count) => format!(
+                "The tournament with id ({}) has more participants than its size: {}",
+                id.0, count
+            ),
+            OrganizerError::UnfinishedMatches(ref id, ref matches) => format!(
+                "The tournament with id ({}) has unfinished matches: {}",
+                id.0,
+                matches
+                    .iter()
+                    .map(|m| m.0.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            ),
+            OrganizerError::NoStages(ref id) => {
+                format!("The tournament with id ({}) has no stages configured", id.0)
             }
-            IterError::NoSuchDiscipline(ref id) => {
-                s = format!("A permission with id ({}) does not exist.", id.0);
+        };
+        fmt.write_str(&s)
+    }
+}
+
+/// Client configuration errors
+#[derive(Debug, Clone)]
+pub enum ConfigError {
+    /// A required setting is missing
+    Missing(String),
+    /// A setting has an invalid value
+    Invalid(String, String),
+    /// A configuration file could not be parsed
+    Parse(String),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let s = match *self {
+            ConfigError::Missing(ref name) => format!("The setting {} is missing", name),
+            ConfigError::Invalid(ref name, ref value) => {
+                format!("The setting {} has an invalid value: {}", name, value)
+            }
+            ConfigError::Parse(ref message) => {
+                format!("The configuration could not be parsed: {}", message)
             }
         };
         fmt.write_str(&s)
     }
 }
 
-/// Toornament API error type.
+/// Webhook delivery errors
+#[derive(Debug, Clone)]
+pub enum WebhookError {
+    /// The delivery has no signature header
+    MissingSignature,
+    /// The signature of the delivery does not match its body
+    InvalidSignature,
+}
+
+impl Display for WebhookError {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let s = match *self {
+            WebhookError::MissingSignature => "The webhook delivery is not signed",
+            WebhookError::InvalidSignature => "The signature of the webhook delivery is invalid",
+        };
+        fmt.write_str(s)
+    }
+}
+
+/// Score file import errors
+#[derive(Debug, Clone)]
+pub enum ImportError {
+    /// A record of the file is invalid, with the number of the line it starts at and the
+    /// reason
+    Invalid(usize, String),
+}
+
+impl Display for ImportError {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        match *self {
+            ImportError::Invalid(line, ref reason) => {
+                write!(fmt, "The line {} of the file is invalid: {}", line, reason)
+            }
+        }
+    }
+}
+
+/// Object validation errors
+#[derive(Debug, Clone)]
+pub enum ValidationError {
+    /// A field has an invalid value, with the name of the field and the reason
+    Invalid(String, String),
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        match *self {
+            ValidationError::Invalid(ref field, ref reason) => {
+                write!(fmt, "The field \"{}\" is invalid: {}", field, reason)
+            }
+        }
+    }
+}
+
+/// Toornament API error type. New variants may be added in minor versions, and the variants
+/// holding the errors of the HTTP client, `Reqwest`, `Toornament` and `Status`, exist only
+/// with the `client-blocking` or the `client-async` feature, so a `match` on it must have a
+/// wildcard arm.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// A `reqwest` crate error
-    Reqwest(ReqwestError),
+    #[cfg(any(feature = "client-blocking", feature = "client-async"))]
+    Reqwest(reqwest::Error),
     /// A `serde_json` crate error
     Json(JsonError),
     /// A `std::io` module error
@@ -112,36 +345,96 @@ pub enum Error {
     /// A date parse error (`chrono` crate error)
     Date(ParseError),
     /// A error common toornament service error
+    #[cfg(any(feature = "client-blocking", feature = "client-async"))]
     Toornament(::reqwest::StatusCode, ToornamentServiceError),
-    /// A generic non-success response from the REST API
-    Status(::reqwest::StatusCode),
+    /// A generic non-success response from the REST API, or a response which is not JSON
+    /// (like an error page of a proxy), with the beginning of its body
+    #[cfg(any(feature = "client-blocking", feature = "client-async"))]
+    Status(::reqwest::StatusCode, String),
     /// A rate limit error, with how many milliseconds to wait before retrying
     RateLimited(u64),
     /// An iter error
     Iter(IterError),
+    /// An organizer error
+    Organizer(OrganizerError),
+    /// A client configuration error
+    Config(ConfigError),
+    /// A webhook delivery error
+    Webhook(WebhookError),
+    /// A score file import error
+    Import(ImportError),
+    /// An object validation error
+    Validation(ValidationError),
+    /// The operation was cancelled or its deadline has passed, with the results it got before
+    Cancelled(crate::Partial),
+    /// The request was not sent because the circuit breaker of the client is open
+    CircuitOpen,
+    /// A destructive operation was called without confirmation on a client requiring it
+    Unconfirmed,
+    /// An update was denied by the write policy of the client, with the reason
+    Denied(String),
+    /// A participant was rejected by the moderation of the client, with the reason
+    Rejected(String),
+    /// The service refused the request because the access token lacks a scope, with the
+    /// scopes the service asked for, if it told them
+    MissingScope(Vec<crate::Scope>),
     /// A rest-api error
     Rest(&'static str),
+    /// An error with the description of the operation which caused it, for example
+    /// "fetching match 2 of tournament 1". It displays the description only, the error is its
+    /// `source()`. Matching on the error needs `Error::root`.
+    Context(String, Box<Error>),
 }
 
-impl From<::reqwest::blocking::Response> for Error {
-    fn from(response: ::reqwest::blocking::Response) -> Error {
-        #[derive(serde::Deserialize)]
-        struct TooManyRequests {
-            retry_after: u64,
+impl Error {
+    /// Wraps the error with the description of the operation which caused it. The wrapped
+    /// error is no longer matched by its own variant: match on `Error::root` instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use toornament::*;
+    ///
+    /// let error = Error::Unconfirmed.context("deleting tournament 1");
+    /// assert_eq!(error.to_string(), "Error while deleting tournament 1");
+    /// assert!(matches!(error.root(), Error::Unconfirmed));
+    /// ```
+    pub fn context<S: Into<String>>(self, context: S) -> Error {
+        Error::Context(context.into(), Box::new(self))
+    }
+
+    /// Returns the error without the contexts it is wrapped with.
+    pub fn root(&self) -> &Error {
+        match *self {
+            Error::Context(_, ref inner) => inner.root(),
+            _ => self,
         }
+    }
 
-        let status = response.status();
-        if status == ::reqwest::StatusCode::TOO_MANY_REQUESTS {
-            if let Ok(value) = response.json::<TooManyRequests>() {
-                return Error::RateLimited(value.retry_after);
-            }
-        } else if !status.is_success() {
-            if let Ok(e) = response.json::<ToornamentServiceError>() {
-                return Error::Toornament(status, e);
+    /// Returns the error with the `results` as the partial results of a cancellation, wrapped
+    /// in contexts or not. The other errors are returned as they are.
+    #[cfg(feature = "client-blocking")]
+    pub(crate) fn with_partial<T: std::any::Any + Send + Sync>(self, results: T) -> Error {
+        match self {
+            Error::Cancelled(_) => Error::Cancelled(crate::Partial::new(results)),
+            Error::Context(context, inner) => {
+                Error::Context(context, Box::new(inner.with_partial(results)))
             }
+            e => e,
         }
+    }
+}
 
-        Error::Status(status)
+#[cfg(feature = "client-blocking")]
+impl From<::reqwest::blocking::Response> for Error {
+    fn from(response: ::reqwest::blocking::Response) -> Error {
+        let status = response.status();
+        let content_type = crate::protocol::content_type(response.headers()).map(str::to_owned);
+        let body = response.bytes().unwrap_or_default();
+        match crate::protocol::check(status, content_type.as_deref(), &body) {
+            Err(e) => e,
+            Ok(()) => Error::Status(status, String::new()),
+        }
     }
 }
 
@@ -151,8 +444,9 @@ impl From<IoError> for Error {
     }
 }
 
-impl From<ReqwestError> for Error {
-    fn from(err: ReqwestError) -> Error {
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Error {
         Error::Reqwest(err)
     }
 }
@@ -163,6 +457,13 @@ impl From<JsonError> for Error {
     }
 }
 
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for Error {
+    fn from(err: rusqlite::Error) -> Error {
+        Error::Io(IoError::other(err))
+    }
+}
+
 impl From<ParseError> for Error {
     fn from(err: ParseError) -> Error {
         Error::Date(err)
@@ -172,18 +473,51 @@ impl From<ParseError> for Error {
 impl Display for Error {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
         match *self {
+            #[cfg(any(feature = "client-blocking", feature = "client-async"))]
             Error::Reqwest(ref inner) => inner.fmt(f),
             Error::Json(ref inner) => inner.fmt(f),
             Error::Io(ref inner) => inner.fmt(f),
             Error::Date(ref inner) => inner.fmt(f),
-            _ => f.write_str(&format!("{:?}", self)),
+            Error::Organizer(ref inner) => inner.fmt(f),
+            Error::Config(ref inner) => inner.fmt(f),
+            Error::Webhook(ref inner) => inner.fmt(f),
+            Error::Import(ref inner) => inner.fmt(f),
+            Error::Validation(ref inner) => inner.fmt(f),
+            Error::Context(ref context, _) => write!(f, "Error while {}", context),
+            #[cfg(any(feature = "client-blocking", feature = "client-async"))]
+            Error::Toornament(status, ref inner) => {
+                write!(f, "The service responded with {}: {}", status, inner)
+            }
+            #[cfg(any(feature = "client-blocking", feature = "client-async"))]
+            Error::Status(status, _) => write!(f, "The service responded with {}", status),
+            Error::RateLimited(ms) => write!(f, "Too many requests, retry after {} ms", ms),
+            Error::Iter(ref inner) => inner.fmt(f),
+            Error::Cancelled(_) => f.write_str("The operation was cancelled"),
+            Error::CircuitOpen => f.write_str(
+                "The circuit breaker is open after repeated failures of the service",
+            ),
+            Error::Unconfirmed => f.write_str(
+                "The destructive operation must be confirmed with `Toornament::confirm_destructive`",
+            ),
+            Error::Denied(ref reason) => write!(f, "The update was denied: {}", reason),
+            Error::Rejected(ref reason) => write!(f, "The participant was rejected: {}", reason),
+            Error::MissingScope(ref scopes) if scopes.is_empty() => {
+                f.write_str("The access token lacks a scope required by the request")
+            }
+            Error::MissingScope(ref scopes) => {
+                let scopes = scopes.iter().map(crate::Scope::as_str).collect::<Vec<_>>();
+                write!(f, "The access token lacks the scopes: {}", scopes.join(" "))
+            }
+            Error::Rest(message) => f.write_str(message),
         }
     }
 }
 
 impl StdError for Error {
-    fn cause(&self) -> Option<&dyn StdError> {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
         match *self {
+            Error::Context(_, ref inner) => Some(inner.as_ref()),
+            #[cfg(any(feature = "client-blocking", feature = "client-async"))]
             Error::Reqwest(ref inner) => Some(inner),
             Error::Json(ref inner) => Some(inner),
             Error::Io(ref inner) => Some(inner),
@@ -192,3 +526,68 @@ impl StdError for Error {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_without_client() {
+        let error = Error::Validation(ValidationError::Invalid(
+            "name".to_owned(),
+            "must not be empty".to_owned(),
+        ))
+        .context("creating a tournament");
+        let reason = match *error.root() {
+            Error::Validation(ValidationError::Invalid(_, ref reason)) => reason.as_str(),
+            _ => unreachable!(),
+        };
+        assert_eq!(reason, "must not be empty");
+        assert!(StdError::source(&error).is_some());
+        let error = Error::from(serde_json::from_str::<u8>("x").unwrap_err());
+        assert!(matches!(error, Error::Json(_)));
+    }
+
+    #[cfg(any(feature = "client-blocking", feature = "client-async"))]
+    #[test]
+    fn test_service_error_display() {
+        let json = r#"{
+            "errors": [
+                {
+                    "message": "This value is already used.",
+                    "scope": "body",
+                    "property_path": "email",
+                    "invalid_value": "a@b.c",
+                    "type": "email_duplicate"
+                },
+                {
+                    "message": "This value should not be blank.",
+                    "scope": "query",
+                    "property_path": null,
+                    "invalid_value": null,
+                    "type": "some_future_type"
+                }
+            ]
+        }"#;
+        let errors = serde_json::from_str::<ToornamentServiceError>(json).unwrap();
+        let error = Error::Toornament(::reqwest::StatusCode::BAD_REQUEST, errors);
+        assert_eq!(
+            error.to_string(),
+            "The service responded with 400 Bad Request: This value is already used. (in the \
+             body, property \"email\", invalid value \"a@b.c\", type email_duplicate); This \
+             value should not be blank. (in the query, type some_future_type)"
+        );
+        let errors = match error {
+            Error::Toornament(_, e) => e.errors,
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            errors.0[1].error_type,
+            Some(ToornamentErrorType::Other("some_future_type".to_owned()))
+        );
+        assert_eq!(
+            serde_json::to_string(&errors.0[0].error_type).unwrap(),
+            "\"email_duplicate\""
+        );
+    }
+}
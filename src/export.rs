@@ -0,0 +1,941 @@
+//! This module exports the brackets of a tournament for embedding them into web sites.
+//!
+//! The JSON structure produced by `bracket_to_json` is stable:
+//!
+//! ```json
+//! {
+//!   "stages": [{
+//!     "number": 1, "name": "Playoffs", "type": "single_elimination", "size": 8,
+//!     "groups": [{
+//!       "number": 1,
+//!       "rounds": [{
+//!         "number": 1,
+//!         "matches": [{
+//!           "id": "5617bb3af3df95f2318b4567", "number": 1, "status": "completed",
+//!           "date": "2015-09-06T00:10:00-06:00",
+//!           "opponents": [{
+//!             "number": 1, "name": "Evil Geniuses", "score": 2, "forfeit": false,
+//!             "winner": true
+//!           }]
+//!         }]
+//!       }]
+//!     }]
+//!   }]
+//! }
+//! ```
+//!
+//! Groups, rounds and matches are ordered by their numbers, opponents by their numbers. The
+//! `name` and `score` of an opponent are `null` when unknown yet.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use toornament::*;
+//!
+//! let toornament = Toornament::with_application("API_TOKEN",
+//!                                               "CLIENT_ID",
+//!                                               "CLIENT_SECRET").unwrap();
+//! let id = TournamentId("1".to_owned());
+//! let stages = toornament.tournament_stages(id.clone()).unwrap();
+//! let matches = toornament.matches(id, None, Includes::none()).unwrap();
+//! let json = toornament::export::bracket_to_json(&stages, &matches).unwrap();
+//! let html = toornament::export::bracket_to_html(&stages, &matches);
+//! ```
+//!
+//! `to_dot` draws the structure of the brackets as a Graphviz graph, to check the progression
+//! of the opponents between the matches (see `Progression`) or to look at an unusual format:
+//!
+//! ```sh
+//! dot -Tsvg bracket.dot > bracket.svg
+//! ```
+//!
+//! With the `sqlite` feature enabled, `to_sqlite` writes a whole `TournamentSnapshot` into
+//! an SQLite database with the `tournaments`, `participants`, `matches`, `games` and
+//! `opponents` tables, so that it can be analyzed with plain SQL.
+//!
+//! `participant_contacts` extracts the emails and the contact custom fields of the
+//! participants (and of the players of their lineups) as CSV or JSON, for example to mail
+//! check-in instructions. The private custom fields are only exported when asked for with
+//! `ContactOptions::include_private`.
+//!
+//! ```rust,no_run
+//! use toornament::*;
+//! use toornament::export::{ContactFormat, ContactOptions};
+//!
+//! # let toornament = Toornament::from_env().unwrap();
+//! let filter = TournamentParticipantsFilter::default()
+//!     .with_lineup(true)
+//!     .with_custom_fields(true);
+//! let participants = toornament
+//!     .tournament_participants(TournamentId("1".to_owned()), filter)
+//!     .unwrap();
+//! let options = ContactOptions::default()
+//!     .field_types(vec![CustomFieldType::Fullname])
+//!     .checked_in_only(true);
+//! let csv = toornament::export::participant_contacts(&participants, &options).unwrap();
+//! ```
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, FixedOffset};
+
+#[cfg(feature = "sqlite")]
+use crate::common::MatchResultSimple;
+use crate::error::Result;
+use crate::matches::{Match, MatchStatus, Matches};
+use crate::participants::{CustomFieldType, CustomFields, Participant, Participants};
+use crate::progression::{Outcome, Progression};
+use crate::stages::{StageType, Stages};
+
+/// An exported opponent of a match.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct BracketOpponent {
+    /// Number of the opponent in the match.
+    pub number: i64,
+    /// Name of the participant, if known.
+    pub name: Option<String>,
+    /// Score of the opponent, if known.
+    pub score: Option<i64>,
+    /// Whether the opponent has forfeited.
+    pub forfeit: bool,
+    /// Whether the opponent has won the match.
+    pub winner: bool,
+}
+
+/// An exported match.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct BracketMatch {
+    /// Match identifier.
+    pub id: String,
+    /// Number of the match.
+    pub number: u64,
+    /// Status of the match.
+    pub status: MatchStatus,
+    /// Date of the match.
+    pub date: DateTime<FixedOffset>,
+    /// Opponents of the match.
+    pub opponents: Vec<BracketOpponent>,
+}
+
+/// An exported round of a group.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct BracketRound {
+    /// Number of the round.
+    pub number: u64,
+    /// Matches of the round.
+    pub matches: Vec<BracketMatch>,
+}
+
+/// An exported group of a stage.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct BracketGroup {
+    /// Number of the group.
+    pub number: u64,
+    /// Rounds of the group.
+    pub rounds: Vec<BracketRound>,
+}
+
+/// An exported stage.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct BracketStage {
+    /// Number of the stage.
+    pub number: i64,
+    /// Name of the stage.
+    pub name: String,
+    /// Type of the stage.
+    #[serde(rename = "type")]
+    pub stage_type: StageType,
+    /// Number of participants of the stage.
+    pub size: i64,
+    /// Groups of the stage.
+    pub groups: Vec<BracketGroup>,
+}
+
+/// An exported bracket of a tournament.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct Bracket {
+    /// Stages of the tournament.
+    pub stages: Vec<BracketStage>,
+}
+
+fn export_match(m: &Match) -> BracketMatch {
+    let winner = m.winner().map(|o| o.number);
+    let mut opponents = m
+        .opponents
+        .0
+        .iter()
+        .map(|o| BracketOpponent {
+            number: o.number,
+            name: o.participant.as_ref().map(|p| p.name.clone()),
+            score: o.score,
+            forfeit: o.forfeit,
+            winner: Some(o.number) == winner || o.rank == Some(1),
+        })
+        .collect::<Vec<BracketOpponent>>();
+    opponents.sort_by_key(|o| o.number);
+    BracketMatch {
+        id: m.id.0.clone(),
+        number: m.number,
+        status: m.status.clone(),
+        date: m.date,
+        opponents,
+    }
+}
+
+/// Builds the bracket structure from the stages and the matches of a tournament.
+pub fn bracket(stages: &Stages, matches: &Matches) -> Bracket {
+    let stages = stages
+        .0
+        .iter()
+        .map(|stage| {
+            let mut groups = BTreeMap::<u64, BTreeMap<u64, Vec<&Match>>>::new();
+            for m in matches
+                .0
+                .iter()
+                .filter(|m| m.stage_number as i64 == stage.number.0)
+            {
+                groups
+                    .entry(m.group_number)
+                    .or_default()
+                    .entry(m.round_number)
+                    .or_default()
+                    .push(m);
+            }
+            BracketStage {
+                number: stage.number.0,
+                name: stage.name.clone(),
+                stage_type: stage.stage_type.clone(),
+                size: stage.size,
+                groups: groups
+                    .into_iter()
+                    .map(|(number, rounds)| BracketGroup {
+                        number,
+                        rounds: rounds
+                            .into_iter()
+                            .map(|(number, mut matches)| {
+                                matches.sort_by_key(|m| m.number);
+                                BracketRound {
+                                    number,
+                                    matches: matches.into_iter().map(export_match).collect(),
+                                }
+                            })
+                            .collect(),
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+    Bracket { stages }
+}
+
+/// Exports the bracket of a tournament as JSON. See the module documentation for the
+/// structure.
+pub fn bracket_to_json(stages: &Stages, matches: &Matches) -> Result<String> {
+    Ok(serde_json::to_string(&bracket(stages, matches))?)
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Exports the bracket of a tournament as a minimal static HTML fragment without any styles:
+/// a `section` per stage, a `div` per group, an `ol` per round and a `li` per match. The
+/// elements have the `toornament-*` classes to be styled by the embedding page.
+pub fn bracket_to_html(stages: &Stages, matches: &Matches) -> String {
+    let mut html = String::new();
+    for stage in bracket(stages, matches).stages {
+        html.push_str(&format!(
+            "<section class=\"toornament-stage\"><h2>{}</h2>",
+            escape(&stage.name)
+        ));
+        for group in stage.groups {
+            html.push_str(&format!(
+                "<div class=\"toornament-group\" data-number=\"{}\">",
+                group.number
+            ));
+            for round in group.rounds {
+                html.push_str(&format!(
+                    "<ol class=\"toornament-round\" data-number=\"{}\">",
+                    round.number
+                ));
+                for m in round.matches {
+                    html.push_str(&format!(
+                        "<li class=\"toornament-match\" data-id=\"{}\">",
+                        escape(&m.id)
+                    ));
+                    for o in m.opponents {
+                        html.push_str(&format!(
+                            "<span class=\"toornament-opponent{}\">{} <b>{}</b></span>",
+                            if o.winner { " toornament-winner" } else { "" },
+                            escape(o.name.as_deref().unwrap_or("TBD")),
+                            o.score.map(|s| s.to_string()).unwrap_or_default()
+                        ));
+                    }
+                    html.push_str("</li>");
+                }
+                html.push_str("</ol>");
+            }
+            html.push_str("</div>");
+        }
+        html.push_str("</section>");
+    }
+    html
+}
+
+/// Quotes the text as a Graphviz identifier, whose line breaks are kept in the labels.
+fn dot_quote(text: &str) -> String {
+    let text = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{}\"", text)
+}
+
+/// Exports the structure of the brackets of a tournament as a Graphviz DOT graph: a cluster
+/// per stage and per group, a node per match labelled with its round, number and opponents,
+/// and an edge per move of an opponent to its next match. The winners follow the solid
+/// edges and the losers the dashed ones, labelled with the opponent number they take in the
+/// next match. Only the elimination stages have edges, see `Progression::compute`.
+pub fn to_dot(stages: &Stages, matches: &Matches) -> String {
+    let mut dot = String::from("digraph tournament {\n    rankdir=LR;\n    node [shape=box];\n");
+    for stage in bracket(stages, matches).stages {
+        dot.push_str(&format!(
+            "    subgraph {} {{\n        label={};\n",
+            dot_quote(&format!("cluster_{}", stage.number)),
+            dot_quote(&stage.name)
+        ));
+        for group in stage.groups {
+            dot.push_str(&format!(
+                "        subgraph {} {{\n            label={};\n",
+                dot_quote(&format!("cluster_{}_{}", stage.number, group.number)),
+                dot_quote(&format!("Group {}", group.number))
+            ));
+            for round in group.rounds {
+                for m in round.matches {
+                    let mut label = format!("Round {} - Match {}", round.number, m.number);
+                    for o in &m.opponents {
+                        label.push_str(&format!(
+                            "\n{}{}{}",
+                            o.name.as_deref().unwrap_or("TBD"),
+                            o.score.map(|s| format!(" {}", s)).unwrap_or_default(),
+                            if o.winner { " (W)" } else { "" }
+                        ));
+                    }
+                    dot.push_str(&format!(
+                        "            {} [label={}];\n",
+                        dot_quote(&m.id),
+                        dot_quote(&label)
+                    ));
+                }
+            }
+            dot.push_str("        }\n");
+        }
+        dot.push_str("    }\n");
+    }
+    for edge in Progression::compute(stages, matches).edges {
+        let style = match edge.outcome {
+            Outcome::Winner => "",
+            Outcome::Loser => ", style=dashed",
+        };
+        dot.push_str(&format!(
+            "    {} -> {} [label={}{}];\n",
+            dot_quote(&edge.from.0),
+            dot_quote(&edge.to.0),
+            dot_quote(&edge.slot.to_string()),
+            style
+        ));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+#[cfg(feature = "sqlite")]
+const SQLITE_SCHEMA: &str = "
+    DROP TABLE IF EXISTS toornament_opponents;
+    DROP TABLE IF EXISTS toornament_games;
+    DROP TABLE IF EXISTS toornament_matches;
+    DROP TABLE IF EXISTS toornament_participants;
+    DROP TABLE IF EXISTS toornament_tournaments;
+    CREATE TABLE toornament_tournaments (
+        id TEXT PRIMARY KEY,
+        discipline TEXT NOT NULL,
+        name TEXT NOT NULL,
+        full_name TEXT,
+        status TEXT NOT NULL,
+        date_start TEXT,
+        date_end TEXT,
+        online INTEGER NOT NULL,
+        public INTEGER NOT NULL,
+        size INTEGER NOT NULL
+    );
+    CREATE TABLE toornament_participants (
+        id TEXT,
+        tournament_id TEXT REFERENCES toornament_tournaments(id),
+        name TEXT NOT NULL,
+        country TEXT
+    );
+    CREATE TABLE toornament_matches (
+        id TEXT PRIMARY KEY,
+        tournament_id TEXT NOT NULL,
+        number INTEGER NOT NULL,
+        stage_number INTEGER NOT NULL,
+        group_number INTEGER NOT NULL,
+        round_number INTEGER NOT NULL,
+        status TEXT NOT NULL,
+        date TEXT NOT NULL
+    );
+    CREATE TABLE toornament_games (
+        match_id TEXT NOT NULL REFERENCES toornament_matches(id),
+        number INTEGER NOT NULL,
+        status TEXT NOT NULL,
+        PRIMARY KEY (match_id, number)
+    );
+    CREATE TABLE toornament_opponents (
+        match_id TEXT NOT NULL REFERENCES toornament_matches(id),
+        game_number INTEGER,
+        number INTEGER NOT NULL,
+        participant_id TEXT,
+        participant_name TEXT,
+        result TEXT,
+        rank INTEGER,
+        score INTEGER,
+        forfeit INTEGER NOT NULL
+    );
+";
+
+/// Returns the name a value is serialized with, like `completed` for `MatchStatus::Completed`.
+#[cfg(feature = "sqlite")]
+fn serialized_name<T: serde::Serialize>(value: &T) -> Result<String> {
+    Ok(match serde_json::to_value(value)? {
+        serde_json::Value::String(name) => name,
+        other => other.to_string(),
+    })
+}
+
+#[cfg(feature = "sqlite")]
+fn insert_opponents(
+    tx: &rusqlite::Transaction<'_>,
+    match_id: &str,
+    game_number: Option<i64>,
+    opponents: &crate::opponents::Opponents,
+) -> Result<()> {
+    for o in &opponents.0 {
+        let result = o.result.as_ref().map(|r| match r {
+            MatchResultSimple::Win => "win",
+            MatchResultSimple::Draw => "draw",
+            MatchResultSimple::Loss => "loss",
+        });
+        tx.execute(
+            "INSERT INTO toornament_opponents (match_id, game_number, number, \
+             participant_id, participant_name, result, rank, score, forfeit) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            rusqlite::params![
+                match_id,
+                game_number,
+                o.number,
+                o.participant
+                    .as_ref()
+                    .and_then(|p| p.id.as_ref())
+                    .map(|id| &id.0),
+                o.participant.as_ref().map(|p| &p.name),
+                result,
+                o.rank,
+                o.score,
+                o.forfeit,
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes the tournament, the participants, the matches, the games and the opponents of a
+/// snapshot into normalized tables of the SQLite database at `path`, named
+/// `toornament_tournaments`, `toornament_participants`, `toornament_matches`, `toornament_games`
+/// and `toornament_opponents`. The database is created if it does not exist and these tables
+/// are replaced if they do, so they always reflect the last exported snapshot; the other tables
+/// of the database are left untouched. The SQLite errors are returned as `Error::Io`.
+///
+/// Enums are stored as the strings they have in the Toornament API (the results of the
+/// opponents as `win`, `draw` or `loss`), dates as ISO 8601 strings
+/// and the opponents of the games have their `game_number` set, while the opponents of the
+/// matches have it `NULL`.
+#[cfg(feature = "sqlite")]
+pub fn to_sqlite<P: AsRef<std::path::Path>>(
+    snapshot: &crate::TournamentSnapshot,
+    path: P,
+) -> Result<()> {
+    let mut connection = rusqlite::Connection::open(path)?;
+    let tx = connection.transaction()?;
+    tx.execute_batch(SQLITE_SCHEMA)?;
+
+    let tournament_id = snapshot
+        .tournament
+        .as_ref()
+        .and_then(|t| t.id.as_ref())
+        .map(|id| id.0.clone());
+    if let Some(t) = snapshot.tournament.as_ref() {
+        tx.execute(
+            "INSERT INTO toornament_tournaments (id, discipline, name, full_name, status, \
+             date_start, date_end, online, public, size) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            rusqlite::params![
+                tournament_id,
+                t.discipline.0,
+                t.name,
+                t.full_name,
+                serialized_name(&t.status)?,
+                t.date_start.map(|d| d.to_string()),
+                t.date_end.map(|d| d.to_string()),
+                t.online,
+                t.public,
+                t.size,
+            ],
+        )?;
+    }
+    for p in &snapshot.participants.0 {
+        tx.execute(
+            "INSERT INTO toornament_participants (id, tournament_id, name, country) \
+             VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![
+                p.id.as_ref().map(|id| &id.0),
+                tournament_id,
+                p.name,
+                p.country
+            ],
+        )?;
+    }
+    for m in &snapshot.matches.0 {
+        tx.execute(
+            "INSERT INTO toornament_matches (id, tournament_id, number, stage_number, \
+             group_number, round_number, status, date) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            rusqlite::params![
+                m.id.0,
+                m.tournament_id.0,
+                m.number as i64,
+                m.stage_number as i64,
+                m.group_number as i64,
+                m.round_number as i64,
+                serialized_name(&m.status)?,
+                m.date.to_rfc3339(),
+            ],
+        )?;
+        insert_opponents(&tx, &m.id.0, None, &m.opponents)?;
+        for g in m.games.iter().flat_map(|games| games.0.iter()) {
+            tx.execute(
+                "INSERT INTO toornament_games (match_id, number, status) VALUES (?1, ?2, ?3)",
+                rusqlite::params![m.id.0, g.number.0, serialized_name(&g.status)?],
+            )?;
+            insert_opponents(&tx, &m.id.0, Some(g.number.0), &g.opponents)?;
+        }
+    }
+    Ok(tx.commit()?)
+}
+
+/// Format of the exported contacts.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ContactFormat {
+    /// Comma-separated values with a header line.
+    Csv,
+    /// A JSON array of `Contact` objects.
+    Json,
+}
+
+/// What `participant_contacts` exports.
+#[derive(Clone, Debug)]
+pub struct ContactOptions {
+    /// Format of the export.
+    pub format: ContactFormat,
+    /// Whether to export the emails.
+    pub emails: bool,
+    /// Types of the custom fields to export. All the types are exported when empty.
+    pub field_types: Vec<CustomFieldType>,
+    /// Whether to export the private custom fields too. Only the public ones are exported
+    /// by default.
+    pub include_private: bool,
+    /// Whether to export the players of the lineups of the teams.
+    pub include_lineup: bool,
+    /// Whether to export only the participants which have checked in.
+    pub checked_in_only: bool,
+}
+impl Default for ContactOptions {
+    fn default() -> ContactOptions {
+        ContactOptions {
+            format: ContactFormat::Csv,
+            emails: true,
+            field_types: Vec::new(),
+            include_private: false,
+            include_lineup: true,
+            checked_in_only: false,
+        }
+    }
+}
+impl ContactOptions {
+    builder!(format, ContactFormat);
+    builder!(emails, bool);
+    builder!(field_types, Vec<CustomFieldType>);
+    builder!(include_private, bool);
+    builder!(include_lineup, bool);
+    builder!(checked_in_only, bool);
+}
+
+/// The contact of a participant or of a player of its lineup.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct Contact {
+    /// Name of the participant.
+    pub participant: String,
+    /// Name of the player of the lineup, `None` for the participant itself.
+    pub player: Option<String>,
+    /// Email, if exported and known.
+    pub email: Option<String>,
+    /// Values of the exported custom fields by their labels.
+    pub fields: BTreeMap<String, String>,
+}
+
+fn contact(
+    participant: &str,
+    player: Option<&Participant>,
+    p: &Participant,
+    options: &ContactOptions,
+) -> Contact {
+    let mut fields = BTreeMap::new();
+    let mut add = |custom_fields: Option<&CustomFields>| {
+        for f in custom_fields.iter().flat_map(|fields| fields.0.iter()) {
+            if options.field_types.is_empty() || options.field_types.contains(&f.field_type) {
+                fields.insert(f.label.clone(), f.value.clone());
+            }
+        }
+    };
+    add(p.custom_fields.as_ref());
+    if options.include_private {
+        add(p.custom_fields_private.as_ref());
+    }
+    Contact {
+        participant: participant.to_owned(),
+        player: player.map(|p| p.name.clone()),
+        email: p.email.clone().filter(|_| options.emails),
+        fields,
+    }
+}
+
+/// Returns the contacts of the participants and of the players of their lineups.
+pub fn contacts(participants: &Participants, options: &ContactOptions) -> Vec<Contact> {
+    let mut contacts = Vec::new();
+    for p in &participants.0 {
+        if options.checked_in_only && p.check_in != Some(true) {
+            continue;
+        }
+        contacts.push(contact(&p.name, None, p, options));
+        if options.include_lineup {
+            for player in p.lineup.iter().flat_map(|lineup| lineup.0.iter()) {
+                contacts.push(contact(&p.name, Some(player), player, options));
+            }
+        }
+    }
+    contacts
+}
+
+/// Quotes a CSV value when it contains a separator, a quote or a line break. A value starting
+/// like a formula (with `=`, `+`, `-`, `@`, a tab or a carriage return) is prefixed with a
+/// `'`, so a spreadsheet shows it as a text instead of evaluating it.
+fn csv_value(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", value)
+    } else {
+        value.to_owned()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// Exports the contacts of the participants in the format of the options. The CSV has the
+/// `participant` and `player` columns, the `email` column if the emails are exported, then a
+/// column per label of the exported custom fields, in alphabetical order. The values starting
+/// like a spreadsheet formula, a phone number like "+1 555 0100" too, are prefixed with a `'`.
+pub fn participant_contacts(
+    participants: &Participants,
+    options: &ContactOptions,
+) -> Result<String> {
+    let contacts = contacts(participants, options);
+    if options.format == ContactFormat::Json {
+        return Ok(serde_json::to_string(&contacts)?);
+    }
+    let labels = contacts
+        .iter()
+        .flat_map(|c| c.fields.keys())
+        .collect::<std::collections::BTreeSet<_>>();
+    let mut header = vec!["participant", "player"];
+    if options.emails {
+        header.push("email");
+    }
+    header.extend(labels.iter().map(|l| l.as_str()));
+    let mut csv = header
+        .into_iter()
+        .map(csv_value)
+        .collect::<Vec<_>>()
+        .join(",");
+    csv.push('\n');
+    for c in &contacts {
+        let mut row = vec![
+            c.participant.as_str(),
+            c.player.as_deref().unwrap_or_default(),
+        ];
+        if options.emails {
+            row.push(c.email.as_deref().unwrap_or_default());
+        }
+        row.extend(
+            labels
+                .iter()
+                .map(|&l| c.fields.get(l).map(String::as_str).unwrap_or_default()),
+        );
+        csv.push_str(&row.into_iter().map(csv_value).collect::<Vec<_>>().join(","));
+        csv.push('\n');
+    }
+    Ok(csv)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stages::{Stage, StageNumber};
+
+    fn data() -> (Stages, Matches) {
+        let stages = Stages(vec![Stage {
+            number: StageNumber(1),
+            name: "Playoffs <1>".to_owned(),
+            stage_type: StageType::SingleElimination,
+            size: 2,
+        }]);
+        let string = r#"
+        [{
+            "id": "5617bb3af3df95f2318b4567",
+            "type": "duel",
+            "discipline": "my_discipline",
+            "status": "completed",
+            "tournament_id": "1",
+            "number": 1,
+            "stage_number": 1,
+            "group_number": 1,
+            "round_number": 1,
+            "date": "2015-09-06T00:10:00-06:00",
+            "opponents": [
+                { "number": 2, "result": 3, "score": 1, "forfeit": false },
+                {
+                    "number": 1,
+                    "participant": { "name": "Evil Geniuses" },
+                    "result": 1,
+                    "score": 2,
+                    "forfeit": false
+                }
+            ]
+        }]"#;
+        (stages, serde_json::from_str(string).unwrap())
+    }
+
+    #[test]
+    fn test_bracket_to_json() {
+        let (stages, matches) = data();
+        let json: serde_json::Value =
+            serde_json::from_str(&bracket_to_json(&stages, &matches).unwrap()).unwrap();
+        let expected: serde_json::Value = serde_json::from_str(
+            r#"{
+            "stages": [{
+                "number": 1, "name": "Playoffs <1>", "type": "single_elimination", "size": 2,
+                "groups": [{
+                    "number": 1,
+                    "rounds": [{
+                        "number": 1,
+                        "matches": [{
+                            "id": "5617bb3af3df95f2318b4567", "number": 1,
+                            "status": "completed", "date": "2015-09-06T00:10:00-06:00",
+                            "opponents": [
+                                {
+                                    "number": 1, "name": "Evil Geniuses", "score": 2,
+                                    "forfeit": false, "winner": true
+                                },
+                                {
+                                    "number": 2, "name": null, "score": 1,
+                                    "forfeit": false, "winner": false
+                                }
+                            ]
+                        }]
+                    }]
+                }]
+            }]
+        }"#,
+        )
+        .unwrap();
+        assert_eq!(json, expected);
+    }
+
+    #[test]
+    fn test_bracket_to_html() {
+        let (stages, matches) = data();
+        let html = bracket_to_html(&stages, &matches);
+        assert!(html.starts_with("<section class=\"toornament-stage\"><h2>Playoffs &lt;1&gt;</h2>"));
+        assert!(html.contains(
+            "<span class=\"toornament-opponent toornament-winner\">Evil Geniuses <b>2</b></span>"
+        ));
+        assert!(html.contains("<span class=\"toornament-opponent\">TBD <b>1</b></span>"));
+        assert!(html.ends_with("</li></ol></div></section>"));
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let (stages, mut matches) = data();
+        let mut final_match = matches.0[0].clone();
+        final_match.id = crate::MatchId("5617bb3af3df95f2318b4568".to_owned());
+        final_match.round_number = 2;
+        final_match.status = MatchStatus::Pending;
+        final_match.opponents.0.clear();
+        let mut semi_final = matches.0[0].clone();
+        semi_final.id = crate::MatchId("5617bb3af3df95f2318b4569".to_owned());
+        semi_final.number = 2;
+        matches.0.push(semi_final);
+        matches.0.push(final_match);
+
+        let dot = to_dot(&stages, &matches);
+        assert!(dot.starts_with("digraph tournament {\n"));
+        assert!(dot.contains("label=\"Playoffs <1>\";"));
+        assert!(dot.contains(
+            "\"5617bb3af3df95f2318b4567\" [label=\"Round 1 - Match 1\\nEvil Geniuses 2 (W)\\nTBD 1\"];"
+        ));
+        assert!(dot.contains("\"5617bb3af3df95f2318b4568\" [label=\"Round 2 - Match 1\"];"));
+        assert!(dot.contains(
+            "\"5617bb3af3df95f2318b4567\" -> \"5617bb3af3df95f2318b4568\" [label=\"1\"];"
+        ));
+        assert!(dot.contains(
+            "\"5617bb3af3df95f2318b4569\" -> \"5617bb3af3df95f2318b4568\" [label=\"2\"];"
+        ));
+        assert!(dot.ends_with("}\n"));
+        assert_eq!(dot_quote("a \"b\" \\"), "\"a \\\"b\\\" \\\\\"");
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_to_sqlite() {
+        let (stages, matches) = data();
+        let snapshot = crate::TournamentSnapshot {
+            stages,
+            matches,
+            ..Default::default()
+        };
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!(
+            "toornament_test_to_sqlite_{}_{}.db",
+            std::process::id(),
+            nanos
+        ));
+        rusqlite::Connection::open(&path)
+            .unwrap()
+            .execute_batch("CREATE TABLE matches (id TEXT); INSERT INTO matches VALUES ('mine');")
+            .unwrap();
+        to_sqlite(&snapshot, &path).unwrap();
+        // Exporting twice replaces the tables instead of failing on the existing rows.
+        to_sqlite(&snapshot, &path).unwrap();
+
+        let connection = rusqlite::Connection::open(&path).unwrap();
+        let status: String = connection
+            .query_row("SELECT status FROM toornament_matches", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(status, "completed");
+        let winner: Option<String> = connection
+            .query_row(
+                "SELECT participant_name FROM toornament_opponents WHERE result = 'win'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(winner.as_deref(), Some("Evil Geniuses"));
+        // The tables of the user are not replaced.
+        let mine: String = connection
+            .query_row("SELECT id FROM matches", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(mine, "mine");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_participant_contacts() {
+        let string = r#"
+        [{
+            "name": "Evil Geniuses, Inc",
+            "email": "contact@eg.gg",
+            "check_in": true,
+            "custom_fields": [
+                { "type": "website", "label": "Website", "value": "https://eg.gg" }
+            ],
+            "custom_fields_private": [
+                { "type": "text", "label": "Phone", "value": "+1 555 0100" }
+            ],
+            "lineup": [{
+                "name": "Arteezy",
+                "email": "rtz@eg.gg",
+                "custom_fields": [
+                    { "type": "full_name", "label": "Full name", "value": "Artour \"rtz\" B." }
+                ]
+            }]
+        }, {
+            "name": "Fnatic",
+            "email": "contact@fnatic.com",
+            "check_in": false
+        }]"#;
+        let participants: Participants = serde_json::from_str(string).unwrap();
+
+        let csv = participant_contacts(&participants, &ContactOptions::default()).unwrap();
+        assert_eq!(
+            csv,
+            "participant,player,email,Full name,Website\n\
+             \"Evil Geniuses, Inc\",,contact@eg.gg,,https://eg.gg\n\
+             \"Evil Geniuses, Inc\",Arteezy,rtz@eg.gg,\"Artour \"\"rtz\"\" B.\",\n\
+             Fnatic,,contact@fnatic.com,,\n"
+        );
+
+        // The values are never evaluated as formulas by a spreadsheet.
+        let mut hostile = participants.clone();
+        hostile.0[1].name = "=HYPERLINK(\"http://evil\",\"Fnatic\")".to_owned();
+        hostile.0[1].email = Some("@SUM(1+1)".to_owned());
+        let csv = participant_contacts(&hostile, &ContactOptions::default()).unwrap();
+        assert!(
+            csv.ends_with("\"'=HYPERLINK(\"\"http://evil\"\",\"\"Fnatic\"\")\",,'@SUM(1+1),,\n")
+        );
+        assert_eq!(csv_value("-1"), "'-1");
+        assert_eq!(csv_value("\t=1"), "'\t=1");
+
+        let options = ContactOptions::default()
+            .emails(false)
+            .include_lineup(false)
+            .checked_in_only(true)
+            .field_types(vec![CustomFieldType::Text]);
+        assert!(contacts(&participants, &options)[0].fields.is_empty());
+        let options = options.include_private(true).format(ContactFormat::Json);
+        let json: serde_json::Value =
+            serde_json::from_str(&participant_contacts(&participants, &options).unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([{
+                "participant": "Evil Geniuses, Inc",
+                "player": null,
+                "email": null,
+                "fields": { "Phone": "+1 555 0100" }
+            }])
+        );
+    }
+}
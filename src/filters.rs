@@ -1,41 +1,304 @@
 use crate::common::Date;
+use crate::disciplines::DisciplineId;
+use crate::error::{Error, Result, ValidationError};
 use crate::participants::ParticipantId;
 use crate::tournaments::TournamentId;
 use crate::videos::VideoCategory;
 
+use std::collections::BTreeSet;
 use std::fmt;
 
-/// Date sorting filter
-#[derive(Debug, Clone)]
-pub enum DateSortFilter {
-    /// Sort by date ascending
-    DateAscending,
-    /// Sort by date descending
-    DateDescending,
+/// The maximum number of tournaments a match filter may be restricted to. The service does not
+/// document a limit: this one is chosen by the crate so that the address of the request stays
+/// far below the length the common servers and proxies accept.
+pub const MAX_TOURNAMENT_IDS: usize = 50;
+
+/// Percent-encodes a query parameter value.
+pub(crate) fn encode(value: &str) -> String {
+    let mut out = String::new();
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+/// Renders a flag the way the service expects it.
+pub(crate) fn flag(value: bool) -> String {
+    if value { "1" } else { "0" }.to_owned()
+}
+
+/// The query parameters of a request. The filters of the crate implement it, and so may the
+/// custom filters.
+pub trait QueryParams {
+    /// Returns the names and the values of the parameters, which are not encoded yet.
+    fn query_params(&self) -> Vec<(String, String)>;
+
+    /// Returns the parameters as a query string with percent-encoded values, like
+    /// `sort=date_asc&page=2`, without the leading `?`.
+    fn to_query_string(&self) -> String {
+        self.query_params()
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, encode(value)))
+            .collect::<Vec<String>>()
+            .join("&")
+    }
+}
+
+impl QueryParams for Vec<(String, String)> {
+    fn query_params(&self) -> Vec<(String, String)> {
+        self.clone()
+    }
+}
+
+/// A bound of a `DateRange`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum DateBound {
+    /// The day is in the range.
+    Inclusive(Date),
+    /// The day is not in the range.
+    Exclusive(Date),
+}
+
+/// A range of days, unbounded by default. The service includes the days it is given: its
+/// documentation describes `before_date` as the matches scheduled "before or at the date".
+/// So the inclusive bounds are sent as they are, and the exclusive bounds as the next or the
+/// previous day.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct DateRange {
+    /// The lower bound.
+    pub after: Option<DateBound>,
+    /// The upper bound.
+    pub before: Option<DateBound>,
+}
+impl DateRange {
+    /// Creates new range of the days from `first` to `last`, both included. Returns a
+    /// `ValidationError` if `last` is before `first`.
+    pub fn between(first: Date, last: Date) -> Result<DateRange> {
+        let range = DateRange::default().since(first).until(last);
+        range.validate("dates")?;
+        Ok(range)
+    }
+
+    /// Sets the lower bound to the day, included.
+    pub fn since(mut self, date: Date) -> Self {
+        self.after = Some(DateBound::Inclusive(date));
+        self
+    }
+
+    /// Sets the lower bound to the day, excluded.
+    pub fn after(mut self, date: Date) -> Self {
+        self.after = Some(DateBound::Exclusive(date));
+        self
+    }
+
+    /// Sets the upper bound to the day, included.
+    pub fn until(mut self, date: Date) -> Self {
+        self.before = Some(DateBound::Inclusive(date));
+        self
+    }
+
+    /// Sets the upper bound to the day, excluded.
+    pub fn before(mut self, date: Date) -> Self {
+        self.before = Some(DateBound::Exclusive(date));
+        self
+    }
+
+    /// Returns the first day of the range, if it is bounded.
+    pub fn first_day(&self) -> Option<Date> {
+        match self.after? {
+            DateBound::Inclusive(date) => Some(date),
+            DateBound::Exclusive(date) => date.succ_opt(),
+        }
+    }
+
+    /// Returns the last day of the range, if it is bounded.
+    pub fn last_day(&self) -> Option<Date> {
+        match self.before? {
+            DateBound::Inclusive(date) => Some(date),
+            DateBound::Exclusive(date) => date.pred_opt(),
+        }
+    }
+
+    /// Returns whether the range has no day.
+    pub fn is_empty(&self) -> bool {
+        let (first, last) = (self.first_day(), self.last_day());
+        match (first, last) {
+            (Some(first), Some(last)) => last < first,
+            // An exclusive bound at the end of the calendar leaves no day.
+            _ => self.after.is_some() && first.is_none() || self.before.is_some() && last.is_none(),
+        }
+    }
+
+    /// Returns a `ValidationError` for the `field` if the range has no day.
+    pub(crate) fn validate(&self, field: &str) -> Result<()> {
+        if !self.is_empty() {
+            return Ok(());
+        }
+        Err(Error::Validation(ValidationError::Invalid(
+            field.to_owned(),
+            format!(
+                "no day is after {:?} and before {:?}",
+                self.after, self.before
+            ),
+        )))
+    }
+
+    /// Returns the bounds as the `after` and the `before` query parameters.
+    fn query_params(&self, after: &str, before: &str) -> Vec<(String, String)> {
+        let mut out = Vec::new();
+        if let Some(day) = self.first_day() {
+            out.push((after.to_owned(), day.to_string()));
+        }
+        if let Some(day) = self.last_day() {
+            out.push((before.to_owned(), day.to_string()));
+        }
+        out
+    }
+}
+
+/// The orders of the matches
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MatchSort {
+    /// Sort by scheduled date ascending
+    ScheduledAscending,
+    /// Sort by scheduled date descending
+    ScheduledDescending,
+}
+#[allow(non_upper_case_globals)]
+impl MatchSort {
+    /// Sort by scheduled date ascending
+    #[deprecated(since = "3.0.0", note = "use `MatchSort::ScheduledAscending`")]
+    pub const DateAscending: MatchSort = MatchSort::ScheduledAscending;
+    /// Sort by scheduled date descending
+    #[deprecated(since = "3.0.0", note = "use `MatchSort::ScheduledDescending`")]
+    pub const DateDescending: MatchSort = MatchSort::ScheduledDescending;
 }
-impl fmt::Display for DateSortFilter {
+impl fmt::Display for MatchSort {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            DateSortFilter::DateAscending => fmt.write_str("date_asc"),
-            DateSortFilter::DateDescending => fmt.write_str("date_desc"),
+            MatchSort::ScheduledAscending => fmt.write_str("date_asc"),
+            MatchSort::ScheduledDescending => fmt.write_str("date_desc"),
         }
     }
 }
 
-/// Create date sorting filter
-#[derive(Debug, Clone)]
-pub enum CreateDateSortFilter {
-    /// Sort by date ascending
+/// The orders of the matches, which were the only ones sorted by date.
+#[deprecated(since = "3.0.0", note = "use `MatchSort`")]
+pub type DateSortFilter = MatchSort;
+
+/// The orders of the participants
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ParticipantSort {
+    /// Sort by creation date ascending
     CreatedAscending,
-    /// Sort by date descending
+    /// Sort by creation date descending
     CreatedDescending,
+    /// Sort by name
+    Alphabetic,
 }
-impl fmt::Display for CreateDateSortFilter {
+impl fmt::Display for ParticipantSort {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            CreateDateSortFilter::CreatedAscending => fmt.write_str("created_asc"),
-            CreateDateSortFilter::CreatedDescending => fmt.write_str("created_desc"),
+            ParticipantSort::CreatedAscending => fmt.write_str("created_asc"),
+            ParticipantSort::CreatedDescending => fmt.write_str("created_desc"),
+            ParticipantSort::Alphabetic => fmt.write_str("alphabetic"),
+        }
+    }
+}
+
+/// The orders of the videos
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum VideoSort {
+    /// Sort by creation date ascending
+    CreatedAscending,
+    /// Sort by creation date descending
+    CreatedDescending,
+}
+/// The orders of the videos, which were the only ones sorted by creation date.
+#[deprecated(since = "3.0.0", note = "use `VideoSort`")]
+pub type CreateDateSortFilter = VideoSort;
+
+impl fmt::Display for VideoSort {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            VideoSort::CreatedAscending => fmt.write_str("created_asc"),
+            VideoSort::CreatedDescending => fmt.write_str("created_desc"),
+        }
+    }
+}
+
+/// A filter for the tournaments list
+#[derive(Debug, Clone, Default)]
+pub struct TournamentFilter {
+    /// Returns the tournaments with the name, filtered by the service.
+    pub name: Option<String>,
+    /// When set to `true`, returns only the featured tournaments.
+    /// When set to `false`, returns only the tournaments which are not featured.
+    pub featured: Option<bool>,
+    /// Returns the tournaments of the discipline.
+    pub discipline_id: Option<DisciplineId>,
+    /// Returns the tournaments starting within the days.
+    pub start: DateRange,
+    /// Returns the tournaments ending within the days.
+    pub end: DateRange,
+    /// When set to `true`, returns the archived tournaments too.
+    /// When set to `false` or not set, the archived tournaments are left out by the service.
+    pub archived: Option<bool>,
+    /// Page requested of the list.
+    pub page: Option<i64>,
+    /// Number of tournaments per page.
+    pub per_page: Option<i64>,
+}
+impl TournamentFilter {
+    builder_o!(featured, bool);
+    builder_o!(archived, bool);
+    builder_o!(page, i64);
+    builder_o!(per_page, i64);
+    builder_o!(discipline_id, DisciplineId);
+    builder!(start, DateRange);
+    builder!(end, DateRange);
+
+    /// Checks the filter before it is sent: returns a `ValidationError` if a range has no day.
+    pub fn validate(&self) -> Result<()> {
+        self.start.validate("start")?;
+        self.end.validate("end")
+    }
+
+    /// A builder method for name with `Option` type.
+    pub fn name<S: Into<String>>(mut self, name: S) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+}
+impl QueryParams for TournamentFilter {
+    fn query_params(&self) -> Vec<(String, String)> {
+        let mut out = Vec::new();
+        if let Some(ref name) = self.name {
+            out.push(("name".to_owned(), name.clone()));
+        }
+        if let Some(f) = self.featured {
+            out.push(("featured".to_owned(), flag(f)));
+        }
+        if let Some(ref d) = self.discipline_id {
+            out.push(("discipline".to_owned(), d.0.clone()));
         }
+        out.extend(self.start.query_params("after_start", "before_start"));
+        out.extend(self.end.query_params("after_end", "before_end"));
+        if let Some(a) = self.archived {
+            out.push(("archived".to_owned(), flag(a)));
+        }
+        if let Some(p) = self.page {
+            out.push(("page".to_owned(), p.to_string()));
+        }
+        if let Some(p) = self.per_page {
+            out.push(("per_page".to_owned(), p.to_string()));
+        }
+        out
     }
 }
 
@@ -49,47 +312,144 @@ pub struct MatchFilter {
     /// When set to `true`, returns only matches with a result.
     /// When set to `false`, returns only matches without a result.
     pub has_result: Option<bool>,
-    /// Sorts the collection in a particular order. `DateAscending` sort matches from oldest to
-    /// newest and `DateDescending` sort matches from newest to oldest.
-    pub sort: Option<DateSortFilter>,
+    /// Sorts the collection in a particular order. `ScheduledAscending` sorts the matches from
+    /// the earliest to the latest and `ScheduledDescending` from the latest to the earliest.
+    pub sort: Option<MatchSort>,
     /// Returns matches that involves the given participant's id.
     pub participant_id: Option<ParticipantId>,
-    /// Returns matches from the filtered tournaments.
-    pub tournament_ids: Option<Vec<TournamentId>>,
+    /// Returns matches from the filtered tournaments, or from all of them if empty.
+    pub tournament_ids: BTreeSet<TournamentId>,
     /// When set to `true`, it will include a summary of each game of the match.
     pub with_games: bool,
-    /// Filter all matches scheduled before this date.
-    pub before_date: Option<Date>,
-    /// Filter all matches scheduled after this date.
-    pub after_date: Option<Date>,
+    /// Returns the matches scheduled within the days.
+    pub scheduled: DateRange,
     /// Page requested of the list.
     pub page: Option<i64>,
+    /// Number of matches per page.
+    pub per_page: Option<i64>,
 }
 impl Default for MatchFilter {
     fn default() -> MatchFilter {
         MatchFilter {
             featured: None,
             has_result: None,
-            sort: Some(DateSortFilter::DateAscending),
+            sort: Some(MatchSort::ScheduledAscending),
             participant_id: None,
-            tournament_ids: None,
+            tournament_ids: BTreeSet::new(),
             with_games: false,
-            before_date: None,
-            after_date: None,
+            scheduled: DateRange::default(),
             page: Some(1i64),
+            per_page: None,
         }
     }
 }
 impl MatchFilter {
     builder_o!(featured, bool);
     builder_o!(has_result, bool);
-    builder_o!(sort, DateSortFilter);
+    builder_o!(sort, MatchSort);
     builder_o!(participant_id, ParticipantId);
-    builder_o!(tournament_ids, Vec<TournamentId>);
     builder!(with_games, bool);
-    builder_o!(before_date, Date);
-    builder_o!(after_date, Date);
+    builder!(scheduled, DateRange);
+
+    /// Returns the matches scheduled at or before the day. The service includes the day.
+    #[deprecated(
+        since = "3.0.0",
+        note = "use `MatchFilter::scheduled` with `DateRange::until`"
+    )]
+    pub fn before_date(mut self, date: Date) -> Self {
+        self.scheduled = self.scheduled.until(date);
+        self
+    }
+
+    /// Returns the matches scheduled at or after the day. The service includes the day.
+    #[deprecated(
+        since = "3.0.0",
+        note = "use `MatchFilter::scheduled` with `DateRange::since`"
+    )]
+    pub fn after_date(mut self, date: Date) -> Self {
+        self.scheduled = self.scheduled.since(date);
+        self
+    }
     builder_o!(page, i64);
+    builder_o!(per_page, i64);
+
+    /// Adds the tournament to the filtered tournaments.
+    pub fn add_tournament_id(mut self, tournament_id: TournamentId) -> Self {
+        self.tournament_ids.insert(tournament_id);
+        self
+    }
+
+    /// Replaces the filtered tournaments with the given ones.
+    pub fn tournament_ids<I: IntoIterator<Item = TournamentId>>(mut self, ids: I) -> Self {
+        self.tournament_ids = ids.into_iter().collect();
+        self
+    }
+
+    /// Adds the tournaments to the filtered tournaments.
+    pub fn add_tournament_ids<I: IntoIterator<Item = TournamentId>>(mut self, ids: I) -> Self {
+        self.tournament_ids.extend(ids);
+        self
+    }
+
+    /// Checks the filter before it is sent: returns a `ValidationError` if the scheduled range
+    /// has no day, if it has more than `MAX_TOURNAMENT_IDS` tournaments, or a tournament
+    /// identifier which is empty or contains the comma separating the identifiers.
+    pub fn validate(&self) -> Result<()> {
+        self.scheduled.validate("scheduled")?;
+        let reason = if self.tournament_ids.len() > MAX_TOURNAMENT_IDS {
+            format!(
+                "{} tournaments are more than the {} the crate sends in one request",
+                self.tournament_ids.len(),
+                MAX_TOURNAMENT_IDS
+            )
+        } else if let Some(id) = self
+            .tournament_ids
+            .iter()
+            .find(|id| id.0.is_empty() || id.0.contains(','))
+        {
+            format!("{:?} is not a tournament identifier", id.0)
+        } else {
+            return Ok(());
+        };
+        Err(Error::Validation(ValidationError::Invalid(
+            "tournament_ids".to_owned(),
+            reason,
+        )))
+    }
+}
+impl QueryParams for MatchFilter {
+    fn query_params(&self) -> Vec<(String, String)> {
+        let mut out = Vec::new();
+        if let Some(f) = self.featured {
+            out.push(("featured".to_owned(), flag(f)));
+        }
+        if let Some(r) = self.has_result {
+            out.push(("has_result".to_owned(), flag(r)));
+        }
+        if let Some(ref s) = self.sort {
+            out.push(("sort".to_owned(), s.to_string()));
+        }
+        if let Some(ref i) = self.participant_id {
+            out.push(("participant_id".to_owned(), i.0.clone()));
+        }
+        if !self.tournament_ids.is_empty() {
+            let ids = self
+                .tournament_ids
+                .iter()
+                .map(|i| i.0.as_str())
+                .collect::<Vec<&str>>();
+            out.push(("tournament_ids".to_owned(), ids.join(",")));
+        }
+        out.push(("with_games".to_owned(), flag(self.with_games)));
+        out.extend(self.scheduled.query_params("after_date", "before_date"));
+        if let Some(p) = self.page {
+            out.push(("page".to_owned(), p.to_string()));
+        }
+        if let Some(p) = self.per_page {
+            out.push(("per_page".to_owned(), p.to_string()));
+        }
+        out
+    }
 }
 
 /// A filter for tournament participants
@@ -100,27 +460,49 @@ pub struct TournamentParticipantsFilter {
     pub with_lineup: bool,
     /// When set to `true`, it will include the list of custom fields for this participant.
     pub with_custom_fields: bool,
-    /// Sorts the collection in a particular order. `DateAscending` sort matches from oldest to
-    /// newest and `DateDescending` sort matches from newest to oldest.
-    pub sort: DateSortFilter,
+    /// Sorts the collection in a particular order. `CreatedAscending` sorts the participants
+    /// from the oldest to the newest, `CreatedDescending` from the newest to the oldest and
+    /// `Alphabetic` by name.
+    pub sort: ParticipantSort,
     /// Page requested of the list.
     pub page: i64,
+    /// Number of participants per page.
+    pub per_page: Option<i64>,
 }
 impl Default for TournamentParticipantsFilter {
     fn default() -> TournamentParticipantsFilter {
         TournamentParticipantsFilter {
             with_lineup: false,
-            sort: DateSortFilter::DateAscending,
+            sort: ParticipantSort::CreatedAscending,
             with_custom_fields: false,
             page: 1i64,
+            per_page: None,
         }
     }
 }
 impl TournamentParticipantsFilter {
     builder!(with_lineup, bool);
-    builder!(sort, DateSortFilter);
+    builder!(sort, ParticipantSort);
     builder!(with_custom_fields, bool);
     builder!(page, i64);
+    builder_o!(per_page, i64);
+}
+impl QueryParams for TournamentParticipantsFilter {
+    fn query_params(&self) -> Vec<(String, String)> {
+        let mut out = vec![
+            ("with_lineup".to_owned(), flag(self.with_lineup)),
+            (
+                "with_custom_fields".to_owned(),
+                flag(self.with_custom_fields),
+            ),
+            ("sort".to_owned(), self.sort.to_string()),
+            ("page".to_owned(), self.page.to_string()),
+        ];
+        if let Some(p) = self.per_page {
+            out.push(("per_page".to_owned(), p.to_string()));
+        }
+        out
+    }
 }
 
 /// A filter for tournament videos
@@ -130,21 +512,206 @@ pub struct TournamentVideosFilter {
     pub category: Option<VideoCategory>,
     /// Sorts the collection in a particular order. `CreatedAscending` sorts the videos from older
     /// to newer; `CreatedDescending` sorts the videos from newer to older.
-    pub sort: CreateDateSortFilter,
+    pub sort: VideoSort,
     /// Page requested of the list.
     pub page: Option<i64>,
+    /// Number of videos per page.
+    pub per_page: Option<i64>,
 }
 impl Default for TournamentVideosFilter {
     fn default() -> TournamentVideosFilter {
         TournamentVideosFilter {
             category: None,
-            sort: CreateDateSortFilter::CreatedAscending,
+            sort: VideoSort::CreatedAscending,
             page: None,
+            per_page: None,
         }
     }
 }
 impl TournamentVideosFilter {
     builder_o!(category, VideoCategory);
-    builder!(sort, CreateDateSortFilter);
+    builder!(sort, VideoSort);
     builder_o!(page, i64);
+    builder_o!(per_page, i64);
+}
+impl QueryParams for TournamentVideosFilter {
+    fn query_params(&self) -> Vec<(String, String)> {
+        let mut out = Vec::new();
+        if let Some(ref c) = self.category {
+            out.push(("category".to_owned(), c.to_string()));
+        }
+        out.push(("sort".to_owned(), self.sort.to_string()));
+        if let Some(p) = self.page {
+            out.push(("page".to_owned(), p.to_string()));
+        }
+        if let Some(p) = self.per_page {
+            out.push(("per_page".to_owned(), p.to_string()));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_filter_to_query_string() {
+        let f = MatchFilter::default()
+            .featured(true)
+            .has_result(true)
+            .page(2i64);
+        assert_eq!(
+            f.to_query_string(),
+            "featured=1&has_result=1&sort=date_asc&with_games=0&page=2"
+        );
+        let f = MatchFilter::default().page(3i64).per_page(20i64);
+        assert_eq!(
+            f.to_query_string(),
+            "sort=date_asc&with_games=0&page=3&per_page=20"
+        );
+    }
+
+    #[test]
+    fn test_date_ranges() {
+        let day = |d: u32| Date::from_ymd_opt(2017, 5, d).unwrap();
+        let range = DateRange::default().after(day(1)).until(day(3));
+        assert_eq!(range.first_day(), Some(day(2)));
+        assert_eq!(range.last_day(), Some(day(3)));
+        assert!(!DateRange::default().is_empty());
+        assert!(DateRange::default().since(day(2)).before(day(2)).is_empty());
+        assert!(DateRange::between(day(3), day(2)).is_err());
+
+        let f = MatchFilter::default().scheduled(range);
+        assert_eq!(
+            f.to_query_string(),
+            "sort=date_asc&with_games=0&after_date=2017-05-02&before_date=2017-05-03&page=1"
+        );
+        let f = TournamentFilter::default()
+            .start(DateRange::between(day(1), day(7)).unwrap())
+            .end(DateRange::default().after(day(9)).until(day(9)));
+        assert_eq!(
+            f.to_query_string(),
+            "after_start=2017-05-01&before_start=2017-05-07&after_end=2017-05-10&before_end=2017-05-09"
+        );
+        match f.validate() {
+            Err(Error::Validation(ValidationError::Invalid(field, _))) => assert_eq!(field, "end"),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_date_bounds_on_the_wire() {
+        let day = |d: u32| Date::from_ymd_opt(2017, 5, d).unwrap();
+        // The days given to the deprecated builders are sent as they were before, as the
+        // inclusive bounds are.
+        let deprecated = MatchFilter::default()
+            .after_date(day(1))
+            .before_date(day(9));
+        let inclusive =
+            MatchFilter::default().scheduled(DateRange::default().since(day(1)).until(day(9)));
+        let query =
+            "sort=date_asc&with_games=0&after_date=2017-05-01&before_date=2017-05-09&page=1";
+        assert_eq!(deprecated.to_query_string(), query);
+        assert_eq!(inclusive.to_query_string(), query);
+        // The exclusive bounds leave the given days out.
+        let exclusive =
+            MatchFilter::default().scheduled(DateRange::default().after(day(1)).before(day(9)));
+        assert_eq!(
+            exclusive.to_query_string(),
+            "sort=date_asc&with_games=0&after_date=2017-05-02&before_date=2017-05-08&page=1"
+        );
+        let single = DateRange::between(day(5), day(5)).unwrap();
+        assert_eq!(single.first_day(), single.last_day());
+    }
+
+    #[test]
+    fn test_match_filter_tournament_ids() {
+        let f = MatchFilter::default()
+            .add_tournament_id(TournamentId("b 2".to_owned()))
+            .add_tournament_ids(vec![
+                TournamentId("a".to_owned()),
+                TournamentId("b 2".to_owned()),
+            ]);
+        assert!(f.validate().is_ok());
+        assert_eq!(
+            f.to_query_string(),
+            "sort=date_asc&tournament_ids=a%2Cb%202&with_games=0&page=1"
+        );
+
+        let comma = f.clone().add_tournament_id(TournamentId("c,d".to_owned()));
+        match comma.validate() {
+            Err(Error::Validation(ValidationError::Invalid(field, _))) => {
+                assert_eq!(field, "tournament_ids")
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        let replaced = f.tournament_ids(vec![TournamentId("c".to_owned())]);
+        assert_eq!(
+            replaced.to_query_string(),
+            "sort=date_asc&tournament_ids=c&with_games=0&page=1"
+        );
+        let many = MatchFilter::default()
+            .tournament_ids((0..=MAX_TOURNAMENT_IDS).map(|i| TournamentId(i.to_string())));
+        assert!(many.validate().is_err());
+    }
+
+    #[test]
+    fn test_per_page_to_query_string() {
+        let f = TournamentParticipantsFilter::default().per_page(50i64);
+        assert_eq!(
+            f.to_query_string(),
+            "with_lineup=0&with_custom_fields=0&sort=created_asc&page=1&per_page=50"
+        );
+        let f = TournamentVideosFilter::default().page(2i64).per_page(10i64);
+        assert_eq!(f.to_query_string(), "sort=created_asc&page=2&per_page=10");
+    }
+
+    #[test]
+    fn test_sorts_by_endpoint() {
+        let f = TournamentParticipantsFilter::default().sort(ParticipantSort::Alphabetic);
+        assert!(f.to_query_string().contains("&sort=alphabetic&"));
+        let f = MatchFilter::default().sort(MatchSort::ScheduledDescending);
+        assert!(f.to_query_string().starts_with("sort=date_desc&"));
+        let f = TournamentVideosFilter::default().sort(VideoSort::CreatedDescending);
+        assert_eq!(f.to_query_string(), "sort=created_desc");
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_sorts() {
+        let f = MatchFilter::default().sort(DateSortFilter::DateDescending);
+        assert_eq!(f.sort, Some(MatchSort::ScheduledDescending));
+        assert_eq!(DateSortFilter::DateAscending.to_string(), "date_asc");
+        let f = TournamentVideosFilter::default().sort(CreateDateSortFilter::CreatedDescending);
+        assert_eq!(f.to_query_string(), "sort=created_desc");
+    }
+
+    #[test]
+    fn test_tournament_filter_to_query_string() {
+        assert_eq!(TournamentFilter::default().to_query_string(), "");
+        let f = TournamentFilter::default().name("Weekly Cup #1");
+        assert_eq!(f.to_query_string(), "name=Weekly%20Cup%20%231");
+        let f = TournamentFilter::default()
+            .featured(true)
+            .discipline_id(DisciplineId("counterstrike_go".to_owned()));
+        assert_eq!(
+            f.to_query_string(),
+            "featured=1&discipline=counterstrike_go"
+        );
+        let f = TournamentFilter::default().name("Cup").archived(true);
+        assert_eq!(f.to_query_string(), "name=Cup&archived=1");
+    }
+
+    #[test]
+    fn test_custom_query_params() {
+        struct Region(&'static str);
+        impl QueryParams for Region {
+            fn query_params(&self) -> Vec<(String, String)> {
+                vec![("region".to_owned(), self.0.to_owned())]
+            }
+        }
+        assert_eq!(Region("eu west").to_query_string(), "region=eu%20west");
+    }
 }
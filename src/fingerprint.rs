@@ -0,0 +1,116 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::Result;
+
+/// A stable hash of the content of a resource, to detect cheaply whether it has changed since
+/// it was seen last, for example between two polls.
+///
+/// The hash is computed over the resource as serialized by this crate, with its keys sorted,
+/// with the 64-bit FNV-1a function, so it is the same for equal resources whatever the order
+/// of their fields in the responses. With the same version of this crate, it stays the same
+/// across the processes, the platforms and the versions of the compiler, so it may be stored
+/// for a while. A new version of the crate may model the resources differently, for example
+/// add a field or rename one, which changes the fingerprints, so the stored ones must be
+/// computed again after an upgrade. The fields of the responses which the crate does not
+/// model are not covered. It is not a cryptographic hash.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Fingerprint(pub u64);
+impl std::fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// The 64-bit FNV-1a hash.
+struct Fnv(u64);
+impl Fnv {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    /// Writes a text preceded by its length, so that the texts following each other are not
+    /// mistaken for others.
+    fn write_text(&mut self, text: &str) {
+        self.write(text.len().to_string().as_bytes());
+        self.write(b":");
+        self.write(text.as_bytes());
+    }
+
+    /// Writes the value with the keys of its objects sorted.
+    fn write_value(&mut self, value: &Value) {
+        match *value {
+            Value::Null => self.write(b"n"),
+            Value::Bool(true) => self.write(b"t"),
+            Value::Bool(false) => self.write(b"f"),
+            Value::Number(ref number) => {
+                self.write(b"#");
+                self.write_text(&number.to_string());
+            }
+            Value::String(ref text) => {
+                self.write(b"s");
+                self.write_text(text);
+            }
+            Value::Array(ref items) => {
+                self.write(b"[");
+                for item in items {
+                    self.write_value(item);
+                }
+                self.write(b"]");
+            }
+            Value::Object(ref fields) => {
+                let mut fields = fields.iter().collect::<Vec<_>>();
+                fields.sort_by_key(|(key, _)| *key);
+                self.write(b"{");
+                for (key, value) in fields {
+                    self.write_text(key);
+                    self.write_value(value);
+                }
+                self.write(b"}");
+            }
+        }
+    }
+}
+
+/// Returns the fingerprint of the serialized `value`, or the error of its serialization.
+pub(crate) fn fingerprint<T: Serialize>(value: &T) -> Result<Fingerprint> {
+    let mut hash = Fnv(FNV_OFFSET_BASIS);
+    hash.write_value(&serde_json::to_value(value)?);
+    Ok(Fingerprint(hash.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint() {
+        let a: Value = serde_json::from_str(r#"{ "id": "1", "size": 16, "tags": ["a"] }"#).unwrap();
+        let b: Value = serde_json::from_str(r#"{ "tags": ["a"], "size": 16, "id": "1" }"#).unwrap();
+        assert_eq!(fingerprint(&a).unwrap(), fingerprint(&b).unwrap());
+        // The fingerprints are stable: they may be stored and compared later.
+        assert_eq!(fingerprint(&a).unwrap().to_string(), "627c688be549f85c");
+
+        let c: Value = serde_json::from_str(r#"{ "id": "1", "size": 8, "tags": ["a"] }"#).unwrap();
+        assert_ne!(fingerprint(&a).unwrap(), fingerprint(&c).unwrap());
+        // The texts following each other are told apart.
+        let ab = serde_json::json!(["ab", "c"]);
+        let bc = serde_json::json!(["a", "bc"]);
+        assert_ne!(fingerprint(&ab).unwrap(), fingerprint(&bc).unwrap());
+        assert_ne!(
+            fingerprint(&Value::Null).unwrap(),
+            fingerprint(&Value::Bool(false)).unwrap()
+        );
+
+        // A value which can't be serialized has no fingerprint.
+        let mut keys = std::collections::BTreeMap::new();
+        keys.insert((1, 2), "a");
+        assert!(fingerprint(&keys).is_err());
+    }
+}
@@ -0,0 +1,488 @@
+//! This module contains representative responses of the service for every model, so that the
+//! crates mocking the service in their tests do not have to write payloads which drift from
+//! the models. The fixtures are parsed by the tests of the crate, so they keep up with the
+//! models, and `round_trip` checks that a payload survives being parsed and written again.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use toornament::*;
+//! use toornament::fixtures;
+//!
+//! let matches: Matches = fixtures::round_trip(fixtures::MATCHES).unwrap();
+//! assert_eq!(matches.0[0].opponents.0.len(), 2);
+//! // Answer a mocked `GET /v1/tournaments/{tournament_id}/matches` with `fixtures::MATCHES`.
+//! ```
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{Error, Result, ValidationError};
+
+/// A discipline with its team size and additional fields, as returned by
+/// `GET /v1/disciplines/{discipline_id}`.
+pub const DISCIPLINE: &str = r#"{
+    "id": "counterstrike_go",
+    "name": "Counter-Strike: GO",
+    "shortname": "CS:GO",
+    "fullname": "Counter-Strike: Global Offensive",
+    "copyrights": "Valve Software",
+    "team_size": { "min": 5, "max": 7 },
+    "additional_fields": { "country": { "value": "label" } }
+}"#;
+
+/// The disciplines, as returned by `GET /v1/disciplines`.
+pub const DISCIPLINES: &str = r#"[
+    {
+        "id": "counterstrike_go",
+        "name": "Counter-Strike: GO",
+        "shortname": "CS:GO",
+        "fullname": "Counter-Strike: Global Offensive",
+        "copyrights": "Valve Software"
+    },
+    {
+        "id": "quakelive",
+        "name": "Quake Live",
+        "shortname": "QL",
+        "fullname": "Quake Live",
+        "copyrights": "id Software"
+    }
+]"#;
+
+/// A running tournament with its streams, as returned by
+/// `GET /v1/tournaments/{tournament_id}?with_streams=1`.
+pub const TOURNAMENT: &str = r#"{
+    "id": "5608fd12140ba061298b4569",
+    "discipline": "counterstrike_go",
+    "name": "My Weekly Tournament",
+    "full_name": "My Weekly Tournament - Long title",
+    "status": "running",
+    "date_start": "2015-09-06",
+    "date_end": "2015-09-07",
+    "timezone": "America/Sao_Paulo",
+    "online": true,
+    "public": true,
+    "location": "London",
+    "country": "UK",
+    "size": 16,
+    "participant_type": "team",
+    "match_type": "duel",
+    "organization": "Avery Bullock",
+    "website": "http://www.toornament.com",
+    "description": "My description \n on multiple lines",
+    "rules": "My rules \n on multiple lines",
+    "prize": "1 - 10,000$ \n 2 - 5,000$",
+    "streams": [
+        {
+            "id": "56742bc7cc3c17ee608b4567",
+            "name": "DreamhackCS",
+            "url": "http://www.twitch.tv/dreamhackcs",
+            "language": "en"
+        }
+    ],
+    "check_in": true,
+    "participant_nationality": true,
+    "match_format": "bo3",
+    "registration_enabled": true,
+    "registration_opening_datetime": "2015-09-01T00:00:00+00:00",
+    "registration_closing_datetime": "2015-09-05T12:30:00+02:00",
+    "registration_notification_enabled": false,
+    "archived": false
+}"#;
+
+/// The account of the credentials, as returned by `GET /v1/me`.
+pub const ACCOUNT: &str = r#"{
+    "id": "378426939508809728",
+    "name": "Avery Bullock",
+    "email": "organizer@oxent.net"
+}"#;
+
+/// The tournaments, as returned by `GET /v1/tournaments`.
+pub const TOURNAMENTS: &str = r#"[
+    {
+        "id": "5608fd12140ba061298b4569",
+        "discipline": "counterstrike_go",
+        "name": "My Weekly Tournament",
+        "full_name": "My Weekly Tournament - Long title",
+        "status": "running",
+        "date_start": "2015-09-06",
+        "date_end": "2015-09-07",
+        "online": true,
+        "public": true,
+        "size": 16
+    },
+    {
+        "id": "5608fd12140ba061298b4570",
+        "discipline": "quakelive",
+        "name": "My Monthly Tournament",
+        "status": "setup",
+        "online": false,
+        "public": false,
+        "country": "FR",
+        "size": 8
+    }
+]"#;
+
+/// A pending duel of the first round of a bracket, as returned by
+/// `GET /v1/tournaments/{tournament_id}/matches/{match_id}`.
+pub const MATCH: &str = r#"{
+    "id": "5617bb3af3df95f2318b4567",
+    "type": "duel",
+    "discipline": "counterstrike_go",
+    "status": "pending",
+    "tournament_id": "5608fd12140ba061298b4569",
+    "number": 1,
+    "stage_number": 1,
+    "group_number": 1,
+    "round_number": 1,
+    "date": "2015-09-06T00:10:00-06:00",
+    "timezone": "America/Chicago",
+    "match_format": "bo3",
+    "opponents": [
+        {
+            "number": 1,
+            "participant": { "id": "378426939508809728", "name": "Evil Geniuses", "country": "US" },
+            "forfeit": false
+        },
+        {
+            "number": 2,
+            "participant": { "id": "378426939508809729", "name": "Fnatic", "country": "SE" },
+            "forfeit": false
+        }
+    ]
+}"#;
+
+/// The matches of a tournament, one completed and one pending, as returned by
+/// `GET /v1/tournaments/{tournament_id}/matches`.
+pub const MATCHES: &str = r#"[
+    {
+        "id": "5617bb3af3df95f2318b4567",
+        "type": "duel",
+        "discipline": "counterstrike_go",
+        "status": "completed",
+        "tournament_id": "5608fd12140ba061298b4569",
+        "number": 1,
+        "stage_number": 1,
+        "group_number": 1,
+        "round_number": 1,
+        "date": "2015-09-06T00:10:00-06:00",
+        "opponents": [
+            {
+                "number": 1,
+                "participant": { "id": "378426939508809728", "name": "Evil Geniuses" },
+                "result": 1,
+                "score": 16,
+                "forfeit": false
+            },
+            {
+                "number": 2,
+                "participant": { "id": "378426939508809729", "name": "Fnatic" },
+                "result": 3,
+                "score": 9,
+                "forfeit": false
+            }
+        ]
+    },
+    {
+        "id": "5617bb3af3df95f2318b4568",
+        "type": "duel",
+        "discipline": "counterstrike_go",
+        "status": "pending",
+        "tournament_id": "5608fd12140ba061298b4569",
+        "number": 2,
+        "stage_number": 1,
+        "group_number": 1,
+        "round_number": 2,
+        "date": "2015-09-06T02:10:00-06:00",
+        "opponents": [
+            {
+                "number": 1,
+                "participant": { "id": "378426939508809728", "name": "Evil Geniuses" },
+                "forfeit": false
+            },
+            { "number": 2, "forfeit": false }
+        ]
+    }
+]"#;
+
+/// The games of a match, the first with its properties, as returned by
+/// `GET /v1/tournaments/{tournament_id}/matches/{match_id}/games`.
+pub const GAMES: &str = r#"[
+    {
+        "number": 1,
+        "status": "completed",
+        "opponents": [
+            { "number": 1, "result": 1, "score": 16, "forfeit": false },
+            { "number": 2, "result": 3, "score": 9, "forfeit": false }
+        ],
+        "properties": { "map": "de_dust2", "side": "ct", "duration": 2400 }
+    },
+    {
+        "number": 2,
+        "status": "pending",
+        "opponents": [
+            { "number": 1, "forfeit": false },
+            { "number": 2, "forfeit": false }
+        ]
+    }
+]"#;
+
+/// A team with its lineup and custom fields, as returned by
+/// `GET /v1/tournaments/{tournament_id}/participants/{participant_id}`.
+pub const PARTICIPANT: &str = r#"{
+    "id": "378426939508809728",
+    "name": "Evil Geniuses",
+    "logo": {
+        "icon_large_square": "http://api.toornament.com/id/icon_large_square",
+        "extra_small_square": "http://api.toornament.com/id/extra_small_square",
+        "medium_small_square": "http://api.toornament.com/id/medium_small_square",
+        "medium_large_square": "http://api.toornament.com/id/medium_large_square"
+    },
+    "country": "US",
+    "lineup": [
+        {
+            "name": "Storm Spirit",
+            "country": "US",
+            "custom_fields": [
+                { "type": "steam_player_id", "label": "Steam ID", "value": "STEAM_0:1:1234567" }
+            ],
+            "email": "player@oxent.net"
+        }
+    ],
+    "custom_fields": [
+        { "type": "website", "label": "Website", "value": "https://evilgeniuses.gg" }
+    ],
+    "email": "contact@oxent.net",
+    "check_in": true
+}"#;
+
+/// The participants of a tournament, as returned by
+/// `GET /v1/tournaments/{tournament_id}/participants`.
+pub const PARTICIPANTS: &str = r#"[
+    { "id": "378426939508809728", "name": "Evil Geniuses", "country": "US" },
+    { "id": "378426939508809729", "name": "Fnatic", "country": "SE", "check_in": false }
+]"#;
+
+/// The settings of a tournament, as returned by `GET /v1/tournaments/{tournament_id}/settings`.
+pub const TOURNAMENT_SETTINGS: &str = r#"{
+    "registration_enabled": true,
+    "registration_opening_datetime": "2015-09-01T00:00:00+00:00",
+    "registration_closing_datetime": "2015-09-05T00:00:00+00:00",
+    "registration_notification_enabled": false,
+    "check_in": true,
+    "participant_nationality": true,
+    "team_size_min": 5,
+    "team_size_max": 7
+}"#;
+
+/// The custom fields of a tournament, as returned by
+/// `GET /v1/tournaments/{tournament_id}/custom-fields`.
+pub const CUSTOM_FIELDS: &str = r#"[
+    { "type": "steam_player_id", "label": "Steam ID", "value": "" },
+    { "type": "birth_date", "label": "Birth date", "value": "" }
+]"#;
+
+/// The permissions of a tournament, as returned by
+/// `GET /v1/tournaments/{tournament_id}/permissions`.
+pub const PERMISSIONS: &str = r#"[
+    {
+        "id": "5617bb3af3df95f2318b4567",
+        "email": "organizer@oxent.net",
+        "attributes": ["edit", "report", "place", "register"],
+        "status": "accepted"
+    },
+    {
+        "id": "5617bb3af3df95f2318b4568",
+        "email": "referee@oxent.net",
+        "attributes": ["report"],
+        "status": "pending"
+    }
+]"#;
+
+/// The stages of a tournament, as returned by `GET /v1/tournaments/{tournament_id}/stages`.
+pub const STAGES: &str = r#"[
+    { "number": 1, "name": "Groups", "type": "group", "size": 4 },
+    { "number": 2, "name": "Playoffs", "type": "single_elimination", "size": 8 }
+]"#;
+
+/// The videos of a tournament, as returned by `GET /v1/tournaments/{tournament_id}/videos`.
+pub const VIDEOS: &str = r#"[
+    {
+        "name": "Game 1: TSM vs. EnVyUs",
+        "url": "https://www.youtube.com/watch?v=SI5QgDJkaSU",
+        "language": "en",
+        "category": "replay",
+        "match_id": "5617bb3af3df95f2318b4567"
+    }
+]"#;
+
+/// The final standings of a tournament, as returned by
+/// `GET /v1/tournaments/{tournament_id}/final-standings`.
+pub const FINAL_STANDINGS: &str = r#"[
+    {
+        "rank": 1,
+        "participant": { "id": "5617c3acf3df95f2318b4567", "name": "Evil Geniuses" },
+        "points": 9
+    },
+    {
+        "rank": 2,
+        "rank_max": 3,
+        "participant": { "id": "5617c3acf3df95f2318b4568", "name": "Fnatic" },
+        "points": 4
+    },
+    {
+        "rank": 2,
+        "rank_max": 3,
+        "participant": { "id": "5617c3acf3df95f2318b4569", "name": "Natus Vincere" },
+        "points": 4
+    }
+]"#;
+
+/// Deliveries of webhook events, as posted by the service to the address of a webhook: a
+/// `webhooks::Delivery` each.
+pub const WEBHOOK_DELIVERIES: &str = r#"[
+    {
+        "id": "5617d1f0f3df95f2318b4567",
+        "timestamp": "2015-09-06T00:05:00+00:00",
+        "name": "participant.updated",
+        "data": { "id": "5617c3acf3df95f2318b4567", "name": "Evil Geniuses", "country": "US" }
+    },
+    {
+        "id": "5617d1f0f3df95f2318b4568",
+        "timestamp": "2015-09-06T00:45:00+00:00",
+        "name": "match.result_updated",
+        "data": {
+            "match_id": "5617bb3af3df95f2318b4567",
+            "result": {
+                "status": "completed",
+                "opponents": [
+                    { "number": 1, "result": 1, "score": 16, "forfeit": false },
+                    { "number": 2, "result": 3, "score": 9, "forfeit": false }
+                ]
+            }
+        }
+    },
+    {
+        "id": "5617d1f0f3df95f2318b4569",
+        "timestamp": "2015-09-06T01:00:00+00:00",
+        "name": "participant.deleted",
+        "data": { "id": "5617c3acf3df95f2318b4569" }
+    }
+]"#;
+
+/// Parses the `json` as the model, writes it and returns the model. Returns `Error::Json` if
+/// the model does not parse the `json`, and a `ValidationError` with the path of the field if
+/// the written model differs from the `json`: a field is lost, changed or added. A field
+/// missing on one side and `null` on the other is the same, since the models skip writing the
+/// fields they do not have, and so is a date written in another form of the same offset.
+pub fn round_trip<T: Serialize + DeserializeOwned>(json: &str) -> Result<T> {
+    let model: T = serde_json::from_str(json)?;
+    let written = serde_json::to_value(&model)?;
+    let read: Value = serde_json::from_str(json)?;
+    match difference(&read, &written, String::new()) {
+        Some(path) => Err(Error::Validation(ValidationError::Invalid(
+            format!("{}{}", std::any::type_name::<T>(), path),
+            "is not written back as it was parsed".to_owned(),
+        ))),
+        None => Ok(model),
+    }
+}
+
+/// Returns `true` if the strings are the same date and time, written in different forms like
+/// `+00:00` and `Z`.
+fn same_date(read: &str, written: &str) -> bool {
+    match (
+        chrono::DateTime::parse_from_rfc3339(read),
+        chrono::DateTime::parse_from_rfc3339(written),
+    ) {
+        (Ok(read), Ok(written)) => read == written && read.offset() == written.offset(),
+        _ => false,
+    }
+}
+
+/// Returns the path of the first field which differs between the values.
+fn difference(read: &Value, written: &Value, path: String) -> Option<String> {
+    match (read, written) {
+        (Value::Object(read), Value::Object(written)) => {
+            read.keys().chain(written.keys()).find_map(|key| {
+                let null = Value::Null;
+                difference(
+                    read.get(key).unwrap_or(&null),
+                    written.get(key).unwrap_or(&null),
+                    format!("{}.{}", path, key),
+                )
+            })
+        }
+        (Value::Array(read), Value::Array(written)) if read.len() == written.len() => read
+            .iter()
+            .zip(written)
+            .enumerate()
+            .find_map(|(i, (read, written))| difference(read, written, format!("{}[{}]", path, i))),
+        (read, written) if read == written => None,
+        (Value::String(read), Value::String(written)) if same_date(read, written) => None,
+        _ => Some(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn test_fixtures_round_trip() {
+        let discipline: Discipline = round_trip(DISCIPLINE).unwrap();
+        assert_eq!(discipline.team_size.unwrap().max, 7);
+        assert_eq!(round_trip::<Disciplines>(DISCIPLINES).unwrap().0.len(), 2);
+        let account: Account = round_trip(ACCOUNT).unwrap();
+        assert_eq!(account.name, "Avery Bullock");
+        let tournament: Tournament = round_trip(TOURNAMENT).unwrap();
+        assert_eq!(tournament.status, TournamentStatus::Running);
+        assert_eq!(round_trip::<Tournaments>(TOURNAMENTS).unwrap().0.len(), 2);
+        let settings: TournamentSettings = round_trip(TOURNAMENT_SETTINGS).unwrap();
+        assert_eq!(settings.team_size_max, Some(7));
+        let m: Match = round_trip(MATCH).unwrap();
+        assert_eq!(m.opponents.0.len(), 2);
+        let matches: Matches = round_trip(MATCHES).unwrap();
+        assert_eq!(matches.0[0].status, MatchStatus::Completed);
+        let games: Games = round_trip(GAMES).unwrap();
+        assert_eq!(games.0[0].properties.map(), Some("de_dust2"));
+        let participant: Participant = round_trip(PARTICIPANT).unwrap();
+        assert_eq!(participant.lineup.unwrap().len(), 1);
+        assert_eq!(round_trip::<Participants>(PARTICIPANTS).unwrap().0.len(), 2);
+        assert_eq!(
+            round_trip::<CustomFields>(CUSTOM_FIELDS).unwrap().0.len(),
+            2
+        );
+        // The status of the permissions is set by the service and never written back.
+        assert!(round_trip::<Permissions>(PERMISSIONS).is_err());
+        let permissions: Permissions = serde_json::from_str(PERMISSIONS).unwrap();
+        assert_eq!(permissions.0.len(), 2);
+        assert_eq!(permissions.pending().len(), 1);
+        assert_eq!(round_trip::<Stages>(STAGES).unwrap().0.len(), 2);
+        assert_eq!(round_trip::<Videos>(VIDEOS).unwrap().0.len(), 1);
+        let standings: FinalStandings = round_trip(FINAL_STANDINGS).unwrap();
+        assert_eq!(standings.0[1].ranks(), 2..=3);
+        let deliveries: Vec<webhooks::Delivery> = round_trip(WEBHOOK_DELIVERIES).unwrap();
+        assert_eq!(deliveries.len(), 3);
+        assert!(matches!(
+            deliveries[1].event,
+            webhooks::Event::MatchResultUpdated { .. }
+        ));
+
+        assert!(matches!(round_trip::<Match>(STAGES), Err(Error::Json(_))));
+        // A field the model does not know is lost, or rejected with `strict-serde`.
+        let mut unknown: serde_json::Value = serde_json::from_str(VIDEOS).unwrap();
+        unknown[0]["views"] = 10.into();
+        let result = round_trip::<Video>(&unknown[0].to_string());
+        #[cfg(feature = "strict-serde")]
+        assert!(matches!(result, Err(Error::Json(_))));
+        #[cfg(not(feature = "strict-serde"))]
+        match result {
+            Err(Error::Validation(ValidationError::Invalid(path, _))) => {
+                assert!(path.ends_with("Video.views"), "{}", path)
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+}
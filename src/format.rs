@@ -0,0 +1,247 @@
+//! This module turns matches, results, standings and schedules into compact text summaries,
+//! suitable for chat bots (Discord, Slack, IRC) and logs.
+//!
+//! The look of the summaries is defined by a `Template`: use `PlainText` or `Markdown`, or
+//! implement the trait to change only the parts you need.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use toornament::*;
+//! use toornament::format::{self, Markdown};
+//!
+//! let toornament = Toornament::with_application("API_TOKEN",
+//!                                               "CLIENT_ID",
+//!                                               "CLIENT_SECRET").unwrap();
+//! let matches = toornament.matches(TournamentId("1".to_owned()), None, Includes::none()).unwrap();
+//! let now = chrono::Local::now().fixed_offset();
+//! println!("{}", format::schedule(&Markdown, &matches, now, 5));
+//! ```
+use chrono::{DateTime, FixedOffset};
+
+use crate::matches::{Match, MatchResult, MatchStatus, Matches};
+use crate::opponents::{Opponent, Opponents};
+use crate::participants::Participant;
+use crate::swiss::Standings;
+
+/// Defines how the parts of a summary look.
+pub trait Template {
+    /// Formats a participant, which may be unknown yet.
+    fn participant(&self, participant: Option<&Participant>) -> String {
+        match participant {
+            Some(p) => p.name.clone(),
+            None => "TBD".to_owned(),
+        }
+    }
+
+    /// Highlights a text, for example the name of a winner.
+    fn highlight(&self, text: &str) -> String {
+        text.to_owned()
+    }
+
+    /// Formats a date.
+    fn date(&self, date: &DateTime<FixedOffset>) -> String {
+        date.format("%Y-%m-%d %H:%M %:z").to_string()
+    }
+
+    /// Formats an item of a list.
+    fn list_item(&self, position: usize, text: &str) -> String {
+        format!("{}. {}", position, text)
+    }
+}
+
+/// A template producing plain text.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PlainText;
+impl Template for PlainText {}
+
+/// A template producing markdown: winners are bold and dates are inline code.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Markdown;
+impl Template for Markdown {
+    fn highlight(&self, text: &str) -> String {
+        format!("**{}**", text)
+    }
+
+    fn date(&self, date: &DateTime<FixedOffset>) -> String {
+        format!("`{}`", PlainText.date(date))
+    }
+}
+
+fn opponents_summary<T: Template>(
+    template: &T,
+    opponents: &Opponents,
+    winner: Option<i64>,
+) -> String {
+    let mut sorted = opponents.0.iter().collect::<Vec<&Opponent>>();
+    sorted.sort_by_key(|o| o.number);
+    let names = sorted
+        .iter()
+        .map(|o| {
+            let name = template.participant(o.participant.as_ref());
+            if Some(o.number) == winner {
+                template.highlight(&name)
+            } else {
+                name
+            }
+        })
+        .collect::<Vec<String>>();
+    match opponents.score_summary() {
+        Some(scores) if names.len() == 2 => {
+            let scores = scores.split(" - ").collect::<Vec<&str>>();
+            format!("{} {} - {} {}", names[0], scores[0], scores[1], names[1])
+        }
+        _ => names.join(" vs "),
+    }
+}
+
+/// Returns a one-line summary of a match: "A 2 - 1 B" for a completed duel or
+/// "A vs B (date)" for a match to be played.
+pub fn match_summary<T: Template>(template: &T, m: &Match) -> String {
+    if m.status == MatchStatus::Pending {
+        return format!(
+            "{} ({})",
+            opponents_summary(template, &m.opponents, None),
+            template.date(&m.date)
+        );
+    }
+    let winner = m.winner().map(|o| o.number);
+    opponents_summary(template, &m.opponents, winner)
+}
+
+/// Returns a one-line summary of a match result: "A 2 - 1 B".
+pub fn result_summary<T: Template>(template: &T, result: &MatchResult) -> String {
+    let winner = result.opponents.winner().map(|o| o.number);
+    opponents_summary(template, &result.opponents, winner)
+}
+
+/// Returns the standings as a numbered list, one participant with its points per line.
+pub fn standings<T: Template>(template: &T, standings: &Standings) -> String {
+    let mut sorted = standings.0.iter().collect::<Vec<_>>();
+    sorted.sort_by_key(|s| std::cmp::Reverse(s.points));
+    sorted
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            let name = template.participant(Some(&s.participant));
+            let name = if i == 0 {
+                template.highlight(&name)
+            } else {
+                name
+            };
+            template.list_item(i + 1, &format!("{} - {} pts", name, s.points))
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Returns up to `limit` pending matches scheduled after `now` as a numbered list, the
+/// earliest first.
+pub fn schedule<T: Template>(
+    template: &T,
+    matches: &Matches,
+    now: DateTime<FixedOffset>,
+    limit: usize,
+) -> String {
+    let mut upcoming = matches
+        .0
+        .iter()
+        .filter(|m| m.status == MatchStatus::Pending && m.date > now)
+        .collect::<Vec<&Match>>();
+    upcoming.sort_by_key(|m| m.date);
+    upcoming
+        .iter()
+        .take(limit)
+        .enumerate()
+        .map(|(i, m)| template.list_item(i + 1, &match_summary(template, m)))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swiss::Standing;
+
+    fn duel() -> Match {
+        let string = r#"
+        {
+            "id": "1",
+            "type": "duel",
+            "discipline": "my_discipline",
+            "status": "completed",
+            "tournament_id": "1",
+            "number": 1,
+            "stage_number": 1,
+            "group_number": 1,
+            "round_number": 1,
+            "date": "2015-09-06T10:00:00+00:00",
+            "opponents": [
+                {
+                    "number": 1,
+                    "participant": { "name": "Evil Geniuses" },
+                    "result": 1,
+                    "score": 2,
+                    "forfeit": false
+                },
+                {
+                    "number": 2,
+                    "result": 3,
+                    "score": 1,
+                    "forfeit": false
+                }
+            ]
+        }"#;
+        serde_json::from_str(string).unwrap()
+    }
+
+    #[test]
+    fn test_match_summaries() {
+        let m = duel();
+        assert_eq!(match_summary(&PlainText, &m), "Evil Geniuses 2 - 1 TBD");
+        assert_eq!(match_summary(&Markdown, &m), "**Evil Geniuses** 2 - 1 TBD");
+
+        let result = MatchResult {
+            status: MatchStatus::Completed,
+            opponents: m.opponents.clone(),
+        };
+        assert_eq!(
+            result_summary(&PlainText, &result),
+            "Evil Geniuses 2 - 1 TBD"
+        );
+
+        let mut pending = m.status(MatchStatus::Pending);
+        for o in &mut pending.opponents.0 {
+            o.score = None;
+            o.result = None;
+        }
+        assert_eq!(
+            match_summary(&Markdown, &pending),
+            "Evil Geniuses vs TBD (`2015-09-06 10:00 +00:00`)"
+        );
+    }
+
+    #[test]
+    fn test_standings_and_schedule() {
+        let table = Standings(vec![
+            Standing::new(Participant::create("B"), 3),
+            Standing::new(Participant::create("A"), 6),
+        ]);
+        assert_eq!(
+            standings(&Markdown, &table),
+            "1. **A** - 6 pts\n2. B - 3 pts"
+        );
+
+        let date = |s| DateTime::parse_from_rfc3339(s).unwrap();
+        let m = duel().status(MatchStatus::Pending);
+        let matches = Matches::from(vec![
+            m.clone().date(date("2015-09-06T12:00:00+00:00")),
+            m.clone().date(date("2015-09-06T11:00:00+00:00")),
+            m.clone().date(date("2015-09-06T09:00:00+00:00")),
+        ]);
+        assert_eq!(
+            schedule(&PlainText, &matches, date("2015-09-06T10:00:00+00:00"), 1),
+            "1. Evil Geniuses 2 - 1 TBD (2015-09-06 11:00 +00:00)"
+        );
+    }
+}
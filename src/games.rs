@@ -1,5 +1,13 @@
-use crate::matches::MatchStatus;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use chrono::Duration;
+use serde_json::Value;
+
+use crate::matches::{MatchRef, MatchStatus};
 use crate::opponents::Opponents;
+use crate::stats::{DisciplineStats, RawStats};
+use crate::Result;
 
 /// A game number.
 #[derive(
@@ -8,7 +16,8 @@ use crate::opponents::Opponents;
 pub struct GameNumber(pub i64);
 
 /// A game description.
-#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct Game {
     /// Game's number.
     pub number: GameNumber,
@@ -16,8 +25,209 @@ pub struct Game {
     pub status: MatchStatus,
     /// Game's opponents
     pub opponents: Opponents,
+    /// Game's properties, like the map it was played on.
+    #[serde(default, skip_serializing_if = "GameProperties::is_empty")]
+    pub properties: GameProperties,
+    /// Game's statistics, returned with `Include::Stats`. They are parsed by `Game::stats`.
+    #[serde(rename = "stats", default, skip_serializing_if = "Option::is_none")]
+    pub raw_stats: Option<RawStats>,
+    /// The match of the game, set when the game is fetched. It is not compared: the same game
+    /// fetched on its own or with its match is equal.
+    #[serde(skip)]
+    pub match_ref: Option<MatchRef>,
+}
+impl Game {
+    /// Returns the fields the games are compared by.
+    fn compared(
+        &self,
+    ) -> (
+        &GameNumber,
+        &MatchStatus,
+        &Opponents,
+        &GameProperties,
+        &Option<RawStats>,
+    ) {
+        (
+            &self.number,
+            &self.status,
+            &self.opponents,
+            &self.properties,
+            &self.raw_stats,
+        )
+    }
+
+    /// Sets the match of the game.
+    #[cfg(any(feature = "client-blocking", feature = "client-async"))]
+    pub(crate) fn of_match(mut self, m: &MatchRef) -> Game {
+        self.match_ref = Some(m.clone());
+        self
+    }
+
+    /// Parses the statistics of the game, or returns `None` if they were not fetched.
+    pub fn stats<S: DisciplineStats>(&self) -> Option<Result<S>> {
+        self.raw_stats.as_ref().map(S::parse)
+    }
+}
+
+/// The discipline specific properties of a game, written with the game by
+/// `Toornament::update_match_game`. The service stores any keys there and defines none: the
+/// keys of the typed accessors, `map`, `side` and `duration`, are conventions of this crate
+/// only. The properties written by other tools are reached with `get` and `set`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GameProperties(pub BTreeMap<String, Value>);
+impl GameProperties {
+    /// The key of the map the game is played on, by convention.
+    pub const MAP: &'static str = "map";
+    /// The key of the side the first opponent plays on, by convention.
+    pub const SIDE: &'static str = "side";
+    /// The key of the duration of the game in seconds, by convention.
+    pub const DURATION: &'static str = "duration";
+
+    /// Returns `true` if the game has no properties.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the property with the `key`.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.0.get(key)
+    }
+
+    /// Sets the property with the `key`, replacing the previous value.
+    pub fn set<K: Into<String>, V: Into<Value>>(mut self, key: K, value: V) -> GameProperties {
+        self.0.insert(key.into(), value.into());
+        self
+    }
+
+    /// Returns the map the game is played on.
+    pub fn map(&self) -> Option<&str> {
+        self.get(Self::MAP).and_then(Value::as_str)
+    }
+
+    /// Sets the map the game is played on.
+    pub fn set_map<S: Into<String>>(self, map: S) -> GameProperties {
+        self.set(Self::MAP, map.into())
+    }
+
+    /// Returns the side the first opponent plays on, like "ct" or "blue".
+    pub fn side(&self) -> Option<&str> {
+        self.get(Self::SIDE).and_then(Value::as_str)
+    }
+
+    /// Sets the side the first opponent plays on.
+    pub fn set_side<S: Into<String>>(self, side: S) -> GameProperties {
+        self.set(Self::SIDE, side.into())
+    }
+
+    /// Returns the duration of the game. The duration is stored in seconds.
+    pub fn duration(&self) -> Option<Duration> {
+        self.get(Self::DURATION)
+            .and_then(Value::as_i64)
+            .map(Duration::seconds)
+    }
+
+    /// Sets the duration of the game, rounded down to the second.
+    pub fn set_duration(self, duration: Duration) -> GameProperties {
+        self.set(Self::DURATION, duration.num_seconds())
+    }
+}
+// The json values are not ordered, so the properties are ordered by their written form.
+impl Ord for GameProperties {
+    fn cmp(&self, other: &GameProperties) -> Ordering {
+        let written = |p: &GameProperties| {
+            p.0.iter()
+                .map(|(k, v)| (k.clone(), v.to_string()))
+                .collect::<Vec<_>>()
+        };
+        written(self).cmp(&written(other))
+    }
+}
+impl PartialOrd for GameProperties {
+    fn partial_cmp(&self, other: &GameProperties) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Game {
+    fn eq(&self, other: &Game) -> bool {
+        self.compared() == other.compared()
+    }
+}
+impl Eq for Game {}
+impl Ord for Game {
+    fn cmp(&self, other: &Game) -> Ordering {
+        self.compared().cmp(&other.compared())
+    }
+}
+impl PartialOrd for Game {
+    fn partial_cmp(&self, other: &Game) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 /// Array of games
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
 pub struct Games(pub Vec<Game>);
+impl Games {
+    /// Sets the match of the games.
+    #[cfg(any(feature = "client-blocking", feature = "client-async"))]
+    pub(crate) fn of_match(self, m: &MatchRef) -> Games {
+        Games(self.0.into_iter().map(|g| g.of_match(m)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_properties_parse() {
+        let string = r#"
+        {
+            "number": 1,
+            "status": "completed",
+            "opponents": [],
+            "properties": {
+                "map": "de_dust2",
+                "side": "ct",
+                "duration": 2400,
+                "first_pick": 2,
+                "vetoes": ["de_nuke", "de_train"],
+                "mvp": "s1mple"
+            }
+        }"#;
+        let g: Game = serde_json::from_str(string).unwrap();
+        assert_eq!(g.properties.map(), Some("de_dust2"));
+        assert_eq!(g.properties.side(), Some("ct"));
+        assert_eq!(g.properties.duration(), Some(Duration::minutes(40)));
+        assert_eq!(g.properties.get("first_pick"), Some(&Value::from(2)));
+        assert_eq!(g.properties.get("mvp"), Some(&Value::from("s1mple")));
+
+        let properties = GameProperties::default()
+            .set_map("de_dust2")
+            .set_side("ct")
+            .set_duration(Duration::seconds(2400))
+            .set("first_pick", 2)
+            .set("vetoes", vec!["de_nuke", "de_train"])
+            .set("mvp", "s1mple");
+        assert_eq!(properties, g.properties);
+
+        // A game without properties is written without them.
+        let game = Game {
+            properties: GameProperties::default(),
+            ..g
+        };
+        assert!(!serde_json::to_string(&game).unwrap().contains("properties"));
+
+        // The match a game was fetched with does not change the game.
+        #[cfg(any(feature = "client-blocking", feature = "client-async"))]
+        {
+            let m = MatchRef::new(
+                crate::TournamentId("1".to_owned()),
+                crate::MatchId("2".to_owned()),
+            );
+            assert_eq!(game.clone().of_match(&m), game);
+            assert_eq!(game.clone().of_match(&m).cmp(&game), Ordering::Equal);
+        }
+    }
+}
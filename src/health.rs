@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+/// Result of a health check made by `Toornament::ping`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Ping {
+    /// Time taken by the request to the service.
+    pub latency: Duration,
+    /// Time left before the access token expires and is refreshed.
+    pub token_expires_in: Duration,
+}
+
+/// Information about the current access token, without the token itself.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenInfo {
+    /// When the token was obtained.
+    pub issued: DateTime<Utc>,
+    /// When the token expires and is refreshed.
+    pub expires: DateTime<Utc>,
+    /// Scopes granted to the token.
+    pub scopes: Vec<String>,
+}
+impl TokenInfo {
+    /// Returns how long ago the token was obtained.
+    pub fn age(&self) -> chrono::Duration {
+        Utc::now() - self.issued
+    }
+
+    /// Returns the time left before the token expires, which is negative if it has expired.
+    pub fn expires_in(&self) -> chrono::Duration {
+        self.expires - Utc::now()
+    }
+
+    /// Returns `true` if the token has expired and will be refreshed by the next request.
+    pub fn is_expired(&self) -> bool {
+        self.expires <= Utc::now()
+    }
+}
@@ -0,0 +1,379 @@
+//! This module imports the results of matches from score files, for example the spreadsheets
+//! of the scorekeepers of a LAN event, to apply them with `Toornament::update_matches`.
+//!
+//! # Format
+//!
+//! The files read by `results_from_csv` are CSV files with a header line naming the columns,
+//! separated by commas (or by semicolons, as some spreadsheets export them). The names of the
+//! columns are case-insensitive and the unknown columns are ignored:
+//!
+//! * `match_id` (required): the identifier of the match;
+//! * `status`: `pending`, `running` or `completed`, `completed` if absent or empty;
+//! * `score_N`, `result_N`, `rank_N`, `forfeit_N`: the score, the result (`win`, `draw` or
+//!   `loss`), the rank and whether the opponent with the number `N` (starting from 1) has
+//!   forfeited (`true`/`false`, `yes`/`no` or `1`/`0`). An opponent is included if any of its
+//!   columns is filled in.
+//!
+//! When the results of a duel are not filled in, they are deduced from the forfeits, then from
+//! the scores. The empty lines are skipped.
+//!
+//! ```text
+//! match_id,score_1,score_2
+//! 5617bb3af3df95f2318b4567,2,1
+//! 5617bb3af3df95f2318b4568,0,2
+//! ```
+//!
+//! Files of other formats, like the exports of other platforms, are read by implementing
+//! `ResultAdaptor` for them and calling `results_from_csv_with`. `ScoreLine` reads the files
+//! with the scores of a duel in a single column, like "2-1".
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use toornament::*;
+//!
+//! let toornament = Toornament::with_application("API_TOKEN",
+//!                                               "CLIENT_ID",
+//!                                               "CLIENT_SECRET").unwrap();
+//! let file = std::fs::File::open("round-1.csv").unwrap();
+//! let updates = import::results_from_csv(file)
+//!     .unwrap()
+//!     .into_iter()
+//!     .map(|(id, result)| (id, MatchPatch::from(result)))
+//!     .collect();
+//! let outcomes = toornament.update_matches(TournamentId("1".to_owned()),
+//!                                          updates,
+//!                                          batch::BatchOptions::default(),
+//!                                          None);
+//! ```
+use std::collections::BTreeMap;
+use std::io::Read;
+
+use crate::common::MatchResultSimple;
+use crate::error::ImportError;
+use crate::matches::{MatchId, MatchResult, MatchStatus};
+use crate::opponents::{Opponent, Opponents};
+use crate::{Error, Result};
+
+/// A row of a file: the values by the names of the columns, in lower case.
+pub type Row = BTreeMap<String, String>;
+
+/// Converts the rows of a file of some format into match results.
+pub trait ResultAdaptor {
+    /// Returns the result of the match of the row, `None` to skip the row, or the reason why
+    /// the row is invalid.
+    fn convert(&self, row: &Row) -> std::result::Result<Option<(MatchId, MatchResult)>, String>;
+}
+
+/// The format documented in the module.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StandardColumns;
+impl ResultAdaptor for StandardColumns {
+    fn convert(&self, row: &Row) -> std::result::Result<Option<(MatchId, MatchResult)>, String> {
+        let id = match_id(row, "match_id")?;
+        let status = match value(row, "status") {
+            None => MatchStatus::Completed,
+            Some(status) => parse_status(status)?,
+        };
+        let mut opponents = Vec::new();
+        for number in 1.. {
+            let column = |name: &str| format!("{}_{}", name, number);
+            let (score, result, rank, forfeit) = (
+                column("score"),
+                column("result"),
+                column("rank"),
+                column("forfeit"),
+            );
+            if ![&score, &result, &rank, &forfeit]
+                .iter()
+                .any(|c| row.contains_key(c.as_str()))
+            {
+                break;
+            }
+            if [&score, &result, &rank, &forfeit]
+                .iter()
+                .all(|c| value(row, c).is_none())
+            {
+                continue;
+            }
+            opponents.push(Opponent {
+                number,
+                participant: None,
+                result: value(row, &result).map(parse_result).transpose()?,
+                rank: value(row, &rank)
+                    .map(|v| parse_number(&rank, v))
+                    .transpose()?,
+                score: value(row, &score)
+                    .map(|v| parse_number(&score, v))
+                    .transpose()?,
+                forfeit: value(row, &forfeit)
+                    .map(|v| parse_bool(&forfeit, v))
+                    .transpose()?
+                    .unwrap_or(false),
+            });
+        }
+        Ok(Some((id, result(status, opponents))))
+    }
+}
+
+/// A format with the scores of a duel in a single column, like "2-1", as exported by some
+/// platforms. The matches without scores are skipped.
+#[derive(Clone, Debug)]
+pub struct ScoreLine {
+    /// Name of the column with the identifier of the match.
+    pub match_id: String,
+    /// Name of the column with the scores.
+    pub scores: String,
+}
+impl Default for ScoreLine {
+    fn default() -> ScoreLine {
+        ScoreLine {
+            match_id: "match_id".to_owned(),
+            scores: "scores".to_owned(),
+        }
+    }
+}
+impl ResultAdaptor for ScoreLine {
+    fn convert(&self, row: &Row) -> std::result::Result<Option<(MatchId, MatchResult)>, String> {
+        let id = match_id(row, &self.match_id.to_lowercase())?;
+        let scores = match value(row, &self.scores.to_lowercase()) {
+            Some(scores) => scores,
+            None => return Ok(None),
+        };
+        let (first, second) = scores
+            .split_once('-')
+            .ok_or_else(|| format!("the scores \"{}\" are not like \"2-1\"", scores))?;
+        let opponents = [first, second]
+            .iter()
+            .zip(1..)
+            .map(|(score, number)| {
+                Ok(Opponent {
+                    number,
+                    participant: None,
+                    result: None,
+                    rank: None,
+                    score: Some(parse_number(&self.scores, score.trim())?),
+                    forfeit: false,
+                })
+            })
+            .collect::<std::result::Result<Vec<_>, String>>()?;
+        Ok(Some((id, result(MatchStatus::Completed, opponents))))
+    }
+}
+
+/// Reads the results of the matches from a CSV file of the format documented in the module.
+pub fn results_from_csv<R: Read>(reader: R) -> Result<Vec<(MatchId, MatchResult)>> {
+    results_from_csv_with(reader, &StandardColumns)
+}
+
+/// Reads the results of the matches from a CSV file with a header line, converting its rows
+/// with the adaptor.
+pub fn results_from_csv_with<R: Read, A: ResultAdaptor + ?Sized>(
+    mut reader: R,
+    adaptor: &A,
+) -> Result<Vec<(MatchId, MatchResult)>> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let mut records = parse_csv(text.trim_start_matches('\u{feff}'))?.into_iter();
+    let header = match records.next() {
+        Some((_, header)) => header
+            .into_iter()
+            .map(|name| name.trim().to_lowercase())
+            .collect::<Vec<_>>(),
+        None => return Ok(Vec::new()),
+    };
+    let mut results = Vec::new();
+    for (line, record) in records {
+        if record.iter().all(|v| v.trim().is_empty()) {
+            continue;
+        }
+        let row = header.iter().cloned().zip(record).collect::<Row>();
+        match adaptor.convert(&row) {
+            Ok(Some(result)) => results.push(result),
+            Ok(None) => {}
+            Err(message) => return Err(Error::Import(ImportError::Invalid(line, message))),
+        }
+    }
+    Ok(results)
+}
+
+/// Splits a CSV text into records with the numbers of the lines they start at. The separator
+/// is a semicolon if the header line has semicolons and no comma, a comma otherwise.
+fn parse_csv(text: &str) -> Result<Vec<(usize, Vec<String>)>> {
+    let header = text.lines().next().unwrap_or_default();
+    let separator = if header.contains(';') && !header.contains(',') {
+        ';'
+    } else {
+        ','
+    };
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let (mut line, mut start) = (1, 1);
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+                records.push((start, std::mem::take(&mut record)));
+                line += 1;
+                start = line;
+            }
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            c if c == separator && !quoted => record.push(std::mem::take(&mut field)),
+            c => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+        }
+    }
+    if quoted {
+        return Err(Error::Import(ImportError::Invalid(
+            start,
+            "a quoted value is not closed".to_owned(),
+        )));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push((start, record));
+    }
+    Ok(records)
+}
+
+/// Returns the trimmed value of the column, `None` if it is absent or empty.
+fn value<'a>(row: &'a Row, column: &str) -> Option<&'a str> {
+    row.get(column).map(|v| v.trim()).filter(|v| !v.is_empty())
+}
+
+fn match_id(row: &Row, column: &str) -> std::result::Result<MatchId, String> {
+    value(row, column)
+        .map(|id| MatchId(id.to_owned()))
+        .ok_or_else(|| format!("the column {} is empty or missing", column))
+}
+
+fn parse_status(value: &str) -> std::result::Result<MatchStatus, String> {
+    match value.to_lowercase().as_str() {
+        "pending" => Ok(MatchStatus::Pending),
+        "running" => Ok(MatchStatus::Running),
+        "completed" => Ok(MatchStatus::Completed),
+        _ => Err(format!("the status \"{}\" is unknown", value)),
+    }
+}
+
+fn parse_result(value: &str) -> std::result::Result<MatchResultSimple, String> {
+    match value.to_lowercase().as_str() {
+        "win" => Ok(MatchResultSimple::Win),
+        "draw" => Ok(MatchResultSimple::Draw),
+        "loss" => Ok(MatchResultSimple::Loss),
+        _ => Err(format!("the result \"{}\" is unknown", value)),
+    }
+}
+
+fn parse_number(column: &str, value: &str) -> std::result::Result<i64, String> {
+    value
+        .parse()
+        .map_err(|_| format!("the {} \"{}\" is not a number", column, value))
+}
+
+fn parse_bool(column: &str, value: &str) -> std::result::Result<bool, String> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "1" => Ok(true),
+        "false" | "no" | "0" => Ok(false),
+        _ => Err(format!(
+            "the {} \"{}\" is not a yes/no value",
+            column, value
+        )),
+    }
+}
+
+/// Creates the result, deducing the results of a duel without them from the forfeits, then
+/// from the scores.
+fn result(status: MatchStatus, mut opponents: Vec<Opponent>) -> MatchResult {
+    if let [ref mut a, ref mut b] = opponents[..] {
+        if a.result.is_none() && b.result.is_none() {
+            let results = match (a.forfeit, b.forfeit, a.score, b.score) {
+                (true, false, _, _) => Some((MatchResultSimple::Loss, MatchResultSimple::Win)),
+                (false, true, _, _) => Some((MatchResultSimple::Win, MatchResultSimple::Loss)),
+                (false, false, Some(x), Some(y)) if x > y => {
+                    Some((MatchResultSimple::Win, MatchResultSimple::Loss))
+                }
+                (false, false, Some(x), Some(y)) if x < y => {
+                    Some((MatchResultSimple::Loss, MatchResultSimple::Win))
+                }
+                (false, false, Some(_), Some(_)) => {
+                    Some((MatchResultSimple::Draw, MatchResultSimple::Draw))
+                }
+                _ => None,
+            };
+            if let Some((x, y)) = results {
+                a.result = Some(x);
+                b.result = Some(y);
+            }
+        }
+    }
+    MatchResult {
+        status,
+        opponents: Opponents(opponents),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_results_from_csv() {
+        let csv = "\u{feff}Match_ID;Status;Score_1;Score_2;Forfeit_2;Comment\r\n\
+                   1;;2;1;;\"Close one; \"\"GG\"\"\r\nreally\"\r\n\
+                   \r\n\
+                   2;running;0;0;no;\n\
+                   3;;;;yes;\n";
+        let results = results_from_csv(csv.as_bytes()).unwrap();
+        assert_eq!(results.len(), 3);
+
+        let (ref id, ref first) = results[0];
+        assert_eq!(id, &MatchId("1".to_owned()));
+        assert_eq!(first.status, MatchStatus::Completed);
+        assert_eq!(first.opponents.0[0].score, Some(2));
+        assert_eq!(first.opponents.0[0].result, Some(MatchResultSimple::Win));
+        assert_eq!(first.opponents.0[1].result, Some(MatchResultSimple::Loss));
+
+        let (_, ref second) = results[1];
+        assert_eq!(second.status, MatchStatus::Running);
+        assert_eq!(second.opponents.0[1].result, Some(MatchResultSimple::Draw));
+
+        let (_, ref third) = results[2];
+        assert_eq!(third.opponents.0.len(), 1);
+        assert!(third.opponents.0[0].forfeit);
+        assert_eq!(third.opponents.0[0].number, 2);
+
+        match results_from_csv("match_id,score_1\n1,2\n2,two\n".as_bytes()) {
+            Err(Error::Import(ImportError::Invalid(line, message))) => {
+                assert_eq!(line, 3);
+                assert!(message.contains("two"));
+            }
+            other => panic!("The invalid score was accepted: {:?}", other),
+        }
+
+        let csv = "id,round,scores\na,1,3-2\nb,1,\n";
+        let adaptor = ScoreLine {
+            match_id: "ID".to_owned(),
+            ..Default::default()
+        };
+        let results = results_from_csv_with(csv.as_bytes(), &adaptor).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].1.opponents.0[1].result,
+            Some(MatchResultSimple::Loss)
+        );
+    }
+}
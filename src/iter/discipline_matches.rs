@@ -2,7 +2,7 @@ use crate::*;
 
 /// A discipline matches iterator
 pub struct DisciplineMatchesIter<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Fetch matches of discipline
     discipline_id: DisciplineId,
@@ -11,9 +11,12 @@ pub struct DisciplineMatchesIter<'a> {
 }
 impl<'a> DisciplineMatchesIter<'a> {
     /// Creates new match iterator
-    pub fn new(client: &'a Toornament, discipline_id: DisciplineId) -> DisciplineMatchesIter {
+    pub fn new<C: Into<ClientHandle<'a>>>(
+        client: C,
+        discipline_id: DisciplineId,
+    ) -> DisciplineMatchesIter<'a> {
         DisciplineMatchesIter {
-            client,
+            client: client.into(),
             discipline_id,
             filter: MatchFilter::default(),
         }
@@ -28,6 +31,18 @@ impl<'a> DisciplineMatchesIter<'a> {
         self
     }
 
+    /// Fetch the page with the following number, starting from one
+    pub fn page(mut self, page: i64) -> Self {
+        self.filter.page = Some(page);
+        self
+    }
+
+    /// Fetch the following number of matches per page
+    pub fn per_page(mut self, per_page: i64) -> Self {
+        self.filter.per_page = Some(per_page);
+        self
+    }
+
     /// Fetch match by discipline id
     pub fn of_discipline(mut self, id: DisciplineId) -> Self {
         self.discipline_id = id;
@@ -45,3 +60,8 @@ impl<'a> DisciplineMatchesIter<'a> {
         ))
     }
 }
+
+into_owned!(DisciplineMatchesIter {
+    discipline_id,
+    filter
+});
@@ -2,14 +2,17 @@ use crate::*;
 
 /// Disciplines iterator
 pub struct DisciplinesIter<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     all: bool,
 }
 impl<'a> DisciplinesIter<'a> {
     /// Creates new disciplines iterator
-    pub fn new(client: &'a Toornament) -> DisciplinesIter<'a> {
-        DisciplinesIter { client, all: true }
+    pub fn new<C: Into<ClientHandle<'a>>>(client: C) -> DisciplinesIter<'a> {
+        DisciplinesIter {
+            client: client.into(),
+            all: true,
+        }
     }
 
     /// Fetch all disciplines
@@ -42,7 +45,7 @@ impl<'a> DisciplinesIter<'a> {
 
 /// Discipline iterator
 pub struct DisciplineIter<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Fetch a discipline with the following id
     discipline_id: DisciplineId,
@@ -50,9 +53,12 @@ pub struct DisciplineIter<'a> {
 
 impl<'a> DisciplineIter<'a> {
     /// Creates new discipline iterator
-    pub fn new(client: &'a Toornament, discipline_id: DisciplineId) -> DisciplineIter<'a> {
+    pub fn new<C: Into<ClientHandle<'a>>>(
+        client: C,
+        discipline_id: DisciplineId,
+    ) -> DisciplineIter<'a> {
         DisciplineIter {
-            client,
+            client: client.into(),
             discipline_id,
         }
     }
@@ -75,10 +81,12 @@ impl<'a> DisciplineIter<'a> {
             .disciplines(Some(self.discipline_id.clone()))?
             .0
             .first()
-            .take()
         {
             Some(d) => Ok(T::from(d.to_owned())),
             None => Err(Error::Iter(IterError::NoSuchDiscipline(self.discipline_id))),
         }
     }
 }
+
+into_owned!(DisciplinesIter { all });
+into_owned!(DisciplineIter { discipline_id });
@@ -2,7 +2,7 @@ use crate::*;
 
 /// A match games iterator
 pub struct GamesIter<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Fetch games of tournament with id
     tournament_id: TournamentId,
@@ -14,13 +14,13 @@ pub struct GamesIter<'a> {
 
 impl<'a> GamesIter<'a> {
     /// Creates new games iterator
-    pub fn new(
-        client: &'a Toornament,
+    pub fn new<C: Into<ClientHandle<'a>>>(
+        client: C,
         tournament_id: TournamentId,
         match_id: MatchId,
     ) -> GamesIter<'a> {
         GamesIter {
-            client,
+            client: client.into(),
             tournament_id,
             match_id,
             with_stats: false,
@@ -58,14 +58,14 @@ impl<'a> GamesIter<'a> {
         Ok(T::from(self.client.match_games(
             self.tournament_id,
             self.match_id,
-            self.with_stats,
+            Includes::when(self.with_stats, Include::Stats),
         )?))
     }
 }
 
 /// A match game iterator
 pub struct GameIter<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Fetch game of tournament with id
     tournament_id: TournamentId,
@@ -80,7 +80,7 @@ pub struct GameIter<'a> {
 /// Modifiers
 impl<'a> GameIter<'a> {
     /// Match game lazy editor
-    pub fn edit<F: 'static + FnMut(Game) -> Game>(self, editor: F) -> GameEditor<'a> {
+    pub fn edit<F: 'static + Send + FnMut(Game) -> Game>(self, editor: F) -> GameEditor<'a> {
         GameEditor {
             client: self.client,
             tournament_id: self.tournament_id,
@@ -110,14 +110,14 @@ impl<'a> GameIter<'a> {
             self.tournament_id,
             self.match_id,
             self.number,
-            self.with_stats,
+            Includes::when(self.with_stats, Include::Stats),
         )?))
     }
 }
 
 /// A lazy game result editor
 pub struct GameEditor<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Fetch match of tournament
     tournament_id: TournamentId,
@@ -128,7 +128,7 @@ pub struct GameEditor<'a> {
     /// Fetch game with a number
     number: GameNumber,
     /// Match result editor
-    editor: Box<dyn FnMut(Game) -> Game>,
+    editor: Box<dyn FnMut(Game) -> Game + Send>,
 }
 
 /// Terminators
@@ -139,7 +139,7 @@ impl<'a> GameEditor<'a> {
             self.tournament_id.clone(),
             self.match_id.clone(),
             self.number,
-            self.with_stats,
+            Includes::when(self.with_stats, Include::Stats),
         )?;
         self.client.update_match_game(
             self.tournament_id,
@@ -152,7 +152,7 @@ impl<'a> GameEditor<'a> {
 
 /// A match game result iterator
 pub struct GameResultIter<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Fetch match of tournament
     tournament_id: TournamentId,
@@ -165,7 +165,7 @@ pub struct GameResultIter<'a> {
 /// Modifiers
 impl<'a> GameResultIter<'a> {
     /// Game result lazy editor
-    pub fn edit<F: 'static + FnMut(MatchResult) -> MatchResult>(
+    pub fn edit<F: 'static + Send + FnMut(MatchResult) -> MatchResult>(
         self,
         editor: F,
     ) -> GameResultEditor<'a> {
@@ -193,7 +193,7 @@ impl<'a> GameResultIter<'a> {
 
 /// A lazy game result editor
 pub struct GameResultEditor<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Fetch match of tournament
     tournament_id: TournamentId,
@@ -202,7 +202,7 @@ pub struct GameResultEditor<'a> {
     /// Fetch game with a number
     number: GameNumber,
     /// Editor
-    editor: Box<dyn FnMut(MatchResult) -> MatchResult>,
+    editor: Box<dyn FnMut(MatchResult) -> MatchResult + Send>,
 }
 
 /// Terminators
@@ -223,3 +223,33 @@ impl<'a> GameResultEditor<'a> {
         )
     }
 }
+
+into_owned!(GamesIter {
+    tournament_id,
+    match_id,
+    with_stats
+});
+into_owned!(GameIter {
+    tournament_id,
+    match_id,
+    with_stats,
+    number
+});
+into_owned!(GameEditor {
+    tournament_id,
+    match_id,
+    with_stats,
+    number,
+    editor
+});
+into_owned!(GameResultIter {
+    tournament_id,
+    match_id,
+    number
+});
+into_owned!(GameResultEditor {
+    tournament_id,
+    match_id,
+    number,
+    editor
+});
@@ -3,6 +3,12 @@
 //! It was made to provide an easy and rust-idiomatic way to interact with the service.
 //! Note that everything here is "lazy". Think of it as you use an iterator over remote data.
 //!
+//! The iterators over the lists the service returns in pages, the tournaments, the matches,
+//! the participants and the videos, fetch the first page unless told another one with `page`,
+//! and the size of the pages with `per_page`. The other lists, like the disciplines, the
+//! stages, their groups and rankings, the games and the permissions, are returned whole by the
+//! service, so their iterators have no such builders.
+//!
 //! # Usage
 //!
 //! Delete a participant:
@@ -43,6 +49,12 @@
 //! let delete_result = toornament.tournaments_iter()
 //!                               .with_id(TournamentId("1".to_owned()))
 //!                               .delete();
+//! // Enable the check-in of tournament with id = 1
+//! let settings = toornament.tournaments_iter()
+//!                          .with_id(TournamentId("1".to_owned()))
+//!                          .settings()
+//!                          .edit(|s| s.check_in(true))
+//!                          .update();
 //! // Get tournament's permissions
 //! let permissions = toornament.tournaments_iter()
 //!                             .with_id(TournamentId("1".to_owned()))
@@ -76,11 +88,48 @@
 //!                             .with_number(GameNumber(3i64))
 //!                             .result()
 //!                             .collect::<MatchResult>();
+//! // Get matches of the group 1 of the stage 2
+//! let group_matches = toornament.tournaments_iter()
+//!                               .with_id(TournamentId("1".to_owned()))
+//!                               .stages()
+//!                               .with_number(StageNumber(2))
+//!                               .matches()
+//!                               .group(1)
+//!                               .collect::<Matches>();
+//! // Get matches of the participant with id = 2
+//! let schedule = toornament.tournaments_iter()
+//!                          .with_id(TournamentId("1".to_owned()))
+//!                          .participants()
+//!                          .with_id(ParticipantId("2".to_owned()))
+//!                          .matches()
+//!                          .collect::<Matches>();
 //! ```
 //!
 //! Note that iter-like interface is lazy - no action is done before you actually do something.
 //! So, the finish states are usually a modifier of an iterator (like `matches()` of
 //! `TournamentIter`) or a `collect()` methods.
+//!
+//! The iterators created from an owned (cloned) client do not borrow anything and can be moved
+//! into another thread:
+//!
+//! ```rust,no_run
+//! use toornament::*;
+//!
+//! let toornament = Toornament::with_application("API_TOKEN",
+//!                                               "CLIENT_ID",
+//!                                               "CLIENT_SECRET").unwrap();
+//! let tournaments = TournamentsIter::new(toornament.clone());
+//! let handle = std::thread::spawn(move || tournaments.my().collect::<Tournaments>());
+//! let my = handle.join().unwrap().unwrap();
+//!
+//! // Or convert a borrowing iterator into an owned one
+//! let participants = toornament.tournaments_iter()
+//!                              .with_id(TournamentId("1".to_owned()))
+//!                              .participants()
+//!                              .into_owned();
+//! let handle = std::thread::spawn(move || participants.collect::<Participants>());
+//! let participants = handle.join().unwrap().unwrap();
+//! ```
 
 mod discipline_matches;
 mod disciplines;
@@ -101,3 +150,45 @@ pub use self::stages::*;
 pub use self::tournament_matches::*;
 pub use self::tournaments::*;
 pub use self::videos::*;
+
+use crate::Toornament;
+
+/// A client used by the iterators: either borrowed or owned. The iterators holding an owned
+/// client have the `'static` lifetime and can be moved into other threads.
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)] // Boxing the owned client would not make it cheaper.
+pub enum ClientHandle<'a> {
+    /// A borrowed client
+    Borrowed(&'a Toornament),
+    /// An owned client, cheap to clone
+    Owned(Toornament),
+}
+impl<'a> ClientHandle<'a> {
+    /// Converts the handle into an owned one, cloning the client if it is borrowed.
+    pub fn into_owned(self) -> ClientHandle<'static> {
+        match self {
+            ClientHandle::Borrowed(client) => ClientHandle::Owned(client.clone()),
+            ClientHandle::Owned(client) => ClientHandle::Owned(client),
+        }
+    }
+}
+impl<'a> std::ops::Deref for ClientHandle<'a> {
+    type Target = Toornament;
+
+    fn deref(&self) -> &Toornament {
+        match *self {
+            ClientHandle::Borrowed(client) => client,
+            ClientHandle::Owned(ref client) => client,
+        }
+    }
+}
+impl<'a> From<&'a Toornament> for ClientHandle<'a> {
+    fn from(client: &'a Toornament) -> ClientHandle<'a> {
+        ClientHandle::Borrowed(client)
+    }
+}
+impl<'a> From<Toornament> for ClientHandle<'a> {
+    fn from(client: Toornament) -> ClientHandle<'a> {
+        ClientHandle::Owned(client)
+    }
+}
@@ -2,7 +2,7 @@ use crate::*;
 
 /// A remote participants iterator
 pub struct ParticipantsIter<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Participants of the following tournament id
     tournament_id: TournamentId,
@@ -11,9 +11,12 @@ pub struct ParticipantsIter<'a> {
 }
 impl<'a> ParticipantsIter<'a> {
     /// Create new participants iter
-    pub fn new(client: &'a Toornament, tournament_id: TournamentId) -> ParticipantsIter {
+    pub fn new<C: Into<ClientHandle<'a>>>(
+        client: C,
+        tournament_id: TournamentId,
+    ) -> ParticipantsIter<'a> {
         ParticipantsIter {
-            client,
+            client: client.into(),
             tournament_id,
             filter: TournamentParticipantsFilter::default(),
         }
@@ -28,6 +31,18 @@ impl<'a> ParticipantsIter<'a> {
         self
     }
 
+    /// Fetch the page with the following number, starting from one
+    pub fn page(mut self, page: i64) -> Self {
+        self.filter.page = page;
+        self
+    }
+
+    /// Fetch the following number of participants per page
+    pub fn per_page(mut self, per_page: i64) -> Self {
+        self.filter.per_page = Some(per_page);
+        self
+    }
+
     /// Fetch participant of tournament with id
     pub fn of_tournament(mut self, id: TournamentId) -> Self {
         self.tournament_id = id;
@@ -43,7 +58,7 @@ impl<'a> ParticipantsIter<'a> {
     }
 
     /// Update the list of participants
-    pub fn edit<F: 'static + FnMut(Participants) -> Participants>(
+    pub fn edit<F: 'static + Send + FnMut(Participants) -> Participants>(
         self,
         editor: F,
     ) -> ParticipantsEditor<'a> {
@@ -56,7 +71,10 @@ impl<'a> ParticipantsIter<'a> {
     }
 
     /// Create a participant
-    pub fn create<F: 'static + FnMut() -> Participant>(self, creator: F) -> ParticipantCreator<'a> {
+    pub fn create<F: 'static + Send + FnMut() -> Participant>(
+        self,
+        creator: F,
+    ) -> ParticipantCreator<'a> {
         ParticipantCreator {
             client: self.client,
             tournament_id: self.tournament_id,
@@ -78,14 +96,14 @@ impl<'a> ParticipantsIter<'a> {
 
 /// A lazy participants editor
 pub struct ParticipantsEditor<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Tournament id in which the participants is in
     tournament_id: TournamentId,
     /// Participants with filter
     filter: TournamentParticipantsFilter,
     /// Participant editor
-    editor: Box<dyn FnMut(Participants) -> Participants>,
+    editor: Box<dyn FnMut(Participants) -> Participants + Send>,
 }
 
 /// Terminators
@@ -103,7 +121,7 @@ impl<'a> ParticipantsEditor<'a> {
 
 /// A remote participant iterator
 pub struct ParticipantIter<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Fetch a participant with the following id
     tournament_id: TournamentId,
@@ -112,13 +130,13 @@ pub struct ParticipantIter<'a> {
 }
 impl<'a> ParticipantIter<'a> {
     /// Create new participant iter
-    pub fn new(
-        client: &'a Toornament,
+    pub fn new<C: Into<ClientHandle<'a>>>(
+        client: C,
         tournament_id: TournamentId,
         id: ParticipantId,
-    ) -> ParticipantIter {
+    ) -> ParticipantIter<'a> {
         ParticipantIter {
-            client,
+            client: client.into(),
             tournament_id,
             id,
         }
@@ -128,7 +146,7 @@ impl<'a> ParticipantIter<'a> {
 /// Modifiers
 impl<'a> ParticipantIter<'a> {
     /// Edit the participant
-    pub fn edit<F: 'static + FnMut(Participant) -> Participant>(
+    pub fn edit<F: 'static + Send + FnMut(Participant) -> Participant>(
         self,
         editor: F,
     ) -> ParticipantEditor<'a> {
@@ -141,6 +159,21 @@ impl<'a> ParticipantIter<'a> {
     }
 }
 
+/// Modifiers
+impl<'a> ParticipantIter<'a> {
+    /// Fetch matches of the participant
+    pub fn matches(self) -> ParticipantMatchesIter<'a> {
+        ParticipantMatchesIter {
+            client: self.client,
+            tournament_id: self.tournament_id,
+            id: self.id,
+            with_games: false,
+            page: None,
+            per_page: None,
+        }
+    }
+}
+
 /// Terminators
 impl<'a> ParticipantIter<'a> {
     /// Collects the participant
@@ -164,14 +197,66 @@ impl<'a> ParticipantIter<'a> {
     }
 }
 
+/// A participant matches iterator
+pub struct ParticipantMatchesIter<'a> {
+    client: ClientHandle<'a>,
+
+    /// Fetch matches of tournament
+    tournament_id: TournamentId,
+    /// Fetch matches of the participant with id
+    id: ParticipantId,
+    /// Fetch games with the matches
+    with_games: bool,
+    /// Fetch the page with the following number
+    page: Option<i64>,
+    /// Fetch the following number of matches per page
+    per_page: Option<i64>,
+}
+
+/// Builders
+impl<'a> ParticipantMatchesIter<'a> {
+    /// Fetch match games
+    pub fn with_games(mut self, with_games: bool) -> Self {
+        self.with_games = with_games;
+        self
+    }
+
+    /// Fetch the page with the following number, starting from one
+    pub fn page(mut self, page: i64) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    /// Fetch the following number of matches per page
+    pub fn per_page(mut self, per_page: i64) -> Self {
+        self.per_page = Some(per_page);
+        self
+    }
+}
+
+/// Terminators
+impl<'a> ParticipantMatchesIter<'a> {
+    /// Fetch matches
+    pub fn collect<T: From<Matches>>(self) -> Result<T> {
+        let endpoint = Endpoint::MatchesByParticipant {
+            tournament_id: self.tournament_id,
+            participant_id: self.id,
+            with_games: self.with_games,
+            page: self.page,
+            per_page: self.per_page,
+        };
+        Ok(T::from(self.client.get(&endpoint)?.json::<Matches>()?))
+    }
+}
+
 /// A lazy participant creator
 pub struct ParticipantCreator<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Tournament id in which the participant is in
     tournament_id: TournamentId,
     /// Participant editor
-    creator: Box<dyn FnMut() -> Participant>,
+    creator: Box<dyn FnMut() -> Participant + Send>,
 }
 
 /// Terminators
@@ -185,14 +270,14 @@ impl<'a> ParticipantCreator<'a> {
 
 /// A lazy participant editor
 pub struct ParticipantEditor<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Tournament id in which the participant is in
     tournament_id: TournamentId,
     /// Participant's id
     id: ParticipantId,
     /// Participant editor
-    editor: Box<dyn FnMut(Participant) -> Participant>,
+    editor: Box<dyn FnMut(Participant) -> Participant + Send>,
 }
 
 /// Terminators
@@ -207,3 +292,30 @@ impl<'a> ParticipantEditor<'a> {
             .update_tournament_participant(self.tournament_id, self.id, edited)
     }
 }
+
+into_owned!(ParticipantsIter {
+    tournament_id,
+    filter
+});
+into_owned!(ParticipantsEditor {
+    tournament_id,
+    filter,
+    editor
+});
+into_owned!(ParticipantIter { tournament_id, id });
+into_owned!(ParticipantMatchesIter {
+    tournament_id,
+    id,
+    with_games,
+    page,
+    per_page
+});
+into_owned!(ParticipantCreator {
+    tournament_id,
+    creator
+});
+into_owned!(ParticipantEditor {
+    tournament_id,
+    id,
+    editor
+});
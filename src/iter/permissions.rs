@@ -2,16 +2,19 @@ use crate::*;
 
 /// Tournament permissions iterator
 pub struct PermissionsIter<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Fetch permissions of the following tournament id
     tournament_id: TournamentId,
 }
 impl<'a> PermissionsIter<'a> {
     /// Create new permissions iter
-    pub fn new(client: &'a Toornament, tournament_id: TournamentId) -> PermissionsIter {
+    pub fn new<C: Into<ClientHandle<'a>>>(
+        client: C,
+        tournament_id: TournamentId,
+    ) -> PermissionsIter<'a> {
         PermissionsIter {
-            client,
+            client: client.into(),
             tournament_id,
         }
     }
@@ -29,7 +32,10 @@ impl<'a> PermissionsIter<'a> {
     }
 
     /// Create a permission
-    pub fn create<F: 'static + FnMut() -> Permission>(self, creator: F) -> PermissionCreator<'a> {
+    pub fn create<F: 'static + Send + FnMut() -> Permission>(
+        self,
+        creator: F,
+    ) -> PermissionCreator<'a> {
         PermissionCreator {
             client: self.client,
             tournament_id: self.tournament_id,
@@ -50,7 +56,7 @@ impl<'a> PermissionsIter<'a> {
 
 /// Tournament permission iterator
 pub struct PermissionIter<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Fetch permissions of the following tournament id
     tournament_id: TournamentId,
@@ -59,13 +65,13 @@ pub struct PermissionIter<'a> {
 }
 impl<'a> PermissionIter<'a> {
     /// Create new permission iter
-    pub fn new(
-        client: &'a Toornament,
+    pub fn new<C: Into<ClientHandle<'a>>>(
+        client: C,
         tournament_id: TournamentId,
         permission_id: PermissionId,
-    ) -> PermissionIter {
+    ) -> PermissionIter<'a> {
         PermissionIter {
-            client,
+            client: client.into(),
             tournament_id,
             permission_id,
         }
@@ -86,7 +92,7 @@ impl<'a> PermissionIter<'a> {
     // TODO
     /* There is no ability to edit permissions yet
     /// Edit a permission
-    pub fn edit<F: 'static + FnMut(Permission) -> Permission>(self, editor: F)
+    pub fn edit<F: 'static + Send + FnMut(Permission) -> Permission>(self, editor: F)
         -> PermissionEditor<'a> {
         PermissionEditor {
             client: self.client,
@@ -117,6 +123,12 @@ impl<'a> PermissionIter<'a> {
         )?))
     }
 
+    /// Send the invitation of this permission to its user again
+    pub fn resend_invitation(self) -> Result<()> {
+        self.client
+            .resend_permission_invitation(self.tournament_id, self.permission_id)
+    }
+
     /// Delete this permission
     pub fn delete(self) -> Result<()> {
         self.client
@@ -126,12 +138,12 @@ impl<'a> PermissionIter<'a> {
 
 /// A lazy permission creator
 pub struct PermissionCreator<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// A tournament to which the permission will belong to
     tournament_id: TournamentId,
     /// Permission creator
-    creator: Box<dyn FnMut() -> Permission>,
+    creator: Box<dyn FnMut() -> Permission + Send>,
 }
 
 /// Terminators
@@ -159,7 +171,7 @@ impl<'a> PermissionCreator<'a> {
 /* There is no ability to edit permissions yet
 /// A lazy permission editor
 pub struct PermissionEditor<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// A tournament to which the permission will belong to
     tournament_id: TournamentId,
@@ -175,7 +187,7 @@ impl<'a> PermissionEditor<'a> {
     pub fn update(mut self) -> Result<Permission> {
         // self.client.create_tournament_permission(self.tournament_id, (self.editor)())
 
-        let original = match self.client.tournaments(Some(self.id), self.with_streams)?.0.first() {
+        let original = match self.client.tournaments(Some(self.id), Includes::when(self.with_streams, Include::Streams))?.0.first() {
             Some(t) => t.to_owned(),
             None => return Err(Error::Other("No such tournament")),
         };
@@ -198,7 +210,7 @@ impl<'a> PermissionEditor<'a> {
 
 /// A permission attributes iterator
 pub struct PermissionAttributesIter<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// A tournament to which the permission will belong to
     tournament_id: TournamentId,
@@ -218,7 +230,7 @@ impl<'a> PermissionAttributesIter<'a> {
     }
 
     /// Edit the permission attributes
-    pub fn edit<F: 'static + FnMut(PermissionAttributes) -> PermissionAttributes>(
+    pub fn edit<F: 'static + Send + FnMut(PermissionAttributes) -> PermissionAttributes>(
         self,
         editor: F,
     ) -> PermissionAttributesEditor<'a> {
@@ -242,14 +254,14 @@ impl<'a> PermissionAttributesIter<'a> {
 
 /// A lazy permission attributes editor
 pub struct PermissionAttributesEditor<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// A tournament to which the permission will belong to
     tournament_id: TournamentId,
     /// A permission to edit
     permission_id: PermissionId,
     /// Permission attributes editor
-    editor: Box<dyn FnMut(PermissionAttributes) -> PermissionAttributes>,
+    editor: Box<dyn FnMut(PermissionAttributes) -> PermissionAttributes + Send>,
 }
 
 /// Terminators
@@ -287,3 +299,22 @@ impl<'a> PermissionAttributesEditor<'a> {
         })
     }
 }
+
+into_owned!(PermissionsIter { tournament_id });
+into_owned!(PermissionIter {
+    tournament_id,
+    permission_id
+});
+into_owned!(PermissionCreator {
+    tournament_id,
+    creator
+});
+into_owned!(PermissionAttributesIter {
+    tournament_id,
+    permission_id
+});
+into_owned!(PermissionAttributesEditor {
+    tournament_id,
+    permission_id,
+    editor
+});
@@ -1,22 +1,35 @@
+use crate::swiss::Standings;
 use crate::*;
+use std::collections::BTreeMap;
 
 /// Tournament stages iterator
 pub struct StagesIter<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Fetch stages of the following tournament id
     tournament_id: TournamentId,
 }
 impl<'a> StagesIter<'a> {
     /// Create new stages iter
-    pub fn new(client: &'a Toornament, tournament_id: TournamentId) -> StagesIter {
+    pub fn new<C: Into<ClientHandle<'a>>>(
+        client: C,
+        tournament_id: TournamentId,
+    ) -> StagesIter<'a> {
         StagesIter {
-            client,
+            client: client.into(),
             tournament_id,
         }
     }
 }
 
+/// Modifiers
+impl<'a> StagesIter<'a> {
+    /// Fetch a stage with the number
+    pub fn with_number(self, number: StageNumber) -> StageIter<'a> {
+        StageIter::new(self.client, self.tournament_id, number)
+    }
+}
+
 /// Terminators
 impl<'a> StagesIter<'a> {
     /// Collect the stages
@@ -24,3 +37,233 @@ impl<'a> StagesIter<'a> {
         Ok(T::from(self.client.tournament_stages(self.tournament_id)?))
     }
 }
+
+/// Tournament stage iterator
+pub struct StageIter<'a> {
+    client: ClientHandle<'a>,
+
+    /// Fetch stage of the following tournament id
+    tournament_id: TournamentId,
+    /// Fetch stage with the following number
+    number: StageNumber,
+}
+impl<'a> StageIter<'a> {
+    /// Create new stage iter
+    pub fn new<C: Into<ClientHandle<'a>>>(
+        client: C,
+        tournament_id: TournamentId,
+        number: StageNumber,
+    ) -> StageIter<'a> {
+        StageIter {
+            client: client.into(),
+            tournament_id,
+            number,
+        }
+    }
+}
+
+/// Modifiers
+impl<'a> StageIter<'a> {
+    /// Fetch matches of the stage
+    pub fn matches(self) -> StageMatchesIter<'a> {
+        StageMatchesIter {
+            client: self.client,
+            tournament_id: self.tournament_id,
+            number: self.number,
+            group: None,
+            with_games: false,
+            page: None,
+            per_page: None,
+        }
+    }
+
+    /// Fetch matches of the stage grouped by the group number
+    pub fn groups(self) -> StageGroupsIter<'a> {
+        StageGroupsIter {
+            client: self.client,
+            tournament_id: self.tournament_id,
+            number: self.number,
+        }
+    }
+
+    /// Compute rankings of the stage from the match results
+    pub fn rankings(self) -> StageRankingsIter<'a> {
+        StageRankingsIter {
+            client: self.client,
+            tournament_id: self.tournament_id,
+            number: self.number,
+            group: None,
+            points: (3, 1, 0),
+        }
+    }
+}
+
+/// Terminators
+impl<'a> StageIter<'a> {
+    /// Collect the stage
+    pub fn collect<T: From<Stage>>(self) -> Result<T> {
+        let stages = self.client.tournament_stages(self.tournament_id.clone())?;
+        match stages.0.into_iter().find(|s| s.number == self.number) {
+            Some(s) => Ok(T::from(s)),
+            None => Err(Error::Iter(IterError::NoSuchStage(
+                self.tournament_id,
+                self.number,
+            ))),
+        }
+    }
+}
+
+/// Stage matches iterator
+pub struct StageMatchesIter<'a> {
+    client: ClientHandle<'a>,
+
+    /// Fetch matches of the following tournament id
+    tournament_id: TournamentId,
+    /// Fetch matches of the following stage number
+    number: StageNumber,
+    /// Fetch matches of the following group number only
+    group: Option<u64>,
+    /// Fetch games with the matches
+    with_games: bool,
+    /// Fetch the page with the following number
+    page: Option<i64>,
+    /// Fetch the following number of matches per page
+    per_page: Option<i64>,
+}
+
+/// Builders
+impl<'a> StageMatchesIter<'a> {
+    /// Fetch matches of the group with the number only
+    pub fn group(mut self, group: u64) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    /// Fetch match games
+    pub fn with_games(mut self, with_games: bool) -> Self {
+        self.with_games = with_games;
+        self
+    }
+
+    /// Fetch the page with the following number, starting from one
+    pub fn page(mut self, page: i64) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    /// Fetch the following number of matches per page
+    pub fn per_page(mut self, per_page: i64) -> Self {
+        self.per_page = Some(per_page);
+        self
+    }
+}
+
+/// Terminators
+impl<'a> StageMatchesIter<'a> {
+    /// Fetch the matches
+    pub fn collect<T: From<Matches>>(self) -> Result<T> {
+        let endpoint = Endpoint::MatchesByStage {
+            tournament_id: self.tournament_id,
+            stage_number: self.number,
+            group_number: self.group,
+            with_games: self.with_games,
+            page: self.page,
+            per_page: self.per_page,
+        };
+        Ok(T::from(self.client.get(&endpoint)?.json::<Matches>()?))
+    }
+}
+
+/// Stage groups iterator
+pub struct StageGroupsIter<'a> {
+    client: ClientHandle<'a>,
+
+    /// Fetch groups of the following tournament id
+    tournament_id: TournamentId,
+    /// Fetch groups of the following stage number
+    number: StageNumber,
+}
+
+/// Terminators
+impl<'a> StageGroupsIter<'a> {
+    /// Fetch the matches of the stage grouped by the group number
+    pub fn collect<T: From<BTreeMap<u64, Matches>>>(self) -> Result<T> {
+        let matches =
+            self.client
+                .stage_matches(self.tournament_id, self.number, None, Includes::none())?;
+        let mut groups = BTreeMap::<u64, Matches>::new();
+        for m in matches.0 {
+            groups.entry(m.group_number).or_default().0.push(m);
+        }
+        Ok(T::from(groups))
+    }
+}
+
+/// Stage rankings iterator
+pub struct StageRankingsIter<'a> {
+    client: ClientHandle<'a>,
+
+    /// Fetch rankings of the following tournament id
+    tournament_id: TournamentId,
+    /// Fetch rankings of the following stage number
+    number: StageNumber,
+    /// Fetch rankings of the following group number only
+    group: Option<u64>,
+    /// Points for a win, a draw and a loss
+    points: (i64, i64, i64),
+}
+
+/// Builders
+impl<'a> StageRankingsIter<'a> {
+    /// Rank the participants of the group with the number only
+    pub fn group(mut self, group: u64) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    /// Set the points for a win, a draw and a loss, 3, 1 and 0 by default
+    pub fn points(mut self, win: i64, draw: i64, loss: i64) -> Self {
+        self.points = (win, draw, loss);
+        self
+    }
+}
+
+/// Terminators
+impl<'a> StageRankingsIter<'a> {
+    /// Fetch the stage matches and rank the participants by the points of their duel results,
+    /// see `Standings::from_matches`
+    pub fn collect<T: From<Standings>>(self) -> Result<T> {
+        let matches = self.client.stage_matches(
+            self.tournament_id,
+            self.number,
+            self.group,
+            Includes::none(),
+        )?;
+        let (win, draw, loss) = self.points;
+        Ok(T::from(Standings::from_matches(&matches, win, draw, loss)))
+    }
+}
+
+into_owned!(StagesIter { tournament_id });
+into_owned!(StageIter {
+    tournament_id,
+    number
+});
+into_owned!(StageMatchesIter {
+    tournament_id,
+    number,
+    group,
+    with_games,
+    page,
+    per_page
+});
+into_owned!(StageGroupsIter {
+    tournament_id,
+    number
+});
+into_owned!(StageRankingsIter {
+    tournament_id,
+    number,
+    group,
+    points
+});
@@ -3,20 +3,29 @@ use iter::games::GamesIter;
 
 /// A tournament matches iterator
 pub struct TournamentMatchesIter<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Fetch matches of tournament
     tournament_id: TournamentId,
     /// Fetch games with the match
     with_games: bool,
+    /// Fetch the page with the following number
+    page: Option<i64>,
+    /// Fetch the following number of matches per page
+    per_page: Option<i64>,
 }
 impl<'a> TournamentMatchesIter<'a> {
     /// Creates new match iterator
-    pub fn new(client: &'a Toornament, tournament_id: TournamentId) -> TournamentMatchesIter {
+    pub fn new<C: Into<ClientHandle<'a>>>(
+        client: C,
+        tournament_id: TournamentId,
+    ) -> TournamentMatchesIter<'a> {
         TournamentMatchesIter {
-            client,
+            client: client.into(),
             tournament_id,
             with_games: false,
+            page: None,
+            per_page: None,
         }
     }
 }
@@ -29,6 +38,18 @@ impl<'a> TournamentMatchesIter<'a> {
         self
     }
 
+    /// Fetch the page with the following number, starting from one
+    pub fn page(mut self, page: i64) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    /// Fetch the following number of matches per page
+    pub fn per_page(mut self, per_page: i64) -> Self {
+        self.per_page = Some(per_page);
+        self
+    }
+
     /// Fetch match by tournament id
     pub fn of_tournament(mut self, id: TournamentId) -> Self {
         self.tournament_id = id;
@@ -53,17 +74,21 @@ impl<'a> TournamentMatchesIter<'a> {
 impl<'a> TournamentMatchesIter<'a> {
     /// Fetch matches
     pub fn collect<T: From<Matches>>(self) -> Result<T> {
-        Ok(T::from(self.client.matches(
-            self.tournament_id,
-            None,
-            self.with_games,
-        )?))
+        let endpoint = Endpoint::MatchesByTournament {
+            tournament_id: self.tournament_id,
+            with_games: self.with_games,
+            page: self.page,
+            per_page: self.per_page,
+        };
+        let mut matches: Matches = self.client.get(&endpoint)?.json()?;
+        matches.0 = matches.0.into_iter().map(Match::link_games).collect();
+        Ok(T::from(matches))
     }
 }
 
 /// A tournament match iterator
 pub struct TournamentMatchIter<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Fetch match of tournament
     tournament_id: TournamentId,
@@ -74,14 +99,14 @@ pub struct TournamentMatchIter<'a> {
 }
 impl<'a> TournamentMatchIter<'a> {
     /// Creates new tournament match iter
-    pub fn new(
-        client: &'a Toornament,
+    pub fn new<C: Into<ClientHandle<'a>>>(
+        client: C,
         tournament_id: TournamentId,
         match_id: MatchId,
         with_games: bool,
     ) -> TournamentMatchIter<'a> {
         TournamentMatchIter {
-            client,
+            client: client.into(),
             tournament_id,
             match_id,
             with_games,
@@ -92,7 +117,10 @@ impl<'a> TournamentMatchIter<'a> {
 /// Modifiers
 impl<'a> TournamentMatchIter<'a> {
     /// Tournament match lazy editor
-    pub fn edit<F: 'static + FnMut(Match) -> Match>(self, editor: F) -> TournamentMatchEditor<'a> {
+    pub fn edit<F: 'static + Send + FnMut(Match) -> Match>(
+        self,
+        editor: F,
+    ) -> TournamentMatchEditor<'a> {
         TournamentMatchEditor {
             client: self.client,
             tournament_id: self.tournament_id,
@@ -124,7 +152,7 @@ impl<'a> TournamentMatchIter<'a> {
         let matches = self.client.matches(
             self.tournament_id.clone(),
             Some(self.match_id.clone()),
-            self.with_games,
+            Includes::when(self.with_games, Include::Games),
         )?;
         match matches.0.first() {
             Some(m) => Ok(T::from(m.to_owned())),
@@ -138,7 +166,7 @@ impl<'a> TournamentMatchIter<'a> {
 
 /// A tournament match result iterator
 pub struct TournamentMatchResultIter<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Fetch match of tournament
     tournament_id: TournamentId,
@@ -149,7 +177,7 @@ pub struct TournamentMatchResultIter<'a> {
 /// Modifiers
 impl<'a> TournamentMatchResultIter<'a> {
     /// Tournament match result lazy editor
-    pub fn edit<F: 'static + FnMut(MatchResult) -> MatchResult>(
+    pub fn edit<F: 'static + Send + FnMut(MatchResult) -> MatchResult>(
         self,
         editor: F,
     ) -> TournamentMatchResultEditor<'a> {
@@ -175,14 +203,14 @@ impl<'a> TournamentMatchResultIter<'a> {
 
 /// A lazy match result editor
 pub struct TournamentMatchResultEditor<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Fetch match of tournament
     tournament_id: TournamentId,
     /// Fetch match with id
     match_id: MatchId,
     /// Match result editor
-    editor: Box<dyn FnMut(MatchResult) -> MatchResult>,
+    editor: Box<dyn FnMut(MatchResult) -> MatchResult + Send>,
 }
 
 /// Terminators
@@ -199,7 +227,7 @@ impl<'a> TournamentMatchResultEditor<'a> {
 
 /// A lazy tournament match editor
 pub struct TournamentMatchEditor<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Fetch match of tournament
     tournament_id: TournamentId,
@@ -208,7 +236,7 @@ pub struct TournamentMatchEditor<'a> {
     /// Fetch games with the match
     with_games: bool,
     /// Editor
-    editor: Box<dyn FnMut(Match) -> Match>,
+    editor: Box<dyn FnMut(Match) -> Match + Send>,
 }
 
 /// Terminators
@@ -218,7 +246,7 @@ impl<'a> TournamentMatchEditor<'a> {
         let matches = self.client.matches(
             self.tournament_id.clone(),
             Some(self.match_id.clone()),
-            self.with_games,
+            Includes::when(self.with_games, Include::Games),
         )?;
         let original = match matches.0.first() {
             Some(m) => m.to_owned(),
@@ -233,3 +261,30 @@ impl<'a> TournamentMatchEditor<'a> {
             .update_match(self.tournament_id, self.match_id, (self.editor)(original))
     }
 }
+
+into_owned!(TournamentMatchesIter {
+    tournament_id,
+    with_games,
+    page,
+    per_page
+});
+into_owned!(TournamentMatchIter {
+    tournament_id,
+    match_id,
+    with_games
+});
+into_owned!(TournamentMatchResultIter {
+    tournament_id,
+    match_id
+});
+into_owned!(TournamentMatchResultEditor {
+    tournament_id,
+    match_id,
+    editor
+});
+into_owned!(TournamentMatchEditor {
+    tournament_id,
+    match_id,
+    with_games,
+    editor
+});
@@ -14,22 +14,25 @@ enum TournamentsIterFetch {
 /// A remote iterator over tournaments
 #[derive(Debug)]
 pub struct TournamentsIter<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Fetch tournaments with the streams
     with_streams: bool,
-    /// Fetch tournaments with the following name
-    name: Option<String>,
+    /// Fetch tournaments with the filter
+    filter: TournamentFilter,
+    /// Keep the tournaments with the name containing the following text
+    name_contains: Option<String>,
     /// Fetch type
     fetch: TournamentsIterFetch,
 }
 impl<'a> TournamentsIter<'a> {
     /// Creates new tournaments iterator
-    pub fn new(client: &'a Toornament) -> TournamentsIter {
+    pub fn new<C: Into<ClientHandle<'a>>>(client: C) -> TournamentsIter<'a> {
         TournamentsIter {
-            client,
+            client: client.into(),
             with_streams: false,
-            name: None,
+            filter: TournamentFilter::default(),
+            name_contains: None,
             fetch: TournamentsIterFetch::All,
             // ..Default::default()
         }
@@ -38,9 +41,32 @@ impl<'a> TournamentsIter<'a> {
 
 /// Builders
 impl<'a> TournamentsIter<'a> {
-    /// Fetch a tournament with the following name
+    /// Fetch the tournaments with the following name. The service filters the public
+    /// tournaments; `my()` tournaments are filtered by the name after they are fetched, the
+    /// other filters do not apply to them.
     pub fn with_name<S: Into<String>>(mut self, name: S) -> Self {
-        self.name = Some(name.into());
+        self.filter.name = Some(name.into());
+        self
+    }
+
+    /// Keep the tournaments with the name containing the following text, ignoring the case.
+    /// The service does not support such a search, so all the tournaments are fetched and
+    /// filtered afterwards: prefer `with_name` when the exact name is known.
+    pub fn name_contains<S: Into<String>>(mut self, text: S) -> Self {
+        self.name_contains = Some(text.into());
+        self
+    }
+
+    /// Fetch the featured tournaments only. Featured tournaments are tagged by Toornament as
+    /// major tournaments for a given discipline.
+    pub fn featured(mut self) -> Self {
+        self.filter.featured = Some(true);
+        self
+    }
+
+    /// Fetch the tournaments of the discipline
+    pub fn of_discipline(mut self, id: DisciplineId) -> Self {
+        self.filter.discipline_id = Some(id);
         self
     }
 
@@ -61,6 +87,19 @@ impl<'a> TournamentsIter<'a> {
         self.with_streams = with_streams;
         self
     }
+
+    /// Fetch the page with the following number, starting from one. The service pages all the
+    /// tournaments only: `my()` tournaments are fetched at once.
+    pub fn page(mut self, page: i64) -> Self {
+        self.filter.page = Some(page);
+        self
+    }
+
+    /// Fetch the following number of tournaments per page, see `page`
+    pub fn per_page(mut self, per_page: i64) -> Self {
+        self.filter.per_page = Some(per_page);
+        self
+    }
 }
 
 /// Modifiers
@@ -71,7 +110,10 @@ impl<'a> TournamentsIter<'a> {
     }
 
     /// Create a tournament
-    pub fn create<F: 'static + FnMut() -> Tournament>(self, creator: F) -> TournamentCreator<'a> {
+    pub fn create<F: 'static + Send + FnMut() -> Tournament>(
+        self,
+        creator: F,
+    ) -> TournamentCreator<'a> {
         TournamentCreator {
             client: self.client,
             creator: Box::new(creator),
@@ -84,12 +126,24 @@ impl<'a> TournamentsIter<'a> {
     /// Return the collection
     pub fn collect<T: From<Tournaments>>(self) -> Result<T> {
         let mut tournaments = match self.fetch {
-            TournamentsIterFetch::All => self.client.tournaments(None, self.with_streams),
-            TournamentsIterFetch::My => self.client.my_tournaments(),
-        }?;
+            TournamentsIterFetch::All => self.client.filtered_tournaments(
+                self.filter,
+                Includes::when(self.with_streams, Include::Streams),
+            )?,
+            TournamentsIterFetch::My => {
+                let mut tournaments = self.client.my_tournaments()?;
+                if let Some(name) = self.filter.name {
+                    tournaments.0.retain(|t| t.name == name);
+                }
+                tournaments
+            }
+        };
 
-        if let Some(name) = self.name {
-            tournaments.0.retain(|t| t.name == name);
+        if let Some(text) = self.name_contains {
+            let text = text.to_lowercase();
+            tournaments
+                .0
+                .retain(|t| t.name.to_lowercase().contains(&text));
         }
 
         Ok(T::from(tournaments))
@@ -98,7 +152,7 @@ impl<'a> TournamentsIter<'a> {
 
 /// A remote tournament iterator
 pub struct TournamentIter<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// A tournament id
     id: TournamentId,
@@ -107,9 +161,9 @@ pub struct TournamentIter<'a> {
 }
 impl<'a> TournamentIter<'a> {
     /// Creates new tournament iter for a tournament with id
-    pub fn new(client: &'a Toornament, id: TournamentId) -> TournamentIter {
+    pub fn new<C: Into<ClientHandle<'a>>>(client: C, id: TournamentId) -> TournamentIter<'a> {
         TournamentIter {
-            client,
+            client: client.into(),
             id,
             with_streams: false,
         }
@@ -134,7 +188,7 @@ impl<'a> TournamentIter<'a> {
 /// Modifiers
 impl<'a> TournamentIter<'a> {
     /// Tournament lazy editor
-    pub fn edit<F: 'static + FnMut(Tournament) -> Tournament>(
+    pub fn edit<F: 'static + Send + FnMut(Tournament) -> Tournament>(
         self,
         editor: F,
     ) -> TournamentEditor<'a> {
@@ -146,6 +200,14 @@ impl<'a> TournamentIter<'a> {
         }
     }
 
+    /// Tournament settings
+    pub fn settings(self) -> TournamentSettingsIter<'a> {
+        TournamentSettingsIter {
+            client: self.client,
+            id: self.id,
+        }
+    }
+
     /// Tournament participants
     pub fn participants(self) -> ParticipantsIter<'a> {
         ParticipantsIter::new(self.client, self.id)
@@ -176,9 +238,10 @@ impl<'a> TournamentIter<'a> {
 impl<'a> TournamentIter<'a> {
     /// Return the tournament
     pub fn collect<T: From<Tournament>>(self) -> Result<T> {
-        let tournaments = self
-            .client
-            .tournaments(Some(self.id.clone()), self.with_streams)?;
+        let tournaments = self.client.tournaments(
+            Some(self.id.clone()),
+            Includes::when(self.with_streams, Include::Streams),
+        )?;
         let tournament = match tournaments.0.first() {
             Some(t) => t.to_owned(),
             None => return Err(Error::Iter(IterError::NoSuchTournament(self.id))),
@@ -195,23 +258,24 @@ impl<'a> TournamentIter<'a> {
 
 /// A lazy tournament editor
 pub struct TournamentEditor<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Tournament id
     id: TournamentId,
     /// With streams
     with_streams: bool,
     /// Tournament editor
-    editor: Box<dyn FnMut(Tournament) -> Tournament>,
+    editor: Box<dyn FnMut(Tournament) -> Tournament + Send>,
 }
 
 /// Terminators
 impl<'a> TournamentEditor<'a> {
     /// Sends the edited tournament
     pub fn update(mut self) -> Result<Tournament> {
-        let tournaments = self
-            .client
-            .tournaments(Some(self.id.clone()), self.with_streams)?;
+        let tournaments = self.client.tournaments(
+            Some(self.id.clone()),
+            Includes::when(self.with_streams, Include::Streams),
+        )?;
         let original = match tournaments.0.first() {
             Some(t) => t.to_owned(),
             None => return Err(Error::Iter(IterError::NoSuchTournament(self.id))),
@@ -222,9 +286,10 @@ impl<'a> TournamentEditor<'a> {
 
     /// Update and return iter
     pub fn update_iter(mut self) -> Result<TournamentIter<'a>> {
-        let tournaments = self
-            .client
-            .tournaments(Some(self.id.clone()), self.with_streams)?;
+        let tournaments = self.client.tournaments(
+            Some(self.id.clone()),
+            Includes::when(self.with_streams, Include::Streams),
+        )?;
         let original = match tournaments.0.first() {
             Some(t) => t.to_owned(),
             None => return Err(Error::Iter(IterError::NoSuchTournament(self.id))),
@@ -239,12 +304,72 @@ impl<'a> TournamentEditor<'a> {
     }
 }
 
+/// A tournament settings iterator
+pub struct TournamentSettingsIter<'a> {
+    client: ClientHandle<'a>,
+
+    /// A tournament id
+    id: TournamentId,
+}
+
+/// Modifiers
+impl<'a> TournamentSettingsIter<'a> {
+    /// Tournament settings lazy editor
+    pub fn edit<F: 'static + Send + FnMut(TournamentSettings) -> TournamentSettings>(
+        self,
+        editor: F,
+    ) -> TournamentSettingsEditor<'a> {
+        TournamentSettingsEditor {
+            client: self.client,
+            id: self.id,
+            editor: Box::new(editor),
+        }
+    }
+}
+
+/// Terminators
+impl<'a> TournamentSettingsIter<'a> {
+    /// Fetch the tournament settings
+    pub fn collect<T: From<TournamentSettings>>(self) -> Result<T> {
+        Ok(T::from(self.client.tournament_settings(self.id)?))
+    }
+}
+
+/// A lazy tournament settings editor
+pub struct TournamentSettingsEditor<'a> {
+    client: ClientHandle<'a>,
+
+    /// Tournament id
+    id: TournamentId,
+    /// Tournament settings editor
+    editor: Box<dyn FnMut(TournamentSettings) -> TournamentSettings + Send>,
+}
+
+/// Terminators
+impl<'a> TournamentSettingsEditor<'a> {
+    /// Sends the edited settings
+    pub fn update(mut self) -> Result<TournamentSettings> {
+        let original = self.client.tournament_settings(self.id.clone())?;
+        self.client
+            .update_tournament_settings(self.id, (self.editor)(original))
+    }
+
+    /// Update and return iter
+    pub fn update_iter(mut self) -> Result<TournamentIter<'a>> {
+        let original = self.client.tournament_settings(self.id.clone())?;
+        let _ = self
+            .client
+            .update_tournament_settings(self.id.clone(), (self.editor)(original))?;
+        Ok(TournamentIter::new(self.client, self.id))
+    }
+}
+
 /// A lazy tournament creator
 pub struct TournamentCreator<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Tournament creator
-    creator: Box<dyn FnMut() -> Tournament>,
+    creator: Box<dyn FnMut() -> Tournament + Send>,
 }
 
 /// Terminators
@@ -264,3 +389,19 @@ impl<'a> TournamentCreator<'a> {
         }
     }
 }
+
+into_owned!(TournamentsIter {
+    with_streams,
+    filter,
+    name_contains,
+    fetch
+});
+into_owned!(TournamentIter { id, with_streams });
+into_owned!(TournamentEditor {
+    id,
+    with_streams,
+    editor
+});
+into_owned!(TournamentSettingsIter { id });
+into_owned!(TournamentSettingsEditor { id, editor });
+into_owned!(TournamentCreator { creator });
@@ -2,7 +2,7 @@ use crate::*;
 
 /// Tournament videos iterator
 pub struct VideosIter<'a> {
-    client: &'a Toornament,
+    client: ClientHandle<'a>,
 
     /// Fetch videos of the following tournament id
     tournament_id: TournamentId,
@@ -11,9 +11,12 @@ pub struct VideosIter<'a> {
 }
 impl<'a> VideosIter<'a> {
     /// Create new videos iter
-    pub fn new(client: &'a Toornament, tournament_id: TournamentId) -> VideosIter {
+    pub fn new<C: Into<ClientHandle<'a>>>(
+        client: C,
+        tournament_id: TournamentId,
+    ) -> VideosIter<'a> {
         VideosIter {
-            client,
+            client: client.into(),
             tournament_id,
             filter: TournamentVideosFilter::default(),
         }
@@ -27,6 +30,18 @@ impl<'a> VideosIter<'a> {
         self.filter = filter;
         self
     }
+
+    /// Fetch the page with the following number, starting from one
+    pub fn page(mut self, page: i64) -> Self {
+        self.filter.page = Some(page);
+        self
+    }
+
+    /// Fetch the following number of videos per page
+    pub fn per_page(mut self, per_page: i64) -> Self {
+        self.filter.per_page = Some(per_page);
+        self
+    }
 }
 
 /// Terminators
@@ -39,3 +54,8 @@ impl<'a> VideosIter<'a> {
         ))
     }
 }
+
+into_owned!(VideosIter {
+    tournament_id,
+    filter
+});
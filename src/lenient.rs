@@ -0,0 +1,105 @@
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+use std::sync::{Arc, Mutex};
+
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+use crate::Result;
+
+/// An element of a list which could not be parsed and was skipped by a lenient client. See
+/// `Toornament::lenient`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseFailure {
+    /// The address of the list.
+    pub endpoint: String,
+    /// Position of the element in the list.
+    pub index: usize,
+    /// Why the element could not be parsed.
+    pub error: String,
+    /// The element as received.
+    pub element: Value,
+}
+
+/// The parse failures of a lenient client and its clones.
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+pub(crate) type Failures = Arc<Mutex<Vec<ParseFailure>>>;
+
+/// Parses the body. If it is a list which cannot be parsed as a whole, parses its elements
+/// one by one, records those which cannot be parsed into `failures` and parses the others.
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+pub(crate) fn parse<T: DeserializeOwned>(
+    value: Value,
+    endpoint: &str,
+    failures: &Failures,
+) -> Result<T> {
+    let error = match serde_json::from_value(value.clone()) {
+        Ok(parsed) => return Ok(parsed),
+        Err(e) => e,
+    };
+    let elements = match value {
+        Value::Array(elements) => elements,
+        _ => return Err(error.into()),
+    };
+    let mut parsed = Vec::new();
+    let mut skipped = Vec::new();
+    for (index, element) in elements.into_iter().enumerate() {
+        match serde_json::from_value::<T>(Value::Array(vec![element.clone()])) {
+            Ok(_) => parsed.push(element),
+            Err(e) => skipped.push(ParseFailure {
+                endpoint: endpoint.to_owned(),
+                index,
+                error: e.to_string(),
+                element,
+            }),
+        }
+    }
+    let result = serde_json::from_value(Value::Array(parsed)).map_err(|_| error)?;
+    for failure in &skipped {
+        log::warn!(
+            "Skipped element {} of {}: {}",
+            failure.index,
+            failure.endpoint,
+            failure.error
+        );
+    }
+    if let Ok(mut failures) = failures.lock() {
+        failures.extend(skipped);
+    }
+    Ok(result)
+}
+
+#[cfg(all(test, any(feature = "client-blocking", feature = "client-async")))]
+mod tests {
+    use super::*;
+    use crate::Participants;
+
+    #[test]
+    fn test_lenient_parse() {
+        let json = r#"[
+            { "name": "Evil Geniuses" },
+            { "name": 42 },
+            { "name": "Fnatic", "check_in": "maybe" },
+            { "name": "Natus Vincere" }
+        ]"#;
+        let failures = Failures::default();
+        let value = serde_json::from_str(json).unwrap();
+        let participants: Participants = parse(value, "/v1/participants", &failures).unwrap();
+        let names = participants
+            .0
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Evil Geniuses", "Natus Vincere"]);
+
+        let failures = failures.lock().unwrap();
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].index, 1);
+        assert_eq!(failures[1].element["name"], "Fnatic");
+        assert_eq!(failures[1].endpoint, "/v1/participants");
+
+        let object = serde_json::json!({ "name": 42 });
+        assert!(parse::<crate::Participant>(object, "", &Failures::default()).is_err());
+    }
+}
@@ -34,163 +34,1228 @@
 #![warn(missing_docs)]
 #![deny(warnings)]
 
-use std::io::Read;
-use std::sync::Mutex;
+#[cfg(feature = "client-blocking")]
+use std::collections::BTreeSet;
+#[cfg(feature = "client-blocking")]
+use std::sync::{Arc, Mutex};
 
 #[macro_use]
 mod macroses;
+mod account;
+#[cfg(feature = "client-blocking")]
+pub mod archive;
+#[cfg(feature = "client-async")]
+pub mod asynchronous;
+pub mod audit;
+#[cfg(feature = "client-blocking")]
+pub mod batch;
+mod breaker;
+mod cancellation;
 mod common;
-mod disciplines;
+mod config;
+#[cfg(feature = "client-blocking")]
+mod confirm;
+pub mod disciplines;
+#[cfg(feature = "schema-drift")]
+pub mod drift;
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
 mod endpoints;
 mod error;
+pub mod export;
 mod filters;
+mod fingerprint;
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
+pub mod format;
 mod games;
+mod health;
+pub mod import;
 pub mod info;
+#[cfg(feature = "client-blocking")]
 pub mod iter;
+mod lenient;
+pub mod mapping;
 mod matches;
+#[cfg(feature = "client-blocking")]
+mod meta;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod moderation;
+pub mod notify;
 mod opponents;
+#[cfg(feature = "client-blocking")]
+pub mod organizer;
+mod pages;
 mod participants;
 mod permissions;
+pub mod planning;
+pub mod policy;
+mod progress;
+mod progression;
+#[cfg(feature = "proptest-support")]
+mod proptest_support;
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+mod protocol;
+#[cfg(feature = "client-blocking")]
+mod read_only;
+pub mod registration;
+mod scope;
+#[cfg(any(
+    feature = "client-blocking",
+    feature = "client-async",
+    feature = "webhook-http"
+))]
+mod secret;
+pub mod seeding;
+mod snapshot;
 mod stages;
+mod standings;
+pub mod stats;
 mod streams;
+pub mod swiss;
+#[cfg(feature = "client-blocking")]
+pub mod sync;
+mod token;
 mod tournaments;
+mod undo;
 mod videos;
-
-pub use common::{Date, MatchResultSimple, TeamSize};
+#[cfg(feature = "client-blocking")]
+pub mod watcher;
+pub mod webhooks;
+
+pub use account::{Account, AccountId};
+pub use breaker::{BreakerState, CircuitBreakerPolicy};
+pub use cancellation::{Cancellation, Partial};
+pub use common::{Date, Include, Includes, MatchResultSimple, TeamSize};
+pub use config::{ConnectionPool, RequestOptions, RetryPolicy, Timeouts};
+#[cfg(feature = "client-blocking")]
+pub use confirm::Confirmed;
 pub use disciplines::{AdditionalFields, Discipline, DisciplineId, Disciplines};
+#[cfg(feature = "client-blocking")]
 use endpoints::Endpoint;
 pub use error::{
-    Error, IterError, Result, ToornamentError, ToornamentErrorScope, ToornamentErrorType,
-    ToornamentErrors, ToornamentServiceError,
+    ConfigError, Error, ImportError, IterError, OrganizerError, Result, ToornamentError,
+    ToornamentErrorScope, ToornamentErrorType, ToornamentErrors, ToornamentServiceError,
+    ValidationError, WebhookError,
 };
+#[allow(deprecated)]
+pub use filters::{CreateDateSortFilter, DateSortFilter};
 pub use filters::{
-    CreateDateSortFilter, DateSortFilter, MatchFilter, TournamentParticipantsFilter,
-    TournamentVideosFilter,
+    DateBound, DateRange, MatchFilter, MatchSort, ParticipantSort, QueryParams, TournamentFilter,
+    TournamentParticipantsFilter, TournamentVideosFilter, VideoSort, MAX_TOURNAMENT_IDS,
 };
-pub use games::{Game, GameNumber, Games};
+pub use fingerprint::Fingerprint;
+pub use games::{Game, GameNumber, GameProperties, Games};
+pub use health::{Ping, TokenInfo};
+#[cfg(feature = "client-blocking")]
 pub use iter::*;
-pub use matches::{Match, MatchFormat, MatchId, MatchResult, MatchStatus, MatchType, Matches};
+pub use lenient::ParseFailure;
+pub use matches::{
+    BracketBranch, Match, MatchFormat, MatchId, MatchPatch, MatchRef, MatchResult, MatchStatus,
+    MatchType, Matches, ScoreLine,
+};
+#[cfg(feature = "client-blocking")]
+pub use meta::WithMeta;
+#[cfg(feature = "metrics")]
+pub use metrics::MetricsSnapshot;
 pub use opponents::{Opponent, Opponents};
+#[cfg(feature = "client-blocking")]
+pub use organizer::Organizer;
+#[cfg(feature = "client-blocking")]
+pub use pages::Pager;
+pub use pages::{ContentRange, Page, Paging};
 pub use participants::{
-    CustomField, CustomFieldType, CustomFields, Participant, ParticipantId, ParticipantLogo,
-    ParticipantType, Participants,
+    CustomField, CustomFieldType, CustomFields, DuplicateGroup, DuplicateReason, Participant,
+    ParticipantId, ParticipantLogo, ParticipantType, Participants,
 };
 pub use permissions::{
-    Permission, PermissionAttribute, PermissionAttributes, PermissionId, Permissions,
+    Permission, PermissionAttribute, PermissionAttributes, PermissionId, PermissionStatus,
+    Permissions,
 };
-pub use stages::{Stage, StageNumber, StageType, Stages};
+pub use progress::{Progress, ProgressUpdate};
+pub use progression::{Outcome, Progression, ProgressionEdge};
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+use protocol::parse_token;
+#[cfg(feature = "client-blocking")]
+pub use read_only::ReadOnlyToornament;
+pub use scope::Scope;
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+use secret::Secret;
+pub use snapshot::{Difference, SnapshotDiff, TournamentSnapshot};
+pub use stages::{Stage, StageNumber, StageType, Stages, TournamentSize};
+pub use standings::{FinalStanding, FinalStandings};
+pub use stats::{DisciplineStats, RawStats};
 pub use streams::{Stream, StreamId, Streams};
-pub use tournaments::{Tournament, TournamentId, TournamentStatus, Tournaments};
+pub use token::TokenStore;
+pub use tournaments::{
+    Tournament, TournamentBuilder, TournamentId, TournamentSettings, TournamentStatus, Tournaments,
+};
+pub use undo::UndoToken;
 pub use videos::{Video, VideoCategory, Videos};
 
-/// Create the request builer.
-macro_rules! build_request {
-    ($toornament:ident, $method:ident, $address:expr) => {{
-        $toornament
-            .client
-            .$method($address)
-            .header("X-Api-Key", $toornament.keys.0.clone())
-            .bearer_auth(&$toornament.fresh_token()?)
-    }};
-}
-
+#[cfg(feature = "client-blocking")]
 /// Macro only for internal use with the `Toornament` object (relies on it's fields)
 macro_rules! request {
-    ($toornament:ident, $method:ident, $address:expr) => {{
-        build_request!($toornament, $method, $address).send()
+    ($toornament:ident, $method:ident, $endpoint:expr) => {{
+        $toornament.send(stringify!($method), $endpoint, None)
     }};
 }
 
+#[cfg(feature = "client-blocking")]
 /// Macro only for internal use with the `Toornament` object (relies on it's fields)
 macro_rules! request_body {
-    ($toornament:ident, $method:ident, $address:expr, $body:expr) => {{
-        build_request!($toornament, $method, $address)
-            .body($body)
-            .send()
+    ($toornament:ident, $method:ident, $endpoint:expr, $body:expr) => {{
+        let body: String = $body;
+        $toornament.send(stringify!($method), $endpoint, Some(body))
     }};
 }
 
-#[derive(Debug, Clone)]
+#[cfg(feature = "client-blocking")]
+/// A successful response of the service.
+struct Reply {
+    headers: reqwest::header::HeaderMap,
+    /// The address of the response.
+    path: String,
+    body: Vec<u8>,
+    /// Description of the request, added to the errors.
+    context: String,
+    decoder: protocol::Decoder,
+}
+#[cfg(feature = "client-blocking")]
+impl Reply {
+    /// Parses the body of the response.
+    fn json<T: serde::de::DeserializeOwned>(self) -> Result<T> {
+        self.decoder
+            .decode(&self.path, &self.body)
+            .map_err(|e| e.context(self.context))
+    }
+
+    /// Returns the `Content-Range` of the response, if it has a valid one.
+    fn content_range(&self) -> Option<ContentRange> {
+        self.headers
+            .get(reqwest::header::CONTENT_RANGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 struct AccessToken {
-    access_token: String,
+    #[cfg(any(feature = "client-blocking", feature = "client-async"))]
+    access_token: Secret,
+    issued: u64,
     expires: u64,
+    scopes: Vec<String>,
 }
 
-fn parse_token<R: Read>(json_str: R) -> Result<AccessToken> {
-    #[derive(Debug, Clone, serde::Deserialize)]
-    struct OauthAccessToken {
-        access_token: String,
-        expires_in: u64,
+fn token_info(token: &AccessToken) -> TokenInfo {
+    let time = |t: u64| chrono::DateTime::from_timestamp(t as i64, 0).unwrap_or_default();
+    TokenInfo {
+        issued: time(token.issued),
+        expires: time(token.expires),
+        scopes: token.scopes.clone(),
     }
-
-    let oauth = serde_json::from_reader::<_, OauthAccessToken>(json_str)?;
-    Ok(AccessToken {
-        access_token: oauth.access_token,
-        expires: chrono::Local::now().timestamp() as u64 + oauth.expires_in,
-    })
 }
 
+/// Requests a new access token, sending the `headers` of the client like its other requests.
+#[cfg(feature = "client-blocking")]
 fn authenticate(
     client: &reqwest::blocking::Client,
+    base_url: &str,
     client_id: &str,
     client_secret: &str,
+    scopes: &BTreeSet<Scope>,
+    headers: &[(String, String)],
 ) -> Result<AccessToken> {
-    use std::collections::HashMap;
-
-    let mut params = HashMap::new();
-    params.insert("grant_type", "client_credentials");
-    params.insert("client_id", client_id);
-    params.insert("client_secret", client_secret);
-    parse_token(
-        client
-            .post(&Endpoint::OauthToken.to_string())
-            .form(&params)
-            .send()?,
-    )
+    let (address, form) = protocol::token_request(base_url, client_id, client_secret, scopes);
+    let mut builder = client.post(address).form(&form);
+    for (name, value) in headers {
+        builder = builder.header(name.as_str(), value.as_str());
+    }
+    let response = builder.send()?;
+    let status = response.status();
+    let content_type = protocol::content_type(response.headers()).map(str::to_owned);
+    let body = response.bytes()?;
+    protocol::check(status, content_type.as_deref(), &body)?;
+    parse_token(&body[..])
+}
+
+/// The client id and secret of the application, with the secondary ones used when the service
+/// refuses them, while the keys are rotated.
+#[cfg(feature = "client-blocking")]
+#[derive(Clone, Debug)]
+struct Credentials {
+    current: (String, Secret),
+    secondary: Option<(String, Secret)>,
+}
+
+/// The settings the HTTP client of `Toornament` is built with, kept to build it again with all
+/// of them when one changes.
+#[cfg(feature = "client-blocking")]
+#[derive(Clone, Debug, Default)]
+struct Transport {
+    timeouts: Timeouts,
+    pool: ConnectionPool,
+    proxy: Option<reqwest::Proxy>,
+}
+#[cfg(feature = "client-blocking")]
+impl Transport {
+    /// Builds the HTTP client.
+    fn client(&self) -> Result<reqwest::blocking::Client> {
+        let mut builder = reqwest::blocking::ClientBuilder::new();
+        if let Some(timeout) = self.pool.idle_timeout() {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(max) = self.pool.max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(interval) = self.pool.tcp_keepalive() {
+            builder = builder.tcp_keepalive(interval);
+        }
+        if let Some(timeout) = self.timeouts.connect_timeout() {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeouts.attempt_timeout() {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(ref proxy) = self.proxy {
+            builder = builder.proxy(proxy.clone());
+        }
+        Ok(builder.build()?)
+    }
 }
 
+#[cfg(feature = "client-blocking")]
 /// Main structure. Should be your point of start using the service.
 /// This struct covers all the `toornament` API.
-#[derive(Debug)]
+///
+/// Cloning is cheap: the clones share the connection pool, the credentials and the access
+/// token, so a clone may be moved into another thread instead of wrapping the client in `Arc`.
+#[derive(Debug, Clone)]
 pub struct Toornament {
     client: reqwest::blocking::Client,
-    keys: (String, String, String),
-    oauth_token: Mutex<AccessToken>,
+    api_token: Secret,
+    credentials: Arc<Mutex<Credentials>>,
+    scopes: BTreeSet<Scope>,
+    base_url: String,
+    retry: RetryPolicy,
+    transport: Transport,
+    options: Arc<RequestOptions>,
+    breaker: Option<Arc<breaker::Breaker>>,
+    tokens: Arc<TokenStore>,
+    #[cfg(feature = "metrics")]
+    metrics: Arc<metrics::Metrics>,
+    audit: Option<audit::Auditor>,
+    require_confirmation: bool,
+    write_policy: Option<policy::Policy>,
+    moderation: Option<moderation::Moderator>,
+    parse_failures: Option<lenient::Failures>,
+    #[cfg(feature = "schema-drift")]
+    drift: Option<drift::Drifts>,
 }
+#[cfg(feature = "client-blocking")]
 impl Toornament {
-    /// Returns currently stored token
-    fn current_token(&self) -> Result<String> {
-        match self.oauth_token.lock() {
-            Ok(g) => Ok(g.access_token.to_owned()),
-            Err(_) => Err(Error::Rest("Can't get the token")),
+    /// Always returns fresh token (refreshes it if neeeded). A single thread of the clients
+    /// sharing the token store refreshes it, the others wait for it, see `TokenStore::fresh`.
+    fn fresh_token(&self) -> Result<String> {
+        self.tokens.fresh(false, || {
+            self.authenticate().map_err(|e| match e {
+                Error::CircuitOpen => e,
+                e => {
+                    log::error!("Unable to refresh token: {:?}", e);
+                    Error::Rest("Could not refresh the token")
+                }
+            })
+        })
+    }
+
+    /// Gets new access token with the current credentials, or with the secondary ones if the
+    /// service refuses the current ones: the secondary credentials become the current ones
+    /// then. The token request goes through the circuit breaker like the other requests, since
+    /// it reaches the same service.
+    fn authenticate(&self) -> Result<AccessToken> {
+        let Credentials { current, secondary } = match self.credentials.lock() {
+            Ok(c) => c.clone(),
+            Err(_) => return Err(Error::Rest("Can't get the credentials")),
+        };
+        let permit = match self.breaker {
+            Some(ref breaker) => Some(breaker.allow(std::time::Instant::now())?),
+            None => None,
+        };
+        let request = |(client_id, client_secret): &(String, Secret)| {
+            authenticate(
+                &self.client,
+                &self.base_url,
+                client_id,
+                client_secret.expose(),
+                &self.scopes,
+                &self.options.headers,
+            )
+        };
+        let mut result = request(&current);
+        let refused = matches!(
+            protocol::Attempt::of(&result),
+            protocol::Attempt::Status(400..=499)
+        );
+        if let (true, Some(secondary)) = (refused, secondary) {
+            log::warn!(
+                "The client id {} was refused, trying the secondary client id {}",
+                current.0,
+                secondary.0
+            );
+            result = request(&secondary);
+            if result.is_ok() {
+                match self.credentials.lock() {
+                    Ok(mut credentials) if credentials.current.0 == current.0 => {
+                        *credentials = Credentials {
+                            current: secondary,
+                            secondary: None,
+                        };
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if let Some(permit) = permit {
+            let outcome = protocol::Attempt::of(&result);
+            if permit.record(outcome.failure(), std::time::Instant::now()) {
+                #[cfg(feature = "metrics")]
+                self.metrics.circuit_opened();
+            }
+        }
+        let token = result?;
+        #[cfg(feature = "metrics")]
+        self.metrics.token_refresh();
+        Ok(token)
+    }
+
+    /// Sends the request to the endpoint, retrying it according to the retry policy. The
+    /// errors and the non-success responses are returned with the description of the request.
+    /// The mutating requests are recorded by the audit sink, if any.
+    fn send(
+        &self,
+        method: &'static str,
+        endpoint: &Endpoint,
+        body: Option<String>,
+    ) -> Result<Reply> {
+        let request = protocol::Request::new(method, endpoint, &self.base_url, body);
+        let context = request.context.clone();
+        endpoint
+            .validate()
+            .and_then(|_| self.confirmed(method, endpoint))
+            .map_err(|e| e.context(context.clone()))?;
+        let audited = request.body.clone();
+        let started = std::time::Instant::now();
+        let result = self.send_retrying(request);
+        if let Ok(ref response) = result {
+            meta::record(response, started.elapsed());
+            #[cfg(feature = "metrics")]
+            if let Some(info) = response
+                .extensions()
+                .get::<hyper::client::connect::HttpInfo>()
+            {
+                self.metrics
+                    .connection(info.local_addr(), info.remote_addr());
+            }
+        }
+        #[cfg(feature = "metrics")]
+        self.metrics.request(
+            endpoint.route(),
+            method,
+            result.as_ref().ok().map(|r| r.status().as_u16()),
+        );
+        if let Some(audit::Auditor(ref sink)) = self.audit {
+            if method != "get" {
+                let mut record =
+                    audit::AuditRecord::new(method, endpoint.to_string(), audited.as_deref());
+                match result {
+                    Ok(ref response) => record.status = Some(response.status().as_u16()),
+                    Err(ref e) => record.error = Some(e.to_string()),
+                }
+                sink.record(&record);
+            }
+        }
+        let response = result.map_err(|e| e.context(context.clone()))?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let path = response.url().path().to_owned();
+        let body = response
+            .bytes()
+            .map_err(|e| Error::from(e).context(context.clone()))?;
+        protocol::check(status, protocol::content_type(&headers), &body)
+            .map_err(|e| e.context(context.clone()))?;
+        Ok(Reply {
+            headers,
+            path,
+            body: body.to_vec(),
+            context,
+            decoder: protocol::Decoder {
+                lenient: self.parse_failures.clone(),
+                #[cfg(feature = "schema-drift")]
+                drift: self.drift.clone(),
+            },
+        })
+    }
+
+    /// Fails with `Error::Unconfirmed` if the request is destructive (a deletion or a
+    /// replacement of a whole list) and the client requires confirmation.
+    fn confirmed(&self, method: &str, endpoint: &Endpoint) -> Result<()> {
+        let destructive = match *endpoint {
+            Endpoint::ParticipantsUpdate(_) => true,
+            _ => method == "delete",
+        };
+        if self.require_confirmation && destructive {
+            return Err(Error::Unconfirmed);
+        }
+        Ok(())
+    }
+
+    /// Passes the participant through the moderation of the client, if any, failing with
+    /// `Error::Rejected` if it is rejected.
+    fn moderate(&self, participant: &mut Participant) -> Result<()> {
+        match self.moderation {
+            Some(ref moderator) => moderator.moderate(participant).map_err(Error::Rejected),
+            None => Ok(()),
+        }
+    }
+
+    /// Fails with `Error::Denied` if the write policy of the client does not allow to update
+    /// the match. The match is only fetched if there is a policy.
+    fn check_write_policy(&self, tournament_id: &TournamentId, match_id: &MatchId) -> Result<()> {
+        let policy = match self.write_policy {
+            Some(policy::Policy(ref policy)) => policy,
+            None => return Ok(()),
+        };
+        let current = self
+            .matches(
+                tournament_id.clone(),
+                Some(match_id.clone()),
+                Includes::none(),
+            )?
+            .0
+            .pop()
+            .ok_or_else(|| {
+                Error::Iter(IterError::NoSuchMatch(
+                    tournament_id.clone(),
+                    match_id.clone(),
+                ))
+            })?;
+        match policy.deny_match_update(&current, chrono::Utc::now()) {
+            Some(reason) => Err(Error::Denied(reason)),
+            None => Ok(()),
+        }
+    }
+
+    fn send_retrying(&self, request: protocol::Request) -> Result<reqwest::blocking::Response> {
+        let method = reqwest::Method::from_bytes(request.method.to_uppercase().as_bytes())
+            .map_err(|_| Error::Rest("Invalid method"))?;
+        let deadline = self
+            .transport
+            .timeouts
+            .total_timeout()
+            .map(|t| std::time::Instant::now() + t);
+        let mut attempt = 1;
+        loop {
+            let request = request
+                .clone()
+                .authorized(&self.api_token, &self.fresh_token()?)
+                .with_options(&self.options);
+            let mut builder = self.client.request(method.clone(), &request.url);
+            let timeout = protocol::attempt_timeout(
+                self.transport.timeouts.attempt_timeout(),
+                deadline,
+                std::time::Instant::now(),
+            )?;
+            if let Some(timeout) = timeout {
+                builder = builder.timeout(timeout);
+            }
+            for (name, value) in request.headers {
+                builder = builder.header(name, value);
+            }
+            if let Some(body) = request.body {
+                builder = builder.body(body);
+            }
+            let permit = match self.breaker {
+                Some(ref breaker) => Some(breaker.allow(std::time::Instant::now())?),
+                None => None,
+            };
+            let result = builder.send();
+            let outcome = match result {
+                Ok(ref r) => protocol::Attempt::Status(r.status().as_u16()),
+                Err(ref e) if e.is_connect() || e.is_timeout() => protocol::Attempt::Transient,
+                Err(_) => protocol::Attempt::Failed,
+            };
+            if let Some(permit) = permit {
+                if permit.record(outcome.failure(), std::time::Instant::now()) {
+                    #[cfg(feature = "metrics")]
+                    self.metrics.circuit_opened();
+                }
+            }
+            let wait = match protocol::retry_wait(&self.retry, request.method, attempt, outcome) {
+                Some(wait) if deadline.is_none_or(|d| std::time::Instant::now() + wait < d) => wait,
+                _ => return Ok(result?),
+            };
+            #[cfg(feature = "metrics")]
+            self.metrics
+                .retry(outcome == protocol::Attempt::Status(429), wait);
+            std::thread::sleep(wait);
+            attempt += 1;
+        }
+    }
+
+    /// Sends an authorized GET request.
+    fn get(&self, endpoint: &Endpoint) -> Result<Reply> {
+        request!(self, get, endpoint)
+    }
+
+    /// Creates new `Toornament` object with client credentials
+    /// which is your user API_Token, application's client id and secret.
+    /// You may obtain application's credentials [here](<https://developer.toornament.com/applications/>)
+    /// (You must be logged in to open the page).
+    /// This method connects to the toornament service and if there is a error it returns the `Error`
+    /// object and on success it returns `Toornament` object.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET");
+    /// assert!(t.is_ok());
+    /// ```
+    pub fn with_application<S: Into<String>>(
+        api_token: S,
+        client_id: S,
+        client_secret: S,
+    ) -> Result<Toornament> {
+        Toornament::with_application_scopes(api_token, client_id, client_secret, Vec::new())
+    }
+
+    /// Creates new `Toornament` object with client credentials, like
+    /// `Toornament::with_application`, whose access token is granted the `scopes` only: the
+    /// client may only do what it needs. A request outside of the scopes fails with
+    /// `Error::MissingScope`. Without scopes, the token gets the default scopes of the
+    /// application.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// // A client reporting the results of the matches, and nothing else
+    /// let t = Toornament::with_application_scopes("API_TOKEN",
+    ///                                             "CLIENT_ID",
+    ///                                             "CLIENT_SECRET",
+    ///                                             vec![Scope::View, Scope::Result]).unwrap();
+    /// match t.delete_tournament(TournamentId("1".to_owned())) {
+    ///     Err(e) => assert!(matches!(e.root(), Error::MissingScope(_))),
+    ///     Ok(()) => unreachable!(),
+    /// }
+    /// ```
+    pub fn with_application_scopes<S: Into<String>, I: IntoIterator<Item = Scope>>(
+        api_token: S,
+        client_id: S,
+        client_secret: S,
+        scopes: I,
+    ) -> Result<Toornament> {
+        Toornament::connect_with_store(
+            Transport::default(),
+            endpoints::API_BASE.to_owned(),
+            (
+                Secret::from(api_token.into()),
+                client_id.into(),
+                Secret::from(client_secret.into()),
+            ),
+            scopes.into_iter().collect(),
+            Arc::new(TokenStore::new()),
+        )
+    }
+
+    /// Creates new `Toornament` object from the environment variables:
+    ///
+    /// - `TOORNAMENT_API_TOKEN`, `TOORNAMENT_CLIENT_ID` and `TOORNAMENT_CLIENT_SECRET` are the
+    ///   credentials, see `Toornament::with_application`;
+    /// - `TOORNAMENT_BASE_URL` is an optional base address of the API;
+    /// - `TOORNAMENT_TIMEOUT` is an optional timeout of the requests in seconds, see
+    ///   `Toornament::timeout`;
+    /// - `TOORNAMENT_LOCALE` is an optional language of the translated labels, see
+    ///   `Toornament::locale`.
+    ///
+    /// Returns `Error::Config` naming the variable which is missing or invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::from_env().unwrap();
+    /// ```
+    pub fn from_env() -> Result<Toornament> {
+        Toornament::from_settings(config::Settings::from_vars(|name| {
+            std::env::var(name).ok()
+        })?)
+    }
+
+    /// Creates new `Toornament` object from a TOML configuration file:
+    ///
+    /// ```toml
+    /// api_token = "API_TOKEN"
+    /// client_id = "CLIENT_ID"
+    /// client_secret = "CLIENT_SECRET"
+    /// # Optional settings
+    /// base_url = "https://api.toornament.com/organizer/v2"
+    /// timeout = 5  # seconds
+    /// proxy = "http://proxy:3128"
+    /// locale = "fr"  # see `Toornament::locale`
+    ///
+    /// [retry]
+    /// attempts = 3
+    /// backoff_ms = 500
+    ///
+    /// [timeouts]  # see `Toornament::timeouts`
+    /// connect_ms = 2000
+    /// attempt_ms = 30000
+    /// total_ms = 120000
+    ///
+    /// [pool]  # see `Toornament::connection_pool`
+    /// idle_timeout_ms = 90000
+    /// max_idle_per_host = 8
+    /// tcp_keepalive_ms = 30000
+    /// ```
+    ///
+    /// Unknown settings are rejected. Returns `Error::Config` if the file is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::from_config("toornament.toml").unwrap();
+    /// ```
+    #[cfg(feature = "config")]
+    pub fn from_config<P: AsRef<std::path::Path>>(path: P) -> Result<Toornament> {
+        let document = std::fs::read_to_string(path)?;
+        Toornament::from_settings(config::Settings::from_toml(&document)?)
+    }
+
+    fn from_settings(settings: config::Settings) -> Result<Toornament> {
+        let retry = settings.retry.unwrap_or_default();
+        let mut timeouts = settings.timeouts.unwrap_or_default();
+        if let Some(seconds) = settings.timeout {
+            timeouts.attempt_ms = timeouts.attempt_ms.or(Some(seconds * 1000));
+        }
+        let proxy = match settings.proxy {
+            Some(proxy) => Some(reqwest::Proxy::all(&proxy).map_err(|_| {
+                Error::Config(ConfigError::Invalid("proxy".to_owned(), proxy.clone()))
+            })?),
+            None => None,
+        };
+        let locale = settings.locale;
+        let transport = Transport {
+            timeouts,
+            pool: settings.pool.unwrap_or_default(),
+            proxy,
+        };
+        Toornament::connect_with_store(
+            transport,
+            settings
+                .base_url
+                .unwrap_or_else(|| endpoints::API_BASE.to_owned()),
+            (
+                settings.api_token,
+                settings.client_id,
+                settings.client_secret,
+            ),
+            BTreeSet::new(),
+            Arc::new(TokenStore::new()),
+        )
+        .and_then(|t| {
+            let t = t.retry(retry);
+            match locale {
+                Some(locale) => t.locale(locale),
+                None => Ok(t),
+            }
+        })
+    }
+
+    /// Creates new `Toornament` object with client credentials, like
+    /// `Toornament::with_application`, which shares the access token with the other clients
+    /// using the same `tokens` store: the service is only asked for a token if the store has
+    /// no valid one, and an expired token is refreshed once for all the clients. All the clients
+    /// sharing a store must use the same application's credentials.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::sync::Arc;
+    /// use toornament::*;
+    /// let tokens = Arc::new(TokenStore::new());
+    /// let workers = (0..4).map(|_| {
+    ///     Toornament::with_token_store(tokens.clone(),
+    ///                                  "API_TOKEN",
+    ///                                  "CLIENT_ID",
+    ///                                  "CLIENT_SECRET").unwrap()
+    /// }).collect::<Vec<Toornament>>();
+    /// ```
+    pub fn with_token_store<S: Into<String>>(
+        tokens: Arc<TokenStore>,
+        api_token: S,
+        client_id: S,
+        client_secret: S,
+    ) -> Result<Toornament> {
+        Toornament::connect_with_store(
+            Transport::default(),
+            endpoints::API_BASE.to_owned(),
+            (
+                Secret::from(api_token.into()),
+                client_id.into(),
+                Secret::from(client_secret.into()),
+            ),
+            BTreeSet::new(),
+            tokens,
+        )
+    }
+
+    /// Returns the token store of the client, to create other clients sharing its token
+    /// with `Toornament::with_token_store`.
+    pub fn token_store(&self) -> Arc<TokenStore> {
+        self.tokens.clone()
+    }
+
+    fn connect_with_store(
+        transport: Transport,
+        base_url: String,
+        keys: (Secret, String, Secret),
+        scopes: BTreeSet<Scope>,
+        tokens: Arc<TokenStore>,
+    ) -> Result<Toornament> {
+        let (api_token, client_id, client_secret) = keys;
+        let toornament = Toornament {
+            client: transport.client()?,
+            api_token,
+            credentials: Arc::new(Mutex::new(Credentials {
+                current: (client_id, client_secret),
+                secondary: None,
+            })),
+            scopes,
+            base_url,
+            retry: RetryPolicy::default(),
+            transport,
+            options: Arc::default(),
+            breaker: None,
+            tokens,
+            #[cfg(feature = "metrics")]
+            metrics: Arc::new(metrics::Metrics::default()),
+            audit: None,
+            require_confirmation: false,
+            write_policy: None,
+            moderation: None,
+            parse_failures: None,
+            #[cfg(feature = "schema-drift")]
+            drift: None,
+        };
+        toornament
+            .tokens
+            .fresh(false, || toornament.authenticate())?;
+        Ok(toornament)
+    }
+
+    /// Refreshes the oauth token. Automatically used when it is expired. A refresh already in
+    /// progress in another thread is waited for instead of being repeated.
+    pub fn refresh(&self) -> bool {
+        match self.tokens.fresh(true, || self.authenticate()) {
+            Ok(_) => true,
+            Err(e) => {
+                log::error!("Unable to refresh token: {:?}", e);
+                false
+            }
+        }
+    }
+
+    /// Replaces the application's client id and secret, for example when the keys are rotated.
+    /// The new credentials are used to get a new access token first: on success both the
+    /// credentials and the token are replaced at once, otherwise the old ones are kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// t.rotate_credentials("NEW_CLIENT_ID", "NEW_CLIENT_SECRET").unwrap();
+    /// ```
+    pub fn rotate_credentials<S: Into<String>>(
+        &self,
+        client_id: S,
+        client_secret: S,
+    ) -> Result<()> {
+        let client_id = client_id.into();
+        let client_secret = Secret::from(client_secret.into());
+        log::debug!("Rotating the credentials to the client id: {}", client_id);
+        let token = authenticate(
+            &self.client,
+            &self.base_url,
+            &client_id,
+            client_secret.expose(),
+            &self.scopes,
+            &self.options.headers,
+        )?;
+
+        let mut g = self.tokens.lock()?;
+        let mut credentials = match self.credentials.lock() {
+            Ok(c) => c,
+            Err(_) => return Err(Error::Rest("Can't get the credentials")),
+        };
+        if credentials.secondary.as_ref().map(|s| &s.0) == Some(&client_id) {
+            credentials.secondary = None;
         }
+        credentials.current = (client_id, client_secret);
+        *g = token;
+        #[cfg(feature = "metrics")]
+        self.metrics.token_refresh();
+        Ok(())
+    }
+
+    /// Registers a secondary client id and secret of the application, used to get the access
+    /// token once the service refuses the current ones: the keys may be rotated by the policy
+    /// of the application without calling `rotate_credentials` at the right time. The
+    /// secondary credentials replace the current ones as soon as they are used. Clones share
+    /// them.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// t.secondary_credentials("NEW_CLIENT_ID", "NEW_CLIENT_SECRET").unwrap();
+    /// ```
+    pub fn secondary_credentials<S: Into<String>>(
+        &self,
+        client_id: S,
+        client_secret: S,
+    ) -> Result<()> {
+        let client_id = client_id.into();
+        log::debug!("Registering the secondary client id: {}", client_id);
+        match self.credentials.lock() {
+            Ok(mut credentials) => {
+                credentials.secondary = Some((client_id, Secret::from(client_secret.into())));
+                Ok(())
+            }
+            Err(_) => Err(Error::Rest("Can't get the credentials")),
+        }
+    }
+
+    /// Consumes `Toornament` object and sets the timeouts of its requests: the connect
+    /// timeout, the timeout of every attempt and the deadline of a call with all its retries,
+    /// after which it fails with `Error::Cancelled`. The timeouts which are not set are the
+    /// defaults of `reqwest`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap()
+    ///     .timeouts(Timeouts::new()
+    ///         .connect(Duration::from_secs(2))
+    ///         .attempt(Duration::from_secs(60))
+    ///         .total(Duration::from_secs(300))).unwrap();
+    /// ```
+    pub fn timeouts(mut self, timeouts: Timeouts) -> Result<Toornament> {
+        self.transport.timeouts = timeouts;
+        self.client = self.transport.client()?;
+        Ok(self)
+    }
+
+    /// Consumes `Toornament` object and sets the timeout of every attempt of its requests,
+    /// keeping its other timeouts, see `Toornament::timeouts`.
+    pub fn timeout(self, seconds: u64) -> Result<Toornament> {
+        let timeouts = self
+            .transport
+            .timeouts
+            .attempt(std::time::Duration::from_secs(seconds));
+        self.timeouts(timeouts)
+    }
+
+    /// Consumes `Toornament` object and sets how its connections to the service are kept open
+    /// to be reused, keeping its timeouts. With the `metrics` feature, the requests sent on new
+    /// and on reused connections are counted by `Toornament::metrics_snapshot`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap()
+    ///     .connection_pool(ConnectionPool::new()
+    ///         .idle(Duration::from_secs(90))
+    ///         .max_idle(8)
+    ///         .keepalive(Duration::from_secs(30))).unwrap();
+    /// ```
+    pub fn connection_pool(mut self, pool: ConnectionPool) -> Result<Toornament> {
+        self.transport.pool = pool;
+        self.client = self.transport.client()?;
+        Ok(self)
+    }
+
+    /// Checks that the service is reachable with the credentials: refreshes the access token if
+    /// needed and makes a cheap request, for the first discipline only. Suitable for readiness
+    /// probes.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// let ping = t.ping().unwrap();
+    /// println!("Latency: {:?}, token expires in {:?}", ping.latency, ping.token_expires_in);
+    /// ```
+    pub fn ping(&self) -> Result<Ping> {
+        use std::time::{Duration, Instant};
+
+        log::debug!("Checking the service availability");
+        let _ = self.fresh_token()?;
+        let started = Instant::now();
+        let _ = self.get(&Endpoint::DisciplinesPage { per_page: 1 })?;
+        let latency = started.elapsed();
+        let expires = self.tokens.lock()?.expires;
+        let now = chrono::Local::now().timestamp() as u64;
+        Ok(Ping {
+            latency,
+            token_expires_in: Duration::from_secs(expires.saturating_sub(now)),
+        })
+    }
+
+    /// Returns information about the current access token: when it was obtained, when it
+    /// expires and its scopes. The token itself is not exposed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// let info = t.token_info().unwrap();
+    /// println!("Token refreshes in {} min", info.expires_in().num_minutes());
+    /// ```
+    pub fn token_info(&self) -> Result<TokenInfo> {
+        Ok(token_info(&*self.tokens.lock()?))
+    }
+
+    /// Returns the account the credentials belong to: its id, name and email, with the scopes
+    /// granted to the credentials. A service acting for several accounts may check it before
+    /// acting.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// let account = t.me().unwrap();
+    /// assert_eq!(account.id, AccountId("378426939508809728".to_owned()));
+    /// assert!(account.has_scope("organizer:result"));
+    /// ```
+    pub fn me(&self) -> Result<Account> {
+        log::debug!("Getting the account of the credentials");
+        let endpoint = Endpoint::Me;
+        let mut account: Account = request!(self, get, &endpoint)?.json()?;
+        // The service tells the scopes with the token only.
+        if account.scopes.is_empty() {
+            account.scopes = self.tokens.lock()?.scopes.clone();
+        }
+        Ok(account)
+    }
+
+    /// Calls `f` and returns its value with the status, the headers and the duration of the last
+    /// response received during the call, to read the pagination ranges, the rate limits or the
+    /// request ids without giving up the typed results.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// let matches = t.with_meta(|t| t.matches(TournamentId("1".to_owned()),
+    ///                                         None,
+    ///                                         Includes::none()))
+    ///                .unwrap();
+    /// println!("{} matches in {:?}, request id: {:?}",
+    ///          matches.value.0.len(),
+    ///          matches.duration,
+    ///          matches.request_id());
+    /// ```
+    pub fn with_meta<T, F>(&self, f: F) -> Result<WithMeta<T>>
+    where
+        F: FnOnce(&Toornament) -> Result<T>,
+    {
+        let was_recording = meta::start();
+        let value = f(self);
+        let recorded = meta::finish(was_recording);
+        let value = value?;
+        let (status, headers, duration) = match recorded {
+            Some(recorded) => recorded,
+            None => return Err(Error::Rest("No response was received")),
+        };
+        Ok(WithMeta {
+            value,
+            status,
+            headers,
+            duration,
+        })
+    }
+
+    /// Returns the statistics of the requests sent by the client and its clones: the requests
+    /// by endpoint and status, the retries, the waits for the rate limits and the token
+    /// refreshes. See the `metrics` module for encoding them in the Prometheus format.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// let metrics = t.metrics_snapshot();
+    /// println!("{} requests, {} retries", metrics.total_requests(), metrics.retries);
+    /// ```
+    #[cfg(feature = "metrics")]
+    pub fn metrics_snapshot(&self) -> MetricsSnapshot {
+        let mut snapshot = self.metrics.snapshot();
+        snapshot.circuit_breaker = self.circuit_breaker_state();
+        snapshot
+    }
+
+    /// Consumes `Toornament` object and makes it lenient: when a list cannot be parsed as a
+    /// whole, its elements are parsed one by one and those which cannot be parsed are skipped
+    /// instead of failing the call. The skipped elements are reported by
+    /// `Toornament::take_parse_failures`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap()
+    ///                    .lenient();
+    /// let tournaments = t.tournaments(None, Include::Streams).unwrap();
+    /// for failure in t.take_parse_failures() {
+    ///     println!("Skipped element {} of {}: {}", failure.index, failure.endpoint,
+    ///              failure.error);
+    /// }
+    /// ```
+    pub fn lenient(mut self) -> Toornament {
+        self.parse_failures = Some(Default::default());
+        self
+    }
+
+    /// Returns and forgets the elements skipped by the lenient client (and its clones) since
+    /// the last call. Always empty if the client is not lenient.
+    pub fn take_parse_failures(&self) -> Vec<ParseFailure> {
+        self.parse_failures
+            .as_ref()
+            .and_then(|failures| failures.lock().ok().map(|mut f| std::mem::take(&mut *f)))
+            .unwrap_or_default()
+    }
+
+    /// Consumes `Toornament` object and makes it compare the responses with the models of the
+    /// crate, recording the fields the models do not know and the nulls they do not expect.
+    /// The differences are reported by `Toornament::schema_drift`. See the `drift` module.
+    #[cfg(feature = "schema-drift")]
+    pub fn detect_schema_drift(mut self) -> Toornament {
+        self.drift = Some(Default::default());
+        self
+    }
+
+    /// Returns the differences between the responses and the models found by the client (and
+    /// its clones) detecting them, once per endpoint and field.
+    #[cfg(feature = "schema-drift")]
+    pub fn schema_drift(&self) -> Vec<drift::Drift> {
+        self.drift
+            .as_ref()
+            .and_then(|drifts| drifts.lock().ok().map(|d| d.iter().cloned().collect()))
+            .unwrap_or_default()
+    }
+
+    /// Consumes `Toornament` object and sets the retry policy of the requests to it. The
+    /// `POST` and `PATCH` requests are never retried, see `RetryPolicy`.
+    pub fn retry(mut self, policy: RetryPolicy) -> Toornament {
+        self.retry = policy;
+        self
+    }
+
+    /// Consumes `Toornament` object and sets the `User-Agent` of its requests, to identify the
+    /// application. See `Toornament::header`, which explains when it is sent to get the access
+    /// token.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap()
+    ///     .user_agent("my-tournament-bot/1.0").unwrap();
+    /// ```
+    pub fn user_agent<S: Into<String>>(self, user_agent: S) -> Result<Toornament> {
+        self.header("User-Agent", user_agent)
     }
 
-    /// Always returns fresh token (refreshes it if neeeded)
-    fn fresh_token(&self) -> Result<String> {
-        let mut need_refresh = false;
-        {
-            let access_token = match self.oauth_token.lock() {
-                Ok(g) => g,
-                Err(_) => return Err(Error::Rest("Can't get the token")),
-            };
-            if chrono::Local::now().timestamp() as u64 > access_token.expires {
-                need_refresh = true;
-            }
-        }
-        if need_refresh && !self.refresh() {
-            return Err(Error::Rest("Could not refresh the token"));
-        }
+    /// Consumes `Toornament` object and adds the header to all its requests, replacing the
+    /// header with the same name. The requests for a new access token send it too, but the
+    /// first one is made by the constructor, before the header is added. Returns
+    /// `Error::Config`, without the value, if the header is invalid or is one of the headers
+    /// authorizing the requests.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap()
+    ///     .header("X-Partner-Application", "my-tournament-bot").unwrap();
+    /// ```
+    pub fn header<N: Into<String>, V: Into<String>>(self, name: N, value: V) -> Result<Toornament> {
+        self.with_request_options(RequestOptions::new().header(name, value))
+    }
 
-        self.current_token()
+    /// Consumes `Toornament` object and sets the language of the translated labels of the
+    /// responses, like the names of the disciplines and of the custom fields, sent to the
+    /// endpoints accepting it. A call may use another one with `with_request_options`. Returns
+    /// `Error::Config` if the locale is not a language tag like "fr" or "pt-BR".
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap()
+    ///     .locale("fr").unwrap();
+    /// let german = t.with_request_options(RequestOptions::new().locale("de")).unwrap();
+    /// let disciplines = german.disciplines(None).unwrap();
+    /// ```
+    pub fn locale<S: Into<String>>(self, locale: S) -> Result<Toornament> {
+        self.with_request_options(RequestOptions::new().locale(locale))
     }
 
-    /// Creates new `Toornament` object with client credentials
-    /// which is your user API_Token, application's client id and secret.
-    /// You may obtain application's credentials [here](<https://developer.toornament.com/applications/>)
-    /// (You must be logged in to open the page).
-    /// This method connects to the toornament service and if there is a error it returns the `Error`
-    /// object and on success it returns `Toornament` object.
+    /// Returns a clone of the client adding the headers and the query parameters of the
+    /// `options` to its requests, to send a flag like `?debug=1` with a few calls only. The
+    /// headers replace the ones with the same name. Returns `Error::Config` if a header is
+    /// invalid or is one of the headers authorizing the requests.
     ///
     /// # Example
     ///
@@ -198,67 +1263,118 @@ impl Toornament {
     /// use toornament::*;
     /// let t = Toornament::with_application("API_TOKEN",
     ///                                      "CLIENT_ID",
-    ///                                      "CLIENT_SECRET");
-    /// assert!(t.is_ok());
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// let debug = t.with_request_options(RequestOptions::new().query("debug", "1")).unwrap();
+    /// let disciplines = debug.disciplines(None).unwrap();
     /// ```
-    pub fn with_application<S: Into<String>>(
-        api_token: S,
-        client_id: S,
-        client_secret: S,
-    ) -> Result<Toornament> {
-        let client = reqwest::blocking::Client::new();
-        let keys = (api_token.into(), client_id.into(), client_secret.into());
-        let token = authenticate(&client, &keys.1, &keys.2)?;
-
-        Ok(Toornament {
-            client,
-            keys,
-            oauth_token: Mutex::new(token),
-        })
+    pub fn with_request_options(&self, options: RequestOptions) -> Result<Toornament> {
+        protocol::validate(&options)?;
+        let mut client = self.clone();
+        client.options = Arc::new(self.options.merge(&options));
+        Ok(client)
     }
 
-    /// Refreshes the oauth token. Automatically used when it is expired.
-    pub fn refresh(&self) -> bool {
-        let mut g = match self.oauth_token.lock() {
-            Ok(g) => g,
-            Err(e) => {
-                log::error!("Unable to refresh token: {:?}", e);
-                return false;
-            }
-        };
+    /// Consumes `Toornament` object and sets the circuit breaker of the requests to it, shared
+    /// with its clones: after repeated server errors or timeouts the requests fail at once
+    /// with `Error::CircuitOpen` until the cool-down of the policy passes.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap()
+    ///     .circuit_breaker(CircuitBreakerPolicy::new(5, Duration::from_secs(30)));
+    /// ```
+    pub fn circuit_breaker(mut self, policy: CircuitBreakerPolicy) -> Toornament {
+        self.breaker = Some(Arc::new(breaker::Breaker::new(policy)));
+        self
+    }
 
-        match authenticate(&self.client, &self.keys.1, &self.keys.2) {
-            Ok(token) => {
-                *g = token;
-                true
-            }
-            Err(e) => {
-                log::error!("Unable to refresh token: {:?}", e);
-                false
-            }
-        }
+    /// Returns the state of the circuit breaker, if the client has one.
+    pub fn circuit_breaker_state(&self) -> Option<BreakerState> {
+        self.breaker
+            .as_ref()
+            .map(|b| b.state(std::time::Instant::now()))
     }
 
-    /// Consumes `Toornament` object and sets timeout to it
-    pub fn timeout(mut self, seconds: u64) -> Result<Toornament> {
-        use std::time::Duration;
+    /// Consumes `Toornament` object and sets the sink recording its mutating calls (and the
+    /// ones of its clones) with the endpoint, the beginning of the payload and the result.
+    /// See the `audit` module.
+    pub fn audit<S: audit::AuditSink + 'static>(mut self, sink: S) -> Toornament {
+        self.audit = Some(audit::Auditor(Arc::new(sink)));
+        self
+    }
 
-        self.client = reqwest::blocking::ClientBuilder::new()
-            .timeout(Duration::from_secs(seconds))
-            .build()?;
-        Ok(self)
+    /// Consumes `Toornament` object and makes it require confirmation of the destructive calls:
+    /// the deletions and the replacements of whole lists fail with `Error::Unconfirmed` unless
+    /// they are called through `Toornament::confirm_destructive`.
+    pub fn require_confirmation(mut self) -> Toornament {
+        self.require_confirmation = true;
+        self
+    }
+
+    /// Returns a handle allowing the destructive calls on a client requiring confirmation.
+    /// See `Confirmed`.
+    pub fn confirm_destructive(&self) -> Confirmed {
+        let mut client = self.clone();
+        client.require_confirmation = false;
+        Confirmed::new(client)
+    }
+
+    /// Consumes `Toornament` object and sets the policy deciding which matches may be updated.
+    /// See the `policy` module.
+    pub fn write_policy<P: policy::WritePolicy + 'static>(mut self, policy: P) -> Toornament {
+        self.write_policy = Some(policy::Policy(Arc::new(policy)));
+        self
+    }
+
+    /// Consumes `Toornament` object and sets the moderation of the participants it creates or
+    /// updates. See the `moderation` module.
+    pub fn moderation<M: moderation::Moderation + 'static>(mut self, moderation: M) -> Toornament {
+        self.moderation = Some(moderation::Moderator(Arc::new(moderation)));
+        self
     }
 
     /// Returns Iterator-like objects to work with tournaments and it's subobjects.
-    pub fn tournaments_iter(&self) -> iter::TournamentsIter {
+    pub fn tournaments_iter(&self) -> iter::TournamentsIter<'_> {
         iter::TournamentsIter::new(self)
     }
 
+    /// Returns Iterator-like object to work with a match and its result and games.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// let m = MatchRef::new(TournamentId("1".to_owned()), MatchId("2".to_owned()));
+    /// let games = t.match_iter(m).games().collect::<Games>().unwrap();
+    /// ```
+    pub fn match_iter<R: Into<MatchRef>>(&self, m: R) -> iter::TournamentMatchIter<'_> {
+        let m = m.into();
+        iter::TournamentMatchIter::new(self, m.tournament_id, m.match_id, false)
+    }
+
     /// Returns Iterator-like objects to work with disciplines and it's subobjects.
-    pub fn disciplines_iter(&self) -> iter::DisciplinesIter {
+    pub fn disciplines_iter(&self) -> iter::DisciplinesIter<'_> {
         iter::DisciplinesIter::new(self)
     }
 
+    /// Converts `Toornament` object into a client which can only read the data.
+    pub fn into_read_only(self) -> ReadOnlyToornament {
+        ReadOnlyToornament::from(self)
+    }
+
+    /// Returns a facade for common multi-step tournament organization operations.
+    pub fn organizer(&self) -> Organizer<'_> {
+        Organizer::new(self)
+    }
+
     /// [Returns either a collection of disciplines](<https://developer.toornament.com/doc/disciplines#get:disciplines>) if id is None or
     /// [a disciplines with the detail of his features](<https://developer.toornament.com/doc/disciplines#get:disciplines:id>)
     ///
@@ -278,22 +1394,20 @@ impl Toornament {
     /// DisciplineId("wwe2k17".to_owned()));
     /// ```
     pub fn disciplines(&self, id: Option<DisciplineId>) -> Result<Disciplines> {
-        let address;
+        let endpoint;
         let id_is_set = id.is_some();
         if let Some(id) = id {
             log::debug!("Getting disciplines with id: {:?}", id);
-            address = Endpoint::DisciplineById(id).to_string();
+            endpoint = Endpoint::DisciplineById(id);
         } else {
             log::debug!("Getting all disciplines");
-            address = Endpoint::AllDisciplines.to_string();
+            endpoint = Endpoint::AllDisciplines;
         }
-        let response = request!(self, get, &address)?;
+        let reply = request!(self, get, &endpoint)?;
         if id_is_set {
-            Ok(Disciplines(vec![serde_json::from_reader::<_, Discipline>(
-                response,
-            )?]))
+            Ok(Disciplines::from(vec![reply.json::<Discipline>()?]))
         } else {
-            Ok(serde_json::from_reader(response)?)
+            reply.json()
         }
     }
 
@@ -309,41 +1423,69 @@ impl Toornament {
     ///                                      "CLIENT_ID",
     ///                                      "CLIENT_SECRET").unwrap();
     /// // Getting all tournaments
-    /// let all_tournaments: Tournaments = t.tournaments(None, true).unwrap();
+    /// let all_tournaments: Tournaments = t.tournaments(None, Include::Streams).unwrap();
     /// // Get tournament by it's id
-    /// let tournament = t.tournaments(Some(TournamentId("1".to_owned())), true).unwrap();
+    /// let tournament = t.tournaments(Some(TournamentId("1".to_owned())), Include::Streams).unwrap();
     /// assert_eq!(tournament.0.len(), 1);
     /// assert_eq!(tournament.0.first().unwrap().id,
     /// Some(TournamentId("1".to_owned())));
     /// ```
-    pub fn tournaments(
+    pub fn tournaments<I: Into<Includes>>(
         &self,
         tournament_id: Option<TournamentId>,
-        with_streams: bool,
+        include: I,
     ) -> Result<Tournaments> {
-        let address;
+        let with_streams = include.into().contains(Include::Streams);
+        let endpoint;
         let id_is_set = tournament_id.is_some();
         if let Some(tournament_id) = tournament_id {
             log::debug!("Getting tournament with id: {:?}", tournament_id);
-            address = Endpoint::TournamentByIdGet {
+            endpoint = Endpoint::TournamentByIdGet {
                 tournament_id,
                 with_streams,
-            }
-            .to_string();
+            };
         } else {
             log::debug!("Getting all tournaments");
-            address = Endpoint::AllTournaments { with_streams }.to_string();
+            endpoint = Endpoint::AllTournaments {
+                with_streams,
+                filter: TournamentFilter::default(),
+            };
         }
-        let response = request!(self, get, &address)?;
+        let reply = request!(self, get, &endpoint)?;
         if id_is_set {
-            Ok(Tournaments(vec![serde_json::from_reader::<_, Tournament>(
-                response,
-            )?]))
+            Ok(Tournaments::from(vec![reply.json::<Tournament>()?]))
         } else {
-            Ok(serde_json::from_reader(response)?)
+            reply.json()
         }
     }
 
+    /// Returns the public tournaments filtered by the service with the `filter`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// // Get the tournaments named "Weekly Cup"
+    /// let tournaments = t.filtered_tournaments(TournamentFilter::default().name("Weekly Cup"),
+    ///                                          Includes::none()).unwrap();
+    /// ```
+    pub fn filtered_tournaments<I: Into<Includes>>(
+        &self,
+        filter: TournamentFilter,
+        include: I,
+    ) -> Result<Tournaments> {
+        let with_streams = include.into().contains(Include::Streams);
+        log::debug!("Getting tournaments with filter: {:?}", filter);
+        let endpoint = Endpoint::AllTournaments {
+            with_streams,
+            filter,
+        };
+        request!(self, get, &endpoint)?.json()
+    }
+
     /// [Updates some of the editable information on a tournament.](<https://developer.toornament.com/doc/tournaments#patch:tournaments:id>) if `tournament.id`
     /// is set otherwise [creates a tournament](<https://developer.toornament.com/doc/tournaments#post:tournaments>).
     ///
@@ -355,7 +1497,7 @@ impl Toornament {
     ///                                      "CLIENT_ID",
     ///                                      "CLIENT_SECRET").unwrap();
     /// // Get tournament by it's id
-    /// let tournaments = t.tournaments(Some(TournamentId("1".to_owned())), true).unwrap();
+    /// let tournaments = t.tournaments(Some(TournamentId("1".to_owned())), Include::Streams).unwrap();
     /// assert_eq!(tournaments.0.len(), 1);
     /// let mut tournament = tournaments.0.first().unwrap().clone();
     /// assert_eq!(tournament.id, Some(TournamentId("1".to_owned())));
@@ -366,22 +1508,114 @@ impl Toornament {
     /// Some("https://toornament.com".to_owned()));
     /// ```
     pub fn edit_tournament(&self, tournament: Tournament) -> Result<Tournament> {
-        let address;
+        let endpoint;
         let id_is_set = tournament.id.is_some();
         if let Some(id) = tournament.id.clone() {
-            address = Endpoint::TournamentByIdUpdate(id).to_string();
+            endpoint = Endpoint::TournamentByIdUpdate(id);
         } else {
-            address = Endpoint::TournamentCreate.to_string();
+            endpoint = Endpoint::TournamentCreate;
         }
         let body = serde_json::to_string(&tournament)?;
-        let response = if id_is_set {
+        let reply = if id_is_set {
             log::debug!("Editing tournament: {:#?}", tournament);
-            request_body!(self, patch, &address, body)?
+            request_body!(self, patch, &endpoint, body)?
         } else {
             log::debug!("Creating tournament: {:#?}", tournament);
-            request_body!(self, post, &address, body)?
+            request_body!(self, post, &endpoint, body)?
         };
-        Ok(serde_json::from_reader(response)?)
+        reply.json()
+    }
+
+    /// Archives a tournament: it is kept with its participants and matches but becomes
+    /// read-only and is left out of the listings unless `TournamentFilter::archived` is set.
+    /// Returns the archived tournament.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// // Archiving tournament with id = "1"
+    /// let tournament = t.archive_tournament(TournamentId("1".to_owned())).unwrap();
+    /// assert!(tournament.is_archived());
+    /// ```
+    pub fn archive_tournament(&self, id: TournamentId) -> Result<Tournament> {
+        self.set_tournament_archived(id, true)
+    }
+
+    /// Restores an archived tournament, see `Toornament::archive_tournament`. Returns the
+    /// restored tournament.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// // Restoring tournament with id = "1"
+    /// let tournament = t.unarchive_tournament(TournamentId("1".to_owned())).unwrap();
+    /// assert!(!tournament.is_archived());
+    /// ```
+    pub fn unarchive_tournament(&self, id: TournamentId) -> Result<Tournament> {
+        self.set_tournament_archived(id, false)
+    }
+
+    /// Sets whether the tournament is archived.
+    fn set_tournament_archived(&self, id: TournamentId, archived: bool) -> Result<Tournament> {
+        log::debug!("Setting archived of tournament {:?} to {}", id, archived);
+        let endpoint = Endpoint::TournamentByIdUpdate(id);
+        let body = serde_json::json!({ "archived": archived }).to_string();
+        request_body!(self, patch, &endpoint, body)?.json()
+    }
+
+    /// Returns the editable settings of a tournament: its registration, check-in and participant
+    /// options.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// // Get the settings of a tournament with id = "1"
+    /// let settings = t.tournament_settings(TournamentId("1".to_owned())).unwrap();
+    /// ```
+    pub fn tournament_settings(&self, id: TournamentId) -> Result<TournamentSettings> {
+        log::debug!("Getting tournament settings by tournament id: {:?}", id);
+        let endpoint = Endpoint::TournamentSettings(id);
+        request!(self, get, &endpoint)?.json()
+    }
+
+    /// Updates the settings of a tournament which are set in `settings`, after checking them
+    /// with `TournamentSettings::validate`, and returns all the settings.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// // Enable the check-in of a tournament with id = "1"
+    /// let settings = t.update_tournament_settings(TournamentId("1".to_owned()),
+    ///                                             TournamentSettings::default().check_in(true))
+    ///                 .unwrap();
+    /// assert_eq!(settings.check_in, Some(true));
+    /// ```
+    pub fn update_tournament_settings(
+        &self,
+        id: TournamentId,
+        settings: TournamentSettings,
+    ) -> Result<TournamentSettings> {
+        log::debug!("Updating settings of tournament with id: {:?}", id);
+        settings.validate()?;
+        let endpoint = Endpoint::TournamentSettings(id);
+        let body = serde_json::to_string(&settings)?;
+        request_body!(self, patch, &endpoint, body)?.json()
     }
 
     /// [Deletes a tournament, its participants and all its matches](<https://developer.toornament.com/doc/tournaments#delete:tournaments:id>).
@@ -398,11 +1632,62 @@ impl Toornament {
     /// ```
     pub fn delete_tournament(&self, id: TournamentId) -> Result<()> {
         log::debug!("Deleting tournament by id: {:?}", id);
-        let address = Endpoint::TournamentByIdUpdate(id).to_string();
-        let _ = request!(self, delete, &address)?;
+        let endpoint = Endpoint::TournamentByIdUpdate(id);
+        let _ = request!(self, delete, &endpoint)?;
         Ok(())
     }
 
+    /// Deletes a tournament like `Toornament::delete_tournament`, fetching the tournament and
+    /// its participants first. The returned token recreates them with `Toornament::undo`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// let token = t.delete_tournament_with_undo(TournamentId("1".to_owned())).unwrap();
+    /// // Oops, that was the wrong one
+    /// let new_id = t.undo(token).unwrap();
+    /// ```
+    pub fn delete_tournament_with_undo(&self, id: TournamentId) -> Result<UndoToken> {
+        self.confirmed("delete", &Endpoint::TournamentByIdUpdate(id.clone()))?;
+        let tournament = self
+            .tournaments(Some(id.clone()), Includes::none())?
+            .0
+            .pop()
+            .ok_or(Error::Rest("The tournament was not found"))?;
+        let participants = UndoToken::participants(self, id.clone())?;
+        self.delete_tournament(id)?;
+        Ok(UndoToken::TournamentDeleted {
+            tournament,
+            participants,
+        })
+    }
+
+    /// Recreates, on a best-effort basis, the resources removed by a call returning the `token`
+    /// and returns the id of the tournament they belong to. The recreated resources get new ids:
+    /// a deleted tournament is created again with its participants (but without its matches)
+    /// and a new id is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// let token = t.delete_tournament_participant_with_undo(TournamentId("1".to_owned()),
+    ///                                                       ParticipantId("2".to_owned()))
+    ///              .unwrap();
+    /// t.undo(token).unwrap();
+    /// ```
+    pub fn undo(&self, token: UndoToken) -> Result<TournamentId> {
+        log::debug!("Undoing: {:?}", token);
+        token.undo(self)
+    }
+
     /// [Returns the private and public tournaments on which the authenticated user has access.
     /// The result is filtered, sorted and paginated by the given query parameters. A maximum of
     /// 50 tournaments is returned (per page).](<https://developer.toornament.com/doc/tournaments#get:metournaments>)
@@ -419,9 +1704,8 @@ impl Toornament {
     /// ```
     pub fn my_tournaments(&self) -> Result<Tournaments> {
         log::debug!("Getting all tournaments");
-        let address = Endpoint::MyTournaments.to_string();
-        let response = request!(self, get, &address)?;
-        Ok(serde_json::from_reader(response)?)
+        let endpoint = Endpoint::MyTournaments;
+        request!(self, get, &endpoint)?.json()
     }
 
     /// [Returns a collection of matches from one tournament. The collection may be filtered and
@@ -436,48 +1720,208 @@ impl Toornament {
     ///                                      "CLIENT_ID",
     ///                                      "CLIENT_SECRET").unwrap();
     /// // Get all matches of a tournament with id = "1"
-    /// let matches = t.matches(TournamentId("1".to_owned()), None, true).unwrap();
+    /// let matches = t.matches(TournamentId("1".to_owned()), None, Include::Games).unwrap();
     /// // Get match with match id = "2" of a tournament with id = "1"
-    /// let matches = t.matches(TournamentId("1".to_owned()), Some(MatchId("2".to_owned())), true).unwrap();
+    /// let matches = t.matches(TournamentId("1".to_owned()),
+    ///                         Some(MatchId("2".to_owned())),
+    ///                         Include::Games).unwrap();
     /// ```
-    pub fn matches(
+    pub fn matches<I: Into<Includes>>(
         &self,
         tournament_id: TournamentId,
         match_id: Option<MatchId>,
-        with_games: bool,
+        include: I,
     ) -> Result<Matches> {
-        let response = match match_id {
+        let with_games = include.into().contains(Include::Games);
+        let reply = match match_id {
             Some(match_id) => {
                 log::debug!(
                     "Getting matches by tournament id and match id: {:?} / {:?}",
                     tournament_id,
                     match_id
                 );
-                let address = Endpoint::MatchByIdGet {
+                let endpoint = Endpoint::MatchByIdGet {
                     tournament_id,
                     match_id,
                     with_games,
-                }
-                .to_string();
-                request!(self, get, &address)?
+                };
+                request!(self, get, &endpoint)?
             }
             None => {
                 log::debug!("Getting matches by tournament id: {:?}", tournament_id);
-                let address = Endpoint::MatchesByTournament {
+                let endpoint = Endpoint::MatchesByTournament {
                     tournament_id,
                     with_games,
-                }
-                .to_string();
-                request!(self, get, &address)?
+                    page: None,
+                    per_page: None,
+                };
+                request!(self, get, &endpoint)?
             }
         };
 
-        Ok(serde_json::from_reader(response)?)
+        let mut matches: Matches = reply.json()?;
+        matches.0 = matches.0.into_iter().map(Match::link_games).collect();
+        Ok(matches)
+    }
+
+    /// Returns the referenced match, see `Toornament::matches`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// let m = MatchRef::new(TournamentId("1".to_owned()), MatchId("2".to_owned()));
+    /// let m = t.match_by_ref(m, Include::Games).unwrap();
+    /// ```
+    pub fn match_by_ref<I: Into<Includes>>(&self, m: MatchRef, include: I) -> Result<Match> {
+        self.matches(m.tournament_id.clone(), Some(m.match_id.clone()), include)?
+            .0
+            .pop()
+            .ok_or(Error::Iter(IterError::NoSuchMatch(
+                m.tournament_id,
+                m.match_id,
+            )))
+    }
+
+    /// Returns the matches of a stage of a tournament or, if `group_number` is set, of a group
+    /// of the stage. The matches are filtered by the service.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// // Get the matches of the group 1 of the stage 2 of a tournament with id = "1"
+    /// let matches = t.stage_matches(TournamentId("1".to_owned()),
+    ///                               StageNumber(2),
+    ///                               Some(1),
+    ///                               Includes::none()).unwrap();
+    /// ```
+    pub fn stage_matches<I: Into<Includes>>(
+        &self,
+        tournament_id: TournamentId,
+        stage_number: StageNumber,
+        group_number: Option<u64>,
+        include: I,
+    ) -> Result<Matches> {
+        let with_games = include.into().contains(Include::Games);
+        log::debug!(
+            "Getting matches by stage: {:?} / {:?} / {:?}",
+            tournament_id,
+            stage_number,
+            group_number
+        );
+        let endpoint = Endpoint::MatchesByStage {
+            tournament_id,
+            stage_number,
+            group_number,
+            with_games,
+            page: None,
+            per_page: None,
+        };
+        request!(self, get, &endpoint)?.json()
+    }
+
+    /// Returns the matches of a tournament which involve the participant. The matches are
+    /// filtered by the service.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// // Get the matches of a participant with id = "2" of a tournament with id = "1"
+    /// let matches = t.participant_matches(TournamentId("1".to_owned()),
+    ///                                     ParticipantId("2".to_owned()),
+    ///                                     Includes::none()).unwrap();
+    /// ```
+    pub fn participant_matches<I: Into<Includes>>(
+        &self,
+        tournament_id: TournamentId,
+        participant_id: ParticipantId,
+        include: I,
+    ) -> Result<Matches> {
+        let with_games = include.into().contains(Include::Games);
+        log::debug!(
+            "Getting matches by participant: {:?} / {:?}",
+            tournament_id,
+            participant_id
+        );
+        let endpoint = Endpoint::MatchesByParticipant {
+            tournament_id,
+            participant_id,
+            with_games,
+            page: None,
+            per_page: None,
+        };
+        request!(self, get, &endpoint)?.json()
+    }
+
+    /// [Retrieve a collection of matches from a specific discipline, filtered and sorted by the
+    /// given query parameters. It might be a list of matches from different tournaments, but only
+    /// from public tournaments. The matches are returned by 20.](<https://developer.toornament.com/doc/matches#get:tournaments:tournament_id:matches>)
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// // Get all matches by a discipline with id = "1" with default filter
+    /// let matches = t.matches_by_discipline(DisciplineId("1".to_owned()), MatchFilter::default()).unwrap();
+    /// ```
+    pub fn matches_by_discipline(
+        &self,
+        discipline_id: DisciplineId,
+        filter: MatchFilter,
+    ) -> Result<Matches> {
+        log::debug!("Getting matches by discipline id: {:?}", discipline_id);
+        let endpoint = Endpoint::MatchesByDiscipline {
+            discipline_id,
+            filter,
+        };
+        request!(self, get, &endpoint)?.json()
+    }
+
+    /// Returns a pager over the matches of a tournament, see `Toornament::matches`. The
+    /// tournaments with many matches are returned in several pages.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// // Get all the matches of a tournament with id = "1" page by page
+    /// let matches = t.tournament_matches_pager(TournamentId("1".to_owned()), Include::Games)
+    ///                .fetch_all()
+    ///                .unwrap();
+    /// ```
+    pub fn tournament_matches_pager<I: Into<Includes>>(
+        &self,
+        tournament_id: TournamentId,
+        include: I,
+    ) -> Pager<'_, Match> {
+        let with_games = include.into().contains(Include::Games);
+        Pager::new(self, move |page| Endpoint::MatchesByTournament {
+            tournament_id: tournament_id.clone(),
+            with_games,
+            page: Some(page),
+            per_page: None,
+        })
     }
 
-    /// [Retrieve a collection of matches from a specific discipline, filtered and sorted by the
-    /// given query parameters. It might be a list of matches from different tournaments, but only
-    /// from public tournaments. The matches are returned by 20.](<https://developer.toornament.com/doc/matches#get:tournaments:tournament_id:matches>)
+    /// Returns a pager over the matches of a discipline: the page of the `filter` is replaced by
+    /// the number of the page being fetched.
     ///
     /// # Example
     ///
@@ -486,23 +1930,19 @@ impl Toornament {
     /// let t = Toornament::with_application("API_TOKEN",
     ///                                      "CLIENT_ID",
     ///                                      "CLIENT_SECRET").unwrap();
-    /// // Get all matches by a discipline with id = "1" with default filter
-    /// let matches = t.matches_by_discipline(DisciplineId("1".to_owned()), MatchFilter::default()).unwrap();
+    /// // Get all matches of a discipline with id = "1" page by page
+    /// let matches = t.matches_by_discipline_pager(DisciplineId("1".to_owned()),
+    ///                                             MatchFilter::default()).fetch_all().unwrap();
     /// ```
-    pub fn matches_by_discipline(
+    pub fn matches_by_discipline_pager(
         &self,
         discipline_id: DisciplineId,
         filter: MatchFilter,
-    ) -> Result<Matches> {
-        log::debug!("Getting matches by discipline id: {:?}", discipline_id);
-        let address = Endpoint::MatchesByDiscipline {
-            discipline_id,
-            filter,
-        }
-        .to_string();
-        let response = request!(self, get, &address)?;
-
-        Ok(serde_json::from_reader(response)?)
+    ) -> Pager<'_, Match> {
+        Pager::new(self, move |page| Endpoint::MatchesByDiscipline {
+            discipline_id: discipline_id.clone(),
+            filter: filter.clone().page(page),
+        })
     }
 
     /// [If you need to make changes on your match data, you are able to do so by patching one or
@@ -518,7 +1958,7 @@ impl Toornament {
     /// // Get a match with id = "2" of a tournament with id = "1"
     /// let matches = t.matches(TournamentId("1".to_owned()),
     ///                         Some(MatchId("2".to_owned())),
-    ///                         true).unwrap();
+    ///                         Include::Games).unwrap();
     /// let mut match_to_edit = matches.0.first().unwrap().clone()
     ///                                .number(2u64);
     /// match_to_edit = t.update_match(TournamentId("1".to_owned()),
@@ -537,15 +1977,118 @@ impl Toornament {
             tournament_id,
             match_id
         );
-        let address = Endpoint::MatchByIdUpdate {
+        self.check_write_policy(&tournament_id, &match_id)?;
+        let endpoint = Endpoint::MatchByIdUpdate {
             tournament_id,
             match_id,
-        }
-        .to_string();
+        };
         let body = serde_json::to_string(&updated_match)?;
-        let response = request_body!(self, patch, &address, body)?;
+        request_body!(self, patch, &endpoint, body)?.json()
+    }
+
+    /// Applies a partial update to a match.
+    pub(crate) fn patch_match(
+        &self,
+        tournament_id: TournamentId,
+        match_id: MatchId,
+        patch: &MatchPatch,
+    ) -> Result<Match> {
+        log::debug!(
+            "Patching a match by tournament id and match id: {:?} / {:?}",
+            tournament_id,
+            match_id
+        );
+        self.check_write_policy(&tournament_id, &match_id)?;
+        let endpoint = Endpoint::MatchByIdUpdate {
+            tournament_id,
+            match_id,
+        };
+        let body = serde_json::to_string(patch)?;
+        request_body!(self, patch, &endpoint, body)?.json()
+    }
+
+    /// Applies partial updates to several matches of a tournament with bounded concurrency,
+    /// continuing, stopping or rolling back on the first failure according to the `options`.
+    /// The outcome of every update is returned along with the match id, in the order of the
+    /// updates. The `progress`, if any, is told about every update. See the `batch` module.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// // Complete the matches with ids "2" and "3" of a tournament with id = "1"
+    /// let updates = vec![
+    ///     (MatchId("2".to_owned()), MatchPatch::default().status(MatchStatus::Completed)),
+    ///     (MatchId("3".to_owned()), MatchPatch::default().status(MatchStatus::Completed)),
+    /// ];
+    /// let outcomes = t.update_matches(TournamentId("1".to_owned()),
+    ///                                 updates,
+    ///                                 batch::BatchOptions::default(),
+    ///                                 None);
+    /// ```
+    pub fn update_matches(
+        &self,
+        tournament_id: TournamentId,
+        updates: Vec<(MatchId, MatchPatch)>,
+        options: batch::BatchOptions,
+        progress: Option<&dyn Progress>,
+    ) -> Vec<(MatchId, batch::UpdateOutcome)> {
+        log::debug!(
+            "Updating {} matches of tournament {:?}",
+            updates.len(),
+            tournament_id
+        );
+        batch::update_matches(self, tournament_id, updates, options, progress)
+    }
 
-        Ok(serde_json::from_reader(response)?)
+    /// Moves the matches of a tournament selected by the `filter` by the `offset`, for example
+    /// to reschedule a delayed event. All the pages of the matches are fetched once, then the
+    /// date of every selected match is updated, and nothing else; the result of every update is
+    /// returned along with the match id, so a failed update does not prevent the others.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// // Delay the pending matches of a tournament with id = "1" by an hour
+    /// let report = t.shift_matches(TournamentId("1".to_owned()),
+    ///                              |m| m.status == MatchStatus::Pending,
+    ///                              chrono::Duration::hours(1)).unwrap();
+    /// for (id, result) in report {
+    ///     if let Err(e) = result {
+    ///         println!("Could not shift match {}: {}", id.0, e);
+    ///     }
+    /// }
+    /// ```
+    pub fn shift_matches<F: FnMut(&Match) -> bool>(
+        &self,
+        tournament_id: TournamentId,
+        mut filter: F,
+        offset: chrono::Duration,
+    ) -> Result<Vec<(MatchId, Result<Match>)>> {
+        log::debug!(
+            "Shifting matches of tournament {:?} by {}",
+            tournament_id,
+            offset
+        );
+        let matches = self
+            .tournament_matches_pager(tournament_id.clone(), Includes::none())
+            .fetch_all()?;
+        Ok(matches
+            .into_iter()
+            .filter(|m| filter(m))
+            .map(|m| {
+                let patch = MatchPatch::default().date(m.date + offset);
+                let result = self.patch_match(tournament_id.clone(), m.id.clone(), &patch);
+                (m.id, result)
+            })
+            .collect())
     }
 
     /// [Returns detailed result about one match.](<https://developer.toornament.com/doc/matches#get:tournaments:tournament_id:matches:id:result>)
@@ -567,10 +2110,8 @@ impl Toornament {
             id,
             match_id
         );
-        let address = Endpoint::MatchResult(id, match_id).to_string();
-        let response = request!(self, get, &address)?;
-
-        Ok(serde_json::from_reader(response)?)
+        let endpoint = Endpoint::MatchResult(id, match_id);
+        request!(self, get, &endpoint)?.json()
     }
 
     /// [Update or create detailed result about one match.](<https://developer.toornament.com/doc/matches#put:tournaments:tournament_id:matches:id:result>)
@@ -603,11 +2144,10 @@ impl Toornament {
             id,
             match_id
         );
-        let address = Endpoint::MatchResult(id, match_id).to_string();
+        self.check_write_policy(&id, &match_id)?;
+        let endpoint = Endpoint::MatchResult(id, match_id);
         let body = serde_json::to_string(&result)?;
-        let response = request_body!(self, put, &address, body)?;
-
-        Ok(serde_json::from_reader(response)?)
+        request_body!(self, put, &endpoint, body)?.json()
     }
 
     /// [Returns a collection of games from one match.](<https://developer.toornament.com/doc/games#get:tournaments:tournament_id:matches:match_id:games>)
@@ -622,27 +2162,29 @@ impl Toornament {
     /// // Get match games of a match with id = "2" of a tournament with id = "1"
     /// let games = t.match_games(TournamentId("1".to_owned()),
     ///                           MatchId("2".to_owned()),
-    ///                           true).unwrap();
+    ///                           Include::Stats).unwrap();
     /// ```
-    pub fn match_games(
+    pub fn match_games<I: Into<Includes>>(
         &self,
         tournament_id: TournamentId,
         match_id: MatchId,
-        with_stats: bool,
+        include: I,
     ) -> Result<Games> {
+        let with_stats = include.into().contains(Include::Stats);
         log::debug!(
             "Getting match games by tournament id and match id: {:?} / {:?}",
             tournament_id,
             match_id
         );
-        let address = Endpoint::MatchGames {
+        let m = MatchRef::new(tournament_id.clone(), match_id.clone());
+        let endpoint = Endpoint::MatchGames {
             tournament_id,
             match_id,
             with_stats,
-        }
-        .to_string();
-        let response = request!(self, get, &address)?;
-        Ok(serde_json::from_reader(response)?)
+        };
+        Ok(request!(self, get, &endpoint)?
+            .json::<Games>()?
+            .of_match(&m))
     }
 
     /// [Returns detailed information about one game.](<https://developer.toornament.com/doc/games?#get:tournaments:tournament_id:matches:match_id:games:number>)
@@ -658,30 +2200,29 @@ impl Toornament {
     /// let game = t.match_game(TournamentId("1".to_owned()),
     ///                         MatchId("2".to_owned()),
     ///                         GameNumber(3i64),
-    ///                         true).unwrap();
+    ///                         Include::Stats).unwrap();
     /// ```
-    pub fn match_game(
+    pub fn match_game<I: Into<Includes>>(
         &self,
         tournament_id: TournamentId,
         match_id: MatchId,
         game_number: GameNumber,
-        with_stats: bool,
+        include: I,
     ) -> Result<Game> {
+        let with_stats = include.into().contains(Include::Stats);
         log::debug!(
             "Getting match game in details by tournament id and match id: {:?} / {:?}",
             tournament_id,
             match_id
         );
-        let address = Endpoint::MatchGameByNumberGet {
+        let m = MatchRef::new(tournament_id.clone(), match_id.clone());
+        let endpoint = Endpoint::MatchGameByNumberGet {
             tournament_id,
             match_id,
             game_number,
             with_stats,
-        }
-        .to_string();
-        let response = request!(self, get, &address)?;
-
-        Ok(serde_json::from_reader(response)?)
+        };
+        Ok(request!(self, get, &endpoint)?.json::<Game>()?.of_match(&m))
     }
 
     /// [If you need to make changes on your game data, you are able to do so by patching one
@@ -698,6 +2239,9 @@ impl Toornament {
     ///     number: GameNumber(3i64),
     ///     status: MatchStatus::Completed,
     ///     opponents: Opponents::default(),
+    ///     properties: GameProperties::default().set_map("de_dust2"),
+    ///     raw_stats: None,
+    ///     match_ref: None,
     /// };
     /// // Update a match game with number "3" of a match with id = "2" of a tournament with id = "1"
     /// assert!(t.update_match_game(TournamentId("1".to_owned()),
@@ -717,16 +2261,70 @@ impl Toornament {
             tournament_id,
             match_id
         );
-        let address = Endpoint::MatchGameByNumberUpdate {
+        self.check_write_policy(&tournament_id, &match_id)?;
+        let m = MatchRef::new(tournament_id.clone(), match_id.clone());
+        let endpoint = Endpoint::MatchGameByNumberUpdate {
             tournament_id,
             match_id,
             game_number,
-        }
-        .to_string();
-        let body = serde_json::to_string(&game)?;
-        let response = request_body!(self, patch, &address, body)?;
+        };
+        // The statistics are computed by the service and are not written.
+        let body = serde_json::to_string(&Game {
+            raw_stats: None,
+            ..game
+        })?;
+        Ok(request_body!(self, patch, &endpoint, body)?
+            .json::<Game>()?
+            .of_match(&m))
+    }
+
+    /// Updates a game of the referenced match, see `Toornament::update_match_game`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// let m = t.match_by_ref(MatchRef::new(TournamentId("1".to_owned()),
+    ///                                      MatchId("2".to_owned())),
+    ///                        Include::Games).unwrap();
+    /// let mut game = m.games.clone().unwrap().0.remove(0);
+    /// game.status = MatchStatus::Completed;
+    /// let game = t.update_match_game_by_ref(m.match_ref(), game.number, game).unwrap();
+    /// ```
+    pub fn update_match_game_by_ref(
+        &self,
+        m: MatchRef,
+        game_number: GameNumber,
+        game: Game,
+    ) -> Result<Game> {
+        self.update_match_game(m.tournament_id, m.match_id, game_number, game)
+    }
 
-        Ok(serde_json::from_reader(response)?)
+    /// Updates a fetched game, which knows its match. Fails if the game was not fetched.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// let mut game = t.match_game(TournamentId("1".to_owned()),
+    ///                             MatchId("2".to_owned()),
+    ///                             GameNumber(3),
+    ///                             Includes::none()).unwrap();
+    /// game.status = MatchStatus::Completed;
+    /// let game = t.update_game(game).unwrap();
+    /// ```
+    pub fn update_game(&self, game: Game) -> Result<Game> {
+        let m = game
+            .match_ref
+            .clone()
+            .ok_or(Error::Rest("The match of the game is unknown"))?;
+        self.update_match_game(m.tournament_id, m.match_id, game.number, game)
     }
 
     /// [Returns detailed result about one specific game.](<https://developer.toornament.com/doc/games?#get:tournaments:tournament_id:matches:match_id:games:number:result>)
@@ -754,15 +2352,12 @@ impl Toornament {
             tournament_id,
             match_id
         );
-        let address = Endpoint::MatchGameResultGet {
+        let endpoint = Endpoint::MatchGameResultGet {
             tournament_id,
             match_id,
             game_number,
-        }
-        .to_string();
-        let response = request!(self, get, &address)?;
-
-        Ok(serde_json::from_reader(response)?)
+        };
+        request!(self, get, &endpoint)?.json()
     }
 
     /// [Updates or creates detailed result about one game.](<https://developer.toornament.com/doc/games?#put:tournaments:tournament_id:matches:match_id:games:number:result>)
@@ -799,17 +2394,15 @@ impl Toornament {
             tournament_id,
             match_id
         );
-        let address = Endpoint::MatchGameResultUpdate {
+        self.check_write_policy(&tournament_id, &match_id)?;
+        let endpoint = Endpoint::MatchGameResultUpdate {
             tournament_id,
             match_id,
             game_number,
             update_match,
-        }
-        .to_string();
+        };
         let body = serde_json::to_string(&result)?;
-        let response = request_body!(self, put, &address, body)?;
-
-        Ok(serde_json::from_reader(response)?)
+        request_body!(self, put, &endpoint, body)?.json()
     }
 
     /// [Returns a collection of participants from one tournament. The tournament must be public
@@ -837,14 +2430,37 @@ impl Toornament {
             "Getting tournament participants by tournament id: {:?}",
             tournament_id
         );
-        let address = Endpoint::Participants {
+        let endpoint = Endpoint::Participants {
             tournament_id,
             filter,
-        }
-        .to_string();
-        let response = request!(self, get, &address)?;
+        };
+        request!(self, get, &endpoint)?.json()
+    }
 
-        Ok(serde_json::from_reader(response)?)
+    /// Returns a pager over the participants of a tournament: the page of the `filter` is
+    /// replaced by the number of the page being fetched.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// // Get all the participants of a tournament with id = "1", 4 pages at a time
+    /// let participants = t.tournament_participants_pager(
+    ///     TournamentId("1".to_owned()),
+    ///     TournamentParticipantsFilter::default()).fetch_all_parallel(4).unwrap();
+    /// ```
+    pub fn tournament_participants_pager(
+        &self,
+        tournament_id: TournamentId,
+        filter: TournamentParticipantsFilter,
+    ) -> Pager<'_, Participant> {
+        Pager::new(self, move |page| Endpoint::Participants {
+            tournament_id: tournament_id.clone(),
+            filter: filter.clone().page(page),
+        })
     }
 
     /// [Create a participant in a tournament.](<https://developer.toornament.com/doc/participants?#post:tournaments:tournament_id:participants>)
@@ -866,14 +2482,13 @@ impl Toornament {
     pub fn create_tournament_participant(
         &self,
         id: TournamentId,
-        participant: Participant,
+        mut participant: Participant,
     ) -> Result<Participant> {
         log::debug!("Creating a participant for tournament with id: {:?}", id);
-        let address = Endpoint::ParticipantCreate(id).to_string();
+        self.moderate(&mut participant)?;
+        let endpoint = Endpoint::ParticipantCreate(id);
         let body = serde_json::to_string(&participant)?;
-        let response = request_body!(self, post, &address, body)?;
-
-        Ok(serde_json::from_reader(response)?)
+        request_body!(self, post, &endpoint, body)?.json()
     }
 
     /// [Create a list of participants in a tournament. If any participant already exists he will
@@ -890,23 +2505,57 @@ impl Toornament {
     ///                             Participant::create("Second participant")];
     /// // Update a participant for a tournament with id = "1"
     /// let new_participants = t.update_tournament_participants(TournamentId("1".to_owned()),
-    ///                                                         Participants(participants)).unwrap();
+    ///                                                         Participants::from(participants)).unwrap();
     /// assert_eq!(new_participants.0.len(), 2);
     /// ```
     pub fn update_tournament_participants(
         &self,
         id: TournamentId,
-        participants: Participants,
+        mut participants: Participants,
     ) -> Result<Participants> {
         log::debug!(
             "Creating a list of participants for tournament with id: {:?}",
             id
         );
-        let address = Endpoint::ParticipantsUpdate(id).to_string();
+        for participant in &mut participants.0 {
+            self.moderate(participant)?;
+        }
+        let endpoint = Endpoint::ParticipantsUpdate(id);
         let body = serde_json::to_string(&participants)?;
-        let response = request_body!(self, put, &address, body)?;
+        request_body!(self, put, &endpoint, body)?.json()
+    }
 
-        Ok(serde_json::from_reader(response)?)
+    /// Replaces the participants of a tournament like
+    /// `Toornament::update_tournament_participants`, fetching the current ones first. The
+    /// returned token restores them with `Toornament::undo`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// let participants = Participants::from(vec![Participant::create("First participant")]);
+    /// let (new_participants, token) = t.update_tournament_participants_with_undo(
+    ///     TournamentId("1".to_owned()),
+    ///     participants).unwrap();
+    /// ```
+    pub fn update_tournament_participants_with_undo(
+        &self,
+        id: TournamentId,
+        participants: Participants,
+    ) -> Result<(Participants, UndoToken)> {
+        self.confirmed("put", &Endpoint::ParticipantsUpdate(id.clone()))?;
+        let previous = UndoToken::participants(self, id.clone())?;
+        let participants = self.update_tournament_participants(id.clone(), participants)?;
+        Ok((
+            participants,
+            UndoToken::ParticipantsReplaced {
+                tournament_id: id,
+                participants: previous,
+            },
+        ))
     }
 
     /// [Returns detailed information about one participant.](<https://developer.toornament.com/doc/participants?_locale=en#get:tournaments:tournament_id:participants:id>)
@@ -933,10 +2582,8 @@ impl Toornament {
             id,
             participant_id
         );
-        let address = Endpoint::ParticipantById(id, participant_id).to_string();
-        let response = request!(self, get, &address)?;
-
-        Ok(serde_json::from_reader(response)?)
+        let endpoint = Endpoint::ParticipantById(id, participant_id);
+        request!(self, get, &endpoint)?.json()
     }
 
     /// [Update some of the editable information on a participant.](<https://developer.toornament.com/doc/participants?_locale=en#patch:tournaments:tournament_id:participants:id>)
@@ -965,18 +2612,17 @@ impl Toornament {
         &self,
         id: TournamentId,
         participant_id: ParticipantId,
-        participant: Participant,
+        mut participant: Participant,
     ) -> Result<Participant> {
         log::debug!(
             "Updating a participant for tournament with id and participant id: {:?} / {:?}",
             id,
             participant_id
         );
-        let address = Endpoint::ParticipantById(id, participant_id).to_string();
+        self.moderate(&mut participant)?;
+        let endpoint = Endpoint::ParticipantById(id, participant_id);
         let body = serde_json::to_string(&participant)?;
-        let response = request_body!(self, patch, &address, body)?;
-
-        Ok(serde_json::from_reader(response)?)
+        request_body!(self, patch, &endpoint, body)?.json()
     }
 
     /// [Deletes one participant.](<https://developer.toornament.com/doc/participants?_locale=en#delete:tournaments:tournament_id:participants:id>)
@@ -1002,13 +2648,40 @@ impl Toornament {
             id,
             participant_id
         );
-        let address = Endpoint::ParticipantById(id, participant_id).to_string();
-        let response = request!(self, delete, &address)?;
-        if response.status().is_success() {
-            Ok(())
-        } else {
-            Err(Error::Rest("Something went wrong"))
-        }
+        let endpoint = Endpoint::ParticipantById(id, participant_id);
+        let _ = request!(self, delete, &endpoint)?;
+        Ok(())
+    }
+
+    /// Deletes one participant like `Toornament::delete_tournament_participant`, fetching it
+    /// first. The returned token recreates it with `Toornament::undo`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// let token = t.delete_tournament_participant_with_undo(TournamentId("1".to_owned()),
+    ///                                                       ParticipantId("2".to_owned()))
+    ///              .unwrap();
+    /// ```
+    pub fn delete_tournament_participant_with_undo(
+        &self,
+        id: TournamentId,
+        participant_id: ParticipantId,
+    ) -> Result<UndoToken> {
+        self.confirmed(
+            "delete",
+            &Endpoint::ParticipantById(id.clone(), participant_id.clone()),
+        )?;
+        let participant = self.tournament_participant(id.clone(), participant_id.clone())?;
+        self.delete_tournament_participant(id.clone(), participant_id)?;
+        Ok(UndoToken::ParticipantDeleted {
+            tournament_id: id,
+            participant,
+        })
     }
 
     /// [Returns a collection of permission from one tournament.](<https://developer.toornament.com/doc/permissions?_locale=en#get:tournaments:tournament_id:permissions>)
@@ -1025,10 +2698,8 @@ impl Toornament {
     /// ```
     pub fn tournament_permissions(&self, id: TournamentId) -> Result<Permissions> {
         log::debug!("Getting tournament permissions by tournament id: {:?}", id);
-        let address = Endpoint::Permissions(id).to_string();
-        let response = request!(self, get, &address)?;
-
-        Ok(serde_json::from_reader(response)?)
+        let endpoint = Endpoint::Permissions(id);
+        request!(self, get, &endpoint)?.json()
     }
 
     /// [Create a permission for a user on a tournament.](<https://developer.toornament.com/doc/permissions?_locale=en#post:tournaments:tournament_id:permissions>)
@@ -1060,11 +2731,9 @@ impl Toornament {
         permission: Permission,
     ) -> Result<Permission> {
         log::debug!("Creating tournament permissions by tournament id: {:?}", id);
-        let address = Endpoint::Permissions(id).to_string();
+        let endpoint = Endpoint::Permissions(id);
         let body = serde_json::to_string(&permission)?;
-        let response = request_body!(self, post, &address, body)?;
-
-        Ok(serde_json::from_reader(response)?)
+        request_body!(self, post, &endpoint, body)?.json()
     }
 
     /// [Retrieves a permission of a tournament.](<https://developer.toornament.com/doc/permissions?_locale=en#get:tournaments:tournament_id:permissions:permission_id>)
@@ -1092,10 +2761,8 @@ impl Toornament {
             id,
             permission_id
         );
-        let address = Endpoint::PermissionById(id, permission_id).to_string();
-        let response = request!(self, get, &address)?;
-
-        Ok(serde_json::from_reader(response)?)
+        let endpoint = Endpoint::PermissionById(id, permission_id);
+        request!(self, get, &endpoint)?.json()
     }
 
     /// [Update rights of a permission.](<https://developer.toornament.com/doc/permissions?_locale=en#patch:tournaments:tournament_id:permissions:permission_id>)
@@ -1139,12 +2806,10 @@ impl Toornament {
             id,
             permission_id
         );
-        let address = Endpoint::PermissionById(id, permission_id).to_string();
+        let endpoint = Endpoint::PermissionById(id, permission_id);
         let wrapped_attributes = WrappedAttributes { attributes };
         let body = serde_json::to_string(&wrapped_attributes)?;
-        let response = request_body!(self, patch, &address, body)?;
-
-        Ok(serde_json::from_reader(response)?)
+        request_body!(self, patch, &endpoint, body)?.json()
     }
 
     /// [Delete a user permission of a tournament.](<https://developer.toornament.com/doc/permissions?_locale=en#delete:tournaments:tournament_id:permissions:permission_id>)
@@ -1171,13 +2836,43 @@ impl Toornament {
             id,
             permission_id
         );
-        let address = Endpoint::PermissionById(id, permission_id).to_string();
-        let response = request!(self, delete, &address)?;
-        if response.status().is_success() {
-            Ok(())
-        } else {
-            Err(Error::Rest("Something went wrong"))
-        }
+        let endpoint = Endpoint::PermissionById(id, permission_id);
+        let _ = request!(self, delete, &endpoint)?;
+        Ok(())
+    }
+
+    /// Sends the invitation of a permission again to its user, when the user has not accepted
+    /// it yet (see `Permission::is_pending`).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// let id = TournamentId("1".to_owned());
+    /// // Remind the invited users of a tournament with id = "1"
+    /// for permission in t.tournament_permissions(id.clone()).unwrap().pending() {
+    ///     if let Some(ref permission_id) = permission.id {
+    ///         t.resend_permission_invitation(id.clone(), permission_id.clone()).unwrap();
+    ///     }
+    /// }
+    /// ```
+    pub fn resend_permission_invitation(
+        &self,
+        id: TournamentId,
+        permission_id: PermissionId,
+    ) -> Result<()> {
+        log::debug!(
+            "Resending the invitation of permission for tournament with id and permission id: \
+             {:?} / {:?}",
+            id,
+            permission_id
+        );
+        let endpoint = Endpoint::PermissionInvitation(id, permission_id);
+        let _ = request!(self, post, &endpoint)?;
+        Ok(())
     }
 
     /// [Returns a collection of stages from one tournament. The tournament must be public to have
@@ -1196,10 +2891,86 @@ impl Toornament {
     /// ```
     pub fn tournament_stages(&self, id: TournamentId) -> Result<Stages> {
         log::debug!("Getting tournament stages by tournament id: {:?}", id);
-        let address = Endpoint::Stages(id).to_string();
-        let response = request!(self, get, &address)?;
+        let endpoint = Endpoint::Stages(id);
+        request!(self, get, &endpoint)?.json()
+    }
+
+    /// Returns the final standings of a tournament: the ranks of its participants, the
+    /// participants tied sharing a range of ranks. See `FinalStandings::compute` for the
+    /// disciplines whose standings the service does not provide.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// // Get the final standings of a tournament with id = "1"
+    /// let standings = t.final_standings(TournamentId("1".to_owned())).unwrap();
+    /// ```
+    pub fn final_standings(&self, id: TournamentId) -> Result<FinalStandings> {
+        log::debug!("Getting final standings by tournament id: {:?}", id);
+        let endpoint = Endpoint::FinalStandings(id);
+        request!(self, get, &endpoint)?.json()
+    }
+
+    /// Replaces the final standings of a tournament, after checking them with
+    /// `FinalStandings::validate`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// let id = TournamentId("1".to_owned());
+    /// // Compute the final standings of a tournament with id = "1" from all its matches, with
+    /// // three points for a win and one for a draw
+    /// let stages = t.tournament_stages(id.clone()).unwrap();
+    /// let matches = t.tournament_matches_pager(id.clone(), Includes::none())
+    ///                .fetch_all()
+    ///                .unwrap();
+    /// if let Some(standings) = FinalStandings::compute(&stages, &Matches::from(matches), 3, 1, 0) {
+    ///     t.update_final_standings(id, standings).unwrap();
+    /// }
+    /// ```
+    pub fn update_final_standings(
+        &self,
+        id: TournamentId,
+        standings: FinalStandings,
+    ) -> Result<FinalStandings> {
+        log::debug!("Updating final standings of tournament with id: {:?}", id);
+        standings.validate()?;
+        let endpoint = Endpoint::FinalStandings(id);
+        let body = serde_json::to_string(&standings)?;
+        request_body!(self, put, &endpoint, body)?.json()
+    }
 
-        Ok(serde_json::from_reader(response)?)
+    /// Returns the custom fields of the registration form of a tournament.
+    /// See the `registration` module.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use toornament::*;
+    /// let t = Toornament::with_application("API_TOKEN",
+    ///                                      "CLIENT_ID",
+    ///                                      "CLIENT_SECRET").unwrap();
+    /// // Get the registration form of a tournament with id = "1"
+    /// let form = t.tournament_registration_form(TournamentId("1".to_owned())).unwrap();
+    /// ```
+    pub fn tournament_registration_form(
+        &self,
+        id: TournamentId,
+    ) -> Result<registration::FormSchema> {
+        log::debug!(
+            "Getting tournament registration form by tournament id: {:?}",
+            id
+        );
+        let endpoint = Endpoint::CustomFields(id);
+        request!(self, get, &endpoint)?.json()
     }
 
     /// [Returns a collection of videos from one tournament. The collection may be filtered and
@@ -1227,17 +2998,22 @@ impl Toornament {
             "Getting tournament videos by tournament id: {:?}",
             tournament_id
         );
-        let address = Endpoint::Videos {
+        let endpoint = Endpoint::Videos {
             tournament_id,
             filter,
-        }
-        .to_string();
-        let response = request!(self, get, &address)?;
-
-        Ok(serde_json::from_reader(response)?)
+        };
+        request!(self, get, &endpoint)?.json()
     }
 }
 
+/// Returns the address of a local port nothing listens on: it is bound, then released.
+#[cfg(all(test, any(feature = "client-blocking", feature = "client-async")))]
+pub(crate) fn closed_address() -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    format!("http://{}", listener.local_addr().unwrap())
+}
+
+#[cfg(feature = "client-blocking")]
 #[cfg(test)]
 mod tests {
     fn assert_sync_and_send<T: Sync + Send>() {}
@@ -1245,5 +3021,105 @@ mod tests {
     #[test]
     fn test_sync_and_send() {
         assert_sync_and_send::<crate::Toornament>();
+        assert_sync_and_send::<crate::ReadOnlyToornament>();
+    }
+
+    #[test]
+    fn test_rebase() {
+        let address = crate::Endpoint::AllDisciplines.to_string();
+        assert_eq!(
+            crate::protocol::rebase("http://localhost:8080/", &address),
+            "http://localhost:8080/v1/disciplines"
+        );
+        assert_eq!(
+            crate::protocol::rebase(crate::endpoints::API_BASE, &address),
+            address
+        );
+    }
+
+    #[test]
+    fn test_token_info() {
+        let json = r#"{
+            "access_token": "secret",
+            "expires_in": 3600,
+            "token_type": "bearer",
+            "scope": "organizer:view organizer:result"
+        }"#;
+        let token = crate::parse_token(json.as_bytes()).unwrap();
+        let info = crate::token_info(&token);
+        assert_eq!(info.scopes, vec!["organizer:view", "organizer:result"]);
+        assert_eq!(info.expires - info.issued, chrono::Duration::seconds(3600));
+        assert!(!info.is_expired());
+        assert!(info.expires_in() > chrono::Duration::minutes(59));
+        assert!(!format!("{:?}", info).contains("secret"));
+        assert!(!format!("{:?}", token).contains("secret"));
+    }
+
+    #[test]
+    fn test_owned_iterators_are_send() {
+        fn assert_send<T: Send + 'static>() {}
+
+        assert_send::<crate::Toornament>();
+        assert_send::<crate::TournamentsIter<'static>>();
+        assert_send::<crate::TournamentMatchEditor<'static>>();
+        assert_send::<crate::ParticipantCreator<'static>>();
+    }
+
+    #[test]
+    fn test_require_confirmation() {
+        use crate::*;
+
+        let tokens = std::sync::Arc::new(TokenStore::new());
+        let json = r#"{ "access_token": "secret", "expires_in": 3600 }"#;
+        *tokens.lock().unwrap() = crate::parse_token(json.as_bytes()).unwrap();
+        let t = Toornament::with_token_store(tokens, "API_TOKEN", "CLIENT_ID", "CLIENT_SECRET")
+            .unwrap()
+            .require_confirmation();
+
+        let id = TournamentId("1".to_owned());
+        for result in [
+            t.delete_tournament(id.clone()),
+            t.delete_tournament_with_undo(id.clone()).map(|_| ()),
+            t.update_tournament_participants(id.clone(), Participants::from(Vec::new()))
+                .map(|_| ()),
+            t.delete_tournament_participant(id, ParticipantId("2".to_owned())),
+        ] {
+            match result {
+                Err(e) => assert!(matches!(e.root(), Error::Unconfirmed), "{}", e),
+                Ok(_) => panic!("The destructive call was not guarded"),
+            }
+        }
+        assert!(!t.confirm_destructive().client().require_confirmation);
+    }
+
+    #[test]
+    fn test_transport_settings_are_kept() {
+        use crate::*;
+        use std::time::Duration;
+
+        let tokens = std::sync::Arc::new(TokenStore::new());
+        let json = r#"{ "access_token": "secret", "expires_in": 3600 }"#;
+        *tokens.lock().unwrap() = crate::parse_token(json.as_bytes()).unwrap();
+        let mut t = Toornament::with_token_store(tokens, "API_TOKEN", "CLIENT_ID", "CLIENT_SECRET")
+            .unwrap();
+        t.transport.proxy = Some(reqwest::Proxy::all("http://proxy:3128").unwrap());
+        let t = t
+            .timeouts(
+                Timeouts::new()
+                    .connect(Duration::from_secs(2))
+                    .total(Duration::from_secs(60)),
+            )
+            .unwrap()
+            .timeout(5)
+            .unwrap()
+            .connection_pool(ConnectionPool::new().max_idle(4))
+            .unwrap();
+
+        let timeouts = t.transport.timeouts;
+        assert_eq!(timeouts.connect_timeout(), Some(Duration::from_secs(2)));
+        assert_eq!(timeouts.attempt_timeout(), Some(Duration::from_secs(5)));
+        assert_eq!(timeouts.total_timeout(), Some(Duration::from_secs(60)));
+        assert_eq!(t.transport.pool.max_idle_per_host, Some(4));
+        assert!(t.transport.proxy.is_some());
     }
 }
@@ -5,6 +5,33 @@ macro_rules! builder {
             self.$field = $field;
             self
         }
+
+        paste::paste! {
+            /// A mutator method for $field with `$field_type` type.
+            pub fn [<set_ $field>](&mut self, $field: $field_type) -> &mut Self {
+                self.$field = $field;
+                self
+            }
+        }
+    };
+    (#[deprecated($($deprecation:tt)*)] $field:ident, $field_type:ty) => {
+        /// A builder method for $field with `$field_type` type.
+        #[deprecated($($deprecation)*)]
+        #[allow(deprecated)]
+        pub fn $field(mut self, $field: $field_type) -> Self {
+            self.$field = $field;
+            self
+        }
+
+        paste::paste! {
+            /// A mutator method for $field with `$field_type` type.
+            #[deprecated($($deprecation)*)]
+            #[allow(deprecated)]
+            pub fn [<set_ $field>](&mut self, $field: $field_type) -> &mut Self {
+                self.$field = $field;
+                self
+            }
+        }
     };
 }
 
@@ -15,6 +42,14 @@ macro_rules! builder_s {
             self.$field = $field.into();
             self
         }
+
+        paste::paste! {
+            /// A mutator method for $field with `String` type.
+            pub fn [<set_ $field>]<S: Into<String>>(&mut self, $field: S) -> &mut Self {
+                self.$field = $field.into();
+                self
+            }
+        }
     };
 }
 
@@ -25,6 +60,14 @@ macro_rules! builder_o {
             self.$field = Some($field.into());
             self
         }
+
+        paste::paste! {
+            /// A mutator method for $field with `Option` type.
+            pub fn [<set_ $field>](&mut self, $field: $field_type) -> &mut Self {
+                self.$field = Some($field.into());
+                self
+            }
+        }
     };
 }
 
@@ -33,3 +76,51 @@ macro_rules! builder_so {
         builder!($field, Option<String>);
     };
 }
+
+/// Implements the conversions and the dereferencing to a slice of a list wrapper
+/// `$list(pub Vec<$item>)`.
+macro_rules! list_wrapper {
+    ($list:ident, $item:ty) => {
+        impl From<Vec<$item>> for $list {
+            fn from(items: Vec<$item>) -> $list {
+                $list(items)
+            }
+        }
+
+        impl From<$list> for Vec<$item> {
+            fn from(list: $list) -> Vec<$item> {
+                list.0
+            }
+        }
+
+        impl std::iter::FromIterator<$item> for $list {
+            fn from_iter<I: IntoIterator<Item = $item>>(items: I) -> $list {
+                $list(items.into_iter().collect())
+            }
+        }
+
+        impl std::ops::Deref for $list {
+            type Target = [$item];
+
+            fn deref(&self) -> &[$item] {
+                &self.0
+            }
+        }
+    };
+}
+
+#[cfg(feature = "client-blocking")]
+macro_rules! into_owned {
+    ($name:ident { $($field:ident),* }) => {
+        impl<'a> $name<'a> {
+            /// Converts the object into one owning a clone of the client, so it does not borrow
+            /// anything and can be moved into another thread.
+            pub fn into_owned(self) -> $name<'static> {
+                $name {
+                    client: self.client.into_owned(),
+                    $($field: self.$field),*
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,283 @@
+//! This module correlates the participants of a tournament with their identities on other
+//! platforms, taken from their custom fields, so that a bot can tell which Discord user is a
+//! participant and which participant a Steam account plays for.
+//!
+//! The identities are read from the public and the private custom fields of the participants
+//! and of the players of their lineups, which are mapped to their team:
+//!
+//! * `Platform::Steam`: the steam player id fields, in any form, normalized to the 64-bit
+//!   numeric form;
+//! * `Platform::Discord`: the text fields with "discord" in their labels;
+//! * `Platform::Twitch` and `Platform::Twitter`: the twitch and twitter fields.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use toornament::*;
+//! use toornament::mapping::{IdentityMap, Platform};
+//!
+//! let toornament = Toornament::with_application("API_TOKEN",
+//!                                               "CLIENT_ID",
+//!                                               "CLIENT_SECRET").unwrap();
+//! let filter = TournamentParticipantsFilter::default()
+//!     .with_lineup(true)
+//!     .with_custom_fields(true);
+//! let participants = toornament
+//!     .tournament_participants(TournamentId("1".to_owned()), filter)
+//!     .unwrap();
+//! let map = IdentityMap::new(&participants);
+//! if let Some(id) = map.participant(Platform::Discord, "player#1234") {
+//!     println!("The Discord user plays for participant {}", id.0);
+//! }
+//! ```
+use std::collections::BTreeMap;
+
+use crate::participants::{
+    steam_id64, CustomField, CustomFieldType, Participant, ParticipantId, Participants,
+};
+
+/// A platform the participants have identities on.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Platform {
+    /// Steam, with the ids in the 64-bit numeric form.
+    Steam,
+    /// Discord, with the user ids or the user names.
+    Discord,
+    /// Twitch.
+    Twitch,
+    /// Twitter.
+    Twitter,
+}
+impl Platform {
+    /// Returns the identity of the custom field on this platform, if it holds one.
+    fn identity(self, field: &CustomField) -> Option<String> {
+        let value = match (self, &field.field_type) {
+            (Platform::Steam, _) => return field.as_steam_id().and_then(steam_id64),
+            (Platform::Discord, CustomFieldType::Text)
+                if field.label.to_lowercase().contains("discord") =>
+            {
+                &field.value
+            }
+            (Platform::Twitch, CustomFieldType::Twitch) => &field.value,
+            (Platform::Twitter, CustomFieldType::Twitter) => &field.value,
+            _ => return None,
+        };
+        Some(self.normalize(value)).filter(|v| !v.is_empty())
+    }
+
+    /// Returns the identity in the form it is stored in the map.
+    fn normalize(self, id: &str) -> String {
+        let id = id.trim();
+        match self {
+            Platform::Steam => {
+                let field = CustomField {
+                    field_type: CustomFieldType::SteamId,
+                    label: String::new(),
+                    value: id.to_owned(),
+                };
+                field
+                    .as_steam_id()
+                    .and_then(steam_id64)
+                    .unwrap_or_else(|| id.to_owned())
+            }
+            Platform::Discord => id.to_lowercase(),
+            Platform::Twitch | Platform::Twitter => id.trim_start_matches('@').to_lowercase(),
+        }
+    }
+}
+
+/// An identity of a participant on a platform.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Serialize)]
+pub struct Identity {
+    /// The platform.
+    pub platform: Platform,
+    /// The normalized identifier on the platform.
+    pub id: String,
+    /// Name of the player of the lineup having this identity, `None` for the participant
+    /// itself.
+    pub player: Option<String>,
+}
+
+/// The lookup tables between the participants and their identities.
+#[derive(Clone, Debug, Default)]
+pub struct IdentityMap {
+    identities: BTreeMap<ParticipantId, Vec<Identity>>,
+    participants: BTreeMap<(Platform, String), Vec<ParticipantId>>,
+}
+impl IdentityMap {
+    /// Builds the tables from the participants. The participants without id are skipped.
+    pub fn new(participants: &Participants) -> IdentityMap {
+        let mut map = IdentityMap::default();
+        for p in &participants.0 {
+            let id = match p.id {
+                Some(ref id) => id,
+                None => continue,
+            };
+            map.add(id, p, None);
+            for player in p.lineup.iter().flat_map(|l| l.0.iter()) {
+                map.add(id, player, Some(player.name.clone()));
+            }
+        }
+        map
+    }
+
+    fn add(&mut self, id: &ParticipantId, p: &Participant, player: Option<String>) {
+        let fields = p.custom_fields.iter().chain(p.custom_fields_private.iter());
+        for field in fields.flat_map(|f| f.0.iter()) {
+            for &platform in &[
+                Platform::Steam,
+                Platform::Discord,
+                Platform::Twitch,
+                Platform::Twitter,
+            ] {
+                let identity = match platform.identity(field) {
+                    Some(identity) => identity,
+                    None => continue,
+                };
+                let owners = self
+                    .participants
+                    .entry((platform, identity.clone()))
+                    .or_default();
+                if !owners.contains(id) {
+                    owners.push(id.clone());
+                }
+                let identities = self.identities.entry(id.clone()).or_default();
+                let identity = Identity {
+                    platform,
+                    id: identity,
+                    player: player.clone(),
+                };
+                if !identities.contains(&identity) {
+                    identities.push(identity);
+                }
+            }
+        }
+    }
+
+    /// Returns the participant having the identity on the platform. If several participants
+    /// have it, returns the first one; see `IdentityMap::conflicts`.
+    pub fn participant(&self, platform: Platform, id: &str) -> Option<&ParticipantId> {
+        self.participants
+            .get(&(platform, platform.normalize(id)))
+            .and_then(|owners| owners.first())
+    }
+
+    /// Returns the identities of the participant and of the players of its lineup.
+    pub fn identities(&self, participant: &ParticipantId) -> &[Identity] {
+        self.identities
+            .get(participant)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns the identities of the participant and of the players of its lineup on the
+    /// platform.
+    pub fn identities_on(&self, participant: &ParticipantId, platform: Platform) -> Vec<&str> {
+        self.identities(participant)
+            .iter()
+            .filter(|i| i.platform == platform)
+            .map(|i| i.id.as_str())
+            .collect()
+    }
+
+    /// Returns the identities claimed by several participants, with the participants, which
+    /// usually reveal duplicate or mistyped sign-ups.
+    pub fn conflicts(&self) -> Vec<(Platform, &str, &[ParticipantId])> {
+        self.participants
+            .iter()
+            .filter(|(_, owners)| owners.len() > 1)
+            .map(|((platform, id), owners)| (*platform, id.as_str(), owners.as_slice()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity_map() {
+        let string = r#"
+        [{
+            "id": "1",
+            "name": "Evil Geniuses",
+            "custom_fields": [
+                { "type": "twitter", "label": "Twitter", "value": "@EvilGeniuses" }
+            ],
+            "lineup": [{
+                "name": "Arteezy",
+                "custom_fields": [
+                    { "type": "steam_player_id", "label": "Steam", "value": "STEAM_0:1:1234567" }
+                ],
+                "custom_fields_private": [
+                    { "type": "text", "label": "Discord ID", "value": "Arteezy#0001" }
+                ]
+            }]
+        }, {
+            "id": "2",
+            "name": "Fnatic",
+            "custom_fields": [
+                { "type": "steam_player_id", "label": "Steam", "value": "76561197962734863" },
+                { "type": "text", "label": "Motto", "value": "Arteezy#0001" }
+            ]
+        }, {
+            "name": "Not registered yet",
+            "custom_fields": [
+                { "type": "twitch", "label": "Twitch", "value": "unknown" }
+            ]
+        }]"#;
+        let participants: Participants = serde_json::from_str(string).unwrap();
+        let map = IdentityMap::new(&participants);
+        let eg = ParticipantId("1".to_owned());
+
+        assert_eq!(
+            map.participant(Platform::Discord, " arteezy#0001"),
+            Some(&eg)
+        );
+        assert_eq!(
+            map.participant(Platform::Twitter, "evilgeniuses"),
+            Some(&eg)
+        );
+        assert_eq!(
+            map.participant(Platform::Steam, "STEAM_0:1:1234567"),
+            Some(&eg)
+        );
+        assert!(map.participant(Platform::Twitch, "unknown").is_none());
+
+        assert_eq!(map.identities(&eg).len(), 3);
+        assert_eq!(
+            map.identities_on(&eg, Platform::Steam),
+            vec!["76561197962734863"]
+        );
+        assert_eq!(map.identities(&eg)[1].player.as_deref(), Some("Arteezy"));
+
+        let conflicts = map.conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].0, Platform::Steam);
+        assert_eq!(conflicts[0].2.len(), 2);
+    }
+
+    #[test]
+    fn test_identity_map_oversized_steam_id() {
+        let string = r#"
+        [{
+            "id": "1",
+            "name": "Evil Geniuses",
+            "custom_fields": [
+                { "type": "steam_player_id", "label": "Steam", "value": "STEAM_0:1:9223372036854775807" }
+            ]
+        }]"#;
+        let participants: Participants = serde_json::from_str(string).unwrap();
+        let map = IdentityMap::new(&participants);
+        let eg = ParticipantId("1".to_owned());
+
+        assert!(map.identities(&eg).is_empty());
+        assert!(map
+            .participant(Platform::Steam, "STEAM_0:1:9223372036854775807")
+            .is_none());
+        assert!(map
+            .participant(Platform::Steam, "STEAM_0:1:99999999999999999999")
+            .is_none());
+    }
+}
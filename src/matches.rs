@@ -1,16 +1,70 @@
-use chrono::{DateTime, FixedOffset};
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone};
+use std::collections::{BTreeMap, HashMap};
 
+use crate::common::MatchResultSimple;
 use crate::disciplines::DisciplineId;
+use crate::fingerprint::{fingerprint, Fingerprint};
 use crate::games::Games;
-use crate::opponents::Opponents;
+use crate::opponents::{Opponent, Opponents};
+use crate::participants::ParticipantId;
+use crate::stages::StageType;
 use crate::tournaments::TournamentId;
+use crate::Result;
 
 /// Match unique identificator.
 #[derive(
-    Clone, Default, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
+    Clone,
+    Default,
+    Debug,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    serde::Serialize,
+    serde::Deserialize,
 )]
 pub struct MatchId(pub String);
 
+/// A reference to a match of a tournament: the match identifiers are only unique within their
+/// tournament, so the two are kept together.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
+pub struct MatchRef {
+    /// The tournament of the match.
+    pub tournament_id: TournamentId,
+    /// The match.
+    pub match_id: MatchId,
+}
+impl MatchRef {
+    /// Creates new reference to the match of the tournament.
+    pub fn new(tournament_id: TournamentId, match_id: MatchId) -> MatchRef {
+        MatchRef {
+            tournament_id,
+            match_id,
+        }
+    }
+}
+impl From<&Match> for MatchRef {
+    fn from(m: &Match) -> MatchRef {
+        MatchRef::new(m.tournament_id.clone(), m.id.clone())
+    }
+}
+impl From<Match> for MatchRef {
+    fn from(m: Match) -> MatchRef {
+        MatchRef::new(m.tournament_id, m.id)
+    }
+}
+impl ::std::fmt::Display for MatchRef {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(
+            f,
+            "match {} of tournament {}",
+            self.match_id.0, self.tournament_id.0
+        )
+    }
+}
+
 /// A match type enumeration.
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
 pub enum MatchType {
@@ -63,8 +117,23 @@ pub enum MatchFormat {
     BestOf11,
 }
 
+/// A branch of an elimination bracket.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BracketBranch {
+    /// The winner bracket (or the only bracket of a single-elimination stage)
+    Winners,
+    /// The loser bracket of a double-elimination stage
+    Losers,
+    /// The grand final of a double-elimination stage
+    GrandFinal,
+    /// The second grand final, played when the loser bracket finalist wins the first one
+    GrandFinalReset,
+}
+
 /// Tournament or discipline match definition.
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct Match {
     /// A hexadecimal unique identifier for this match.
     /// Example: "5617bb3af3df95f2318b4567"
@@ -98,11 +167,28 @@ pub struct Match {
     /// Date of this match, either expected or actual. This value is represented as an ISO 8601 date containing the date, the time and the time zone.
     /// Example: "2015-09-06T00:10:00-0600"
     pub date: DateTime<FixedOffset>,
+    /// Time zone of the match. This value is represented using the IANA tz database.
+    /// Example: "America/Chicago"
+    #[serde(rename = "timezone")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_zone: Option<String>,
+    /// Format of the match.
+    /// Possible values: none, one, home_away, bo3, bo5, bo7, bo9, bo11
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_format: Option<MatchFormat>,
     /// List of the opponents involved in this match.
     pub opponents: Opponents,
     /// This property is added when the parameter "with_games" is enabled.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub games: Option<Games>,
+    /// The branch of the bracket of this match. Only available on elimination stages.
+    /// Possible values: winners, losers, grand_final, grand_final_reset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<BracketBranch>,
+    /// Position of this match within its round of the bracket.
+    /// Example: 1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<u64>,
 }
 impl Match {
     builder!(id, MatchId);
@@ -115,9 +201,33 @@ impl Match {
     builder!(group_number, u64);
     builder!(round_number, u64);
     builder!(date, DateTime<FixedOffset>);
+    builder!(branch, Option<BracketBranch>);
+    builder!(position, Option<u64>);
+    builder!(time_zone, Option<String>);
+    builder!(match_format, Option<MatchFormat>);
 }
 
 impl Match {
+    /// Returns the fingerprint of the match, which changes with any of its fields. See
+    /// `Fingerprint`. Fails only if the serialization fails.
+    pub fn fingerprint(&self) -> Result<Fingerprint> {
+        fingerprint(self)
+    }
+
+    /// Returns the reference to this match, to pass it to the methods accepting a `MatchRef`.
+    pub fn match_ref(&self) -> MatchRef {
+        MatchRef::from(self)
+    }
+
+    /// Sets the match of the games of the match, if they were fetched.
+    #[cfg(any(feature = "client-blocking", feature = "client-async"))]
+    pub(crate) fn link_games(mut self) -> Match {
+        let m = self.match_ref();
+        self.games = self.games.map(|g| g.of_match(&m));
+        self
+    }
+
+    #[cfg(feature = "client-blocking")]
     /// Returns iter for the tournament match
     pub fn iter_tournament<'a>(
         &self,
@@ -131,6 +241,7 @@ impl Match {
         )
     }
 
+    #[cfg(feature = "client-blocking")]
     /// Converts into iter for the tournament match
     pub fn into_iter_tournament(
         self,
@@ -139,6 +250,7 @@ impl Match {
         crate::TournamentMatchIter::new(client, self.tournament_id, self.id, self.games.is_some())
     }
 
+    #[cfg(feature = "client-blocking")]
     /// Returns iter for the discipline matches
     pub fn iter_discipline<'a>(
         &self,
@@ -147,6 +259,7 @@ impl Match {
         crate::DisciplineMatchesIter::new(client, self.discipline_id.clone())
     }
 
+    #[cfg(feature = "client-blocking")]
     /// Converts into iter for the discipline matches
     pub fn into_iter_discipline(
         self,
@@ -156,26 +269,302 @@ impl Match {
     }
 }
 
+impl Match {
+    /// Returns the bracket branch of the match in a stage of the type. If the service has not
+    /// set the branch, it is inferred from the group and round numbers: in double-elimination
+    /// stages the group 1 is the winner bracket, the group 2 is the loser bracket and the
+    /// group 3 holds the grand final and, as its second round, the grand final reset.
+    pub fn bracket_branch(&self, stage_type: &StageType) -> Option<BracketBranch> {
+        if self.branch.is_some() {
+            return self.branch.clone();
+        }
+        match (stage_type, self.group_number, self.round_number) {
+            (StageType::SingleElimination, _, _) => Some(BracketBranch::Winners),
+            (StageType::DoubleElimination, 1, _) => Some(BracketBranch::Winners),
+            (StageType::DoubleElimination, 2, _) => Some(BracketBranch::Losers),
+            (StageType::DoubleElimination, 3, 1) => Some(BracketBranch::GrandFinal),
+            (StageType::DoubleElimination, 3, _) => Some(BracketBranch::GrandFinalReset),
+            _ => None,
+        }
+    }
+
+    /// Returns the date of the match in the time zone, for example `chrono::Local` or a
+    /// `chrono_tz::Tz` of the viewer.
+    pub fn local_start<Tz: TimeZone>(&self, tz: &Tz) -> DateTime<Tz> {
+        self.date.with_timezone(tz)
+    }
+
+    /// Returns the winner of the match. Only available on "duel" matches.
+    pub fn winner(&self) -> Option<&Opponent> {
+        match self.match_type {
+            MatchType::Duel => self.opponents.winner(),
+            MatchType::FreeForAll => None,
+        }
+    }
+
+    /// Returns the loser of the match. Only available on "duel" matches.
+    pub fn loser(&self) -> Option<&Opponent> {
+        match self.match_type {
+            MatchType::Duel => self.opponents.loser(),
+            MatchType::FreeForAll => None,
+        }
+    }
+
+    /// Returns the scores of the opponents ordered by their numbers, like "2 - 1".
+    pub fn score_summary(&self) -> Option<String> {
+        self.opponents.score_summary()
+    }
+
+    /// Returns the opponents ordered by their rank. Only available on "ffa" matches.
+    pub fn rankings(&self) -> Vec<&Opponent> {
+        match self.match_type {
+            MatchType::Duel => Vec::new(),
+            MatchType::FreeForAll => self.opponents.rankings(),
+        }
+    }
+
+    /// Returns the result of the match.
+    pub fn result(&self) -> MatchResult {
+        MatchResult {
+            status: self.status.clone(),
+            opponents: self.opponents.clone(),
+        }
+    }
+
+    /// Returns the score line of the match, with the scores of its games if it was fetched
+    /// with them. See `MatchResult::score_line_with`.
+    pub fn score_line(&self) -> Option<ScoreLine> {
+        score_line(&self.opponents, self.games.as_ref())
+    }
+}
+
+/// A partial update of a match: only the set fields are changed. See
+/// `Toornament::update_matches`.
+#[derive(
+    Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
+)]
+pub struct MatchPatch {
+    /// New status of the match.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<MatchStatus>,
+    /// New date of the match.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<DateTime<FixedOffset>>,
+    /// New results, scores and forfeits of the opponents.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opponents: Option<Opponents>,
+}
+impl MatchPatch {
+    builder_o!(status, MatchStatus);
+    builder_o!(date, DateTime<FixedOffset>);
+    builder_o!(opponents, Opponents);
+
+    /// Returns the patch restoring the fields of the match which may be changed by a patch.
+    pub fn restoring(m: &Match) -> MatchPatch {
+        MatchPatch {
+            status: Some(m.status.clone()),
+            date: Some(m.date),
+            opponents: Some(m.opponents.clone()),
+        }
+    }
+}
+
+impl From<MatchResult> for MatchPatch {
+    fn from(result: MatchResult) -> MatchPatch {
+        MatchPatch {
+            status: Some(result.status),
+            date: None,
+            opponents: Some(result.opponents),
+        }
+    }
+}
+
 /// A list of `Match` objects.
 #[derive(
     Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
 )]
 pub struct Matches(pub Vec<Match>);
+list_wrapper!(Matches, Match);
+impl Matches {
+    /// Returns the earliest pending match scheduled after the `now` moment.
+    pub fn next_upcoming(&self, now: DateTime<FixedOffset>) -> Option<&Match> {
+        self.0
+            .iter()
+            .filter(|m| m.status == MatchStatus::Pending && m.date > now)
+            .min_by_key(|m| m.date)
+    }
+
+    /// Returns the match with the identifier.
+    pub fn by_id(&self, id: &MatchId) -> Option<&Match> {
+        self.0.iter().find(|m| &m.id == id)
+    }
+
+    /// Converts the list into a map of the matches by their identifiers, to join them with
+    /// other data.
+    pub fn into_map_by_id(self) -> HashMap<MatchId, Match> {
+        self.0.into_iter().map(|m| (m.id.clone(), m)).collect()
+    }
+
+    /// Converts the list into a map of the matches ordered by their identifiers.
+    pub fn into_btree_map_by_id(self) -> BTreeMap<MatchId, Match> {
+        self.0.into_iter().map(|m| (m.id.clone(), m)).collect()
+    }
+
+    /// Returns the matches where the participant is one of the opponents.
+    pub fn by_participant(&self, id: &ParticipantId) -> Vec<&Match> {
+        self.0
+            .iter()
+            .filter(|m| {
+                m.opponents
+                    .0
+                    .iter()
+                    .filter_map(|o| o.participant.as_ref())
+                    .any(|p| p.id.as_ref() == Some(id))
+            })
+            .collect()
+    }
+
+    /// Returns the matches of the round with the specified number. Note that round numbers
+    /// are only unique within a stage group, so filter the matches by stage and group first
+    /// if the list contains several of them.
+    pub fn by_round(&self, round: u64) -> Vec<&Match> {
+        self.0.iter().filter(|m| m.round_number == round).collect()
+    }
+
+    /// Groups the matches by their day in the time zone, for example `chrono::Local` or a
+    /// `chrono_tz::Tz` of the viewer. The matches of a day are ordered by their dates.
+    pub fn grouped_by_local_day<Tz: TimeZone>(&self, tz: &Tz) -> BTreeMap<NaiveDate, Vec<&Match>> {
+        let mut days = BTreeMap::new();
+        for m in &self.0 {
+            days.entry(m.local_start(tz).date_naive())
+                .or_insert_with(Vec::new)
+                .push(m);
+        }
+        for matches in days.values_mut() {
+            matches.sort_by_key(|m: &&Match| m.date);
+        }
+        days
+    }
+
+    /// Groups the matches by their round number.
+    pub fn group_by_round(&self) -> BTreeMap<u64, Vec<&Match>> {
+        let mut rounds = BTreeMap::new();
+        for m in &self.0 {
+            rounds
+                .entry(m.round_number)
+                .or_insert_with(Vec::new)
+                .push(m);
+        }
+        rounds
+    }
+}
 
 /// Result of a match
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct MatchResult {
     /// Status of a match
     pub status: MatchStatus,
     /// Opponents in a match
     pub opponents: Opponents,
 }
+impl MatchResult {
+    /// Returns the score line of the match: the scores of the opponents. Returns `None` if
+    /// there is no score to show.
+    pub fn score_line(&self) -> Option<ScoreLine> {
+        score_line(&self.opponents, None)
+    }
+
+    /// Returns the score line of the match with the scores of each of the `games` which has
+    /// them, like the games of a match fetched with them. The scores of the match are the
+    /// numbers of games won by the opponents if the service gives none. Returns `None` if there
+    /// is no score to show.
+    pub fn score_line_with(&self, games: &Games) -> Option<ScoreLine> {
+        score_line(&self.opponents, Some(games))
+    }
+}
+
+/// Returns the score line of the opponents of a match and of its games.
+fn score_line(opponents: &Opponents, games: Option<&Games>) -> Option<ScoreLine> {
+    let all = || games.iter().flat_map(|games| games.0.iter());
+    let lines = all()
+        .filter_map(|game| Some((game.number, scores(&game.opponents)?)))
+        .collect::<BTreeMap<_, _>>()
+        .into_values()
+        .collect::<Vec<_>>();
+    let total = match scores(opponents) {
+        Some(total) => total,
+        None if !lines.is_empty() => {
+            let mut numbers = opponents.0.iter().map(|o| o.number).collect::<Vec<_>>();
+            numbers.sort_unstable();
+            numbers
+                .iter()
+                .map(|number| {
+                    all()
+                        .flat_map(|game| game.opponents.0.iter())
+                        .filter(|o| o.number == *number)
+                        .filter(|o| o.result == Some(MatchResultSimple::Win))
+                        .count() as i64
+                })
+                .collect()
+        }
+        None => return None,
+    };
+    Some(ScoreLine {
+        total,
+        games: lines,
+    })
+}
+
+/// Returns the scores of the opponents ordered by their numbers, if they all have one.
+fn scores(opponents: &Opponents) -> Option<Vec<i64>> {
+    let mut opponents = opponents.0.iter().collect::<Vec<&Opponent>>();
+    if opponents.is_empty() {
+        return None;
+    }
+    opponents.sort_by_key(|o| o.number);
+    opponents.iter().map(|o| o.score).collect()
+}
+
+/// The scores of a match and of its games, each ordered by the numbers of the opponents. It
+/// is shown like "2 - 1 (16-14, 12-16, 16-9)".
+#[derive(
+    Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
+)]
+pub struct ScoreLine {
+    /// The scores of the match.
+    pub total: Vec<i64>,
+    /// The scores of the games, ordered by their numbers.
+    pub games: Vec<Vec<i64>>,
+}
+impl ::std::fmt::Display for ScoreLine {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let join = |scores: &[i64], separator: &str| {
+            scores
+                .iter()
+                .map(i64::to_string)
+                .collect::<Vec<_>>()
+                .join(separator)
+        };
+        fmt.write_str(&join(&self.total, " - "))?;
+        if !self.games.is_empty() {
+            let games = self
+                .games
+                .iter()
+                .map(|g| join(g, "-"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            write!(fmt, " ({})", games)?;
+        }
+        Ok(())
+    }
+}
 
 #[cfg(test)]
 mod tests {
     #[test]
     fn test_match_parse() {
-        use crate::matches::{Match, MatchStatus, MatchType};
+        use crate::matches::{BracketBranch, Match, MatchFormat, MatchStatus, MatchType};
         let string = r#"
         {
             "id": "5617bb3af3df95f2318b4567",
@@ -190,6 +579,8 @@ mod tests {
             "date": "2015-09-06T00:10:00-0600",
             "timezone": "America\/Chicago",
             "match_format": "bo3",
+            "branch": "grand_final_reset",
+            "position": 1,
             "opponents": [
                 {
                     "number": 1,
@@ -215,6 +606,95 @@ mod tests {
         assert_eq!(d.stage_number, 1u64);
         assert_eq!(d.group_number, 2u64);
         assert_eq!(d.round_number, 3u64);
+        assert_eq!(d.branch, Some(BracketBranch::GrandFinalReset));
+        assert_eq!(d.position, Some(1));
+        assert_eq!(d.time_zone.as_deref(), Some("America/Chicago"));
+        assert_eq!(d.match_format, Some(MatchFormat::BestOf3));
+        assert_eq!(
+            d.match_ref().to_string(),
+            "match 5617bb3af3df95f2318b4567 of tournament 5608fd12140ba061298b4569"
+        );
+
+        #[cfg(any(feature = "client-blocking", feature = "client-async"))]
+        {
+            let games: crate::Games =
+                serde_json::from_str(r#"[{ "number": 1, "status": "pending", "opponents": [] }]"#)
+                    .unwrap();
+            let mut linked = d.clone();
+            linked.games = Some(games);
+            let linked = linked.link_games();
+            assert_eq!(linked.games.unwrap().0[0].match_ref, Some(d.match_ref()));
+        }
+    }
+
+    #[test]
+    fn test_match_unknown_field() {
+        use crate::matches::Match;
+        let mut value = serde_json::json!({
+            "id": "5617bb3af3df95f2318b4567",
+            "type": "duel",
+            "discipline": "my_discipline",
+            "status": "pending",
+            "tournament_id": "5608fd12140ba061298b4569",
+            "number": 1,
+            "stage_number": 1,
+            "group_number": 1,
+            "round_number": 1,
+            "date": "2015-09-06T00:10:00-06:00",
+            "opponents": []
+        });
+        assert!(serde_json::from_value::<Match>(value.clone()).is_ok());
+        value["replay_url"] = "https://example.com/replay".into();
+        let parsed = serde_json::from_value::<Match>(value);
+        #[cfg(feature = "strict-serde")]
+        assert!(parsed
+            .unwrap_err()
+            .to_string()
+            .contains("unknown field `replay_url`"));
+        #[cfg(not(feature = "strict-serde"))]
+        assert!(parsed.is_ok());
+    }
+
+    #[test]
+    fn test_match_bracket_branch() {
+        use crate::matches::{BracketBranch, Match};
+        use crate::stages::StageType;
+        let string = r#"
+        {
+            "id": "5617bb3af3df95f2318b4567",
+            "type": "duel",
+            "discipline": "my_discipline",
+            "status": "pending",
+            "tournament_id": "5608fd12140ba061298b4569",
+            "number": 1,
+            "stage_number": 1,
+            "group_number": 3,
+            "round_number": 2,
+            "date": "2015-09-06T00:10:00-0600",
+            "opponents": []
+        }"#;
+        let m: Match = serde_json::from_str(string).unwrap();
+        assert!(m.branch.is_none());
+        assert_eq!(
+            m.bracket_branch(&StageType::DoubleElimination),
+            Some(BracketBranch::GrandFinalReset)
+        );
+        assert_eq!(
+            m.clone()
+                .round_number(1)
+                .bracket_branch(&StageType::DoubleElimination),
+            Some(BracketBranch::GrandFinal)
+        );
+        assert_eq!(
+            m.bracket_branch(&StageType::SingleElimination),
+            Some(BracketBranch::Winners)
+        );
+        assert!(m.bracket_branch(&StageType::Swiss).is_none());
+        let m = m.branch(Some(BracketBranch::Losers));
+        assert_eq!(
+            m.bracket_branch(&StageType::DoubleElimination),
+            Some(BracketBranch::Losers)
+        );
     }
 
     #[test]
@@ -236,10 +716,306 @@ mod tests {
         let r: MatchResult = serde_json::from_str(string).unwrap();
 
         assert_eq!(r.status, MatchStatus::Pending);
-        let op = r.opponents.0.get(0).unwrap();
+        let op = r.opponents.0.first().unwrap();
         assert_eq!(op.number, 1);
         assert_eq!(op.result, Some(MatchResultSimple::Win));
         assert_eq!(op.score, None);
         assert!(!op.forfeit);
     }
+
+    #[test]
+    fn test_match_score_line() {
+        use crate::matches::{Match, MatchResult};
+
+        let duel = |a: &str, b: &str| {
+            format!(
+                r#"[{{ "number": 1, "forfeit": false{} }}, {{ "number": 2, "forfeit": false{} }}]"#,
+                a, b
+            )
+        };
+        let game = |number: i64, a: i64, b: i64| {
+            let (ra, rb) = if a > b { (1, 3) } else { (3, 1) };
+            format!(
+                r#"{{ "number": {}, "status": "completed", "opponents": {} }}"#,
+                number,
+                duel(
+                    &format!(r#", "score": {}, "result": {}"#, a, ra),
+                    &format!(r#", "score": {}, "result": {}"#, b, rb)
+                )
+            )
+        };
+        let string = format!(
+            r#"{{
+                "id": "1", "type": "duel", "discipline": "my_discipline",
+                "status": "completed", "tournament_id": "1", "number": 1,
+                "stage_number": 1, "group_number": 1, "round_number": 1,
+                "date": "2015-09-06T00:10:00-0600",
+                "opponents": {},
+                "games": [{}, {}, {}, {{ "number": 4, "status": "pending", "opponents": {} }}]
+            }}"#,
+            duel("", ""),
+            game(3, 16, 9),
+            game(1, 16, 14),
+            game(2, 12, 16),
+            duel("", "")
+        );
+        let m: Match = serde_json::from_str(&string).unwrap();
+        let line = m.score_line().unwrap();
+        assert_eq!(line.total, vec![2, 1]);
+        assert_eq!(line.to_string(), "2 - 1 (16-14, 12-16, 16-9)");
+        let result = m.result();
+        assert_eq!(
+            result.score_line_with(m.games.as_ref().unwrap()),
+            Some(line)
+        );
+
+        // The scores of the match given by the service are kept.
+        let mut scored = result.clone();
+        scored.opponents.0[0].score = Some(3);
+        scored.opponents.0[1].score = Some(0);
+        assert_eq!(
+            scored
+                .score_line_with(m.games.as_ref().unwrap())
+                .unwrap()
+                .total,
+            vec![3, 0]
+        );
+        assert_eq!(scored.score_line().unwrap().games, Vec::<Vec<i64>>::new());
+
+        let json = serde_json::to_value(&result).unwrap();
+        let bare: MatchResult = serde_json::from_value(json).unwrap();
+        assert!(bare.score_line().is_none());
+    }
+
+    #[test]
+    fn test_matches_scheduling_helpers() {
+        use crate::matches::{Match, MatchStatus, Matches};
+        use crate::opponents::{Opponent, Opponents};
+        use crate::participants::{Participant, ParticipantId};
+        use chrono::DateTime;
+
+        let string = r#"
+        {
+            "id": "1",
+            "type": "duel",
+            "discipline": "my_discipline",
+            "status": "pending",
+            "tournament_id": "5608fd12140ba061298b4569",
+            "number": 1,
+            "stage_number": 1,
+            "group_number": 1,
+            "round_number": 1,
+            "date": "2015-09-06T00:10:00-0600",
+            "opponents": []
+        }"#;
+        let m: Match = serde_json::from_str(string).unwrap();
+        let date = |s| DateTime::parse_from_rfc3339(s).unwrap();
+        let matches = Matches::from(vec![
+            m.clone()
+                .status(MatchStatus::Completed)
+                .date(date("2015-09-06T10:00:00+00:00")),
+            m.clone()
+                .id(crate::MatchId("2".to_owned()))
+                .round_number(2)
+                .date(date("2015-09-06T14:00:00+00:00")),
+            m.clone()
+                .id(crate::MatchId("3".to_owned()))
+                .round_number(2)
+                .date(date("2015-09-06T12:00:00+00:00")),
+        ]);
+
+        assert_eq!(
+            matches.0[1].fingerprint().unwrap(),
+            matches.0[1].clone().fingerprint().unwrap()
+        );
+        assert_ne!(
+            matches.0[1].fingerprint().unwrap(),
+            matches.0[2].fingerprint().unwrap()
+        );
+
+        let next = matches.next_upcoming(date("2015-09-06T11:00:00+00:00"));
+        assert_eq!(next.unwrap().id.0, "3");
+        let next = matches.next_upcoming(date("2015-09-06T13:00:00+00:00"));
+        assert_eq!(next.unwrap().id.0, "2");
+        assert!(matches
+            .next_upcoming(date("2015-09-06T15:00:00+00:00"))
+            .is_none());
+
+        assert_eq!(matches.by_round(2).len(), 2);
+        assert!(matches.by_round(3).is_empty());
+        assert_eq!(
+            matches
+                .by_id(&crate::MatchId("3".to_owned()))
+                .unwrap()
+                .round_number,
+            2
+        );
+        assert!(matches.by_id(&crate::MatchId("4".to_owned())).is_none());
+        let rounds = matches.group_by_round();
+        assert_eq!(rounds.len(), 2);
+        assert_eq!(rounds[&1].len(), 1);
+        assert_eq!(rounds[&2].len(), 2);
+
+        let mut matches = matches;
+        let participant =
+            Participant::create("Evil Geniuses").id(ParticipantId("378426939508809728".to_owned()));
+        matches.0[1].opponents = Opponents(vec![Opponent {
+            number: 1,
+            participant: Some(participant),
+            ..Default::default()
+        }]);
+        let played = matches.by_participant(&ParticipantId("378426939508809728".to_owned()));
+        assert_eq!(played.len(), 1);
+        assert_eq!(played[0].id.0, "2");
+        assert!(matches
+            .by_participant(&ParticipantId("1".to_owned()))
+            .is_empty());
+
+        let by_id = matches.clone().into_map_by_id();
+        assert_eq!(by_id.len(), 3);
+        assert_eq!(by_id[&crate::MatchId("2".to_owned())].round_number, 2);
+        let ordered = matches.into_btree_map_by_id();
+        assert_eq!(
+            ordered.keys().map(|id| id.0.as_str()).collect::<Vec<_>>(),
+            vec!["1", "2", "3"]
+        );
+    }
+
+    #[test]
+    fn test_matches_local_days() {
+        use crate::matches::{Match, MatchId, Matches};
+        use chrono::{DateTime, FixedOffset, NaiveDate, Timelike};
+
+        let string = r#"{
+            "id": "1",
+            "type": "duel",
+            "discipline": "counterstrike_go",
+            "status": "pending",
+            "tournament_id": "5608fd12140ba061298b4569",
+            "number": 1,
+            "stage_number": 1,
+            "group_number": 1,
+            "round_number": 1,
+            "date": "2015-09-06T00:10:00-0600",
+            "opponents": []
+        }"#;
+        let m: Match = serde_json::from_str(string).unwrap();
+        let date = |s| DateTime::parse_from_rfc3339(s).unwrap();
+        let matches = Matches::from(vec![
+            m.clone().date(date("2015-09-06T22:00:00+00:00")),
+            m.clone()
+                .id(MatchId("2".to_owned()))
+                .date(date("2015-09-06T20:00:00+00:00")),
+        ]);
+        let day = |d| NaiveDate::from_ymd_opt(2015, 9, d).unwrap();
+        let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+        assert_eq!(matches.0[0].local_start(&tokyo).hour(), 7);
+
+        let days = matches.grouped_by_local_day(&chrono::Utc);
+        assert_eq!(days.keys().cloned().collect::<Vec<_>>(), vec![day(6)]);
+        assert_eq!(days[&day(6)][0].id.0, "2");
+        let days = matches.grouped_by_local_day(&tokyo);
+        assert_eq!(days.keys().cloned().collect::<Vec<_>>(), vec![day(7)]);
+        let moscow = FixedOffset::east_opt(3 * 3600).unwrap();
+        let days = matches.grouped_by_local_day(&moscow);
+        assert_eq!(
+            days.keys().cloned().collect::<Vec<_>>(),
+            vec![day(6), day(7)]
+        );
+    }
+
+    #[test]
+    fn test_match_result_helpers() {
+        use crate::common::MatchResultSimple;
+        use crate::matches::{Match, MatchType};
+        use crate::opponents::{Opponent, Opponents};
+
+        let string = r#"
+        {
+            "id": "1",
+            "type": "duel",
+            "discipline": "my_discipline",
+            "status": "completed",
+            "tournament_id": "5608fd12140ba061298b4569",
+            "number": 1,
+            "stage_number": 1,
+            "group_number": 1,
+            "round_number": 1,
+            "date": "2015-09-06T00:10:00-0600",
+            "opponents": [
+                { "number": 1, "result": 3, "score": 1, "forfeit": false },
+                { "number": 2, "result": 1, "score": 2, "forfeit": false }
+            ]
+        }"#;
+        let m: Match = serde_json::from_str(string).unwrap();
+        assert_eq!(m.winner().unwrap().number, 2);
+        assert_eq!(m.loser().unwrap().number, 1);
+        assert_eq!(m.score_summary(), Some("1 - 2".to_owned()));
+        assert!(m.rankings().is_empty());
+
+        let forfeited = Opponents(vec![
+            Opponent {
+                number: 1,
+                forfeit: true,
+                ..Default::default()
+            },
+            Opponent {
+                number: 2,
+                score: Some(0),
+                ..Default::default()
+            },
+        ]);
+        let m = Match {
+            opponents: forfeited,
+            ..m
+        };
+        assert_eq!(m.winner().unwrap().number, 2);
+        assert_eq!(m.loser().unwrap().number, 1);
+        assert_eq!(m.score_summary(), Some("F - 0".to_owned()));
+
+        let draw = Opponents(vec![
+            Opponent {
+                number: 1,
+                result: Some(MatchResultSimple::Draw),
+                ..Default::default()
+            },
+            Opponent {
+                number: 2,
+                result: Some(MatchResultSimple::Draw),
+                ..Default::default()
+            },
+        ]);
+        let m = Match {
+            opponents: draw,
+            ..m
+        };
+        assert!(m.winner().is_none());
+        assert!(m.loser().is_none());
+        assert!(m.score_summary().is_none());
+
+        let ffa = Opponents(vec![
+            Opponent {
+                number: 1,
+                rank: Some(3),
+                ..Default::default()
+            },
+            Opponent {
+                number: 2,
+                ..Default::default()
+            },
+            Opponent {
+                number: 3,
+                rank: Some(1),
+                ..Default::default()
+            },
+        ]);
+        let m = Match {
+            match_type: MatchType::FreeForAll,
+            opponents: ffa,
+            ..m
+        };
+        assert!(m.winner().is_none());
+        let ranks = m.rankings().iter().map(|o| o.number).collect::<Vec<i64>>();
+        assert_eq!(ranks, vec![3, 1, 2]);
+    }
 }
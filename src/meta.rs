@@ -0,0 +1,98 @@
+use std::cell::{Cell, RefCell};
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+
+use crate::ContentRange;
+
+/// A value returned by the service with the details of the response.
+/// See `Toornament::with_meta`.
+#[derive(Clone, Debug)]
+pub struct WithMeta<T> {
+    /// The value.
+    pub value: T,
+    /// Status of the response.
+    pub status: StatusCode,
+    /// Headers of the response.
+    pub headers: HeaderMap,
+    /// Time taken by the request, including the retries.
+    pub duration: Duration,
+}
+impl<T> WithMeta<T> {
+    /// Returns the value of a header if it is a valid string.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|v| v.to_str().ok())
+    }
+
+    /// Returns the range of the items of a paginated collection.
+    pub fn content_range(&self) -> Option<ContentRange> {
+        self.header("content-range").and_then(|v| v.parse().ok())
+    }
+
+    /// Returns the identifier of the request, if the service returned one.
+    pub fn request_id(&self) -> Option<&str> {
+        self.header("x-request-id")
+    }
+
+    /// Returns the value, dropping the details of the response.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+}
+
+type Meta = (StatusCode, HeaderMap, Duration);
+
+thread_local! {
+    static RECORDING: Cell<bool> = const { Cell::new(false) };
+    static RECORDED: RefCell<Option<Meta>> = const { RefCell::new(None) };
+}
+
+/// Starts recording the details of the responses received by the current thread.
+/// Returns whether the recording was already started.
+pub(crate) fn start() -> bool {
+    RECORDED.with(|r| r.borrow_mut().take());
+    RECORDING.with(|r| r.replace(true))
+}
+
+/// Stops recording and returns the details of the last response.
+pub(crate) fn finish(was_recording: bool) -> Option<Meta> {
+    RECORDING.with(|r| r.set(was_recording));
+    RECORDED.with(|r| r.borrow_mut().take())
+}
+
+#[cfg(feature = "client-blocking")]
+/// Records the details of a response if the recording is started.
+pub(crate) fn record(response: &reqwest::blocking::Response, duration: Duration) {
+    if RECORDING.with(|r| r.get()) {
+        RECORDED.with(|r| {
+            *r.borrow_mut() = Some((response.status(), response.headers().clone(), duration))
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_with_meta_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("content-range", HeaderValue::from_static("matches 0-19/45"));
+        headers.insert("x-request-id", HeaderValue::from_static("abc"));
+        let meta = WithMeta {
+            value: 1,
+            status: StatusCode::PARTIAL_CONTENT,
+            headers,
+            duration: Duration::from_millis(10),
+        };
+        assert_eq!(meta.request_id(), Some("abc"));
+        assert_eq!(meta.content_range().unwrap().total, Some(45));
+        assert_eq!(meta.into_value(), 1);
+
+        let was_recording = start();
+        assert!(!was_recording);
+        assert!(finish(was_recording).is_none());
+    }
+}
@@ -0,0 +1,272 @@
+//! This module collects the statistics of a client and encodes them in the Prometheus text
+//! exposition format, to be served by the embedding service on its metrics endpoint.
+//!
+//! The metrics are shared by the clones of a `Toornament` object:
+//!
+//! - `toornament_requests_total{endpoint, method, status}` - the requests sent, by the route
+//!   of the endpoint, the method and the status of the response (`error` if none was received);
+//! - `toornament_retries_total` - the requests sent again according to the retry policy;
+//! - `toornament_rate_limit_waits_total` and `toornament_rate_limit_wait_seconds_total` - the
+//!   retries after a `429 Too Many Requests` response and the time spent waiting for them;
+//! - `toornament_token_refreshes_total` - the access tokens obtained from the service;
+//! - `toornament_new_connection_requests_total` and
+//!   `toornament_reused_connection_requests_total` - the requests sent on a connection opened
+//!   for them and on a connection kept open by a previous request, to diagnose the connection
+//!   pool (see `Toornament::connection_pool`). A connection is known by its addresses, so one
+//!   opened on the local port of a closed one is counted as reused;
+//! - `toornament_circuit_breaker_opened_total` - the times the circuit breaker opened;
+//! - `toornament_circuit_breaker_state` - the state of the circuit breaker: 0 if closed, 1 if
+//!   open and 2 if half-open, only if the client has one.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use toornament::*;
+//!
+//! let toornament = Toornament::with_application("API_TOKEN",
+//!                                               "CLIENT_ID",
+//!                                               "CLIENT_SECRET").unwrap();
+//! let _ = toornament.disciplines(None);
+//! println!("{}", toornament.metrics_snapshot().to_prometheus());
+//! ```
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::BreakerState;
+
+/// The statistics of a client at some moment. See `Toornament::metrics_snapshot`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MetricsSnapshot {
+    /// Number of the requests by the route of the endpoint, the method and the status of the
+    /// response, which is `error` if no response was received.
+    pub requests: BTreeMap<(String, String, String), u64>,
+    /// Number of the requests sent again according to the retry policy.
+    pub retries: u64,
+    /// Number of the retries after a `429 Too Many Requests` response.
+    pub rate_limit_waits: u64,
+    /// Time spent waiting before the retries after a `429 Too Many Requests` response.
+    pub rate_limit_wait_time: Duration,
+    /// Number of the access tokens obtained from the service.
+    pub token_refreshes: u64,
+    /// Number of the times the circuit breaker opened.
+    pub circuit_breaker_opened: u64,
+    /// Number of the requests sent on a new connection.
+    pub new_connection_requests: u64,
+    /// Number of the requests sent on a connection opened by a previous request.
+    pub reused_connection_requests: u64,
+    /// The state of the circuit breaker, if the client has one.
+    pub circuit_breaker: Option<BreakerState>,
+}
+impl MetricsSnapshot {
+    /// Returns the total number of the requests sent.
+    pub fn total_requests(&self) -> u64 {
+        self.requests.values().sum()
+    }
+
+    /// Encodes the statistics in the Prometheus text exposition format.
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "# HELP toornament_requests_total Requests sent to the Toornament API."
+        );
+        let _ = writeln!(out, "# TYPE toornament_requests_total counter");
+        for ((endpoint, method, status), count) in &self.requests {
+            let _ = writeln!(
+                out,
+                "toornament_requests_total{{endpoint=\"{}\",method=\"{}\",status=\"{}\"}} {}",
+                escape(endpoint),
+                escape(method),
+                escape(status),
+                count
+            );
+        }
+        let counters = [
+            (
+                "toornament_retries_total",
+                "Requests sent again according to the retry policy.",
+                self.retries.to_string(),
+            ),
+            (
+                "toornament_rate_limit_waits_total",
+                "Retries after a rate limited response.",
+                self.rate_limit_waits.to_string(),
+            ),
+            (
+                "toornament_rate_limit_wait_seconds_total",
+                "Time spent waiting before the retries after a rate limited response.",
+                self.rate_limit_wait_time.as_secs_f64().to_string(),
+            ),
+            (
+                "toornament_token_refreshes_total",
+                "Access tokens obtained from the service.",
+                self.token_refreshes.to_string(),
+            ),
+            (
+                "toornament_circuit_breaker_opened_total",
+                "Times the circuit breaker opened.",
+                self.circuit_breaker_opened.to_string(),
+            ),
+            (
+                "toornament_new_connection_requests_total",
+                "Requests sent on a new connection.",
+                self.new_connection_requests.to_string(),
+            ),
+            (
+                "toornament_reused_connection_requests_total",
+                "Requests sent on a connection opened by a previous request.",
+                self.reused_connection_requests.to_string(),
+            ),
+        ];
+        for (name, help, value) in counters.iter() {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} counter", name);
+            let _ = writeln!(out, "{} {}", name, value);
+        }
+        if let Some(state) = self.circuit_breaker {
+            let value = match state {
+                BreakerState::Closed => 0,
+                BreakerState::Open => 1,
+                BreakerState::HalfOpen => 2,
+            };
+            let name = "toornament_circuit_breaker_state";
+            let _ = writeln!(
+                out,
+                "# HELP {} State of the circuit breaker: 0 closed, 1 open, 2 half-open.",
+                name
+            );
+            let _ = writeln!(out, "# TYPE {} gauge", name);
+            let _ = writeln!(out, "{} {}", name, value);
+        }
+        out
+    }
+}
+
+/// Escapes a label value of the Prometheus text format.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// The maximum number of the connections remembered to tell the reused ones. The oldest
+/// connections are likely closed once it is reached, so they are forgotten.
+const MAX_CONNECTIONS: usize = 1024;
+
+/// The statistics shared by the clones of a client.
+#[derive(Debug, Default)]
+pub(crate) struct Metrics {
+    snapshot: Mutex<MetricsSnapshot>,
+    /// The local and the remote addresses of the connections seen.
+    connections: Mutex<HashSet<(SocketAddr, SocketAddr)>>,
+}
+impl Metrics {
+    fn update<F: FnOnce(&mut MetricsSnapshot)>(&self, f: F) {
+        if let Ok(mut g) = self.snapshot.lock() {
+            f(&mut g);
+        }
+    }
+
+    pub(crate) fn request(&self, route: &str, method: &str, status: Option<u16>) {
+        let status = status.map_or_else(|| "error".to_owned(), |s| s.to_string());
+        self.update(|m| {
+            *m.requests
+                .entry((route.to_owned(), method.to_uppercase(), status))
+                .or_insert(0) += 1
+        });
+    }
+
+    pub(crate) fn retry(&self, rate_limited: bool, wait: Duration) {
+        self.update(|m| {
+            m.retries += 1;
+            if rate_limited {
+                m.rate_limit_waits += 1;
+                m.rate_limit_wait_time += wait;
+            }
+        });
+    }
+
+    pub(crate) fn token_refresh(&self) {
+        self.update(|m| m.token_refreshes += 1);
+    }
+
+    pub(crate) fn circuit_opened(&self) {
+        self.update(|m| m.circuit_breaker_opened += 1);
+    }
+
+    /// Records a request sent on the connection between the addresses.
+    pub(crate) fn connection(&self, local: SocketAddr, remote: SocketAddr) {
+        let reused = match self.connections.lock() {
+            Ok(mut connections) => {
+                if connections.len() >= MAX_CONNECTIONS {
+                    connections.clear();
+                }
+                !connections.insert((local, remote))
+            }
+            Err(_) => return,
+        };
+        self.update(|m| {
+            if reused {
+                m.reused_connection_requests += 1;
+            } else {
+                m.new_connection_requests += 1;
+            }
+        });
+    }
+
+    pub(crate) fn snapshot(&self) -> MetricsSnapshot {
+        self.snapshot.lock().map(|g| g.clone()).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_prometheus() {
+        let metrics = Metrics::default();
+        metrics.request("/v1/tournaments/{tournament_id}", "get", Some(200));
+        metrics.request("/v1/tournaments/{tournament_id}", "get", Some(200));
+        metrics.request("/v1/tournaments/{tournament_id}", "get", None);
+        metrics.retry(true, Duration::from_millis(1500));
+        metrics.retry(false, Duration::from_millis(500));
+        metrics.token_refresh();
+        metrics.circuit_opened();
+        let local: SocketAddr = "127.0.0.1:50000".parse().unwrap();
+        let remote: SocketAddr = "10.0.0.1:443".parse().unwrap();
+        metrics.connection(local, remote);
+        metrics.connection(local, remote);
+        metrics.connection("127.0.0.1:50001".parse().unwrap(), remote);
+
+        let mut snapshot = metrics.snapshot();
+        assert!(!snapshot
+            .to_prometheus()
+            .contains("toornament_circuit_breaker_state"));
+        snapshot.circuit_breaker = Some(BreakerState::Open);
+        assert_eq!(snapshot.total_requests(), 3);
+        let text = snapshot.to_prometheus();
+        assert!(text.contains(
+            "toornament_requests_total{endpoint=\"/v1/tournaments/{tournament_id}\",\
+             method=\"GET\",status=\"200\"} 2\n"
+        ));
+        assert!(text.contains(
+            "toornament_requests_total{endpoint=\"/v1/tournaments/{tournament_id}\",\
+             method=\"GET\",status=\"error\"} 1\n"
+        ));
+        assert!(text.contains("toornament_retries_total 2\n"));
+        assert!(text.contains("toornament_rate_limit_waits_total 1\n"));
+        assert!(text.contains("toornament_rate_limit_wait_seconds_total 1.5\n"));
+        assert!(text.contains("# TYPE toornament_token_refreshes_total counter\n"));
+        assert!(text.contains("toornament_token_refreshes_total 1\n"));
+        assert!(text.contains("toornament_circuit_breaker_opened_total 1\n"));
+        assert!(text.contains("toornament_new_connection_requests_total 2\n"));
+        assert!(text.contains("toornament_reused_connection_requests_total 1\n"));
+        assert!(text.contains("# TYPE toornament_circuit_breaker_state gauge\n"));
+        assert!(text.contains("toornament_circuit_breaker_state 1\n"));
+    }
+}
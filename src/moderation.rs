@@ -0,0 +1,195 @@
+//! This module defines the moderation of the participants a client creates or updates, to
+//! clean up the names typed by the players and reject the offensive ones before they reach
+//! the service.
+//!
+//! When a moderation is set with `Toornament::moderation`, the methods creating or updating
+//! participants pass every participant (and the players of its lineup) through it first and
+//! fail with `Error::Rejected` if it rejects one of them.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use toornament::*;
+//! use toornament::moderation::{BannedWords, Normalizer};
+//!
+//! let moderations: Vec<Box<dyn moderation::Moderation>> = vec![
+//!     Box::new(Normalizer),
+//!     Box::new(BannedWords::new(vec!["cheater", "noob"])),
+//! ];
+//! let toornament = Toornament::with_application("API_TOKEN",
+//!                                               "CLIENT_ID",
+//!                                               "CLIENT_SECRET").unwrap()
+//!                             .moderation(moderations);
+//! ```
+#[cfg(feature = "client-blocking")]
+use std::sync::Arc;
+
+use crate::participants::Participant;
+
+/// A moderation of the participants.
+pub trait Moderation: Send + Sync {
+    /// Modifies the participant before it is sent, or returns the reason to reject it.
+    fn moderate(&self, participant: &mut Participant) -> Result<(), String>;
+}
+
+/// Normalizes the names: removes the control characters, trims them, collapses the
+/// whitespace and truncates them to `Participant::NAME_MAX_LENGTH` characters. Rejects the empty names.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Normalizer;
+impl Normalizer {
+    /// Returns the normalized name.
+    pub fn normalize(name: &str) -> String {
+        name.split_whitespace()
+            .map(|word| word.chars().filter(|c| !c.is_control()).collect::<String>())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .take(Participant::NAME_MAX_LENGTH)
+            .collect::<String>()
+            .trim_end()
+            .to_owned()
+    }
+}
+impl Moderation for Normalizer {
+    fn moderate(&self, participant: &mut Participant) -> Result<(), String> {
+        participant.name = Normalizer::normalize(&participant.name);
+        if participant.name.is_empty() {
+            Err("the name is empty".to_owned())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Rejects the names containing any of the banned words as a whole word, ignoring the case
+/// and the characters between the letters of a word ("n.o.o.b" is "noob"), but not the words
+/// containing a banned one ("Scunthorpe" is accepted). A banned phrase of several words
+/// matches the same words following each other.
+#[derive(Clone, Debug)]
+pub struct BannedWords(Vec<Vec<String>>);
+impl BannedWords {
+    /// Creates a filter for the words.
+    pub fn new<S: AsRef<str>>(words: Vec<S>) -> BannedWords {
+        BannedWords(
+            words
+                .iter()
+                .map(|w| simplify(w.as_ref()))
+                .filter(|w| !w.is_empty())
+                .collect(),
+        )
+    }
+}
+impl Moderation for BannedWords {
+    fn moderate(&self, participant: &mut Participant) -> Result<(), String> {
+        let name = simplify(&participant.name);
+        let banned = self
+            .0
+            .iter()
+            .find(|words| name.windows(words.len()).any(|w| w == words.as_slice()));
+        match banned {
+            Some(words) => Err(format!(
+                "the name \"{}\" contains the banned word \"{}\"",
+                participant.name,
+                words.join(" ")
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Returns the words of a text in lower case, keeping only their letters and digits.
+fn simplify(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|word| {
+            word.chars()
+                .flat_map(char::to_lowercase)
+                .filter(|c| c.is_alphanumeric())
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Applies the moderations in order, stopping at the first rejection.
+impl Moderation for Vec<Box<dyn Moderation>> {
+    fn moderate(&self, participant: &mut Participant) -> Result<(), String> {
+        self.iter().try_for_each(|m| m.moderate(participant))
+    }
+}
+
+/// The moderation of a client.
+#[cfg(feature = "client-blocking")]
+#[derive(Clone)]
+pub(crate) struct Moderator(pub(crate) Arc<dyn Moderation>);
+#[cfg(feature = "client-blocking")]
+impl Moderator {
+    /// Moderates the participant and the players of its lineup.
+    pub(crate) fn moderate(&self, participant: &mut Participant) -> Result<(), String> {
+        self.0.moderate(participant)?;
+        for player in participant.lineup.iter_mut().flat_map(|l| l.0.iter_mut()) {
+            self.0.moderate(player)?;
+        }
+        Ok(())
+    }
+}
+#[cfg(feature = "client-blocking")]
+impl std::fmt::Debug for Moderator {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("Moderator")
+    }
+}
+
+#[cfg(all(test, feature = "client-blocking"))]
+mod tests {
+    use super::*;
+    use crate::participants::Participants;
+
+    #[test]
+    fn test_moderation() {
+        assert_eq!(
+            Normalizer::normalize("  Evil \u{7}\tGeniuses\n "),
+            "Evil Geniuses"
+        );
+        assert_eq!(
+            Normalizer::normalize(&"a ".repeat(30)).chars().count(),
+            Participant::NAME_MAX_LENGTH - 1
+        );
+
+        let moderations: Vec<Box<dyn Moderation>> = vec![
+            Box::new(Normalizer),
+            Box::new(BannedWords::new(vec!["Noob"])),
+        ];
+        let moderator = Moderator(Arc::new(moderations));
+        let mut team = Participant::create(" Fnatic ")
+            .lineup(Participants::from(vec![Participant::create("\u{0}")]));
+        assert_eq!(
+            moderator.moderate(&mut team),
+            Err("the name is empty".to_owned())
+        );
+        assert_eq!(team.name, "Fnatic");
+
+        let mut player = Participant::create("Team  N.o.O.b");
+        assert!(moderator
+            .moderate(&mut player)
+            .unwrap_err()
+            .contains("noob"));
+        let mut player = Participant::create("Team  Liquid");
+        assert!(moderator.moderate(&mut player).is_ok());
+        assert_eq!(player.name, "Team Liquid");
+
+        // Only the whole words are banned.
+        let banned = BannedWords::new(vec!["ass", "bad guys"]);
+        let mut player = Participant::create("Assassins of Scunthorpe");
+        assert!(banned.moderate(&mut player).is_ok());
+        let mut player = Participant::create("Kick A.S.S.");
+        assert!(banned.moderate(&mut player).is_err());
+        let mut player = Participant::create("The Bad Guys");
+        assert_eq!(
+            banned.moderate(&mut player),
+            Err("the name \"The Bad Guys\" contains the banned word \"bad guys\"".to_owned())
+        );
+        let mut player = Participant::create("Bad Company Guys");
+        assert!(banned.moderate(&mut player).is_ok());
+    }
+}
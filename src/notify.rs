@@ -0,0 +1,190 @@
+//! This module plans the reminders of the upcoming matches, so that a reminder bot only has to
+//! send them: `upcoming` selects the matches starting soon for every participant and `Sent`
+//! remembers the reminders already sent, so that a participant is reminded once per match,
+//! and again only if the match is moved.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use toornament::*;
+//! use toornament::notify::Sent;
+//!
+//! let toornament = Toornament::with_application("API_TOKEN",
+//!                                               "CLIENT_ID",
+//!                                               "CLIENT_SECRET").unwrap();
+//! let mut sent = Sent::default();
+//! loop {
+//!     let matches = toornament.matches(TournamentId("1".to_owned()), None, Includes::none()).unwrap();
+//!     let now = chrono::Utc::now().fixed_offset();
+//!     let mut plan = notify::upcoming(&matches, chrono::Duration::minutes(30), now);
+//!     sent.retain_unsent(&mut plan);
+//!     for reminders in plan.into_values() {
+//!         for reminder in reminders {
+//!             println!("{} plays in {} minutes", reminder.participant.name,
+//!                      (reminder.scheduled.date - now).num_minutes());
+//!             sent.mark(&reminder);
+//!         }
+//!     }
+//!     sent.prune(now);
+//!     std::thread::sleep(std::time::Duration::from_secs(60));
+//! }
+//! ```
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{DateTime, Duration, FixedOffset};
+
+use crate::matches::{Match, MatchId, MatchStatus, Matches};
+use crate::participants::{Participant, ParticipantId};
+
+/// A reminder of a match to a participant.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reminder<'a> {
+    /// The participant to remind.
+    pub participant: &'a Participant,
+    /// The upcoming match of the participant.
+    pub scheduled: &'a Match,
+}
+impl<'a> Reminder<'a> {
+    /// Returns the key identifying the reminder in `Sent`.
+    pub fn key(&self) -> ReminderKey {
+        ReminderKey {
+            participant_id: self.participant.id.clone().unwrap_or_default(),
+            match_id: self.scheduled.id.clone(),
+            date: self.scheduled.date,
+        }
+    }
+}
+
+/// Identifies a reminder: the participant, the match and its date, so that moving a match
+/// makes its reminders due again.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+pub struct ReminderKey {
+    /// The reminded participant.
+    pub participant_id: ParticipantId,
+    /// The match.
+    pub match_id: MatchId,
+    /// The date of the match when the reminder was sent.
+    pub date: DateTime<FixedOffset>,
+}
+
+/// The reminders already sent. It may be serialized to survive the restarts of a bot.
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Sent(pub BTreeSet<ReminderKey>);
+impl Sent {
+    /// Returns whether the reminder was sent.
+    pub fn contains(&self, reminder: &Reminder<'_>) -> bool {
+        self.0.contains(&reminder.key())
+    }
+
+    /// Remembers that the reminder was sent.
+    pub fn mark(&mut self, reminder: &Reminder<'_>) {
+        self.0.insert(reminder.key());
+    }
+
+    /// Removes the reminders already sent from the plan, and the participants left without
+    /// reminders.
+    pub fn retain_unsent(&self, plan: &mut BTreeMap<ParticipantId, Vec<Reminder<'_>>>) {
+        for reminders in plan.values_mut() {
+            reminders.retain(|r| !self.contains(r));
+        }
+        plan.retain(|_, reminders| !reminders.is_empty());
+    }
+
+    /// Forgets the reminders of the matches which have started before `now`.
+    pub fn prune(&mut self, now: DateTime<FixedOffset>) {
+        self.0.retain(|key| key.date >= now);
+    }
+}
+
+/// Returns the reminders of the pending matches starting within the `window` after `now`, by
+/// participant. The reminders of a participant are ordered by the dates of the matches. The
+/// opponents without a participant identifier (not placed yet) are not reminded.
+pub fn upcoming(
+    matches: &Matches,
+    window: Duration,
+    now: DateTime<FixedOffset>,
+) -> BTreeMap<ParticipantId, Vec<Reminder<'_>>> {
+    let mut plan = BTreeMap::new();
+    let soon = matches
+        .0
+        .iter()
+        .filter(|m| m.status == MatchStatus::Pending && m.date >= now && m.date < now + window);
+    for m in soon {
+        for participant in m.opponents.0.iter().filter_map(|o| o.participant.as_ref()) {
+            let id = match participant.id {
+                Some(ref id) => id,
+                None => continue,
+            };
+            let reminders: &mut Vec<Reminder<'_>> = plan.entry(id.clone()).or_default();
+            if !reminders.iter().any(|r| r.scheduled.id == m.id) {
+                reminders.push(Reminder {
+                    participant,
+                    scheduled: m,
+                });
+            }
+        }
+    }
+    for reminders in plan.values_mut() {
+        reminders.sort_by_key(|r| r.scheduled.date);
+    }
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upcoming_reminders() {
+        let m = |id: &str, status: &str, date: &str| {
+            format!(
+                r#"{{
+                    "id": "{}", "type": "duel", "discipline": "my_discipline",
+                    "status": "{}", "tournament_id": "1", "number": 1, "stage_number": 1,
+                    "group_number": 1, "round_number": 1, "date": "{}",
+                    "opponents": [
+                        {{ "number": 1, "participant": {{ "id": "10", "name": "Evil Geniuses" }},
+                           "forfeit": false }},
+                        {{ "number": 2, "participant": {{ "id": "20", "name": "Fnatic" }},
+                           "forfeit": false }}
+                    ]
+                }}"#,
+                id, status, date
+            )
+        };
+        let json = format!(
+            "[{}, {}, {}, {}]",
+            m("b", "pending", "2015-09-06T00:40:00-06:00"),
+            m("a", "pending", "2015-09-06T00:10:00-06:00"),
+            m("c", "running", "2015-09-06T00:20:00-06:00"),
+            m("d", "pending", "2015-09-06T02:00:00-06:00")
+        );
+        let mut matches: Matches = serde_json::from_str(&json).unwrap();
+        let now = DateTime::parse_from_rfc3339("2015-09-06T00:00:00-06:00").unwrap();
+
+        let mut plan = upcoming(&matches, Duration::hours(1), now);
+        assert_eq!(plan.len(), 2);
+        let eg = &plan[&ParticipantId("10".to_owned())];
+        let ids = eg
+            .iter()
+            .map(|r| r.scheduled.id.0.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["a", "b"]);
+        assert_eq!(eg[0].participant.name, "Evil Geniuses");
+
+        let mut sent = Sent::default();
+        sent.mark(&eg[0]);
+        sent.retain_unsent(&mut plan);
+        assert_eq!(plan[&ParticipantId("10".to_owned())].len(), 1);
+        assert_eq!(plan[&ParticipantId("20".to_owned())].len(), 2);
+
+        // Moving the match makes its reminders due again.
+        matches.0[1].date += Duration::minutes(5);
+        let mut plan = upcoming(&matches, Duration::hours(1), now);
+        sent.retain_unsent(&mut plan);
+        assert_eq!(plan[&ParticipantId("10".to_owned())].len(), 2);
+
+        sent.prune(now + Duration::minutes(30));
+        assert!(sent.0.is_empty());
+    }
+}
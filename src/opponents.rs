@@ -5,6 +5,7 @@ use crate::participants::Participant;
 #[derive(
     Clone, Default, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
 )]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct Opponent {
     /// Number of the opponent
     pub number: i64,
@@ -30,3 +31,71 @@ pub struct Opponent {
     Clone, Default, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
 )]
 pub struct Opponents(pub Vec<Opponent>);
+impl Opponents {
+    /// Returns the winner of a duel: the opponent with the "win" result or, if no result is
+    /// set, the only opponent who has not forfeited.
+    pub fn winner(&self) -> Option<&Opponent> {
+        if self.0.len() != 2 {
+            return None;
+        }
+        if let Some(o) = self
+            .0
+            .iter()
+            .find(|o| o.result == Some(MatchResultSimple::Win))
+        {
+            return Some(o);
+        }
+        match (self.0[0].forfeit, self.0[1].forfeit) {
+            (true, false) => Some(&self.0[1]),
+            (false, true) => Some(&self.0[0]),
+            _ => None,
+        }
+    }
+
+    /// Returns the loser of a duel: the opponent with the "loss" result or, if no result is
+    /// set, the only opponent who has forfeited.
+    pub fn loser(&self) -> Option<&Opponent> {
+        if self.0.len() != 2 {
+            return None;
+        }
+        if let Some(o) = self
+            .0
+            .iter()
+            .find(|o| o.result == Some(MatchResultSimple::Loss))
+        {
+            return Some(o);
+        }
+        match (self.0[0].forfeit, self.0[1].forfeit) {
+            (true, false) => Some(&self.0[0]),
+            (false, true) => Some(&self.0[1]),
+            _ => None,
+        }
+    }
+
+    /// Returns the scores of the opponents ordered by their numbers, like "2 - 1". A forfeited
+    /// opponent without a score is shown as "F". Returns `None` if a score is missing.
+    pub fn score_summary(&self) -> Option<String> {
+        let mut opponents = self.0.iter().collect::<Vec<&Opponent>>();
+        if opponents.is_empty() {
+            return None;
+        }
+        opponents.sort_by_key(|o| o.number);
+        let scores = opponents
+            .iter()
+            .map(|o| match (o.score, o.forfeit) {
+                (Some(score), _) => Some(score.to_string()),
+                (None, true) => Some("F".to_owned()),
+                (None, false) => None,
+            })
+            .collect::<Option<Vec<String>>>()?;
+        Some(scores.join(" - "))
+    }
+
+    /// Returns the opponents ordered by their rank. The opponents without a rank are placed
+    /// last, in the order of their numbers.
+    pub fn rankings(&self) -> Vec<&Opponent> {
+        let mut opponents = self.0.iter().collect::<Vec<&Opponent>>();
+        opponents.sort_by_key(|o| (o.rank.is_none(), o.rank, o.number));
+        opponents
+    }
+}
@@ -0,0 +1,209 @@
+//! This module introduces a facade for the common multi-step tournament organization
+//! operations. Each operation composes several calls of the `Toornament` methods and checks
+//! the state of the tournament before changing it, so a misuse is reported as
+//! `Error::Organizer` instead of a service error.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use toornament::*;
+//!
+//! let toornament = Toornament::with_application("API_TOKEN",
+//!                                               "CLIENT_ID",
+//!                                               "CLIENT_SECRET").unwrap();
+//! let organizer = toornament.organizer();
+//! let id = TournamentId("1".to_owned());
+//!
+//! organizer.open_registrations(id.clone()).unwrap();
+//! // Seed the participants by their names
+//! organizer.close_and_seed(id.clone(), |a, b| a.name.cmp(&b.name)).unwrap();
+//! organizer.start_tournament(id.clone()).unwrap();
+//! let matches = organizer.start_stage(id.clone(), StageNumber(1)).unwrap();
+//! // ... play the matches
+//! organizer.complete_tournament(id).unwrap();
+//! ```
+use std::cmp::Ordering;
+
+use crate::*;
+
+/// A facade over `Toornament` for the tournament organization workflow.
+#[derive(Debug, Clone, Copy)]
+pub struct Organizer<'a> {
+    client: &'a Toornament,
+}
+impl<'a> Organizer<'a> {
+    /// Creates new organizer
+    pub fn new(client: &'a Toornament) -> Organizer<'a> {
+        Organizer { client }
+    }
+
+    fn tournament(&self, id: &TournamentId) -> Result<Tournament> {
+        match self
+            .client
+            .tournaments(Some(id.clone()), Includes::none())?
+            .0
+            .into_iter()
+            .next()
+        {
+            Some(t) => Ok(t),
+            None => Err(Error::Organizer(OrganizerError::NoSuchTournament(
+                id.clone(),
+            ))),
+        }
+    }
+
+    /// Fetches all the pages of the matches of the tournament.
+    fn matches(&self, id: &TournamentId) -> Result<Vec<Match>> {
+        self.client
+            .tournament_matches_pager(id.clone(), Includes::none())
+            .fetch_all()
+    }
+
+    fn expect_status(
+        &self,
+        id: &TournamentId,
+        tournament: &Tournament,
+        allowed: &[TournamentStatus],
+    ) -> Result<()> {
+        if allowed.contains(&tournament.status) {
+            Ok(())
+        } else {
+            Err(Error::Organizer(OrganizerError::InvalidStatus(
+                id.clone(),
+                tournament.status.clone(),
+            )))
+        }
+    }
+
+    /// Enables the registrations of a tournament which has not started yet and returns its
+    /// settings.
+    pub fn open_registrations(&self, id: TournamentId) -> Result<TournamentSettings> {
+        let tournament = self.tournament(&id)?;
+        self.expect_status(&id, &tournament, &[TournamentStatus::Setup])?;
+        log::debug!("Opening registrations of tournament: {:?}", id);
+        self.client.update_tournament_settings(
+            id,
+            TournamentSettings::default().registration_enabled(true),
+        )
+    }
+
+    /// Disables the registrations of a tournament which has not started yet and re-creates all
+    /// its participants, fetched page by page, in the order defined by `compare`, so the first
+    /// participant gets the first seed. Fails if there are less than two participants or more than the tournament size.
+    pub fn close_and_seed<F: FnMut(&Participant, &Participant) -> Ordering>(
+        &self,
+        id: TournamentId,
+        compare: F,
+    ) -> Result<Participants> {
+        let tournament = self.tournament(&id)?;
+        self.expect_status(&id, &tournament, &[TournamentStatus::Setup])?;
+        let size = tournament.size;
+        let mut participants = Participants::from(
+            self.client
+                .tournament_participants_pager(id.clone(), TournamentParticipantsFilter::default())
+                .fetch_all()?,
+        );
+        let count = participants.0.len();
+        if count < 2 {
+            return Err(Error::Organizer(OrganizerError::NotEnoughParticipants(
+                id, count,
+            )));
+        }
+        if count as i64 > size {
+            return Err(Error::Organizer(OrganizerError::TooManyParticipants(
+                id, count,
+            )));
+        }
+        log::debug!("Closing registrations and seeding tournament: {:?}", id);
+        self.client.update_tournament_settings(
+            id.clone(),
+            TournamentSettings::default().registration_enabled(false),
+        )?;
+        participants.0.sort_by(compare);
+        self.client.update_tournament_participants(id, participants)
+    }
+
+    /// Checks that the stage exists and has matches, marks the tournament as running and
+    /// returns the matches of the stage.
+    pub fn start_stage(&self, id: TournamentId, stage: StageNumber) -> Result<Matches> {
+        let tournament = self.tournament(&id)?;
+        self.expect_status(
+            &id,
+            &tournament,
+            &[TournamentStatus::Setup, TournamentStatus::Running],
+        )?;
+        let stages = self.client.tournament_stages(id.clone())?;
+        if !stages.0.iter().any(|s| s.number == stage) {
+            return Err(Error::Organizer(OrganizerError::NoSuchStage(id, stage)));
+        }
+        let mut matches = Matches::from(self.matches(&id)?);
+        matches.0.retain(|m| m.stage_number as i64 == stage.0);
+        if matches.0.is_empty() {
+            return Err(Error::Organizer(OrganizerError::NoSuchStage(id, stage)));
+        }
+        if tournament.status != TournamentStatus::Running {
+            log::debug!("Starting stage {:?} of tournament: {:?}", stage, id);
+            self.client
+                .edit_tournament(tournament.status(TournamentStatus::Running))?;
+        }
+        Ok(matches)
+    }
+
+    /// Checks that the tournament can be started (see `Tournament::can_start`), has at least
+    /// two participants and has stages configured, and marks the tournament as running.
+    pub fn start_tournament(&self, id: TournamentId) -> Result<Tournament> {
+        let tournament = self.tournament(&id)?;
+        if !tournament.can_start() {
+            return Err(Error::Organizer(OrganizerError::InvalidStatus(
+                id,
+                tournament.status,
+            )));
+        }
+        let participants = self
+            .client
+            .tournament_participants_pager(id.clone(), TournamentParticipantsFilter::default());
+        let first = participants.page(1)?;
+        // The participants of every page, counted without fetching them if possible.
+        let count = match first.total() {
+            Some(total) => total as usize,
+            None => participants.fetch_all()?.len(),
+        };
+        if count < 2 {
+            return Err(Error::Organizer(OrganizerError::NotEnoughParticipants(
+                id, count,
+            )));
+        }
+        if self.client.tournament_stages(id.clone())?.0.is_empty() {
+            return Err(Error::Organizer(OrganizerError::NoStages(id)));
+        }
+        log::debug!("Starting tournament: {:?}", id);
+        self.client
+            .edit_tournament(tournament.status(TournamentStatus::Running))
+    }
+
+    /// Checks that all the matches of a tournament are completed and marks the tournament as
+    /// completed.
+    pub fn complete_tournament(&self, id: TournamentId) -> Result<Tournament> {
+        let tournament = self.tournament(&id)?;
+        if !tournament.can_complete() {
+            return Err(Error::Organizer(OrganizerError::InvalidStatus(
+                id,
+                tournament.status,
+            )));
+        }
+        let unfinished = self
+            .matches(&id)?
+            .into_iter()
+            .filter(|m| m.status != MatchStatus::Completed)
+            .map(|m| m.id)
+            .collect::<Vec<MatchId>>();
+        if !unfinished.is_empty() {
+            return Err(Error::Organizer(OrganizerError::UnfinishedMatches(
+                id, unfinished,
+            )));
+        }
+        log::debug!("Completing tournament: {:?}", id);
+        self.client
+            .edit_tournament(tournament.status(TournamentStatus::Completed))
+    }
+}
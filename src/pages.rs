@@ -0,0 +1,337 @@
+use std::str::FromStr;
+#[cfg(feature = "client-blocking")]
+use std::{
+    collections::BTreeMap,
+    marker::PhantomData,
+    sync::atomic::{AtomicI64, Ordering},
+    sync::Mutex,
+};
+
+#[cfg(feature = "client-blocking")]
+use serde::de::DeserializeOwned;
+
+#[cfg(feature = "client-blocking")]
+use crate::endpoints::Endpoint;
+#[cfg(feature = "client-blocking")]
+use crate::{Cancellation, Toornament};
+use crate::{Error, Result};
+
+/// A `Content-Range` of a paginated response, for example `participants 0-49/2000`.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct ContentRange {
+    /// Index of the first returned item.
+    pub start: u64,
+    /// Index of the last returned item.
+    pub end: u64,
+    /// Total number of items, if known.
+    pub total: Option<u64>,
+}
+impl ContentRange {
+    /// Returns the number of items in the range.
+    pub fn len(&self) -> u64 {
+        self.end + 1 - self.start
+    }
+
+    /// Returns `true` if the range has no items, which never happens for a valid range.
+    pub fn is_empty(&self) -> bool {
+        self.end < self.start
+    }
+}
+
+impl FromStr for ContentRange {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<ContentRange> {
+        const ERROR: Error = Error::Rest("Invalid content range");
+
+        let range = s.split_whitespace().last().ok_or(ERROR)?;
+        let (range, total) = match range.find('/') {
+            Some(i) => (&range[..i], &range[i + 1..]),
+            None => (range, "*"),
+        };
+        let (start, end) = match range.find('-') {
+            Some(i) => (&range[..i], &range[i + 1..]),
+            None => return Err(ERROR),
+        };
+        let total = match total {
+            "*" => None,
+            total => Some(total.parse().map_err(|_| ERROR)?),
+        };
+        let range = ContentRange {
+            start: start.parse().map_err(|_| ERROR)?,
+            end: end.parse().map_err(|_| ERROR)?,
+            total,
+        };
+        if range.is_empty() {
+            return Err(ERROR);
+        }
+        Ok(range)
+    }
+}
+
+/// Where a page is in the whole collection, as told by the `Content-Range` of the response.
+/// Every field is `None` if the service did not tell it.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Paging {
+    /// Total number of items of the collection.
+    pub total: Option<u64>,
+    /// Index of the first item of the list in the collection.
+    pub offset: Option<u64>,
+    /// Number of items in the list.
+    pub limit: Option<u64>,
+}
+impl From<ContentRange> for Paging {
+    fn from(range: ContentRange) -> Paging {
+        Paging {
+            total: range.total,
+            offset: Some(range.start),
+            limit: Some(range.len()),
+        }
+    }
+}
+
+/// A page of a paginated collection.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Page<T> {
+    /// Number of the page, starting from one.
+    pub number: i64,
+    /// Items of the page.
+    pub items: Vec<T>,
+    /// The range of the items in the whole collection, if returned by the service.
+    pub range: Option<ContentRange>,
+}
+impl<T> Page<T> {
+    /// Returns where the page is in the whole collection.
+    pub fn paging(&self) -> Paging {
+        self.range.map(Paging::from).unwrap_or_default()
+    }
+
+    /// Returns the total number of items of the collection, if the service returned it.
+    pub fn total(&self) -> Option<u64> {
+        self.range.and_then(|r| r.total)
+    }
+
+    /// Returns the number of pages of the collection, if the service returned the total.
+    pub fn pages(&self) -> Option<i64> {
+        let range = self.range?;
+        let total = range.total?;
+        Some(total.div_ceil(range.len()) as i64)
+    }
+}
+
+#[cfg(feature = "client-blocking")]
+/// Fetches the pages of a paginated collection one by one or concurrently.
+pub struct Pager<'a, T> {
+    client: &'a Toornament,
+    endpoint: Box<dyn Fn(i64) -> Endpoint + Send + Sync + 'a>,
+    cancellation: Option<Cancellation>,
+    _items: PhantomData<fn() -> T>,
+}
+#[cfg(feature = "client-blocking")]
+impl<'a, T: DeserializeOwned + Send + Sync + 'static> Pager<'a, T> {
+    /// Creates new pager: `endpoint` returns the endpoint of the given page number.
+    pub(crate) fn new<F>(client: &'a Toornament, endpoint: F) -> Pager<'a, T>
+    where
+        F: Fn(i64) -> Endpoint + Send + Sync + 'a,
+    {
+        Pager {
+            client,
+            endpoint: Box::new(endpoint),
+            cancellation: None,
+            _items: PhantomData,
+        }
+    }
+
+    /// Makes the pager check the `cancellation` before every request.
+    pub fn cancellation(mut self, cancellation: Cancellation) -> Pager<'a, T> {
+        self.cancellation = Some(cancellation);
+        self
+    }
+
+    /// Fetches the page with the given number, starting from one.
+    pub fn page(&self, number: i64) -> Result<Page<T>> {
+        if let Some(ref cancellation) = self.cancellation {
+            cancellation.check()?;
+        }
+        let endpoint = (self.endpoint)(number);
+        log::debug!("Getting page {}: {}", number, endpoint);
+        let reply = self.client.get(&endpoint)?;
+        let range = reply.content_range();
+        Ok(Page {
+            number,
+            items: reply.json()?,
+            range,
+        })
+    }
+
+    /// Fetches all the pages one by one until an empty or the last page. If the operation is
+    /// cancelled, `Error::Cancelled` holds the `Vec<T>` of the items of the pages fetched
+    /// before.
+    pub fn fetch_all(&self) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        match self.fetch_all_into(&mut items) {
+            Ok(()) => Ok(items),
+            Err(e) => Err(e.with_partial(items)),
+        }
+    }
+
+    /// Fetches all the pages one by one into `items`. On an error, for example when the
+    /// operation is cancelled, `items` keeps the items of the pages fetched before.
+    pub fn fetch_all_into(&self, items: &mut Vec<T>) -> Result<()> {
+        self.fetch_from(1, items)
+    }
+
+    /// Fetches the first page and, if the service returned the total number of items, fetches
+    /// the remaining pages with up to `concurrency` requests at a time. Falls back to
+    /// `fetch_all` when the total is unknown. The items are returned in the page order. If the
+    /// operation is cancelled, the running requests are finished and the rest is not started:
+    /// `Error::Cancelled` holds the `Vec<T>` of the items of the first pages fetched, up to
+    /// the first one missing.
+    pub fn fetch_all_parallel(&self, concurrency: usize) -> Result<Vec<T>> {
+        let first = self.page(1).map_err(|e| e.with_partial(Vec::<T>::new()))?;
+        let pages = match first.pages() {
+            Some(pages) if concurrency > 1 => pages,
+            pages => {
+                let more = !first.items.is_empty() && pages.map(|p| p > 1).unwrap_or(true);
+                let mut items = first.items;
+                if more {
+                    if let Err(e) = self.fetch_from(2, &mut items) {
+                        return Err(e.with_partial(items));
+                    }
+                }
+                return Ok(items);
+            }
+        };
+
+        let next = AtomicI64::new(2);
+        let fetched = Mutex::new(BTreeMap::new());
+        let failed = Mutex::new(None);
+        std::thread::scope(|scope| {
+            for _ in 0..concurrency.min(pages.max(1) as usize) {
+                scope.spawn(|| loop {
+                    let number = next.fetch_add(1, Ordering::SeqCst);
+                    if number > pages || failed.lock().map(|f| f.is_some()).unwrap_or(true) {
+                        return;
+                    }
+                    match self.page(number) {
+                        Ok(page) => {
+                            if let Ok(mut fetched) = fetched.lock() {
+                                fetched.insert(number, page.items);
+                            }
+                        }
+                        Err(e) => {
+                            if let Ok(mut failed) = failed.lock() {
+                                failed.get_or_insert(e);
+                            }
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+
+        let fetched = fetched
+            .into_inner()
+            .map_err(|_| Error::Rest("Could not collect the pages"))?;
+        let mut items = first.items;
+        for (next, (number, page)) in (2..).zip(fetched) {
+            if number != next {
+                break;
+            }
+            items.extend(page);
+        }
+        match failed.into_inner().unwrap_or(None) {
+            Some(e) => Err(e.with_partial(items)),
+            None => Ok(items),
+        }
+    }
+
+    fn fetch_from(&self, mut number: i64, items: &mut Vec<T>) -> Result<()> {
+        loop {
+            let page = self.page(number)?;
+            let last = page.items.is_empty() || page.pages().map(|p| number >= p).unwrap_or(false);
+            items.extend(page.items);
+            if last {
+                return Ok(());
+            }
+            number += 1;
+        }
+    }
+}
+
+#[cfg(feature = "client-blocking")]
+impl<'a, T> std::fmt::Debug for Pager<'a, T> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.debug_struct("Pager")
+            .field("first_page", &(self.endpoint)(1).to_string())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_range_parse() {
+        let range: ContentRange = "participants 0-49/2000".parse().unwrap();
+        assert_eq!(
+            range,
+            ContentRange {
+                start: 0,
+                end: 49,
+                total: Some(2000),
+            }
+        );
+        assert_eq!(range.len(), 50);
+        let page = Page::<()> {
+            number: 1,
+            items: Vec::new(),
+            range: Some(range),
+        };
+        assert_eq!(page.pages(), Some(40));
+
+        let range: ContentRange = "items 50-60/*".parse().unwrap();
+        assert_eq!(range.total, None);
+        assert!("items 5-1/10".parse::<ContentRange>().is_err());
+        assert!("items".parse::<ContentRange>().is_err());
+    }
+
+    #[test]
+    fn test_page_paging() {
+        let mut page = Page {
+            number: 26,
+            items: vec!["Evil Geniuses", "Fnatic"],
+            range: None,
+        };
+        assert_eq!(page.total(), None);
+        assert_eq!(page.paging(), Paging::default());
+        page.range = Some("participants 50-51/120".parse().unwrap());
+        assert_eq!(page.total(), Some(120));
+        assert_eq!(
+            page.paging(),
+            Paging {
+                total: Some(120),
+                offset: Some(50),
+                limit: Some(2),
+            }
+        );
+    }
+
+    #[test]
+    fn test_list_wrapper() {
+        use crate::Participants;
+
+        let participants: Participants =
+            serde_json::from_str(r#"[{ "name": "Evil Geniuses" }, { "name": "Fnatic" }]"#).unwrap();
+        assert_eq!(participants.len(), 2);
+        assert_eq!(participants[1].name, "Fnatic");
+
+        let json = serde_json::to_value(&participants).unwrap();
+        assert_eq!(json[0]["name"], "Evil Geniuses");
+        let parsed: Participants = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, participants);
+        let Participants(items) = parsed;
+        assert_eq!(items.into_iter().collect::<Participants>(), participants);
+    }
+}
@@ -1,6 +1,21 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::common::Date;
+use crate::fingerprint::{fingerprint, Fingerprint};
+use crate::Result;
+
 /// Unique participant identifier
 #[derive(
-    Clone, Default, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
+    Clone,
+    Default,
+    Debug,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    serde::Serialize,
+    serde::Deserialize,
 )]
 pub struct ParticipantId(pub String);
 
@@ -16,6 +31,7 @@ pub enum ParticipantType {
 
 /// Logo of the participant.
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct ParticipantLogo {
     /// Url to a picture of 48x48px.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -31,8 +47,9 @@ pub struct ParticipantLogo {
     pub medium_large_square: Option<String>,
 }
 
-/// A type of a participant's custom field
+/// A type of a participant's custom field, or of a field of a registration form
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[non_exhaustive]
 pub enum CustomFieldType {
     /// Participant's steam id
     #[serde(rename = "steam_player_id")]
@@ -70,10 +87,95 @@ pub enum CustomFieldType {
     /// Participant's youtube channel
     #[serde(rename = "youtube")]
     Youtube,
+    /// A multi-line text
+    #[serde(rename = "textarea")]
+    Textarea,
+    /// A yes/no answer
+    #[serde(rename = "checkbox")]
+    Checkbox,
+    /// An email address
+    #[serde(rename = "email")]
+    Email,
+    /// A postal address
+    #[serde(rename = "address")]
+    Address,
+    /// An ISO 3166-1 alpha-2 country code
+    #[serde(rename = "country")]
+    Country,
+    /// A phone number
+    #[serde(rename = "phone")]
+    Phone,
+    /// A web page address
+    #[serde(rename = "url")]
+    Url,
+    /// A type unknown to this version of the library, accepting any text
+    #[serde(other)]
+    Other,
+}
+impl CustomFieldType {
+    /// Returns whether the type is a link to a web page.
+    fn is_link(&self) -> bool {
+        matches!(
+            *self,
+            CustomFieldType::Facebook
+                | CustomFieldType::Instagram
+                | CustomFieldType::Twitch
+                | CustomFieldType::Twitter
+                | CustomFieldType::Vimeo
+                | CustomFieldType::Website
+                | CustomFieldType::Youtube
+                | CustomFieldType::Url
+        )
+    }
+
+    /// Returns whether the text is a valid value of the type: an ISO 8601 date for a birth
+    /// date, an absolute http or https url for a link, a steam id in the textual
+    /// ("STEAM_0:1:1234567") or the 64-bit numeric ("76561197960287930") form, an email
+    /// address, a country code or a phone number. The other types accept any text.
+    pub fn is_valid(&self, text: &str) -> bool {
+        let text = text.trim();
+        let is_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+        match *self {
+            CustomFieldType::Birthdate => Date::parse_from_str(text, "%Y-%m-%d").is_ok(),
+            CustomFieldType::SteamId => match text.strip_prefix("STEAM_") {
+                Some(rest) => {
+                    let parts = rest.split(':').collect::<Vec<&str>>();
+                    parts.len() == 3
+                        && parts.iter().all(|p| is_digits(p))
+                        && (parts[1] == "0" || parts[1] == "1")
+                }
+                None => text.len() == 17 && is_digits(text),
+            },
+            CustomFieldType::Email => {
+                let mut parts = text.splitn(2, '@');
+                let local = parts.next().unwrap_or_default();
+                let domain = parts.next().unwrap_or_default();
+                !local.is_empty() && domain.contains('.') && !text.contains(char::is_whitespace)
+            }
+            CustomFieldType::Country => {
+                text.len() == 2 && text.chars().all(|c| c.is_ascii_uppercase())
+            }
+            CustomFieldType::Phone => {
+                text.chars().filter(|c| c.is_ascii_digit()).count() >= 5
+                    && text
+                        .chars()
+                        .all(|c| c.is_ascii_digit() || " +-().".contains(c))
+            }
+            ref link if link.is_link() => {
+                let rest = text
+                    .strip_prefix("https://")
+                    .or_else(|| text.strip_prefix("http://"))
+                    .unwrap_or_default();
+                !rest.is_empty() && !rest.starts_with('/') && !rest.contains(char::is_whitespace)
+            }
+            _ => true,
+        }
+    }
 }
 
 /// A participant's custom fields
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct CustomField {
     /// Type of field.
     #[serde(rename = "type")]
@@ -83,17 +185,55 @@ pub struct CustomField {
     /// Value informed.
     pub value: String,
 }
+impl CustomField {
+    /// Returns the value as a date if the field is a birth date and the value is a valid
+    /// ISO 8601 date (for example, "1990-01-31").
+    pub fn as_date(&self) -> Option<Date> {
+        match self.field_type {
+            CustomFieldType::Birthdate => Date::parse_from_str(self.value.trim(), "%Y-%m-%d").ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as an url if the field is a link to a web page (website, facebook,
+    /// twitch, etc) and the value is an absolute http or https url.
+    pub fn as_url(&self) -> Option<&str> {
+        if self.field_type.is_link() && self.field_type.is_valid(&self.value) {
+            Some(self.value.trim())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value as a steam id if the field is a steam player id and the value is
+    /// either in the textual form ("STEAM_0:1:1234567") or in the 64-bit numeric form
+    /// ("76561197960287930").
+    pub fn as_steam_id(&self) -> Option<&str> {
+        if self.field_type == CustomFieldType::SteamId && self.field_type.is_valid(&self.value) {
+            Some(self.value.trim())
+        } else {
+            None
+        }
+    }
+}
 
 /// A list of participant's custom fields
 #[derive(
     Clone, Default, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
 )]
 pub struct CustomFields(pub Vec<CustomField>);
+impl CustomFields {
+    /// Returns the first custom field of the specified type.
+    pub fn get(&self, field_type: CustomFieldType) -> Option<&CustomField> {
+        self.0.iter().find(|f| f.field_type == field_type)
+    }
+}
 
 /// An opponent involved in a match/tournament.
 #[derive(
     Clone, Default, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
 )]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct Participant {
     /// Unique identifier for this participant.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -126,6 +266,9 @@ pub struct Participant {
     pub custom_fields_private: Option<CustomFields>,
 }
 impl Participant {
+    /// Maximum length of the name, in characters, as documented for the `name` field.
+    pub const NAME_MAX_LENGTH: usize = 40;
+
     /// Create participant object for adding for a tournament
     /// (Toornament::create_tournament_participant)
     pub fn create<S: Into<String>>(name: S) -> Participant {
@@ -151,10 +294,188 @@ impl Participant {
     Clone, Default, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
 )]
 pub struct Participants(pub Vec<Participant>);
+list_wrapper!(Participants, Participant);
+impl Participants {
+    /// Returns the fingerprint of the list, which changes with any field of a participant and
+    /// with their order. See `Fingerprint`. Fails only if the serialization fails.
+    pub fn fingerprint(&self) -> Result<Fingerprint> {
+        fingerprint(self)
+    }
+
+    /// Converts the list into a map of the participants by their identifiers, to join them
+    /// with the opponents of the matches. The participants without an identifier are left out.
+    pub fn into_map_by_id(self) -> HashMap<ParticipantId, Participant> {
+        self.0
+            .into_iter()
+            .filter_map(|p| Some((p.id.clone()?, p)))
+            .collect()
+    }
+
+    /// Converts the list into a map of the participants ordered by their identifiers. The
+    /// participants without an identifier are left out.
+    pub fn into_btree_map_by_id(self) -> BTreeMap<ParticipantId, Participant> {
+        self.0
+            .into_iter()
+            .filter_map(|p| Some((p.id.clone()?, p)))
+            .collect()
+    }
+
+    /// Returns the first participant with the name.
+    pub fn by_name(&self, name: &str) -> Option<&Participant> {
+        self.0.iter().find(|p| p.name == name)
+    }
+
+    /// Returns the participant with the email, whose case is ignored.
+    pub fn by_email(&self, email: &str) -> Option<&Participant> {
+        self.0.iter().find(|p| {
+            p.email
+                .as_ref()
+                .is_some_and(|e| e.eq_ignore_ascii_case(email))
+        })
+    }
+
+    /// Groups the participants which are likely the same one signed up several times: those
+    /// with the same normalized name (case, punctuation and spacing ignored),
+    /// the same email (case and `+tag` ignored) or the same steam id in their custom fields
+    /// (whatever its form). The groups are ordered by decreasing confidence.
+    pub fn find_duplicates(&self) -> Vec<DuplicateGroup> {
+        let mut parents = (0..self.0.len()).collect::<Vec<usize>>();
+        fn root(parents: &mut [usize], mut i: usize) -> usize {
+            while parents[i] != i {
+                parents[i] = parents[parents[i]];
+                i = parents[i];
+            }
+            i
+        }
+        let mut reasons = BTreeMap::new();
+        let mut seen = BTreeMap::new();
+        for (i, p) in self.0.iter().enumerate() {
+            for key in duplicate_keys(p) {
+                match seen.get(&key) {
+                    Some(&j) => {
+                        let (a, b) = (root(&mut parents, i), root(&mut parents, j));
+                        parents[a] = b;
+                        reasons.entry(i).or_insert_with(BTreeSet::new).insert(key.0);
+                        reasons.entry(j).or_insert_with(BTreeSet::new).insert(key.0);
+                    }
+                    None => {
+                        seen.insert(key, i);
+                    }
+                }
+            }
+        }
+        let mut groups = BTreeMap::new();
+        for i in 0..self.0.len() {
+            let r = root(&mut parents, i);
+            groups.entry(r).or_insert_with(Vec::new).push(i);
+        }
+        let mut groups = groups
+            .into_values()
+            .filter(|members| members.len() > 1)
+            .map(|members| {
+                let reasons = members
+                    .iter()
+                    .flat_map(|i| reasons.get(i).into_iter().flatten().cloned())
+                    .collect::<BTreeSet<_>>();
+                DuplicateGroup {
+                    participants: members.iter().map(|&i| self.0[i].clone()).collect(),
+                    confidence: 1.0 - reasons.iter().map(|r| 1.0 - r.weight()).product::<f64>(),
+                    reasons: reasons.into_iter().collect(),
+                }
+            })
+            .collect::<Vec<_>>();
+        groups.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        groups
+    }
+}
+
+/// Why participants are considered duplicates.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum DuplicateReason {
+    /// They have the same steam id.
+    SteamId,
+    /// They have the same email.
+    Email,
+    /// They have the same name.
+    Name,
+}
+impl DuplicateReason {
+    /// Returns the probability that participants with this in common are the same one.
+    pub fn weight(self) -> f64 {
+        match self {
+            DuplicateReason::SteamId => 0.95,
+            DuplicateReason::Email => 0.9,
+            DuplicateReason::Name => 0.6,
+        }
+    }
+}
+
+/// Participants likely being the same one. See `Participants::find_duplicates`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DuplicateGroup {
+    /// The participants, in their order in the list.
+    pub participants: Vec<Participant>,
+    /// What the participants have in common.
+    pub reasons: Vec<DuplicateReason>,
+    /// Confidence between 0 and 1 that the participants are the same one, combining the
+    /// weights of the reasons.
+    pub confidence: f64,
+}
+
+/// Returns the normalized name, emails and steam ids of a participant.
+fn duplicate_keys(p: &Participant) -> Vec<(DuplicateReason, String)> {
+    let mut keys = Vec::new();
+    let name = p
+        .name
+        .chars()
+        .flat_map(char::to_lowercase)
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>();
+    if !name.is_empty() {
+        keys.push((DuplicateReason::Name, name));
+    }
+    if let Some(email) = p.email.as_ref().map(|e| e.trim().to_lowercase()) {
+        if let Some((local, domain)) = email.split_once('@') {
+            let local = local.split('+').next().unwrap_or_default();
+            keys.push((DuplicateReason::Email, format!("{}@{}", local, domain)));
+        }
+    }
+    let fields = p.custom_fields.iter().chain(p.custom_fields_private.iter());
+    for id in fields
+        .flat_map(|f| f.0.iter())
+        .filter_map(CustomField::as_steam_id)
+        .filter_map(steam_id64)
+    {
+        keys.push((DuplicateReason::SteamId, id));
+    }
+    keys
+}
+
+/// Converts a valid steam id in the textual form to the 64-bit numeric form, `None` if the
+/// account number does not fit in a 64-bit steam id.
+pub(crate) fn steam_id64(id: &str) -> Option<String> {
+    match id.strip_prefix("STEAM_") {
+        Some(rest) => {
+            let parts = rest
+                .split(':')
+                .map(|p| p.parse::<u64>().ok())
+                .collect::<Option<Vec<_>>>()?;
+            parts[2]
+                .checked_mul(2)?
+                .checked_add(parts[1])?
+                .checked_add(76_561_197_960_265_728)
+                .map(|id| id.to_string())
+        }
+        None => Some(id.to_owned()),
+    }
+}
 
 #[cfg(test)]
 mod tests {
-    use super::{CustomFieldType, Participants};
+    use super::{
+        steam_id64, CustomField, CustomFieldType, CustomFields, DuplicateReason, Participant,
+        ParticipantId, Participants,
+    };
 
     #[test]
     fn test_participant_parse() {
@@ -213,7 +534,7 @@ mod tests {
 
         let ps: Participants = serde_json::from_str(s).unwrap();
         assert_eq!(ps.0.len(), 1);
-        let p = ps.0.get(0).unwrap().clone();
+        let p = ps.0.first().unwrap().clone();
 
         assert_eq!(p.id.unwrap().0, "378426939508809728");
         assert_eq!(p.name, "Evil Geniuses");
@@ -237,14 +558,14 @@ mod tests {
         assert_eq!(p.country, Some("US".to_owned()));
         let lineup = p.lineup.unwrap().0;
         assert_eq!(lineup.len(), 1);
-        let lp = lineup.get(0).unwrap();
+        let lp = lineup.first().unwrap();
         assert!(lp.id.is_none());
         assert_eq!(lp.name, "Storm Spirit");
         assert_eq!(lp.country, Some("US".to_owned()));
         {
             let lpcfs = lp.custom_fields.clone().unwrap().0;
             assert_eq!(lpcfs.len(), 1);
-            let lpcf = lpcfs.get(0).unwrap();
+            let lpcf = lpcfs.first().unwrap();
             assert_eq!(lpcf.field_type, CustomFieldType::SteamId);
             assert_eq!(lpcf.label, "Steam ID");
             assert_eq!(lpcf.value, "STEAM_0:1:1234567");
@@ -253,7 +574,7 @@ mod tests {
         {
             let lpcfsp = lp.custom_fields_private.clone().unwrap().0;
             assert_eq!(lpcfsp.len(), 1);
-            let lpcfp = lpcfsp.get(0).unwrap();
+            let lpcfp = lpcfsp.first().unwrap();
             assert_eq!(lpcfp.field_type, CustomFieldType::SteamId);
             assert_eq!(lpcfp.label, "Steam ID");
             assert_eq!(lpcfp.value, "STEAM_0:1:1234567");
@@ -263,7 +584,7 @@ mod tests {
         {
             let pcfs = p.custom_fields.clone().unwrap().0;
             assert_eq!(pcfs.len(), 1);
-            let pcf = pcfs.get(0).unwrap();
+            let pcf = pcfs.first().unwrap();
             assert_eq!(pcf.field_type, CustomFieldType::SteamId);
             assert_eq!(pcf.label, "Steam ID");
             assert_eq!(pcf.value, "STEAM_0:1:1234567");
@@ -271,10 +592,156 @@ mod tests {
         {
             let pcfsp = p.custom_fields_private.unwrap().0;
             assert_eq!(pcfsp.len(), 1);
-            let pcfp = pcfsp.get(0).unwrap();
+            let pcfp = pcfsp.first().unwrap();
             assert_eq!(pcfp.field_type, CustomFieldType::SteamId);
             assert_eq!(pcfp.label, "Steam ID");
             assert_eq!(pcfp.value, "STEAM_0:1:1234567");
         }
     }
+
+    #[test]
+    fn test_custom_field_typed_values() {
+        use chrono::Datelike;
+
+        let field = |field_type, value: &str| CustomField {
+            field_type,
+            label: "Label".to_owned(),
+            value: value.to_owned(),
+        };
+
+        let birth_date = field(CustomFieldType::Birthdate, "1990-01-31")
+            .as_date()
+            .unwrap();
+        assert_eq!(birth_date.year(), 1990);
+        assert_eq!(birth_date.month(), 1);
+        assert_eq!(birth_date.day(), 31);
+        assert!(field(CustomFieldType::Birthdate, "31/01/1990")
+            .as_date()
+            .is_none());
+        assert!(field(CustomFieldType::Text, "1990-01-31")
+            .as_date()
+            .is_none());
+
+        assert_eq!(
+            field(CustomFieldType::Twitch, "https://twitch.tv/someone").as_url(),
+            Some("https://twitch.tv/someone")
+        );
+        assert!(field(CustomFieldType::Website, "not an url")
+            .as_url()
+            .is_none());
+        assert!(field(CustomFieldType::Website, "http://")
+            .as_url()
+            .is_none());
+        assert!(field(CustomFieldType::Text, "http://toornament.com")
+            .as_url()
+            .is_none());
+
+        assert_eq!(
+            field(CustomFieldType::SteamId, "STEAM_0:1:1234567").as_steam_id(),
+            Some("STEAM_0:1:1234567")
+        );
+        assert_eq!(
+            field(CustomFieldType::SteamId, "76561197960287930").as_steam_id(),
+            Some("76561197960287930")
+        );
+        assert!(field(CustomFieldType::SteamId, "STEAM_0:2:1234567")
+            .as_steam_id()
+            .is_none());
+        assert!(field(CustomFieldType::SteamId, "someone")
+            .as_steam_id()
+            .is_none());
+        assert!(field(CustomFieldType::Text, "STEAM_0:1:1234567")
+            .as_steam_id()
+            .is_none());
+
+        let fields = CustomFields(vec![
+            field(CustomFieldType::Text, "Hello"),
+            field(CustomFieldType::SteamId, "STEAM_0:1:1234567"),
+        ]);
+        assert_eq!(
+            fields.get(CustomFieldType::SteamId).unwrap().value,
+            "STEAM_0:1:1234567"
+        );
+        assert!(fields.get(CustomFieldType::Twitter).is_none());
+    }
+
+    #[test]
+    fn test_find_duplicates() {
+        let steam = |value: &str| {
+            CustomFields(vec![CustomField {
+                field_type: CustomFieldType::SteamId,
+                label: "Steam".to_owned(),
+                value: value.to_owned(),
+            }])
+        };
+        let participants = Participants::from(vec![
+            Participant::create("Evil Geniuses").email("EG@example.com".to_owned()),
+            Participant::create("Fnatic").custom_fields(steam("STEAM_0:1:1234567")),
+            Participant::create("evil-geniuses"),
+            Participant::create("Natus Vincere").email("eg+cup@example.com".to_owned()),
+            Participant::create("fnatic.").custom_fields_private(steam("76561197962734863")),
+            Participant::create("Team Liquid"),
+        ]);
+        assert_eq!(
+            participants.by_email("eg@EXAMPLE.com").unwrap().name,
+            "Evil Geniuses"
+        );
+        assert!(participants.by_email("eg@example.org").is_none());
+        assert!(participants.by_name("Team Liquid").is_some());
+        assert!(participants.by_name("team liquid").is_none());
+        let mut identified = participants.clone();
+        identified.0[0].id = Some(ParticipantId("2".to_owned()));
+        identified.0[1].id = Some(ParticipantId("1".to_owned()));
+        assert_ne!(
+            identified.fingerprint().unwrap(),
+            participants.fingerprint().unwrap()
+        );
+        let by_id = identified.clone().into_map_by_id();
+        assert_eq!(by_id.len(), 2);
+        assert_eq!(by_id[&ParticipantId("1".to_owned())].name, "Fnatic");
+        let ordered = identified.into_btree_map_by_id();
+        assert_eq!(
+            ordered
+                .values()
+                .map(|p| p.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Fnatic", "Evil Geniuses"]
+        );
+
+        let groups = participants.find_duplicates();
+        assert_eq!(groups.len(), 2);
+
+        let names = |i: usize| {
+            groups[i]
+                .participants
+                .iter()
+                .map(|p| p.name.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(0), vec!["Fnatic", "fnatic."]);
+        assert_eq!(
+            groups[0].reasons,
+            vec![DuplicateReason::SteamId, DuplicateReason::Name]
+        );
+        assert!((groups[0].confidence - 0.98).abs() < 1e-9);
+        assert_eq!(
+            names(1),
+            vec!["Evil Geniuses", "evil-geniuses", "Natus Vincere"]
+        );
+        assert!((groups[1].confidence - 0.96).abs() < 1e-9);
+
+        assert_eq!(
+            steam_id64("STEAM_0:1:1234567").as_deref(),
+            Some("76561197962734863")
+        );
+        let oversized = "STEAM_0:1:9223372036854775807";
+        assert!(steam(oversized).0[0].as_steam_id().is_some());
+        assert!(steam_id64(oversized).is_none());
+        assert!(steam_id64("STEAM_0:1:99999999999999999999").is_none());
+        let participants = Participants::from(vec![
+            Participant::create("Fnatic").custom_fields(steam(oversized)),
+            Participant::create("Astralis").custom_fields(steam(oversized)),
+        ]);
+        assert!(participants.find_duplicates().is_empty());
+    }
 }
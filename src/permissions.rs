@@ -28,8 +28,25 @@ pub enum PermissionAttribute {
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
 pub struct PermissionAttributes(pub BTreeSet<PermissionAttribute>);
 
+/// The state of the invitation of the user of a permission.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum PermissionStatus {
+    /// The user is invited but has not accepted the invitation yet: the permission has no
+    /// effect until then.
+    Pending,
+    /// The user has accepted the invitation.
+    Accepted,
+    /// A status unknown to this crate, from a newer version of the service.
+    #[serde(other)]
+    Unknown,
+}
+
 /// A user permission
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct Permission {
     /// The permission identifier.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -38,6 +55,10 @@ pub struct Permission {
     pub email: String,
     /// The list of permission of the related user on the tournament.
     pub attributes: PermissionAttributes,
+    /// Whether the user has accepted the invitation, set by the service. It is never sent
+    /// back to the service.
+    #[serde(default, skip_serializing)]
+    pub status: Option<PermissionStatus>,
 }
 impl Permission {
     /// Create permission object for adding it to a tournament
@@ -47,13 +68,26 @@ impl Permission {
             id: None,
             email: email.into(),
             attributes,
+            status: None,
         }
     }
+
+    /// Returns `true` if the user has not accepted the invitation yet. See
+    /// `Toornament::resend_permission_invitation`.
+    pub fn is_pending(&self) -> bool {
+        self.status == Some(PermissionStatus::Pending)
+    }
 }
 
 /// A list of permissions
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
 pub struct Permissions(pub Vec<Permission>);
+impl Permissions {
+    /// Returns the permissions whose users have not accepted the invitation yet.
+    pub fn pending(&self) -> Vec<&Permission> {
+        self.0.iter().filter(|p| p.is_pending()).collect()
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -83,4 +117,35 @@ mod tests {
         assert!(ps.0.iter().any(|p| *p == PermissionAttribute::Fill));
         assert!(ps.0.iter().any(|p| *p == PermissionAttribute::Delete));
     }
+
+    #[test]
+    fn test_permission_status() {
+        let s = r#"
+        [
+            { "id": "1", "email": "a@example.com", "attributes": ["edit"], "status": "accepted" },
+            { "id": "2", "email": "b@example.com", "attributes": ["report"], "status": "pending" },
+            { "id": "3", "email": "c@example.com", "attributes": ["report"] },
+            { "id": "4", "email": "e@example.com", "attributes": ["report"], "status": "declined" }
+        ]
+        "#;
+
+        let ps: Permissions = serde_json::from_str(s).unwrap();
+        assert_eq!(ps.0[0].status, Some(PermissionStatus::Accepted));
+        assert_eq!(ps.0[3].status, Some(PermissionStatus::Unknown));
+        let pending = ps.pending();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].email, "b@example.com");
+
+        // The status is set by the service only.
+        let created = Permission::create("d@example.com", ps.0[0].attributes.clone());
+        assert!(!created.is_pending());
+        assert!(serde_json::to_value(&created)
+            .unwrap()
+            .get("status")
+            .is_none());
+        assert!(serde_json::to_value(&ps.0[1])
+            .unwrap()
+            .get("status")
+            .is_none());
+    }
 }
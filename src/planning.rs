@@ -0,0 +1,521 @@
+//! This module plans the stages of a tournament and the placements of the participants in
+//! their matches offline, so an organizer can build and validate the whole structure, review
+//! the changes it makes to the tournament and only then apply them.
+//!
+//! A `Plan` lists the stages and the matches, placed by their stage, group, round and number
+//! like the matches of the service; a planned match may also be given the id of the match it
+//! places. `Plan::validate` checks the plan without the service, `Plan::review` compares it
+//! with the tournament and returns the placements to change, and `PlanChanges::apply` patches
+//! them as a batch.
+//!
+//! Creating the stages is out of scope: this crate has no method creating them. They are
+//! configured by the organizer of the tournament before the plan is applied, and the review
+//! fails if a planned stage is missing or differs.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use toornament::*;
+//! use toornament::planning::{Plan, PlannedMatch, PlannedStage};
+//!
+//! let toornament = Toornament::with_application("API_TOKEN",
+//!                                               "CLIENT_ID",
+//!                                               "CLIENT_SECRET").unwrap();
+//! let first = Participant::create("First").id(ParticipantId("1".to_owned()));
+//! let second = Participant::create("Second").id(ParticipantId("2".to_owned()));
+//! let plan = Plan::new(TournamentId("1".to_owned()))
+//!     .stage(PlannedStage::new(StageNumber(1), "Playoffs", StageType::SingleElimination, 2))
+//!     .add_match(PlannedMatch::new(1, 1, 1, 1).opponent(1, first).opponent(2, second));
+//! plan.validate().unwrap();
+//! let changes = plan.review(&toornament).unwrap();
+//! for (id, patch) in &changes.placements {
+//!     println!("{}: {:?}", id.0, patch.opponents);
+//! }
+//! changes.apply(&toornament, batch::BatchOptions::default());
+//! ```
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::matches::{Match, MatchId, MatchPatch, Matches};
+use crate::opponents::{Opponent, Opponents};
+use crate::participants::Participant;
+use crate::stages::{StageNumber, StageType, Stages, TournamentSize};
+use crate::tournaments::TournamentId;
+#[cfg(feature = "client-blocking")]
+use crate::{batch, Includes, Toornament};
+use crate::{Error, OrganizerError, Result, ValidationError};
+
+/// A stage of a plan.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlannedStage {
+    /// Stage number.
+    pub number: StageNumber,
+    /// Name of the stage.
+    pub name: String,
+    /// Stage type.
+    pub stage_type: StageType,
+    /// Number of participants of the stage.
+    pub size: TournamentSize,
+}
+impl PlannedStage {
+    /// Creates new planned stage
+    pub fn new<S: Into<String>, T: Into<TournamentSize>>(
+        number: StageNumber,
+        name: S,
+        stage_type: StageType,
+        size: T,
+    ) -> PlannedStage {
+        PlannedStage {
+            number,
+            name: name.into(),
+            stage_type,
+            size: size.into(),
+        }
+    }
+}
+
+/// The placement of the participants in a match of a plan.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PlannedMatch {
+    /// The id of the placed match, if it is known. Otherwise the match is found by its
+    /// position.
+    pub id: Option<MatchId>,
+    /// Number of the stage of the match.
+    pub stage_number: u64,
+    /// Number of the group of the match in the stage.
+    pub group_number: u64,
+    /// Number of the round of the match in the group.
+    pub round_number: u64,
+    /// Number of the match in the round.
+    pub number: u64,
+    /// The participants placed in the match by the numbers of their opponents.
+    pub opponents: BTreeMap<i64, Participant>,
+}
+impl PlannedMatch {
+    /// Creates new planned match at the position, without participants.
+    pub fn new(
+        stage_number: u64,
+        group_number: u64,
+        round_number: u64,
+        number: u64,
+    ) -> PlannedMatch {
+        PlannedMatch {
+            stage_number,
+            group_number,
+            round_number,
+            number,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the id of the placed match.
+    pub fn id(mut self, id: MatchId) -> PlannedMatch {
+        self.id = Some(id);
+        self
+    }
+
+    /// Places the participant as the opponent with the `number`.
+    pub fn opponent(mut self, number: i64, participant: Participant) -> PlannedMatch {
+        self.opponents.insert(number, participant);
+        self
+    }
+
+    fn position(&self) -> (u64, u64, u64, u64) {
+        (
+            self.stage_number,
+            self.group_number,
+            self.round_number,
+            self.number,
+        )
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "match {} of the round {} of the group {} of the stage {}",
+            self.number, self.round_number, self.group_number, self.stage_number
+        )
+    }
+
+    /// Returns the opponents of the match `m` with the planned participants placed, or `None`
+    /// if they are already placed so.
+    fn placed(&self, m: &Match) -> Option<Opponents> {
+        let mut opponents = m.opponents.0.clone();
+        let mut changed = false;
+        for (&number, participant) in &self.opponents {
+            match opponents.iter_mut().find(|o| o.number == number) {
+                Some(o) => {
+                    if o.participant.as_ref().map(key) != Some(key(participant)) {
+                        o.participant = Some(participant.clone());
+                        changed = true;
+                    }
+                }
+                None => {
+                    opponents.push(Opponent {
+                        number,
+                        participant: Some(participant.clone()),
+                        ..Default::default()
+                    });
+                    changed = true;
+                }
+            }
+        }
+        if changed {
+            opponents.sort_by_key(|o| o.number);
+            Some(Opponents(opponents))
+        } else {
+            None
+        }
+    }
+}
+
+/// Identifies a participant by its id or, if there is no id, by its name.
+fn key(participant: &Participant) -> &str {
+    match participant.id {
+        Some(ref id) => &id.0,
+        None => &participant.name,
+    }
+}
+
+fn invalid<T>(field: &str, reason: String) -> Result<T> {
+    Err(Error::Validation(ValidationError::Invalid(
+        field.to_owned(),
+        reason,
+    )))
+}
+
+/// The stages and the placements planned for a tournament.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Plan {
+    /// The planned tournament.
+    pub tournament_id: TournamentId,
+    /// The planned stages.
+    pub stages: Vec<PlannedStage>,
+    /// The planned matches.
+    pub matches: Vec<PlannedMatch>,
+}
+
+/// The changes a plan makes to its tournament, to be reviewed before they are applied.
+#[derive(Clone, Debug, Default)]
+pub struct PlanChanges {
+    /// The tournament to change.
+    pub tournament_id: TournamentId,
+    /// The patches placing the participants, by the ids of the matches.
+    pub placements: Vec<(MatchId, MatchPatch)>,
+}
+impl PlanChanges {
+    /// Returns `true` if the tournament is already as planned.
+    pub fn is_empty(&self) -> bool {
+        self.placements.is_empty()
+    }
+
+    /// Patches the placements as a batch, see `Toornament::update_matches`.
+    #[cfg(feature = "client-blocking")]
+    pub fn apply(
+        self,
+        client: &Toornament,
+        options: batch::BatchOptions,
+    ) -> Vec<(MatchId, batch::UpdateOutcome)> {
+        client.update_matches(self.tournament_id, self.placements, options, None)
+    }
+}
+
+impl Plan {
+    /// Creates new empty plan of the tournament
+    pub fn new(tournament_id: TournamentId) -> Plan {
+        Plan {
+            tournament_id,
+            stages: Vec::new(),
+            matches: Vec::new(),
+        }
+    }
+
+    /// Adds a stage to the plan.
+    pub fn stage(mut self, stage: PlannedStage) -> Plan {
+        self.stages.push(stage);
+        self
+    }
+
+    /// Adds a match to the plan.
+    pub fn add_match(mut self, planned: PlannedMatch) -> Plan {
+        self.matches.push(planned);
+        self
+    }
+
+    /// Checks the plan without the service: the stages are numbered uniquely and have sizes
+    /// accepted for their types, the matches are in the planned stages at unique positions,
+    /// and a participant plays at most once in a round and the stage does not have more
+    /// participants than its size.
+    pub fn validate(&self) -> Result<()> {
+        let mut stages = BTreeMap::new();
+        for stage in &self.stages {
+            if stage.number.0 < 1 {
+                return invalid(
+                    "stages",
+                    format!("{} is not a stage number", stage.number.0),
+                );
+            }
+            stage.size.validate(&stage.stage_type)?;
+            if stages.insert(stage.number.0 as u64, stage).is_some() {
+                return invalid(
+                    "stages",
+                    format!("the stage {} is planned twice", stage.number.0),
+                );
+            }
+        }
+
+        let mut positions = BTreeSet::new();
+        let mut ids = BTreeSet::new();
+        let mut rounds = BTreeSet::new();
+        let mut participants = BTreeMap::<u64, BTreeSet<&str>>::new();
+        for planned in &self.matches {
+            let stage = match stages.get(&planned.stage_number) {
+                Some(stage) => stage,
+                None => {
+                    return invalid(
+                        "matches",
+                        format!("the {} is not in a planned stage", planned.describe()),
+                    )
+                }
+            };
+            if planned.group_number < 1 || planned.round_number < 1 || planned.number < 1 {
+                return invalid(
+                    "matches",
+                    format!("the {} is not a position", planned.describe()),
+                );
+            }
+            if !positions.insert(planned.position()) {
+                return invalid(
+                    "matches",
+                    format!("the {} is planned twice", planned.describe()),
+                );
+            }
+            if let Some(ref id) = planned.id {
+                if !ids.insert(id) {
+                    return invalid("matches", format!("the match {} is planned twice", id.0));
+                }
+            }
+            for (&number, participant) in &planned.opponents {
+                if number < 1 {
+                    return invalid("opponents", format!("{} is not an opponent number", number));
+                }
+                let round = (
+                    planned.stage_number,
+                    planned.group_number,
+                    planned.round_number,
+                    key(participant),
+                );
+                if !rounds.insert(round) {
+                    return invalid(
+                        "opponents",
+                        format!(
+                            "{} plays twice in the round {} of the group {} of the stage {}",
+                            participant.name,
+                            planned.round_number,
+                            planned.group_number,
+                            planned.stage_number
+                        ),
+                    );
+                }
+                let stage_participants = participants.entry(planned.stage_number).or_default();
+                stage_participants.insert(key(participant));
+                if stage_participants.len() as i64 > stage.size.0 {
+                    return invalid(
+                        "opponents",
+                        format!(
+                            "the stage {} has more participants than its size {}",
+                            planned.stage_number, stage.size.0
+                        ),
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Compares the validated plan with the `stages` and the `matches` of the tournament and
+    /// returns the placements to change. Fails with `OrganizerError::NoSuchStage` if a planned
+    /// stage is missing and with a `ValidationError` if it differs or if a planned match is
+    /// not found.
+    pub fn diff(&self, stages: &Stages, matches: &Matches) -> Result<PlanChanges> {
+        self.validate()?;
+        self.compare(stages, matches)
+    }
+
+    /// Compares the plan, already validated, with the tournament, see `Plan::diff`.
+    fn compare(&self, stages: &Stages, matches: &Matches) -> Result<PlanChanges> {
+        for planned in &self.stages {
+            let stage = match stages.0.iter().find(|s| s.number == planned.number) {
+                Some(stage) => stage,
+                None => {
+                    return Err(Error::Organizer(OrganizerError::NoSuchStage(
+                        self.tournament_id.clone(),
+                        planned.number.clone(),
+                    )))
+                }
+            };
+            if stage.stage_type != planned.stage_type || stage.size != planned.size.0 {
+                return invalid(
+                    "stages",
+                    format!(
+                        "the stage {} is a {:?} stage of {} participants instead of {:?} of {}",
+                        stage.number.0,
+                        stage.stage_type,
+                        stage.size,
+                        planned.stage_type,
+                        planned.size.0
+                    ),
+                );
+            }
+        }
+
+        let mut placements = Vec::new();
+        for planned in &self.matches {
+            let found = matches.0.iter().find(|m| match planned.id {
+                Some(ref id) => m.id == *id,
+                None => {
+                    (m.stage_number, m.group_number, m.round_number, m.number) == planned.position()
+                }
+            });
+            let m = match found {
+                Some(m) => m,
+                None => {
+                    return invalid(
+                        "matches",
+                        format!("the {} is not in the tournament", planned.describe()),
+                    )
+                }
+            };
+            if let Some(opponents) = planned.placed(m) {
+                placements.push((m.id.clone(), MatchPatch::default().opponents(opponents)));
+            }
+        }
+        Ok(PlanChanges {
+            tournament_id: self.tournament_id.clone(),
+            placements,
+        })
+    }
+
+    /// Fetches the stages and all the pages of the matches of the tournament and compares the
+    /// plan with them, see `Plan::diff`. Nothing is changed until the returned changes are
+    /// applied.
+    #[cfg(feature = "client-blocking")]
+    pub fn review(&self, client: &Toornament) -> Result<PlanChanges> {
+        self.validate()?;
+        let stages = client.tournament_stages(self.tournament_id.clone())?;
+        let matches = client
+            .tournament_matches_pager(self.tournament_id.clone(), Includes::none())
+            .fetch_all()?;
+        self.compare(&stages, &Matches::from(matches))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::participants::ParticipantId;
+    use crate::stages::Stage;
+
+    fn participant(id: &str) -> Participant {
+        Participant::create(id).id(ParticipantId(id.to_owned()))
+    }
+
+    fn tournament() -> (Stages, Matches) {
+        let stages = Stages(vec![Stage {
+            number: StageNumber(1),
+            name: "Playoffs".to_owned(),
+            stage_type: StageType::SingleElimination,
+            size: 4,
+        }]);
+        let matches: Matches = serde_json::from_str(
+            r#"[
+            {
+                "id": "1", "type": "duel", "discipline": "chess", "status": "pending",
+                "tournament_id": "1", "number": 1, "stage_number": 1, "group_number": 1,
+                "round_number": 1, "date": "2015-09-06T00:10:00-06:00",
+                "opponents": [
+                    { "number": 1, "participant": { "id": "a", "name": "a" }, "forfeit": false },
+                    { "number": 2, "forfeit": false }
+                ]
+            },
+            {
+                "id": "2", "type": "duel", "discipline": "chess", "status": "pending",
+                "tournament_id": "1", "number": 2, "stage_number": 1, "group_number": 1,
+                "round_number": 1, "date": "2015-09-06T00:10:00-06:00",
+                "opponents": []
+            }
+        ]"#,
+        )
+        .unwrap();
+        (stages, matches)
+    }
+
+    fn plan() -> Plan {
+        Plan::new(TournamentId("1".to_owned()))
+            .stage(PlannedStage::new(
+                StageNumber(1),
+                "Playoffs",
+                StageType::SingleElimination,
+                4,
+            ))
+            .add_match(
+                PlannedMatch::new(1, 1, 1, 1)
+                    .opponent(1, participant("a"))
+                    .opponent(2, participant("b")),
+            )
+            .add_match(
+                PlannedMatch::new(1, 1, 1, 2)
+                    .id(MatchId("2".to_owned()))
+                    .opponent(1, participant("c"))
+                    .opponent(2, participant("d")),
+            )
+    }
+
+    #[test]
+    fn test_validate_plan() {
+        assert!(plan().validate().is_ok());
+
+        let twice = plan().add_match(PlannedMatch::new(1, 1, 2, 1).opponent(1, participant("a")));
+        assert!(twice.validate().is_ok());
+        let twice = plan().add_match(PlannedMatch::new(1, 1, 1, 3).opponent(1, participant("a")));
+        assert!(twice.validate().is_err());
+        let crowded = plan().add_match(PlannedMatch::new(1, 1, 2, 1).opponent(1, participant("e")));
+        assert!(crowded.validate().is_err());
+        let unplanned = plan().add_match(PlannedMatch::new(2, 1, 1, 1));
+        assert!(unplanned.validate().is_err());
+        let odd = plan().stage(PlannedStage::new(
+            StageNumber(2),
+            "Final",
+            StageType::SingleElimination,
+            3,
+        ));
+        assert!(odd.validate().is_err());
+    }
+
+    #[test]
+    fn test_diff_plan() {
+        let (stages, matches) = tournament();
+        let changes = plan().diff(&stages, &matches).unwrap();
+        assert_eq!(changes.placements.len(), 2);
+        let (ref id, ref patch) = changes.placements[0];
+        assert_eq!(id.0, "1");
+        let opponents = patch.opponents.as_ref().unwrap();
+        assert_eq!(opponents.0.len(), 2);
+        assert_eq!(opponents.0[1].participant.as_ref().unwrap().name, "b");
+
+        // A placement already done is not changed again.
+        let placed = Plan {
+            matches: vec![PlannedMatch::new(1, 1, 1, 1).opponent(1, participant("a"))],
+            ..plan()
+        };
+        assert!(placed.diff(&stages, &matches).unwrap().is_empty());
+
+        let missing = plan().add_match(PlannedMatch::new(1, 1, 2, 1));
+        assert!(matches!(
+            missing.diff(&stages, &matches),
+            Err(Error::Validation(_))
+        ));
+        assert!(matches!(
+            plan().diff(&Stages::default(), &matches),
+            Err(Error::Organizer(OrganizerError::NoSuchStage(..)))
+        ));
+    }
+}
@@ -0,0 +1,131 @@
+//! This module defines the local policies of a client deciding which matches may be updated,
+//! to lock the matches which must not change anymore even if the service would allow it.
+//!
+//! When a policy is set with `Toornament::write_policy`, the methods updating a match, its
+//! result, its games or their results fetch the match first and fail with
+//! `Error::Denied` if the policy does not allow the update.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use toornament::*;
+//! use toornament::policy::{BroadcastWindows, LockCompletedAfter};
+//!
+//! let now = chrono::Utc::now().fixed_offset();
+//! let policies: Vec<Box<dyn policy::WritePolicy>> = vec![
+//!     Box::new(LockCompletedAfter(chrono::Duration::hours(24))),
+//!     Box::new(BroadcastWindows(vec![(now, now + chrono::Duration::hours(2))])),
+//! ];
+//! let toornament = Toornament::with_application("API_TOKEN",
+//!                                               "CLIENT_ID",
+//!                                               "CLIENT_SECRET").unwrap()
+//!                             .write_policy(policies);
+//! ```
+#[cfg(feature = "client-blocking")]
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+
+use crate::matches::{Match, MatchStatus};
+
+/// A policy deciding whether a match may be updated.
+pub trait WritePolicy: Send + Sync {
+    /// Returns the reason to deny an update of the match `current`, as it is stored by the
+    /// service, at the moment `now`, or `None` to allow it.
+    fn deny_match_update(&self, current: &Match, now: DateTime<Utc>) -> Option<String>;
+}
+
+/// Denies the updates of the matches completed more than the duration ago, judging by the
+/// date of the match.
+#[derive(Clone, Debug)]
+pub struct LockCompletedAfter(pub Duration);
+impl WritePolicy for LockCompletedAfter {
+    fn deny_match_update(&self, current: &Match, now: DateTime<Utc>) -> Option<String> {
+        if current.status == MatchStatus::Completed && current.date + self.0 < now {
+            Some(format!(
+                "match {} is completed for more than {} hours",
+                current.id.0,
+                self.0.num_hours()
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// Denies all the updates during the time windows, for example while the matches are
+/// broadcast.
+#[derive(Clone, Debug)]
+pub struct BroadcastWindows(pub Vec<(DateTime<FixedOffset>, DateTime<FixedOffset>)>);
+impl WritePolicy for BroadcastWindows {
+    fn deny_match_update(&self, _: &Match, now: DateTime<Utc>) -> Option<String> {
+        self.0
+            .iter()
+            .find(|&&(start, end)| start <= now && now < end)
+            .map(|(start, end)| format!("the broadcast window {} - {} is open", start, end))
+    }
+}
+
+/// Denies the update if any of the policies denies it.
+impl WritePolicy for Vec<Box<dyn WritePolicy>> {
+    fn deny_match_update(&self, current: &Match, now: DateTime<Utc>) -> Option<String> {
+        self.iter().find_map(|p| p.deny_match_update(current, now))
+    }
+}
+
+/// The policy of a client.
+#[cfg(feature = "client-blocking")]
+#[derive(Clone)]
+pub(crate) struct Policy(pub(crate) Arc<dyn WritePolicy>);
+#[cfg(feature = "client-blocking")]
+impl std::fmt::Debug for Policy {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("Policy")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_policies() {
+        let string = r#"
+        {
+            "id": "2",
+            "type": "duel",
+            "discipline": "my_discipline",
+            "status": "completed",
+            "tournament_id": "1",
+            "number": 1,
+            "stage_number": 1,
+            "group_number": 1,
+            "round_number": 1,
+            "date": "2015-09-06T00:10:00-06:00",
+            "opponents": []
+        }"#;
+        let m: Match = serde_json::from_str(string).unwrap();
+        let date = m.date.with_timezone(&Utc);
+        let lock = LockCompletedAfter(Duration::hours(24));
+        assert!(lock
+            .deny_match_update(&m, date + Duration::hours(23))
+            .is_none());
+        assert!(lock
+            .deny_match_update(&m, date + Duration::hours(25))
+            .is_some());
+
+        let windows = BroadcastWindows(vec![(m.date, m.date + Duration::hours(2))]);
+        let policies: Vec<Box<dyn WritePolicy>> = vec![Box::new(lock), Box::new(windows)];
+        assert!(policies
+            .deny_match_update(&m, date + Duration::hours(1))
+            .unwrap()
+            .contains("broadcast"));
+        assert!(policies
+            .deny_match_update(&m, date + Duration::hours(3))
+            .is_none());
+        assert!(policies
+            .deny_match_update(&m, date + Duration::days(3))
+            .unwrap()
+            .contains("completed"));
+    }
+}
@@ -0,0 +1,152 @@
+/// Where an operation made of several requests is, for example fetching a snapshot of a
+/// tournament or applying a batch of updates.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgressUpdate {
+    /// Number of the requests done.
+    pub done: usize,
+    /// Number of the requests of the operation, if it is known.
+    pub total: Option<usize>,
+    /// The resource of the request being made, like "the matches of tournament 1", or `None`
+    /// once the operation is finished.
+    pub endpoint: Option<String>,
+}
+impl ProgressUpdate {
+    /// Returns the part of the operation done, from 0 to 1, if its total is known.
+    pub fn fraction(&self) -> Option<f64> {
+        match self.total {
+            Some(0) => Some(1.0),
+            Some(total) => Some(self.done.min(total) as f64 / total as f64),
+            None => None,
+        }
+    }
+}
+
+/// Receives the progress of an operation made of several requests, for example to draw a
+/// progress bar. It is passed to every operation separately, so the updates of the operations
+/// running at the same time are never mixed, and is told before every request and once the
+/// operation is finished. The requests of a batch may run in several threads, so the updates
+/// may come from any of them.
+///
+/// It is implemented for the closures:
+///
+/// ```rust,no_run
+/// use toornament::*;
+///
+/// let toornament = Toornament::with_application("API_TOKEN",
+///                                               "CLIENT_ID",
+///                                               "CLIENT_SECRET").unwrap();
+/// let progress = |update: &ProgressUpdate| {
+///     if let Some(ref endpoint) = update.endpoint {
+///         eprintln!("[{}/{:?}] Fetching {}", update.done, update.total, endpoint);
+///     }
+/// };
+/// let bundle = toornament::archive::snapshot(&toornament,
+///                                            TournamentId("1".to_owned()),
+///                                            Some(&progress));
+/// ```
+pub trait Progress: Send + Sync {
+    /// Called when the operation goes on.
+    fn update(&self, update: &ProgressUpdate);
+}
+impl<F: Fn(&ProgressUpdate) + Send + Sync> Progress for F {
+    fn update(&self, update: &ProgressUpdate) {
+        self(update)
+    }
+}
+
+/// Counts the requests of an operation made one after another and reports them.
+#[cfg(feature = "client-blocking")]
+pub(crate) struct Steps<'a> {
+    progress: Option<&'a dyn Progress>,
+    started: usize,
+    total: Option<usize>,
+}
+#[cfg(feature = "client-blocking")]
+impl<'a> Steps<'a> {
+    /// Creates the steps of an operation of `total` requests.
+    pub fn new(progress: Option<&'a dyn Progress>, total: Option<usize>) -> Steps<'a> {
+        Steps {
+            progress,
+            started: 0,
+            total,
+        }
+    }
+
+    /// Reports the request of the `endpoint` which is about to be made, the previous ones being
+    /// done.
+    pub fn start<S: Into<String>>(&mut self, endpoint: S) {
+        report(
+            self.progress,
+            self.started,
+            self.total,
+            Some(endpoint.into()),
+        );
+        self.started += 1;
+    }
+
+    /// Adds `more` requests to the total, once the operation knows it has to make them.
+    pub fn extend(&mut self, more: usize) {
+        self.total = self.total.map(|total| total + more);
+    }
+
+    /// Reports the end of the operation.
+    pub fn finish(self) {
+        report(self.progress, self.started, self.total, None);
+    }
+}
+
+/// Sends the update to the receiver, if any.
+#[cfg(feature = "client-blocking")]
+pub(crate) fn report(
+    progress: Option<&dyn Progress>,
+    done: usize,
+    total: Option<usize>,
+    endpoint: Option<String>,
+) {
+    if let Some(progress) = progress {
+        progress.update(&ProgressUpdate {
+            done,
+            total,
+            endpoint,
+        });
+    }
+}
+
+#[cfg(all(test, feature = "client-blocking"))]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_steps() {
+        let updates = Mutex::new(Vec::new());
+        let progress = |update: &ProgressUpdate| updates.lock().unwrap().push(update.clone());
+        let mut steps = Steps::new(Some(&progress), Some(1));
+        steps.start("the tournament 1");
+        steps.extend(1);
+        steps.start("the stages of tournament 1");
+        steps.finish();
+
+        let updates = updates.lock().unwrap();
+        assert_eq!(updates.len(), 3);
+        assert_eq!(updates[0].done, 0);
+        assert_eq!(updates[0].total, Some(1));
+        assert_eq!(updates[0].fraction(), Some(0.0));
+        assert_eq!(
+            updates[1].endpoint.as_deref(),
+            Some("the stages of tournament 1")
+        );
+        assert_eq!(
+            updates[2],
+            ProgressUpdate {
+                done: 2,
+                total: Some(2),
+                endpoint: None,
+            }
+        );
+        assert_eq!(updates[2].fraction(), Some(1.0));
+
+        // Nothing is reported without a receiver.
+        Steps::new(None, None).finish();
+    }
+}
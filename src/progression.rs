@@ -0,0 +1,357 @@
+use std::collections::BTreeMap;
+
+use crate::matches::{BracketBranch, Match, MatchId, MatchStatus, Matches};
+use crate::participants::Participant;
+use crate::stages::{StageType, Stages};
+
+/// An outcome of a match for one of its opponents.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Outcome {
+    /// The opponent has won the match
+    Winner,
+    /// The opponent has lost the match
+    Loser,
+}
+
+/// An edge of the bracket progression: the opponent with the `outcome` in the `from` match
+/// plays the `to` match as the opponent with the `slot` number.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct ProgressionEdge {
+    /// The match which is played first.
+    pub from: MatchId,
+    /// The outcome of the opponent in the `from` match.
+    pub outcome: Outcome,
+    /// The match which is played next.
+    pub to: MatchId,
+    /// The opponent number in the `to` match (1 or 2).
+    pub slot: i64,
+}
+
+/// A graph of how the opponents move through the elimination brackets.
+///
+/// The graph is computed from the matches only: inside a bracket branch (see
+/// `Match::bracket_branch`) the matches of a round are ordered by their positions (or numbers),
+/// and the winner of the n-th match of a round plays the `n / 2`-th match of the next round.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Progression {
+    /// All the edges of the graph.
+    pub edges: Vec<ProgressionEdge>,
+    /// The grand final reset matches. Such a match is played only if the loser bracket
+    /// finalist (the opponent number 2) wins the grand final.
+    pub resets: Vec<MatchId>,
+}
+
+fn rounds(
+    matches: &[&Match],
+    stage: u64,
+    stage_type: &StageType,
+    branch: BracketBranch,
+) -> Vec<Vec<MatchId>> {
+    let mut rounds = BTreeMap::new();
+    for m in matches.iter().filter(|m| {
+        m.stage_number == stage && m.bracket_branch(stage_type).as_ref() == Some(&branch)
+    }) {
+        rounds
+            .entry(m.round_number)
+            .or_insert_with(Vec::new)
+            .push((m.position.unwrap_or(m.number), m.id.clone()));
+    }
+    rounds
+        .into_values()
+        .map(|mut round| {
+            round.sort();
+            round.into_iter().map(|(_, id)| id).collect()
+        })
+        .collect()
+}
+
+impl Progression {
+    /// Computes the progression of all the single and double elimination stages. The other
+    /// stages are ignored.
+    pub fn compute(stages: &Stages, matches: &Matches) -> Progression {
+        let mut progression = Progression::default();
+        let matches = matches.0.iter().collect::<Vec<&Match>>();
+        for stage in &stages.0 {
+            let number = stage.number.0 as u64;
+            let branch = |branch| rounds(&matches, number, &stage.stage_type, branch);
+            match stage.stage_type {
+                StageType::SingleElimination => {
+                    progression.link_bracket(&branch(BracketBranch::Winners));
+                }
+                StageType::DoubleElimination => {
+                    let winners = branch(BracketBranch::Winners);
+                    let losers = branch(BracketBranch::Losers);
+                    let grand_final = branch(BracketBranch::GrandFinal);
+                    let reset = branch(BracketBranch::GrandFinalReset);
+                    progression.link_bracket(&winners);
+                    progression.link_loser_bracket(&winners, &losers);
+                    if let Some(grand_final) = grand_final.first().and_then(|r| r.first()) {
+                        if let Some(last) = winners.last().and_then(|r| r.first()) {
+                            progression.link(last, Outcome::Winner, grand_final, 1);
+                        }
+                        if let Some(last) = losers.last().and_then(|r| r.first()) {
+                            progression.link(last, Outcome::Winner, grand_final, 2);
+                        }
+                        if let Some(reset) = reset.first().and_then(|r| r.first()) {
+                            progression.link(grand_final, Outcome::Winner, reset, 1);
+                            progression.link(grand_final, Outcome::Loser, reset, 2);
+                            progression.resets.push(reset.clone());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        progression
+    }
+
+    fn link(&mut self, from: &MatchId, outcome: Outcome, to: &MatchId, slot: i64) {
+        self.edges.push(ProgressionEdge {
+            from: from.clone(),
+            outcome,
+            to: to.clone(),
+            slot,
+        });
+    }
+
+    fn link_pairs(&mut self, from: &[MatchId], outcome: Outcome, to: &[MatchId]) {
+        for (i, id) in from.iter().enumerate() {
+            if let Some(next) = to.get(i / 2) {
+                self.link(id, outcome, next, (i % 2) as i64 + 1);
+            }
+        }
+    }
+
+    fn link_bracket(&mut self, bracket: &[Vec<MatchId>]) {
+        for pair in bracket.windows(2) {
+            self.link_pairs(&pair[0], Outcome::Winner, &pair[1]);
+        }
+    }
+
+    fn link_loser_bracket(&mut self, winners: &[Vec<MatchId>], losers: &[Vec<MatchId>]) {
+        if let (Some(first), Some(to)) = (winners.first(), losers.first()) {
+            self.link_pairs(first, Outcome::Loser, to);
+        }
+        for (round, from) in winners.iter().enumerate().skip(1) {
+            if let Some(to) = losers.get(2 * round - 1) {
+                for (id, next) in from.iter().zip(to.iter()) {
+                    self.link(id, Outcome::Loser, next, 2);
+                }
+            }
+        }
+        for (round, pair) in losers.windows(2).enumerate() {
+            if round % 2 == 0 {
+                for (id, next) in pair[0].iter().zip(pair[1].iter()) {
+                    self.link(id, Outcome::Winner, next, 1);
+                }
+            } else {
+                self.link_pairs(&pair[0], Outcome::Winner, &pair[1]);
+            }
+        }
+    }
+
+    /// Returns the edge the opponent with the `outcome` in the match follows.
+    pub fn next(&self, from: &MatchId, outcome: Outcome) -> Option<&ProgressionEdge> {
+        self.edges
+            .iter()
+            .find(|e| e.from == *from && e.outcome == outcome)
+    }
+
+    /// Returns the edges leading to the match.
+    pub fn feeders(&self, to: &MatchId) -> Vec<&ProgressionEdge> {
+        self.edges.iter().filter(|e| e.to == *to).collect()
+    }
+
+    /// Returns the matches which become schedulable once the `completed` match is completed:
+    /// all the matches which lead to them are completed. A grand final reset is returned only
+    /// if the grand final result in `matches` shows the loser bracket finalist has won.
+    pub fn unlocked_by(&self, completed: &MatchId, matches: &Matches) -> Vec<MatchId> {
+        let is_completed = |id: &MatchId| {
+            id == completed
+                || matches
+                    .0
+                    .iter()
+                    .any(|m| m.id == *id && m.status == MatchStatus::Completed)
+        };
+        let reset_needed = || {
+            matches
+                .0
+                .iter()
+                .find(|m| m.id == *completed)
+                .and_then(|m| m.winner())
+                .map(|o| o.number == 2)
+                .unwrap_or(false)
+        };
+        let mut unlocked = Vec::new();
+        for edge in self.edges.iter().filter(|e| e.from == *completed) {
+            if !unlocked.contains(&edge.to)
+                && self.feeders(&edge.to).iter().all(|e| is_completed(&e.from))
+                && (!self.resets.contains(&edge.to) || reset_needed())
+            {
+                unlocked.push(edge.to.clone());
+            }
+        }
+        unlocked
+    }
+
+    /// Returns the participants who play the match as far as they are known from the results
+    /// of the matches leading to it. The first element is the opponent number 1.
+    pub fn pairing<'a>(&self, to: &MatchId, matches: &'a Matches) -> [Option<&'a Participant>; 2] {
+        let mut pairing = [None, None];
+        for edge in self.feeders(to) {
+            let from = match matches.by_id(&edge.from) {
+                Some(m) if m.status == MatchStatus::Completed => m,
+                _ => continue,
+            };
+            let opponent = match edge.outcome {
+                Outcome::Winner => from.winner(),
+                Outcome::Loser => from.loser(),
+            };
+            if let Some(slot) = pairing.get_mut((edge.slot - 1) as usize) {
+                *slot = opponent.and_then(|o| o.participant.as_ref());
+            }
+        }
+        pairing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::MatchResultSimple;
+    use crate::opponents::{Opponent, Opponents};
+    use crate::stages::{Stage, StageNumber};
+
+    fn stage(stage_type: StageType) -> Stages {
+        Stages(vec![Stage {
+            number: StageNumber(1),
+            name: "Playoffs".to_owned(),
+            stage_type,
+            size: 4,
+        }])
+    }
+
+    fn bracket_match(id: &str, group: u64, round: u64, number: u64) -> Match {
+        let string = r#"
+        {
+            "id": "0",
+            "type": "duel",
+            "discipline": "my_discipline",
+            "status": "pending",
+            "tournament_id": "1",
+            "number": 1,
+            "stage_number": 1,
+            "group_number": 1,
+            "round_number": 1,
+            "date": "2015-09-06T00:10:00-0600",
+            "opponents": []
+        }"#;
+        let m: Match = serde_json::from_str(string).unwrap();
+        m.id(MatchId(id.to_owned()))
+            .group_number(group)
+            .round_number(round)
+            .number(number)
+    }
+
+    fn completed(m: Match, winner: &str, loser: &str) -> Match {
+        let opponent = |number, name: &str, result| Opponent {
+            number,
+            participant: Some(Participant::create(name)),
+            result: Some(result),
+            ..Default::default()
+        };
+        Match {
+            status: MatchStatus::Completed,
+            opponents: Opponents(vec![
+                opponent(1, winner, MatchResultSimple::Win),
+                opponent(2, loser, MatchResultSimple::Loss),
+            ]),
+            ..m
+        }
+    }
+
+    fn id(s: &str) -> MatchId {
+        MatchId(s.to_owned())
+    }
+
+    #[test]
+    fn test_single_elimination_progression() {
+        let matches = Matches::from(vec![
+            completed(bracket_match("sf1", 1, 1, 1), "A", "B"),
+            bracket_match("sf2", 1, 1, 2),
+            bracket_match("final", 1, 2, 3),
+        ]);
+        let p = Progression::compute(&stage(StageType::SingleElimination), &matches);
+
+        assert_eq!(p.edges.len(), 2);
+        let next = p.next(&id("sf2"), Outcome::Winner).unwrap();
+        assert_eq!(next.to, id("final"));
+        assert_eq!(next.slot, 2);
+        assert!(p.next(&id("sf1"), Outcome::Loser).is_none());
+
+        assert!(p.unlocked_by(&id("sf1"), &matches).is_empty());
+        assert_eq!(p.unlocked_by(&id("sf2"), &matches), vec![id("final")]);
+
+        let pairing = p.pairing(&id("final"), &matches);
+        assert_eq!(pairing[0].unwrap().name, "A");
+        assert!(pairing[1].is_none());
+    }
+
+    #[test]
+    fn test_double_elimination_progression() {
+        let matches = Matches::from(vec![
+            bracket_match("w1", 1, 1, 1),
+            bracket_match("w2", 1, 1, 2),
+            bracket_match("wf", 1, 2, 3),
+            bracket_match("l1", 2, 1, 4),
+            bracket_match("lf", 2, 2, 5),
+            bracket_match("gf", 3, 1, 6),
+            bracket_match("reset", 3, 2, 7),
+        ]);
+        let p = Progression::compute(&stage(StageType::DoubleElimination), &matches);
+
+        let edge = |from, outcome| {
+            let e = p.next(&id(from), outcome).unwrap();
+            (e.to.0.clone(), e.slot)
+        };
+        assert_eq!(edge("w1", Outcome::Winner), ("wf".to_owned(), 1));
+        assert_eq!(edge("w2", Outcome::Winner), ("wf".to_owned(), 2));
+        assert_eq!(edge("w1", Outcome::Loser), ("l1".to_owned(), 1));
+        assert_eq!(edge("w2", Outcome::Loser), ("l1".to_owned(), 2));
+        assert_eq!(edge("l1", Outcome::Winner), ("lf".to_owned(), 1));
+        assert_eq!(edge("wf", Outcome::Loser), ("lf".to_owned(), 2));
+        assert_eq!(edge("wf", Outcome::Winner), ("gf".to_owned(), 1));
+        assert_eq!(edge("lf", Outcome::Winner), ("gf".to_owned(), 2));
+        assert_eq!(edge("gf", Outcome::Winner), ("reset".to_owned(), 1));
+        assert_eq!(edge("gf", Outcome::Loser), ("reset".to_owned(), 2));
+        assert_eq!(p.resets, vec![id("reset")]);
+
+        let mut played = matches.clone();
+        played.0[5] = completed(played.0[5].clone(), "W", "L");
+        assert!(p.unlocked_by(&id("gf"), &played).is_empty());
+        played.0[5].opponents.0[0].number = 2;
+        played.0[5].opponents.0[1].number = 1;
+        assert_eq!(p.unlocked_by(&id("gf"), &played), vec![id("reset")]);
+    }
+
+    #[test]
+    fn test_progression_uses_match_branches() {
+        let matches = Matches::from(vec![
+            bracket_match("w1", 5, 1, 1).branch(Some(BracketBranch::Winners)),
+            bracket_match("w2", 5, 1, 2).branch(Some(BracketBranch::Winners)),
+            bracket_match("l1", 5, 1, 3).branch(Some(BracketBranch::Losers)),
+        ]);
+        let p = Progression::compute(&stage(StageType::DoubleElimination), &matches);
+        assert_eq!(p.next(&id("w2"), Outcome::Loser).unwrap().to, id("l1"));
+    }
+
+    #[test]
+    fn test_other_stages_are_ignored() {
+        let matches = Matches::from(vec![
+            bracket_match("1", 1, 1, 1),
+            bracket_match("2", 1, 2, 2),
+        ]);
+        let p = Progression::compute(&stage(StageType::League), &matches);
+        assert!(p.edges.is_empty());
+    }
+}
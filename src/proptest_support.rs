@@ -0,0 +1,444 @@
+//! The `proptest` strategies of the models and the filters, so that the crate and its users
+//! may test the serialization, the builders and the comparisons with any value: `any::<Match>()`
+//! generates the matches. The generated values are valid for the service: the texts are
+//! printable, the numbers are positive and the dates are within the calendar it accepts.
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, FixedOffset, TimeZone};
+use proptest::option;
+use proptest::prelude::*;
+
+use crate::*;
+
+/// A printable text, like a name.
+fn text() -> impl Strategy<Value = String> {
+    "[[:print:]]{1,30}"
+}
+
+/// An identifier of the service.
+fn identifier() -> impl Strategy<Value = String> {
+    "[0-9a-f]{24}"
+}
+
+fn date() -> impl Strategy<Value = Date> {
+    (2000i32..2100, 1u32..=12, 1u32..=28).prop_map(|(y, m, d)| Date::from_ymd_opt(y, m, d).unwrap())
+}
+
+fn date_time() -> impl Strategy<Value = DateTime<FixedOffset>> {
+    // From 2000 to 2100, in the time zones from UTC-12:00 to UTC+14:00 by quarter of an hour.
+    (946_684_800i64..4_102_444_800, -48i32..=56).prop_map(|(seconds, quarters)| {
+        FixedOffset::east_opt(quarters * 900)
+            .unwrap()
+            .timestamp_opt(seconds, 0)
+            .unwrap()
+    })
+}
+
+macro_rules! arbitrary_enum {
+    ($name:ident { $($variant:ident),* $(,)* }) => {
+        impl Arbitrary for $name {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<$name>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                prop_oneof![$(Just($name::$variant)),*].boxed()
+            }
+        }
+    };
+}
+
+arbitrary_enum!(TournamentStatus {
+    Setup,
+    Running,
+    Pending,
+    Completed
+});
+arbitrary_enum!(MatchType { Duel, FreeForAll });
+arbitrary_enum!(MatchStatus {
+    Pending,
+    Running,
+    Completed
+});
+arbitrary_enum!(MatchFormat {
+    None,
+    One,
+    HomeAway,
+    BestOf3,
+    BestOf5,
+    BestOf7,
+    BestOf9,
+    BestOf11
+});
+arbitrary_enum!(BracketBranch {
+    Winners,
+    Losers,
+    GrandFinal,
+    GrandFinalReset
+});
+arbitrary_enum!(ParticipantType { Team, Single });
+arbitrary_enum!(MatchResultSimple { Win, Draw, Loss });
+arbitrary_enum!(CustomFieldType {
+    SteamId,
+    Birthdate,
+    Facebook,
+    Fullname,
+    Instagram,
+    Snapchat,
+    Text,
+    Twitch,
+    Twitter,
+    Vimeo,
+    Website,
+    Youtube
+});
+arbitrary_enum!(MatchSort {
+    ScheduledAscending,
+    ScheduledDescending
+});
+
+impl Arbitrary for CustomField {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<CustomField>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (any::<CustomFieldType>(), text(), text())
+            .prop_map(|(field_type, label, value)| CustomField {
+                field_type,
+                label,
+                value,
+            })
+            .boxed()
+    }
+}
+
+fn custom_fields() -> impl Strategy<Value = Option<CustomFields>> {
+    option::of(prop::collection::vec(any::<CustomField>(), 0..3).prop_map(CustomFields))
+}
+
+/// A participant without a lineup: a player, or a team member.
+fn player() -> impl Strategy<Value = Participant> {
+    (
+        option::of(identifier()),
+        text(),
+        option::of("[A-Z]{2}"),
+        option::of("[a-z]{1,10}@[a-z]{1,10}\\.com"),
+        option::of(any::<bool>()),
+        custom_fields(),
+    )
+        .prop_map(
+            |(id, name, country, email, check_in, custom_fields)| Participant {
+                id: id.map(ParticipantId),
+                country,
+                email,
+                check_in,
+                custom_fields,
+                ..Participant::create(name)
+            },
+        )
+}
+
+impl Arbitrary for Participant {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Participant>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (
+            player(),
+            option::of(prop::collection::vec(player(), 0..5)),
+            custom_fields(),
+        )
+            .prop_map(|(participant, lineup, custom_fields_private)| Participant {
+                lineup: lineup.map(Participants::from),
+                custom_fields_private,
+                ..participant
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Opponent {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Opponent>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (
+            1i64..=16,
+            option::of(player()),
+            option::of(any::<MatchResultSimple>()),
+            option::of(1i64..=16),
+            option::of(0i64..100),
+            any::<bool>(),
+        )
+            .prop_map(
+                |(number, participant, result, rank, score, forfeit)| Opponent {
+                    number,
+                    participant,
+                    result,
+                    rank,
+                    score,
+                    forfeit,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for Match {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Match>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (
+            (
+                identifier(),
+                any::<MatchType>(),
+                "[a-z_]{1,20}",
+                any::<MatchStatus>(),
+                identifier(),
+                date_time(),
+            ),
+            (1u64..=512, 1u64..=8, 1u64..=16, 1u64..=16),
+            (
+                option::of("[A-Za-z]{1,10}/[A-Za-z_]{1,10}"),
+                option::of(any::<MatchFormat>()),
+                prop::collection::vec(any::<Opponent>(), 0..4),
+                option::of(any::<BracketBranch>()),
+                option::of(1u64..=256),
+            ),
+        )
+            .prop_map(
+                |(
+                    (id, match_type, discipline_id, status, tournament_id, date),
+                    (number, stage_number, group_number, round_number),
+                    (time_zone, match_format, opponents, branch, position),
+                )| Match {
+                    id: MatchId(id),
+                    match_type,
+                    discipline_id: DisciplineId(discipline_id),
+                    status,
+                    tournament_id: TournamentId(tournament_id),
+                    number,
+                    stage_number,
+                    group_number,
+                    round_number,
+                    date,
+                    time_zone,
+                    match_format,
+                    opponents: Opponents(opponents),
+                    games: None,
+                    branch,
+                    position,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for Tournament {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Tournament>;
+
+    #[allow(deprecated)]
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (
+            (
+                option::of(identifier()),
+                "[a-z_]{1,20}",
+                text(),
+                any::<TournamentStatus>(),
+                any::<bool>(),
+                any::<bool>(),
+                2i64..=512,
+            ),
+            (
+                option::of(text()),
+                option::of(date()),
+                option::of(date()),
+                option::of("[A-Z]{2}"),
+                option::of(any::<ParticipantType>()),
+                option::of(any::<MatchType>()),
+                option::of(any::<MatchFormat>()),
+            ),
+            (
+                option::of(any::<bool>()),
+                option::of(any::<bool>()),
+                option::of(date_time()),
+                option::of(date_time()),
+                option::of(any::<bool>()),
+            ),
+        )
+            .prop_map(
+                |(
+                    (id, discipline, name, status, online, public, size),
+                    (
+                        full_name,
+                        date_start,
+                        date_end,
+                        country,
+                        participant_type,
+                        match_type,
+                        format,
+                    ),
+                    (check_in, registration_enabled, opening, closing, archived),
+                )| Tournament {
+                    full_name,
+                    date_start,
+                    date_end,
+                    country,
+                    participant_type,
+                    match_type,
+                    match_format: format,
+                    check_in,
+                    registration_enabled,
+                    registration_opening_datetime: opening,
+                    registration_closing_datetime: closing,
+                    archived,
+                    ..Tournament::new(
+                        id.map(TournamentId),
+                        DisciplineId(discipline),
+                        name,
+                        status,
+                        online,
+                        public,
+                        size,
+                    )
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for DateBound {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<DateBound>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        prop_oneof![
+            date().prop_map(DateBound::Inclusive),
+            date().prop_map(DateBound::Exclusive)
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for DateRange {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<DateRange>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (
+            option::of(any::<DateBound>()),
+            option::of(any::<DateBound>()),
+        )
+            .prop_map(|(after, before)| DateRange { after, before })
+            .boxed()
+    }
+}
+
+impl Arbitrary for MatchFilter {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<MatchFilter>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (
+            (
+                option::of(any::<bool>()),
+                option::of(any::<bool>()),
+                option::of(any::<MatchSort>()),
+                option::of(identifier()),
+            ),
+            (
+                prop::collection::btree_set(identifier(), 0..=MAX_TOURNAMENT_IDS),
+                any::<bool>(),
+                any::<DateRange>(),
+                option::of(1i64..=100),
+                option::of(1i64..=100),
+            ),
+        )
+            .prop_map(
+                |(
+                    (featured, has_result, sort, participant_id),
+                    (tournament_ids, with_games, scheduled, page, per_page),
+                )| MatchFilter {
+                    featured,
+                    has_result,
+                    sort,
+                    participant_id: participant_id.map(ParticipantId),
+                    tournament_ids: tournament_ids
+                        .into_iter()
+                        .map(TournamentId)
+                        .collect::<BTreeSet<_>>(),
+                    with_games,
+                    scheduled,
+                    page,
+                    per_page,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for TournamentFilter {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<TournamentFilter>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (
+            option::of(text()),
+            option::of(any::<bool>()),
+            option::of("[a-z_]{1,20}"),
+            any::<DateRange>(),
+            any::<DateRange>(),
+            option::of(any::<bool>()),
+            option::of(1i64..=100),
+            option::of(1i64..=100),
+        )
+            .prop_map(
+                |(name, featured, discipline_id, start, end, archived, page, per_page)| {
+                    TournamentFilter {
+                        name,
+                        featured,
+                        discipline_id: discipline_id.map(DisciplineId),
+                        start,
+                        end,
+                        archived,
+                        page,
+                        per_page,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip<T>(value: &T) -> T
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        serde_json::from_str(&serde_json::to_string(value).unwrap()).unwrap()
+    }
+
+    proptest! {
+        #[test]
+        fn test_models_round_trip(
+            t in any::<Tournament>(),
+            m in any::<Match>(),
+            p in any::<Participant>(),
+        ) {
+            prop_assert_eq!(round_trip(&t), t);
+            prop_assert_eq!(round_trip(&m), m);
+            prop_assert_eq!(round_trip(&p), p);
+        }
+
+        #[test]
+        fn test_filters_validate(m in any::<MatchFilter>(), t in any::<TournamentFilter>()) {
+            prop_assert_eq!(m.validate().is_ok(), !m.scheduled.is_empty());
+            prop_assert_eq!(t.validate().is_ok(), !t.start.is_empty() && !t.end.is_empty());
+            prop_assert!(m.to_query_string().chars().all(|c| c.is_ascii_graphic()));
+        }
+    }
+}
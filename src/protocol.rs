@@ -0,0 +1,590 @@
+//! The parts shared by the blocking and the asynchronous clients: the description of the
+//! requests, the decisions to retry them and the parsing of the responses, without any I/O.
+//! The statuses and the headers are the types of `reqwest`, which both clients are built on.
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::io::Read;
+use std::time::{Duration, Instant};
+
+use serde::de::DeserializeOwned;
+
+use crate::endpoints::{Endpoint, API_BASE};
+use crate::scope::parse_scopes;
+use crate::{
+    lenient, AccessToken, ConfigError, Error, Partial, RequestOptions, Result, RetryPolicy, Scope,
+    Secret, ToornamentServiceError,
+};
+
+/// A request described without sending it: the blocking and the asynchronous clients send it
+/// with their HTTP stacks.
+#[derive(Clone, Debug)]
+pub(crate) struct Request {
+    /// The HTTP method, in lowercase.
+    // The asynchronous client sends only the bodiless `GET` requests.
+    #[cfg_attr(not(feature = "client-blocking"), allow(dead_code))]
+    pub method: &'static str,
+    /// The address, with the base of the client.
+    pub url: String,
+    /// The headers, authorizing the request and set by the client.
+    pub headers: Vec<(String, String)>,
+    /// The body.
+    // The asynchronous client sends only the bodiless `GET` requests.
+    #[cfg_attr(not(feature = "client-blocking"), allow(dead_code))]
+    pub body: Option<String>,
+    /// Description of the request, added to the errors.
+    pub context: String,
+    /// Whether the endpoint accepts the `_locale` parameter.
+    pub localized: bool,
+}
+impl Request {
+    /// Describes the request to the endpoint. The request is not authorized yet.
+    pub fn new(
+        method: &'static str,
+        endpoint: &Endpoint,
+        base_url: &str,
+        body: Option<String>,
+    ) -> Request {
+        let action = match method {
+            "get" => "fetching",
+            "post" => "creating",
+            "delete" => "deleting",
+            _ => "updating",
+        };
+        Request {
+            method,
+            url: rebase(base_url, &endpoint.to_string()),
+            headers: Vec::new(),
+            body,
+            context: format!("{} {}", action, endpoint.resource()),
+            localized: endpoint.localized(),
+        }
+    }
+
+    /// Returns the request authorized by the API token and the access token.
+    pub fn authorized(mut self, api_token: &Secret, access_token: &str) -> Request {
+        self.headers.extend(vec![
+            ("X-Api-Key".to_owned(), api_token.expose().to_owned()),
+            (
+                "Authorization".to_owned(),
+                format!("Bearer {}", access_token),
+            ),
+        ]);
+        self
+    }
+
+    /// Returns the request with the headers and the query parameters of the options, and the
+    /// locale if the endpoint accepts it.
+    pub fn with_options(mut self, options: &RequestOptions) -> Request {
+        self.headers.extend(options.headers.iter().cloned());
+        let localized = self.localized;
+        let locale = options
+            .locale
+            .iter()
+            .filter(|_| localized)
+            .map(|locale| ("_locale".to_owned(), locale.clone()));
+        for (name, value) in options.query.iter().cloned().chain(locale) {
+            let separator = if self.url.contains('?') { '&' } else { '?' };
+            self.url = format!(
+                "{}{}{}={}",
+                self.url,
+                separator,
+                crate::filters::encode(&name),
+                crate::filters::encode(&value)
+            );
+        }
+        self
+    }
+}
+
+/// The headers which authorize the requests and may not be set by the client.
+const AUTHORIZATION_HEADERS: [&str; 2] = ["authorization", "x-api-key"];
+
+/// Fails with `Error::Config` if a header of the options is invalid or would replace the
+/// authorization of the requests, or if the locale is invalid.
+pub(crate) fn validate(options: &RequestOptions) -> Result<()> {
+    if let Some(ref locale) = options.locale {
+        if !crate::config::valid_locale(locale) {
+            return Err(Error::Config(ConfigError::Invalid(
+                "locale".to_owned(),
+                locale.clone(),
+            )));
+        }
+    }
+    // The value is not repeated in the error: it may be a credential.
+    for (name, value) in &options.headers {
+        let reason = if reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err() {
+            "the name is not a header name"
+        } else if AUTHORIZATION_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+            "the header authorizes the requests"
+        } else if reqwest::header::HeaderValue::from_str(value).is_err() {
+            "the value is not a header value"
+        } else {
+            continue;
+        };
+        return Err(Error::Config(ConfigError::Invalid(
+            format!("header {}", name),
+            reason.to_owned(),
+        )));
+    }
+    Ok(())
+}
+
+/// Replaces the default base of the address with the `base_url`.
+pub(crate) fn rebase(base_url: &str, address: &str) -> String {
+    match address.strip_prefix(API_BASE) {
+        Some(path) => format!("{}{}", base_url.trim_end_matches('/'), path),
+        None => address.to_owned(),
+    }
+}
+
+/// Returns the address and the form of the request for a new access token, asking for the
+/// `scopes` if there are any. The form borrows the credentials instead of copying the secret.
+pub(crate) fn token_request<'a>(
+    base_url: &str,
+    client_id: &'a str,
+    client_secret: &'a str,
+    scopes: &BTreeSet<Scope>,
+) -> (String, Vec<(&'static str, Cow<'a, str>)>) {
+    let mut form = vec![
+        ("grant_type", Cow::Borrowed("client_credentials")),
+        ("client_id", Cow::Borrowed(client_id)),
+        ("client_secret", Cow::Borrowed(client_secret)),
+    ];
+    if !scopes.is_empty() {
+        let scopes = scopes.iter().map(Scope::as_str).collect::<Vec<_>>();
+        form.push(("scope", Cow::Owned(scopes.join(" "))));
+    }
+    (rebase(base_url, &Endpoint::OauthToken.to_string()), form)
+}
+
+/// Parses the response to the request for a new access token.
+pub(crate) fn parse_token<R: Read>(json_str: R) -> Result<AccessToken> {
+    #[derive(Debug, Clone, serde::Deserialize)]
+    struct OauthAccessToken {
+        access_token: String,
+        expires_in: u64,
+        #[serde(default)]
+        scope: Option<String>,
+    }
+
+    let oauth = serde_json::from_reader::<_, OauthAccessToken>(json_str)?;
+    let now = chrono::Local::now().timestamp() as u64;
+    Ok(AccessToken {
+        access_token: Secret::from(oauth.access_token),
+        issued: now,
+        expires: now + oauth.expires_in,
+        scopes: oauth
+            .scope
+            .map(|s| s.split_whitespace().map(str::to_owned).collect())
+            .unwrap_or_default(),
+    })
+}
+
+/// Returns `true` if the access token must be refreshed.
+pub(crate) fn expired(token: &AccessToken) -> bool {
+    chrono::Local::now().timestamp() as u64 > token.expires
+}
+
+/// The outcome of an attempt to send a request.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Attempt {
+    /// A response with the status was received.
+    Status(u16),
+    /// The connection failed or timed out.
+    Transient,
+    /// The request failed otherwise.
+    Failed,
+}
+impl Attempt {
+    /// Returns the outcome of a request which has been sent and whose response has been
+    /// checked.
+    pub fn of<T>(result: &Result<T>) -> Attempt {
+        let error = match *result {
+            Ok(_) => return Attempt::Status(200),
+            Err(ref e) => e.root(),
+        };
+        match *error {
+            Error::Reqwest(ref e) if e.is_connect() || e.is_timeout() => Attempt::Transient,
+            Error::Toornament(status, _) | Error::Status(status, _) => {
+                Attempt::Status(status.as_u16())
+            }
+            Error::RateLimited(_) => Attempt::Status(429),
+            _ => Attempt::Failed,
+        }
+    }
+
+    /// Returns whether the outcome is a failure of the service for the circuit breaker, or
+    /// `None` if it tells nothing about the service.
+    pub fn failure(self) -> Option<bool> {
+        match self {
+            Attempt::Status(status) => Some(status >= 500),
+            Attempt::Transient => Some(true),
+            Attempt::Failed => None,
+        }
+    }
+}
+
+/// Returns the timeout of the next attempt at `now`: the read timeout, shortened to the time
+/// left before the deadline of the call. Fails with `Error::Cancelled` once the deadline has
+/// passed.
+pub(crate) fn attempt_timeout(
+    read: Option<Duration>,
+    deadline: Option<Instant>,
+    now: Instant,
+) -> Result<Option<Duration>> {
+    let deadline = match deadline {
+        Some(deadline) => deadline,
+        None => return Ok(read),
+    };
+    let left = deadline.saturating_duration_since(now);
+    if left.is_zero() {
+        return Err(Error::Cancelled(Partial::default()));
+    }
+    Ok(Some(read.map_or(left, |read| read.min(left))))
+}
+
+/// Returns `true` if sending the request with the method several times has the same effect
+/// as sending it once, so that it can be retried.
+fn is_idempotent(method: &str) -> bool {
+    ["get", "head", "options", "put", "delete"]
+        .iter()
+        .any(|m| method.eq_ignore_ascii_case(m))
+}
+
+/// Returns how long to wait before sending the request with the method again after the
+/// `attempt`, starting from one, or `None` if the outcome is final. The requests which are not
+/// idempotent, like `POST` creating a resource, are never retried since the failed attempt may
+/// have been processed by the service.
+pub(crate) fn retry_wait(
+    policy: &RetryPolicy,
+    method: &str,
+    attempt: u32,
+    outcome: Attempt,
+) -> Option<Duration> {
+    if attempt >= policy.attempts || !is_idempotent(method) {
+        return None;
+    }
+    match outcome {
+        Attempt::Status(status) if status >= 500 || status == 429 => {
+            log::warn!("Request failed with status {}, retrying", status);
+        }
+        Attempt::Transient => log::warn!("Request failed, retrying"),
+        _ => return None,
+    }
+    Some(policy.backoff(attempt))
+}
+
+/// How many bytes of a body which is not an error of the service are kept in `Error::Status`.
+const CAPTURED_BODY_LENGTH: usize = 1024;
+
+/// Returns `true` if the content type is JSON, or is not told.
+fn is_json(content_type: Option<&str>) -> bool {
+    content_type.is_none_or(|t| {
+        let t = t.to_ascii_lowercase();
+        t.starts_with("application/json") || t.contains("+json")
+    })
+}
+
+/// Returns the content type of a response.
+pub(crate) fn content_type(headers: &reqwest::header::HeaderMap) -> Option<&str> {
+    headers
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+}
+
+/// Fails with the error of the response if it is not a success, or if its body is not JSON,
+/// like the HTML pages returned by a CDN in front of the service.
+pub(crate) fn check(
+    status: reqwest::StatusCode,
+    content_type: Option<&str>,
+    body: &[u8],
+) -> Result<()> {
+    if !is_json(content_type) && !body.is_empty() {
+        return Err(Error::Status(status, captured(body)));
+    }
+    if status.is_success() {
+        return Ok(());
+    }
+    Err(error(status, body))
+}
+
+/// Returns the error for a non-success response with a JSON body.
+fn error(status: reqwest::StatusCode, body: &[u8]) -> Error {
+    #[derive(serde::Deserialize)]
+    struct TooManyRequests {
+        retry_after: u64,
+    }
+    /// The OAuth error of a token lacking a scope.
+    #[derive(serde::Deserialize)]
+    struct InsufficientScope {
+        error: String,
+        #[serde(default)]
+        scope: Option<String>,
+    }
+
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        if let Ok(value) = serde_json::from_slice::<TooManyRequests>(body) {
+            return Error::RateLimited(value.retry_after);
+        }
+    }
+    if status == reqwest::StatusCode::FORBIDDEN {
+        if let Ok(value) = serde_json::from_slice::<InsufficientScope>(body) {
+            if value.error == "insufficient_scope" {
+                let scopes = value.scope.as_deref().map(parse_scopes);
+                return Error::MissingScope(scopes.unwrap_or_default());
+            }
+        }
+    }
+    if let Ok(e) = serde_json::from_slice::<ToornamentServiceError>(body) {
+        return Error::Toornament(status, e);
+    }
+    Error::Status(status, captured(body))
+}
+
+/// Returns the beginning of the `body`, kept in `Error::Status`.
+fn captured(body: &[u8]) -> String {
+    let end = body.len().min(CAPTURED_BODY_LENGTH);
+    String::from_utf8_lossy(&body[..end]).into_owned()
+}
+
+/// How the bodies of the successful responses are parsed.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Decoder {
+    /// The failures of a lenient client.
+    pub lenient: Option<lenient::Failures>,
+    /// The schema drift found by a client detecting it.
+    #[cfg(feature = "schema-drift")]
+    pub drift: Option<crate::drift::Drifts>,
+}
+impl Decoder {
+    /// Parses the body of a successful response from the address `path`.
+    pub fn decode<T: DeserializeOwned>(&self, path: &str, body: &[u8]) -> Result<T> {
+        #[cfg(feature = "schema-drift")]
+        let inspected = self.lenient.is_some() || self.drift.is_some();
+        #[cfg(not(feature = "schema-drift"))]
+        let inspected = self.lenient.is_some();
+        if !inspected {
+            return Ok(serde_json::from_slice(body)?);
+        }
+        let value: serde_json::Value = serde_json::from_slice(body)?;
+        #[cfg(feature = "schema-drift")]
+        if let Some(ref drifts) = self.drift {
+            crate::drift::inspect::<T>(&value, path, drifts);
+        }
+        match self.lenient {
+            Some(ref failures) => lenient::parse(value, path, failures),
+            None => Ok(serde_json::from_value(value)?),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MatchId, Participants, TournamentId};
+
+    #[test]
+    fn test_request_description() {
+        let endpoint = Endpoint::MatchByIdUpdate {
+            tournament_id: TournamentId("1".to_owned()),
+            match_id: MatchId("2".to_owned()),
+        };
+        let request = Request::new("patch", &endpoint, "http://localhost:8080/", None)
+            .authorized(&Secret::from("API_TOKEN".to_owned()), "token");
+        assert_eq!(
+            request.url,
+            "http://localhost:8080/v1/tournaments/1/matches/2"
+        );
+        assert_eq!(request.context, "updating match 2 of tournament 1");
+        assert_eq!(
+            request.headers[1],
+            ("Authorization".to_owned(), "Bearer token".to_owned())
+        );
+
+        let client = RequestOptions::new()
+            .header("User-Agent", "a")
+            .header("X-Partner", "b");
+        let call = RequestOptions::new()
+            .header("user-agent", "c")
+            .query("debug", "1")
+            .query("note", "a b");
+        let options = client.merge(&call);
+        assert_eq!(
+            options.headers,
+            vec![
+                ("X-Partner".to_owned(), "b".to_owned()),
+                ("user-agent".to_owned(), "c".to_owned())
+            ]
+        );
+        assert!(validate(&options).is_ok());
+        let request =
+            Request::new("get", &endpoint, "http://localhost:8080/", None).with_options(&options);
+        assert_eq!(
+            request.url,
+            "http://localhost:8080/v1/tournaments/1/matches/2?debug=1&note=a%20b"
+        );
+        assert_eq!(request.headers, options.headers);
+
+        let invalid =
+            |name: &str, value: &str| validate(&RequestOptions::new().header(name, value)).is_err();
+        assert!(invalid("Authorization", "Bearer x"));
+        assert!(invalid("Bad Name", "x"));
+        assert!(invalid("X-Partner", "line\nbreak"));
+
+        // The locale is only sent to the endpoints translating their labels, and the one of a
+        // call replaces the one of the client.
+        let options = RequestOptions::new()
+            .locale("fr")
+            .merge(&RequestOptions::new().locale("pt-BR"));
+        let request =
+            Request::new("get", &Endpoint::AllDisciplines, API_BASE, None).with_options(&options);
+        assert!(request.url.ends_with("/v1/disciplines?_locale=pt-BR"));
+        let request = Request::new("get", &endpoint, API_BASE, None).with_options(&options);
+        assert!(request.url.ends_with("/v1/tournaments/1/matches/2"));
+        assert!(validate(&RequestOptions::new().locale("fr FR")).is_err());
+    }
+
+    #[test]
+    fn test_token_request_scopes() {
+        let (address, form) = token_request(API_BASE, "id", "secret", &BTreeSet::new());
+        assert!(address.ends_with("/oauth/v2/token"));
+        assert!(form.iter().all(|(name, _)| *name != "scope"));
+        let scopes = vec![Scope::Result, Scope::View].into_iter().collect();
+        let (_, form) = token_request(API_BASE, "id", "secret", &scopes);
+        assert_eq!(
+            form.last(),
+            Some(&("scope", Cow::from("organizer:view organizer:result")))
+        );
+    }
+
+    #[test]
+    fn test_retry_wait() {
+        let policy = RetryPolicy::new(3, Duration::from_millis(100));
+        assert_eq!(
+            retry_wait(&policy, "get", 1, Attempt::Status(503)),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            retry_wait(&policy, "DELETE", 2, Attempt::Transient),
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(retry_wait(&policy, "get", 3, Attempt::Status(503)), None);
+        assert_eq!(retry_wait(&policy, "get", 1, Attempt::Status(404)), None);
+        assert_eq!(retry_wait(&policy, "get", 1, Attempt::Failed), None);
+        assert_eq!(retry_wait(&policy, "post", 1, Attempt::Status(503)), None);
+        assert_eq!(retry_wait(&policy, "patch", 1, Attempt::Transient), None);
+
+        assert_eq!(Attempt::of(&Ok(())), Attempt::Status(200));
+        let error = Error::Status(reqwest::StatusCode::BAD_GATEWAY, String::new());
+        assert_eq!(
+            Attempt::of::<()>(&Err(error.context("getting a token"))),
+            Attempt::Status(502)
+        );
+        assert_eq!(
+            Attempt::of::<()>(&Err(Error::Cancelled(Default::default()))),
+            Attempt::Failed
+        );
+    }
+
+    #[test]
+    fn test_attempt_timeout() {
+        let now = Instant::now();
+        let read = Some(Duration::from_secs(10));
+        assert_eq!(attempt_timeout(read, None, now).unwrap(), read);
+        let deadline = Some(now + Duration::from_secs(4));
+        assert_eq!(
+            attempt_timeout(read, deadline, now).unwrap(),
+            Some(Duration::from_secs(4))
+        );
+        assert_eq!(
+            attempt_timeout(None, deadline, now + Duration::from_secs(1)).unwrap(),
+            Some(Duration::from_secs(3))
+        );
+        assert!(matches!(
+            attempt_timeout(read, deadline, now + Duration::from_secs(4)),
+            Err(Error::Cancelled(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_responses() {
+        let json = Some("application/json; charset=utf-8");
+        let error = check(
+            reqwest::StatusCode::TOO_MANY_REQUESTS,
+            json,
+            br#"{ "retry_after": 1500 }"#,
+        );
+        assert!(matches!(error, Err(Error::RateLimited(1500))));
+        let error = check(reqwest::StatusCode::BAD_GATEWAY, None, b"<html>");
+        assert!(matches!(error, Err(Error::Status(_, ref body)) if body == "<html>"));
+        assert!(check(reqwest::StatusCode::OK, json, b"[]").is_ok());
+        assert!(check(reqwest::StatusCode::NO_CONTENT, None, b"").is_ok());
+        let error = check(
+            reqwest::StatusCode::FORBIDDEN,
+            json,
+            br#"{ "error": "insufficient_scope", "scope": "organizer:delete" }"#,
+        );
+        match error {
+            Err(Error::MissingScope(scopes)) => assert_eq!(scopes, vec![Scope::Delete]),
+            _ => panic!("the missing scope was not detected: {:?}", error),
+        }
+        // Only the missing scopes are told apart from the other refusals.
+        #[cfg(not(feature = "strict-serde"))]
+        let error = check(
+            reqwest::StatusCode::FORBIDDEN,
+            json,
+            br#"{
+                "error": "access_denied",
+                "errors": [{ "message": "Not your tournament.", "scope": "query" }]
+            }"#,
+        );
+        #[cfg(not(feature = "strict-serde"))]
+        match error {
+            Err(Error::Toornament(status, e)) => {
+                assert_eq!(status, reqwest::StatusCode::FORBIDDEN);
+                assert_eq!(e.errors.0[0].message, "Not your tournament.");
+            }
+            _ => panic!("the service error was not detected: {:?}", error),
+        }
+
+        let page = "<html><body>503 Service Unavailable</body></html>".repeat(100);
+        let error = check(reqwest::StatusCode::OK, Some("text/html"), page.as_bytes());
+        match error {
+            Err(Error::Status(status, body)) => {
+                assert_eq!(status, reqwest::StatusCode::OK);
+                assert_eq!(body.len(), CAPTURED_BODY_LENGTH);
+                assert!(body.starts_with("<html>"));
+            }
+            _ => panic!("the page was not detected: {:?}", error),
+        }
+
+        let participants: Participants = Decoder::default()
+            .decode("/v1/participants", br#"[{ "name": "Fnatic" }]"#)
+            .unwrap();
+        assert_eq!(participants.0[0].name, "Fnatic");
+    }
+
+    #[test]
+    fn test_check_content_type() {
+        // A success is only a success if its body is JSON.
+        let error = check(reqwest::StatusCode::OK, Some("text/plain"), b"maintenance");
+        assert!(
+            matches!(error, Err(Error::Status(reqwest::StatusCode::OK, ref body)) if body == "maintenance")
+        );
+        let json = Some("application/vnd.toornament+json; charset=utf-8");
+        assert!(check(reqwest::StatusCode::OK, json, b"[]").is_ok());
+        assert!(check(reqwest::StatusCode::OK, Some("Application/JSON"), b"[]").is_ok());
+
+        // The body of an error which is not one of the service is capped too.
+        let body = "x".repeat(CAPTURED_BODY_LENGTH * 2);
+        let error = check(
+            reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+            Some("application/json"),
+            body.as_bytes(),
+        );
+        match error {
+            Err(Error::Status(_, body)) => assert_eq!(body.len(), CAPTURED_BODY_LENGTH),
+            _ => panic!("the error was not detected: {:?}", error),
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use crate::*;
+
+/// A client which exposes only the methods which do not change anything on the service.
+/// Use it in the services which must never mutate the tournaments (statistics collectors,
+/// stream overlays and so on): the mutating methods simply do not exist on this type.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use toornament::*;
+/// let t = ReadOnlyToornament::with_application("API_TOKEN",
+///                                              "CLIENT_ID",
+///                                              "CLIENT_SECRET").unwrap();
+/// let tournaments = t.tournaments(None, Includes::none()).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReadOnlyToornament {
+    inner: Toornament,
+}
+impl ReadOnlyToornament {
+    /// Creates new `ReadOnlyToornament` object with client credentials.
+    /// See `Toornament::with_application`.
+    pub fn with_application<S: Into<String>>(
+        api_token: S,
+        client_id: S,
+        client_secret: S,
+    ) -> Result<ReadOnlyToornament> {
+        Ok(ReadOnlyToornament::from(Toornament::with_application(
+            api_token,
+            client_id,
+            client_secret,
+        )?))
+    }
+
+    /// Consumes `ReadOnlyToornament` object and sets timeout to it
+    pub fn timeout(self, seconds: u64) -> Result<ReadOnlyToornament> {
+        Ok(ReadOnlyToornament::from(self.inner.timeout(seconds)?))
+    }
+
+    /// See `Toornament::ping`.
+    pub fn ping(&self) -> Result<Ping> {
+        self.inner.ping()
+    }
+
+    /// See `Toornament::token_info`.
+    pub fn token_info(&self) -> Result<TokenInfo> {
+        self.inner.token_info()
+    }
+
+    /// See `Toornament::metrics_snapshot`.
+    #[cfg(feature = "metrics")]
+    pub fn metrics_snapshot(&self) -> crate::MetricsSnapshot {
+        self.inner.metrics_snapshot()
+    }
+
+    /// See `Toornament::schema_drift`.
+    #[cfg(feature = "schema-drift")]
+    pub fn schema_drift(&self) -> Vec<crate::drift::Drift> {
+        self.inner.schema_drift()
+    }
+
+    /// See `Toornament::take_parse_failures`.
+    pub fn take_parse_failures(&self) -> Vec<crate::ParseFailure> {
+        self.inner.take_parse_failures()
+    }
+
+    /// See `Toornament::match_by_ref`.
+    pub fn match_by_ref<I: Into<Includes>>(&self, m: MatchRef, include: I) -> Result<Match> {
+        self.inner.match_by_ref(m, include)
+    }
+
+    /// See `Toornament::disciplines`.
+    pub fn disciplines(&self, id: Option<DisciplineId>) -> Result<Disciplines> {
+        self.inner.disciplines(id)
+    }
+
+    /// See `Toornament::tournaments`.
+    pub fn tournaments<I: Into<Includes>>(
+        &self,
+        tournament_id: Option<TournamentId>,
+        include: I,
+    ) -> Result<Tournaments> {
+        self.inner.tournaments(tournament_id, include)
+    }
+
+    /// See `Toornament::filtered_tournaments`.
+    pub fn filtered_tournaments<I: Into<Includes>>(
+        &self,
+        filter: TournamentFilter,
+        include: I,
+    ) -> Result<Tournaments> {
+        self.inner.filtered_tournaments(filter, include)
+    }
+
+    /// See `Toornament::my_tournaments`.
+    pub fn my_tournaments(&self) -> Result<Tournaments> {
+        self.inner.my_tournaments()
+    }
+
+    /// See `Toornament::matches`.
+    pub fn matches<I: Into<Includes>>(
+        &self,
+        tournament_id: TournamentId,
+        match_id: Option<MatchId>,
+        include: I,
+    ) -> Result<Matches> {
+        self.inner.matches(tournament_id, match_id, include)
+    }
+
+    /// See `Toornament::stage_matches`.
+    pub fn stage_matches<I: Into<Includes>>(
+        &self,
+        tournament_id: TournamentId,
+        stage_number: StageNumber,
+        group_number: Option<u64>,
+        include: I,
+    ) -> Result<Matches> {
+        self.inner
+            .stage_matches(tournament_id, stage_number, group_number, include)
+    }
+
+    /// See `Toornament::participant_matches`.
+    pub fn participant_matches<I: Into<Includes>>(
+        &self,
+        tournament_id: TournamentId,
+        participant_id: ParticipantId,
+        include: I,
+    ) -> Result<Matches> {
+        self.inner
+            .participant_matches(tournament_id, participant_id, include)
+    }
+
+    /// See `Toornament::matches_by_discipline`.
+    pub fn matches_by_discipline(
+        &self,
+        discipline_id: DisciplineId,
+        filter: MatchFilter,
+    ) -> Result<Matches> {
+        self.inner.matches_by_discipline(discipline_id, filter)
+    }
+
+    /// See `Toornament::match_result`.
+    pub fn match_result(&self, id: TournamentId, match_id: MatchId) -> Result<MatchResult> {
+        self.inner.match_result(id, match_id)
+    }
+
+    /// See `Toornament::match_games`.
+    pub fn match_games<I: Into<Includes>>(
+        &self,
+        tournament_id: TournamentId,
+        match_id: MatchId,
+        include: I,
+    ) -> Result<Games> {
+        self.inner.match_games(tournament_id, match_id, include)
+    }
+
+    /// See `Toornament::match_game`.
+    pub fn match_game<I: Into<Includes>>(
+        &self,
+        tournament_id: TournamentId,
+        match_id: MatchId,
+        game_number: GameNumber,
+        include: I,
+    ) -> Result<Game> {
+        self.inner
+            .match_game(tournament_id, match_id, game_number, include)
+    }
+
+    /// See `Toornament::match_game_result`.
+    pub fn match_game_result(
+        &self,
+        tournament_id: TournamentId,
+        match_id: MatchId,
+        game_number: GameNumber,
+    ) -> Result<MatchResult> {
+        self.inner
+            .match_game_result(tournament_id, match_id, game_number)
+    }
+
+    /// See `Toornament::tournament_participants`.
+    pub fn tournament_participants(
+        &self,
+        tournament_id: TournamentId,
+        filter: TournamentParticipantsFilter,
+    ) -> Result<Participants> {
+        self.inner.tournament_participants(tournament_id, filter)
+    }
+
+    /// See `Toornament::tournament_participant`.
+    pub fn tournament_participant(
+        &self,
+        id: TournamentId,
+        participant_id: ParticipantId,
+    ) -> Result<Participant> {
+        self.inner.tournament_participant(id, participant_id)
+    }
+
+    /// See `Toornament::tournament_permissions`.
+    pub fn tournament_permissions(&self, id: TournamentId) -> Result<Permissions> {
+        self.inner.tournament_permissions(id)
+    }
+
+    /// See `Toornament::tournament_permission`.
+    pub fn tournament_permission(
+        &self,
+        id: TournamentId,
+        permission_id: PermissionId,
+    ) -> Result<Permission> {
+        self.inner.tournament_permission(id, permission_id)
+    }
+
+    /// See `Toornament::tournament_stages`.
+    pub fn tournament_stages(&self, id: TournamentId) -> Result<Stages> {
+        self.inner.tournament_stages(id)
+    }
+
+    /// See `Toornament::tournament_settings`.
+    pub fn tournament_settings(&self, id: TournamentId) -> Result<TournamentSettings> {
+        self.inner.tournament_settings(id)
+    }
+
+    /// See `Toornament::me`.
+    pub fn me(&self) -> Result<Account> {
+        self.inner.me()
+    }
+
+    /// See `Toornament::final_standings`.
+    pub fn final_standings(&self, id: TournamentId) -> Result<FinalStandings> {
+        self.inner.final_standings(id)
+    }
+
+    /// See `Toornament::tournament_registration_form`.
+    pub fn tournament_registration_form(
+        &self,
+        id: TournamentId,
+    ) -> Result<registration::FormSchema> {
+        self.inner.tournament_registration_form(id)
+    }
+
+    /// See `Toornament::tournament_videos`.
+    pub fn tournament_videos(
+        &self,
+        tournament_id: TournamentId,
+        filter: TournamentVideosFilter,
+    ) -> Result<Videos> {
+        self.inner.tournament_videos(tournament_id, filter)
+    }
+}
+
+impl From<Toornament> for ReadOnlyToornament {
+    fn from(inner: Toornament) -> ReadOnlyToornament {
+        ReadOnlyToornament { inner }
+    }
+}
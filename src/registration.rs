@@ -0,0 +1,243 @@
+//! This module describes the registration form of a tournament: the custom fields the
+//! participants fill in, and their answers keyed by the machine names of the fields. A
+//! registration frontend can be generated from the schema returned by
+//! `Toornament::tournament_registration_form` and check the answers with `FormSchema::validate`
+//! before sending them.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use toornament::*;
+//! use toornament::registration::{AnswerValue, Answers, FieldTarget};
+//!
+//! let toornament = Toornament::with_application("API_TOKEN",
+//!                                               "CLIENT_ID",
+//!                                               "CLIENT_SECRET").unwrap();
+//! let form = toornament.tournament_registration_form(TournamentId("1".to_owned())).unwrap();
+//! for field in form.fields(FieldTarget::Team) {
+//!     println!("{} ({:?}){}", field.label, field.field_type,
+//!              if field.required { " *" } else { "" });
+//! }
+//! let mut answers = Answers::default();
+//! answers.insert("website", AnswerValue::Text("https://toornament.com".to_owned()));
+//! for issue in form.validate(&answers, FieldTarget::Team) {
+//!     println!("{}", issue);
+//! }
+//! ```
+use std::collections::BTreeMap;
+
+use crate::common::Date;
+use crate::participants::CustomFieldType;
+
+/// Who fills in a custom field.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldTarget {
+    /// The team.
+    Team,
+    /// Every player of a team, or the participant of a single tournament.
+    Player,
+    /// The user registering.
+    User,
+}
+
+/// A custom field of the registration form.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
+pub struct CustomFieldSchema {
+    /// Name of the field identifying its answer.
+    pub machine_name: String,
+    /// Label of the field shown to the participants.
+    pub label: String,
+    /// Who fills in the field.
+    pub target_type: FieldTarget,
+    /// Type of the value, the same as of the custom fields of the participants.
+    #[serde(rename = "type")]
+    pub field_type: CustomFieldType,
+    /// Whether the field must be answered.
+    #[serde(default)]
+    pub required: bool,
+    /// Whether the answer is shown publicly.
+    #[serde(default)]
+    pub public: bool,
+    /// Position of the field in the form.
+    #[serde(default)]
+    pub position: i64,
+    /// Value proposed by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_value: Option<serde_json::Value>,
+}
+
+/// The custom fields of the registration form of a tournament.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FormSchema(pub Vec<CustomFieldSchema>);
+impl FormSchema {
+    /// Returns the fields filled in by the target, ordered by their positions.
+    pub fn fields(&self, target: FieldTarget) -> Vec<&CustomFieldSchema> {
+        let mut fields = self
+            .0
+            .iter()
+            .filter(|f| f.target_type == target)
+            .collect::<Vec<_>>();
+        fields.sort_by_key(|f| f.position);
+        fields
+    }
+
+    /// Checks the answers of the target: the required fields must be answered, the answers
+    /// must belong to the fields of the target and have the values of their types. Returns
+    /// all the issues found, none if the answers are valid.
+    pub fn validate(&self, answers: &Answers, target: FieldTarget) -> Vec<ValidationIssue> {
+        let fields = self.fields(target);
+        let mut issues = Vec::new();
+        for field in &fields {
+            match answers.0.get(&field.machine_name) {
+                None if field.required => {
+                    issues.push(ValidationIssue::Missing(field.machine_name.clone()))
+                }
+                None => {}
+                Some(value) => {
+                    if let Some(reason) = value.check(&field.field_type) {
+                        issues.push(ValidationIssue::Invalid(field.machine_name.clone(), reason));
+                    }
+                }
+            }
+        }
+        for name in answers.0.keys() {
+            if !fields.iter().any(|f| &f.machine_name == name) {
+                issues.push(ValidationIssue::Unknown(name.clone()));
+            }
+        }
+        issues
+    }
+}
+
+/// A value of an answer.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum AnswerValue {
+    /// A yes/no answer.
+    Bool(bool),
+    /// A date, for the birth dates.
+    Date(Date),
+    /// Any other answer.
+    Text(String),
+}
+impl AnswerValue {
+    /// Returns the reason why the value does not fit the type, if it does not. The texts are
+    /// checked by `CustomFieldType::is_valid`.
+    fn check(&self, field_type: &CustomFieldType) -> Option<String> {
+        match (self, field_type) {
+            (AnswerValue::Bool(_), CustomFieldType::Checkbox) => None,
+            (_, CustomFieldType::Checkbox) => Some("must be a yes/no answer".to_owned()),
+            (AnswerValue::Date(_), CustomFieldType::Birthdate) => None,
+            (AnswerValue::Text(ref text), _) if field_type.is_valid(text) => None,
+            (AnswerValue::Text(_), CustomFieldType::Birthdate) => {
+                Some("must be a date like 1990-01-31".to_owned())
+            }
+            (AnswerValue::Text(_), _) => {
+                Some(format!("is not a valid {:?}", field_type).to_lowercase())
+            }
+            _ => Some("must be a text".to_owned()),
+        }
+    }
+}
+
+/// The answers to a registration form keyed by the machine names of the fields.
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Answers(pub BTreeMap<String, AnswerValue>);
+impl Answers {
+    /// Sets the answer to the field.
+    pub fn insert<S: Into<String>>(&mut self, machine_name: S, value: AnswerValue) {
+        self.0.insert(machine_name.into(), value);
+    }
+
+    /// Returns the answer to the field.
+    pub fn get(&self, machine_name: &str) -> Option<&AnswerValue> {
+        self.0.get(machine_name)
+    }
+}
+
+/// A problem with the answers to a registration form.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValidationIssue {
+    /// A required field is not answered.
+    Missing(String),
+    /// An answer does not belong to any field of the form.
+    Unknown(String),
+    /// An answer does not fit the type of its field, with the reason.
+    Invalid(String, String),
+}
+impl ::std::fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        match *self {
+            ValidationIssue::Missing(ref name) => write!(f, "The field {} is required", name),
+            ValidationIssue::Unknown(ref name) => write!(f, "The field {} does not exist", name),
+            ValidationIssue::Invalid(ref name, ref reason) => {
+                write!(f, "The field {} {}", name, reason)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_answers() {
+        let json = r#"[
+            { "machine_name": "email", "label": "Email", "target_type": "user",
+              "type": "email", "required": true, "position": 1 },
+            { "machine_name": "website", "label": "Website", "target_type": "team",
+              "type": "url", "required": false, "position": 2 },
+            { "machine_name": "accept_rules", "label": "I accept the rules",
+              "target_type": "team", "type": "checkbox", "required": true, "position": 1 },
+            { "machine_name": "discord", "label": "Discord", "target_type": "team",
+              "type": "discord_id", "position": 3 }
+        ]"#;
+        let form: FormSchema = serde_json::from_str(json).unwrap();
+        let team = form.fields(FieldTarget::Team);
+        assert_eq!(team.len(), 3);
+        assert_eq!(team[0].machine_name, "accept_rules");
+        assert_eq!(team[2].field_type, CustomFieldType::Other);
+
+        let mut answers: Answers = serde_json::from_str(
+            r#"{ "website": "toornament.com", "discord": "eg#1234", "email": "a@b.c" }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            form.validate(&answers, FieldTarget::Team),
+            vec![
+                ValidationIssue::Missing("accept_rules".to_owned()),
+                ValidationIssue::Invalid("website".to_owned(), "is not a valid url".to_owned()),
+                ValidationIssue::Unknown("email".to_owned()),
+            ]
+        );
+
+        answers.0.remove("email");
+        answers.insert("accept_rules", AnswerValue::Bool(true));
+        answers.insert(
+            "website",
+            AnswerValue::Text("https://toornament.com".to_owned()),
+        );
+        assert!(form.validate(&answers, FieldTarget::Team).is_empty());
+
+        // The steam ids are checked as those of the participants.
+        let json = r#"[{ "machine_name": "steam", "label": "Steam", "target_type": "player",
+                         "type": "steam_player_id" }]"#;
+        let form: FormSchema = serde_json::from_str(json).unwrap();
+        let mut answers = Answers::default();
+        answers.insert("steam", AnswerValue::Text("STEAM_0:3:1".to_owned()));
+        assert_eq!(
+            form.validate(&answers, FieldTarget::Player),
+            vec![ValidationIssue::Invalid(
+                "steam".to_owned(),
+                "is not a valid steamid".to_owned()
+            )]
+        );
+        answers.insert("steam", AnswerValue::Text("76561197960287930".to_owned()));
+        assert!(form.validate(&answers, FieldTarget::Player).is_empty());
+    }
+}
@@ -0,0 +1,104 @@
+/// A scope of the access token, granting the client a part of the API. A client asks for the
+/// scopes it needs with `Toornament::with_application_scopes`; a request outside of them fails
+/// with `Error::MissingScope`.
+#[derive(
+    Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
+)]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
+pub enum Scope {
+    /// Reading the private tournaments and their resources: "organizer:view"
+    View,
+    /// Creating and editing the tournaments: "organizer:admin"
+    Admin,
+    /// Reporting the results of the matches: "organizer:result"
+    Result,
+    /// Managing the participants: "organizer:participant"
+    Participant,
+    /// Managing the registrations: "organizer:registration"
+    Registration,
+    /// Managing the permissions of the other organizers: "organizer:permission"
+    Permission,
+    /// Deleting the tournaments: "organizer:delete"
+    Delete,
+    /// A scope not known by this crate
+    Other(String),
+}
+
+impl Scope {
+    /// Returns the name of the scope used by the service.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            Scope::View => "organizer:view",
+            Scope::Admin => "organizer:admin",
+            Scope::Result => "organizer:result",
+            Scope::Participant => "organizer:participant",
+            Scope::Registration => "organizer:registration",
+            Scope::Permission => "organizer:permission",
+            Scope::Delete => "organizer:delete",
+            Scope::Other(ref name) => name,
+        }
+    }
+}
+
+impl From<String> for Scope {
+    fn from(name: String) -> Scope {
+        match name.as_str() {
+            "organizer:view" => Scope::View,
+            "organizer:admin" => Scope::Admin,
+            "organizer:result" => Scope::Result,
+            "organizer:participant" => Scope::Participant,
+            "organizer:registration" => Scope::Registration,
+            "organizer:permission" => Scope::Permission,
+            "organizer:delete" => Scope::Delete,
+            _ => Scope::Other(name),
+        }
+    }
+}
+
+impl From<&str> for Scope {
+    fn from(name: &str) -> Scope {
+        Scope::from(name.to_owned())
+    }
+}
+
+impl From<Scope> for String {
+    fn from(scope: Scope) -> String {
+        scope.as_str().to_owned()
+    }
+}
+
+impl std::fmt::Display for Scope {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.write_str(self.as_str())
+    }
+}
+
+/// Parses a list of scopes separated by spaces, like the `scope` of the OAuth responses.
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+pub(crate) fn parse_scopes(list: &str) -> Vec<Scope> {
+    list.split_whitespace().map(Scope::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_names() {
+        #[cfg(any(feature = "client-blocking", feature = "client-async"))]
+        assert_eq!(
+            parse_scopes("organizer:view  organizer:result organizer:stats"),
+            vec![
+                Scope::View,
+                Scope::Result,
+                Scope::Other("organizer:stats".to_owned())
+            ]
+        );
+        assert_eq!(Scope::Permission.to_string(), "organizer:permission");
+        assert_eq!(
+            serde_json::to_string(&Scope::Delete).unwrap(),
+            "\"organizer:delete\""
+        );
+    }
+}
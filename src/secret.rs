@@ -0,0 +1,42 @@
+use zeroize::Zeroize;
+
+/// A string which is wiped from the memory when dropped and never printed by `Debug`.
+#[derive(Clone, Default, Eq, PartialEq, serde::Deserialize)]
+#[serde(transparent)]
+pub(crate) struct Secret(String);
+impl Secret {
+    /// Returns the secret value.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Secret {
+        Secret(value)
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.write_str("\"<redacted>\"")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_is_redacted() {
+        let secret = Secret::from("client secret".to_owned());
+        assert_eq!(secret.expose(), "client secret");
+        assert_eq!(format!("{:?}", (secret.clone(), 1)), "(\"<redacted>\", 1)");
+    }
+}
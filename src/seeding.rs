@@ -0,0 +1,177 @@
+//! This module places the seeded participants in an elimination bracket which is not full:
+//! the bracket size is the tournament size rounded up to a power of two, and the missing
+//! participants are replaced by byes given to the best seeds, so a partially filled bracket
+//! starts without empty matches in the first round.
+//!
+//! A list of participants has no room for a bye, so the placement can be uploaded as the
+//! order of the participants only when the bracket is full. Otherwise the byes have to be
+//! placed by hand from the `Seeding::slots`.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use toornament::*;
+//! use toornament::seeding::Seeding;
+//!
+//! let toornament = Toornament::with_application("API_TOKEN",
+//!                                               "CLIENT_ID",
+//!                                               "CLIENT_SECRET").unwrap();
+//! let tournament_id = TournamentId("1".to_owned());
+//! let tournament = toornament.tournaments(Some(tournament_id.clone()), Includes::none()).unwrap();
+//! // The participants ordered by their seeds, the best first
+//! let mut participants = toornament.tournament_participants(
+//!     tournament_id.clone(),
+//!     TournamentParticipantsFilter::default()).unwrap();
+//! participants.0.retain(|p| p.check_in == Some(true));
+//! let seeding = Seeding::new(TournamentSize(tournament.0[0].size), &participants).unwrap();
+//! match seeding.participants() {
+//!     Ok(participants) => {
+//!         toornament.update_tournament_participants(tournament_id, participants).unwrap();
+//!     }
+//!     Err(_) => {
+//!         for (slot, participant) in seeding.slots.iter().enumerate() {
+//!             let name = participant.as_ref().map(|p| p.name.as_str()).unwrap_or("bye");
+//!             println!("Slot {}: {}", slot + 1, name);
+//!         }
+//!     }
+//! }
+//! ```
+use crate::participants::{Participant, Participants};
+use crate::stages::TournamentSize;
+use crate::{Error, Result};
+
+/// The placement of the participants in the first round of an elimination bracket.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Seeding {
+    /// The slots of the bracket from the top to the bottom: the slots `2 * i` and `2 * i + 1`
+    /// play the match `i` of the first round. `None` is a bye.
+    pub slots: Vec<Option<Participant>>,
+}
+
+/// Returns the seeds (starting from one) in the order of the slots of a bracket of the size,
+/// which must be a power of two: the best seeds meet as late as possible.
+fn seed_order(size: usize) -> Vec<usize> {
+    let mut order = vec![1];
+    while order.len() < size {
+        let sum = order.len() * 2 + 1;
+        order = order.into_iter().flat_map(|s| vec![s, sum - s]).collect();
+    }
+    order
+}
+
+impl Seeding {
+    /// Places the `participants`, ordered by their seeds with the best first, in a bracket
+    /// for a tournament of the `size`. The bracket size is the greater of the tournament size
+    /// and the number of participants, rounded up to a power of two (see
+    /// `TournamentSize::next_power_of_two`, which fails for a size too great to round up).
+    /// The best seeds get the byes.
+    pub fn new(size: TournamentSize, participants: &Participants) -> Result<Seeding> {
+        let size = TournamentSize(size.0.max(participants.0.len() as i64)).next_power_of_two()?;
+        Ok(Seeding {
+            slots: seed_order(size.0 as usize)
+                .into_iter()
+                .map(|seed| participants.0.get(seed - 1).cloned())
+                .collect(),
+        })
+    }
+
+    /// Returns the participants who get a bye in the first round.
+    pub fn byes(&self) -> Vec<&Participant> {
+        self.slots
+            .chunks(2)
+            .filter_map(|pair| match (&pair[0], &pair[1]) {
+                (Some(p), None) | (None, Some(p)) => Some(p),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the participants in the order of their slots, to upload them in the bracket
+    /// placement order. Fails if the bracket has byes: the list would lose their placement,
+    /// so they have to be placed from the `slots` instead.
+    pub fn participants(&self) -> Result<Participants> {
+        self.slots
+            .iter()
+            .map(|slot| {
+                slot.clone()
+                    .ok_or(Error::Rest("The byes of the seeding can't be in a list"))
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(Participants::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(seeding: &Seeding) -> Vec<Option<&str>> {
+        seeding
+            .slots
+            .iter()
+            .map(|s| s.as_ref().map(|p| p.name.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_seed_order() {
+        assert_eq!(seed_order(2), vec![1, 2]);
+        assert_eq!(seed_order(4), vec![1, 4, 2, 3]);
+        assert_eq!(seed_order(8), vec![1, 8, 4, 5, 2, 7, 3, 6]);
+    }
+
+    #[test]
+    fn test_seeding_assigns_byes_to_best_seeds() {
+        let participants: Participants = ["a", "b", "c", "d", "e", "f"]
+            .iter()
+            .map(|&n| Participant::create(n))
+            .collect();
+        let seeding = Seeding::new(TournamentSize(8), &participants).unwrap();
+        assert_eq!(
+            names(&seeding),
+            vec![
+                Some("a"),
+                None,
+                Some("d"),
+                Some("e"),
+                Some("b"),
+                None,
+                Some("c"),
+                Some("f")
+            ]
+        );
+        assert_eq!(
+            seeding
+                .byes()
+                .iter()
+                .map(|p| p.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+        assert!(seeding.participants().is_err());
+        let full = Seeding::new(
+            TournamentSize(4),
+            &participants.0[..4].iter().cloned().collect(),
+        )
+        .unwrap();
+        assert_eq!(
+            full.participants()
+                .unwrap()
+                .0
+                .iter()
+                .map(|p| p.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "d", "b", "c"]
+        );
+
+        // The bracket grows if there are more participants than the tournament size.
+        assert_eq!(
+            Seeding::new(TournamentSize(4), &participants)
+                .unwrap()
+                .slots
+                .len(),
+            8
+        );
+        assert!(Seeding::new(TournamentSize(i64::MAX), &participants).is_err());
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::matches::{Match, MatchId, MatchResult, Matches};
+use crate::participants::{Participant, ParticipantId, Participants};
+use crate::stages::{Stage, StageNumber, Stages};
+use crate::tournaments::Tournament;
+#[cfg(feature = "client-blocking")]
+use crate::{
+    endpoints::Endpoint, progress::Steps, Include, Includes, Pager, Result, Toornament,
+    TournamentId, TournamentParticipantsFilter,
+};
+
+/// A local copy of a tournament with its participants, stages and matches (with their games).
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TournamentSnapshot {
+    /// The tournament, if it is known.
+    pub tournament: Option<Tournament>,
+    /// Participants of the tournament.
+    pub participants: Participants,
+    /// Stages of the tournament.
+    pub stages: Stages,
+    /// Matches of the tournament.
+    pub matches: Matches,
+}
+impl TournamentSnapshot {
+    /// Number of the steps of `TournamentSnapshot::fetch` known before it starts: the first
+    /// pages of the participants and of the matches are counted, the other pages are added
+    /// once the service tells their number.
+    #[cfg(feature = "client-blocking")]
+    pub(crate) const FETCH_STEPS: usize = 4;
+
+    #[cfg(feature = "client-blocking")]
+    /// Fetches the tournament with all the pages of its participants, its stages and all the
+    /// pages of its matches with their games, calling `before_request` before every request.
+    /// Every request, so every page, is a step of the `steps`.
+    pub(crate) fn fetch<F: FnMut()>(
+        client: &Toornament,
+        id: TournamentId,
+        steps: &mut Steps<'_>,
+        mut before_request: F,
+    ) -> Result<TournamentSnapshot> {
+        before_request();
+        steps.start(Endpoint::TournamentByIdUpdate(id.clone()).resource());
+        let tournament = client
+            .tournaments(Some(id.clone()), Includes::none())?
+            .0
+            .pop();
+        let participants = fetch_pages(
+            client
+                .tournament_participants_pager(id.clone(), TournamentParticipantsFilter::default()),
+            Endpoint::ParticipantsUpdate(id.clone()).resource(),
+            steps,
+            &mut before_request,
+        )?;
+        before_request();
+        steps.start(Endpoint::Stages(id.clone()).resource());
+        let stages = client.tournament_stages(id.clone())?;
+        let resource = Endpoint::MatchesByTournament {
+            tournament_id: id.clone(),
+            with_games: true,
+            page: None,
+            per_page: None,
+        }
+        .resource();
+        let matches = fetch_pages(
+            client.tournament_matches_pager(id, Include::Games),
+            resource,
+            steps,
+            &mut before_request,
+        )?;
+        Ok(TournamentSnapshot {
+            tournament,
+            participants: Participants::from(participants),
+            stages,
+            matches: Matches(matches.into_iter().map(Match::link_games).collect()),
+        })
+    }
+
+    /// Adds the participant or replaces the one with the same id.
+    pub fn upsert_participant(&mut self, participant: Participant) {
+        let existing = participant
+            .id
+            .as_ref()
+            .and_then(|id| self.participant_position(id));
+        match existing {
+            Some(i) => self.participants.0[i] = participant,
+            None => self.participants.0.push(participant),
+        }
+    }
+
+    /// Removes the participant with the id. Returns `false` if there was no such participant.
+    pub fn remove_participant(&mut self, id: &ParticipantId) -> bool {
+        match self.participant_position(id) {
+            Some(i) => {
+                self.participants.0.remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Adds the stage or replaces the one with the same number.
+    pub fn upsert_stage(&mut self, stage: Stage) {
+        match self.stages.0.iter().position(|s| s.number == stage.number) {
+            Some(i) => self.stages.0[i] = stage,
+            None => self.stages.0.push(stage),
+        }
+    }
+
+    /// Removes the stage with the number. Returns `false` if there was no such stage.
+    pub fn remove_stage(&mut self, number: &StageNumber) -> bool {
+        match self.stages.0.iter().position(|s| s.number == *number) {
+            Some(i) => {
+                self.stages.0.remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Adds the match or replaces the one with the same id.
+    pub fn upsert_match(&mut self, m: Match) {
+        match self.matches.0.iter().position(|o| o.id == m.id) {
+            Some(i) => self.matches.0[i] = m,
+            None => self.matches.0.push(m),
+        }
+    }
+
+    /// Removes the match with the id. Returns `false` if there was no such match.
+    pub fn remove_match(&mut self, id: &MatchId) -> bool {
+        match self.matches.0.iter().position(|m| m.id == *id) {
+            Some(i) => {
+                self.matches.0.remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Sets the result of the match with the id. Returns `false` if there was no such match.
+    pub fn set_match_result(&mut self, id: &MatchId, result: MatchResult) -> bool {
+        match self.matches.0.iter_mut().find(|m| m.id == *id) {
+            Some(m) => {
+                m.status = result.status;
+                m.opponents = result.opponents;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn participant_position(&self, id: &ParticipantId) -> Option<usize> {
+        self.participants
+            .0
+            .iter()
+            .position(|p| p.id.as_ref() == Some(id))
+    }
+
+    /// Returns the differences which turn this snapshot into the `new` one. The participants
+    /// are told apart by their ids, or by their names for those without an id, the stages by
+    /// their numbers and the matches by their ids.
+    pub fn diff<'a>(&'a self, new: &'a TournamentSnapshot) -> SnapshotDiff<'a> {
+        SnapshotDiff {
+            tournament: new
+                .tournament
+                .as_ref()
+                .filter(|t| self.tournament.as_ref() != Some(*t)),
+            participants: differences(&self.participants.0, &new.participants.0, |p| match p.id {
+                Some(ref id) => ParticipantKey::Id(id.clone()),
+                None => ParticipantKey::Name(p.name.clone()),
+            }),
+            stages: differences(&self.stages.0, &new.stages.0, |s| s.number.clone()),
+            matches: differences(&self.matches.0, &new.matches.0, |m| m.id.clone()),
+        }
+    }
+}
+
+/// A difference of a resource between two snapshots.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Difference<'a, T> {
+    /// The resource is only in the new snapshot.
+    Added(&'a T),
+    /// The resource is in both snapshots but has changed: the old one, then the new one.
+    Changed(&'a T, &'a T),
+    /// The resource is only in the old snapshot.
+    Removed(&'a T),
+}
+
+/// The differences between two snapshots of a tournament, see `TournamentSnapshot::diff`.
+/// The differences of every kind of resource are in the order of the new snapshot, the
+/// removed resources last.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SnapshotDiff<'a> {
+    /// The tournament of the new snapshot, if it has changed.
+    pub tournament: Option<&'a Tournament>,
+    /// The differences of the participants.
+    pub participants: Vec<Difference<'a, Participant>>,
+    /// The differences of the stages.
+    pub stages: Vec<Difference<'a, Stage>>,
+    /// The differences of the matches, with their games.
+    pub matches: Vec<Difference<'a, Match>>,
+}
+impl SnapshotDiff<'_> {
+    /// Returns whether the snapshots are the same.
+    pub fn is_empty(&self) -> bool {
+        self.tournament.is_none()
+            && self.participants.is_empty()
+            && self.stages.is_empty()
+            && self.matches.is_empty()
+    }
+}
+
+/// The identity of a participant in the snapshots.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum ParticipantKey {
+    Id(ParticipantId),
+    Name(String),
+}
+
+/// Returns the differences between the `old` and the `new` resources, which are the same
+/// resource when their `key`s are equal.
+fn differences<'a, T, K, F>(old: &'a [T], new: &'a [T], key: F) -> Vec<Difference<'a, T>>
+where
+    T: PartialEq,
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    let olds = old.iter().map(|o| (key(o), o)).collect::<BTreeMap<_, _>>();
+    let news = new.iter().map(&key).collect::<BTreeSet<_>>();
+    let mut differences = new
+        .iter()
+        .filter_map(|n| match olds.get(&key(n)) {
+            None => Some(Difference::Added(n)),
+            Some(o) if *o != n => Some(Difference::Changed(*o, n)),
+            Some(_) => None,
+        })
+        .collect::<Vec<_>>();
+    differences.extend(
+        old.iter()
+            .filter(|o| !news.contains(&key(o)))
+            .map(Difference::Removed),
+    );
+    differences
+}
+
+#[cfg(feature = "client-blocking")]
+/// Fetches all the pages of the `pager` one by one, calling `before_request` before every
+/// page and reporting it as a step of the `steps`. The first page is already in the total of
+/// the `steps`, the others are added once the service tells their number, or one by one if it
+/// does not.
+fn fetch_pages<T, F>(
+    pager: Pager<'_, T>,
+    resource: String,
+    steps: &mut Steps<'_>,
+    before_request: &mut F,
+) -> Result<Vec<T>>
+where
+    T: serde::de::DeserializeOwned + Send + Sync + 'static,
+    F: FnMut(),
+{
+    let mut items = Vec::new();
+    let mut number = 1;
+    loop {
+        before_request();
+        steps.start(format!("page {} of {}", number, resource));
+        let page = pager.page(number)?;
+        let pages = page.pages();
+        let last = page.items.is_empty() || pages.map(|p| number >= p).unwrap_or(false);
+        items.extend(page.items);
+        if last {
+            return Ok(items);
+        }
+        match pages {
+            Some(pages) if number == 1 => steps.extend(pages as usize - 1),
+            Some(_) => {}
+            None => steps.extend(1),
+        }
+        number += 1;
+    }
+}
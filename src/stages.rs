@@ -1,3 +1,5 @@
+use crate::{Error, Result, ValidationError};
+
 /// A stage number
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
 pub struct StageNumber(pub i64);
@@ -20,8 +22,85 @@ pub enum StageType {
     BracketGroup,
 }
 
+impl StageType {
+    /// Returns `true` for the elimination stages, which are played in brackets whose sizes
+    /// must be powers of two.
+    pub fn is_elimination(&self) -> bool {
+        match *self {
+            StageType::SingleElimination | StageType::DoubleElimination => true,
+            StageType::Group | StageType::League | StageType::Swiss | StageType::BracketGroup => {
+                false
+            }
+        }
+    }
+}
+
+/// A number of participants of a tournament or of a stage.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct TournamentSize(pub i64);
+impl TournamentSize {
+    /// Returns `true` if the size is a power of two.
+    pub fn is_power_of_two(self) -> bool {
+        self.0 > 0 && self.0 & (self.0 - 1) == 0
+    }
+
+    /// Returns the smallest power of two greater than or equal to the size and to two: the
+    /// size of the bracket the participants are placed in. Returns a `ValidationError` if the
+    /// size is greater than the greatest power of two of an `i64`.
+    pub fn next_power_of_two(self) -> Result<TournamentSize> {
+        if self.0 > 1 << 62 {
+            return Err(Error::Validation(ValidationError::Invalid(
+                "size".to_owned(),
+                format!("{} has no greater power of two", self.0),
+            )));
+        }
+        Ok(TournamentSize(
+            (self.0.max(2) as u64).next_power_of_two() as i64
+        ))
+    }
+
+    /// Checks that the size is accepted for a stage of the type: at least two participants,
+    /// and a power of two for the elimination stages.
+    pub fn validate(self, stage_type: &StageType) -> Result<TournamentSize> {
+        let reason = if self.0 < 2 {
+            format!("{} is less than two participants", self.0)
+        } else if stage_type.is_elimination() && !self.is_power_of_two() {
+            format!(
+                "{} is not a power of two as required by {:?} stages, use {}",
+                self.0,
+                stage_type,
+                self.next_power_of_two()?.0
+            )
+        } else {
+            return Ok(self);
+        };
+        Err(Error::Validation(ValidationError::Invalid(
+            "size".to_owned(),
+            reason,
+        )))
+    }
+}
+impl From<i64> for TournamentSize {
+    fn from(size: i64) -> TournamentSize {
+        TournamentSize(size)
+    }
+}
+
 /// A tournament stage
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct Stage {
     /// Stage number.
     pub number: StageNumber,
@@ -35,9 +114,19 @@ pub struct Stage {
 }
 
 /// A list of tournament stages
-#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
+)]
 pub struct Stages(pub Vec<Stage>);
 
+impl Stage {
+    /// Checks that the size of the stage is accepted for its type, see
+    /// `TournamentSize::validate`.
+    pub fn validate_size(&self) -> Result<TournamentSize> {
+        TournamentSize(self.size).validate(&self.stage_type)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,4 +153,44 @@ mod tests {
         assert_eq!(s.stage_type, StageType::SingleElimination);
         assert_eq!(s.size, 8i64);
     }
+
+    #[test]
+    fn test_tournament_size() {
+        assert!(TournamentSize(16).is_power_of_two());
+        assert!(!TournamentSize(12).is_power_of_two());
+        assert!(!TournamentSize(0).is_power_of_two());
+        assert_eq!(
+            TournamentSize(12).next_power_of_two().ok(),
+            Some(TournamentSize(16))
+        );
+        assert_eq!(
+            TournamentSize(16).next_power_of_two().ok(),
+            Some(TournamentSize(16))
+        );
+        assert_eq!(
+            TournamentSize(1).next_power_of_two().ok(),
+            Some(TournamentSize(2))
+        );
+        assert_eq!(
+            TournamentSize(1 << 62).next_power_of_two().ok(),
+            Some(TournamentSize(1 << 62))
+        );
+        assert!(TournamentSize((1 << 62) + 1).next_power_of_two().is_err());
+        assert!(TournamentSize(i64::MAX)
+            .validate(&StageType::SingleElimination)
+            .is_err());
+
+        assert!(TournamentSize(12).validate(&StageType::League).is_ok());
+        assert!(TournamentSize(16)
+            .validate(&StageType::DoubleElimination)
+            .is_ok());
+        assert!(TournamentSize(1).validate(&StageType::League).is_err());
+        match TournamentSize(12).validate(&StageType::SingleElimination) {
+            Err(Error::Validation(ValidationError::Invalid(field, reason))) => {
+                assert_eq!(field, "size");
+                assert!(reason.ends_with("use 16"));
+            }
+            _ => panic!("Expected an invalid size"),
+        }
+    }
 }
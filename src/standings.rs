@@ -0,0 +1,339 @@
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+use crate::common::MatchResultSimple;
+use crate::matches::{MatchStatus, Matches};
+use crate::participants::Participant;
+use crate::stages::{Stage, Stages};
+use crate::swiss::Standings;
+use crate::{Error, Result, ValidationError};
+
+/// The final rank of a participant of a tournament.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
+pub struct FinalStanding {
+    /// The best rank of the participant, starting from one.
+    pub rank: i64,
+    /// The worst rank of the participant when it is tied with others, like 8 for the
+    /// participants ranked from 5 to 8.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rank_max: Option<i64>,
+    /// The participant.
+    pub participant: Participant,
+    /// Points of the participant, when the ranks are decided by points.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<i64>,
+}
+impl FinalStanding {
+    /// Creates new final standing of the participant at the rank.
+    pub fn new(rank: i64, participant: Participant) -> FinalStanding {
+        FinalStanding {
+            rank,
+            rank_max: None,
+            participant,
+            points: None,
+        }
+    }
+
+    builder_o!(rank_max, i64);
+    builder_o!(points, i64);
+
+    /// Returns the ranks of the participant, from the best to the worst.
+    pub fn ranks(&self) -> RangeInclusive<i64> {
+        self.rank..=self.rank_max.unwrap_or(self.rank)
+    }
+
+    /// Returns `true` if the participant shares its rank with others.
+    pub fn is_tied(&self) -> bool {
+        self.rank_max.is_some_and(|max| max > self.rank)
+    }
+}
+
+/// The final standings of a tournament, the best first.
+#[derive(
+    Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
+)]
+pub struct FinalStandings(pub Vec<FinalStanding>);
+
+/// Identifies a participant by its id or, if there is no id, by its name.
+fn key(participant: &Participant) -> &str {
+    match participant.id {
+        Some(ref id) => &id.0,
+        None => &participant.name,
+    }
+}
+
+/// Returns the participants of a completed elimination stage, tied by the round they were
+/// eliminated in, the best first. The groups of an elimination stage are its brackets, so a
+/// participant eliminated in a later group or round goes further. A participant wins a match
+/// by its result or by the forfeit of its opponent, never by its own forfeit.
+fn elimination_ranking(matches: &Matches) -> Vec<(Vec<Participant>, Option<i64>)> {
+    // The last match of every participant, and whether the participant won it.
+    let mut last = BTreeMap::<&str, ((u64, u64), bool, &Participant)>::new();
+    for m in &matches.0 {
+        let position = (m.group_number, m.round_number);
+        for o in &m.opponents.0 {
+            let participant = match o.participant {
+                Some(ref participant) => participant,
+                None => continue,
+            };
+            let forfeited = m
+                .opponents
+                .0
+                .iter()
+                .any(|other| other.number != o.number && other.forfeit);
+            let won = !o.forfeit && (o.result == Some(MatchResultSimple::Win) || forfeited);
+            let entry = last
+                .entry(key(participant))
+                .or_insert((position, won, participant));
+            if entry.0 < position {
+                *entry = (position, won, participant);
+            }
+        }
+    }
+    let mut ties = BTreeMap::<(std::cmp::Reverse<(u64, u64)>, bool), Vec<Participant>>::new();
+    for (position, won, participant) in last.into_values() {
+        ties.entry((std::cmp::Reverse(position), !won))
+            .or_default()
+            .push(participant.clone());
+    }
+    ties.into_values().map(|tied| (tied, None)).collect()
+}
+
+/// Returns the participants of a completed stage played for points, tied by their points,
+/// the best first: the `win`, `draw` and `loss` points for each match.
+fn points_ranking(
+    matches: &Matches,
+    win: i64,
+    draw: i64,
+    loss: i64,
+) -> Vec<(Vec<Participant>, Option<i64>)> {
+    let mut ranking: Vec<(Vec<Participant>, Option<i64>)> = Vec::new();
+    for standing in Standings::from_matches(matches, win, draw, loss).0 {
+        match ranking.last_mut() {
+            Some((tied, points)) if *points == Some(standing.points) => {
+                tied.push(standing.participant)
+            }
+            _ => ranking.push((vec![standing.participant], Some(standing.points))),
+        }
+    }
+    ranking
+}
+
+impl FinalStandings {
+    /// Computes the final standings of a tournament from its completed stages, for the
+    /// disciplines whose standings the service does not provide. The participants of the last
+    /// stage are ranked first, then those who did not reach it by the previous stages. An
+    /// elimination stage ranks the participants by the round they were eliminated in, the
+    /// others by their points: the `win`, `draw` and `loss` points for each match (see
+    /// `swiss::Standings::from_matches`). The participants tied share a range of ranks. Returns
+    /// `None` if a match of the stages is not completed.
+    ///
+    /// The `matches` must be all the matches of the tournament, from all the pages of the
+    /// list, like those of `Toornament::tournament_matches_pager`: the participants of a
+    /// missing match are ranked by the others only.
+    pub fn compute(
+        stages: &Stages,
+        matches: &Matches,
+        win: i64,
+        draw: i64,
+        loss: i64,
+    ) -> Option<FinalStandings> {
+        let mut stages = stages.0.iter().collect::<Vec<&Stage>>();
+        stages.sort_by_key(|s| std::cmp::Reverse(s.number.clone()));
+
+        let mut standings: Vec<FinalStanding> = Vec::new();
+        for stage in stages {
+            let stage_matches = matches
+                .0
+                .iter()
+                .filter(|m| m.stage_number as i64 == stage.number.0)
+                .cloned()
+                .collect::<Matches>();
+            if stage_matches
+                .0
+                .iter()
+                .any(|m| m.status != MatchStatus::Completed)
+            {
+                return None;
+            }
+            let ranking = if stage.stage_type.is_elimination() {
+                elimination_ranking(&stage_matches)
+            } else {
+                points_ranking(&stage_matches, win, draw, loss)
+            };
+            for (tied, points) in ranking {
+                let tied = tied
+                    .into_iter()
+                    .filter(|p| !standings.iter().any(|s| key(&s.participant) == key(p)))
+                    .collect::<Vec<_>>();
+                let rank = standings.len() as i64 + 1;
+                let rank_max = rank + tied.len() as i64 - 1;
+                for participant in tied {
+                    standings.push(FinalStanding {
+                        rank,
+                        rank_max: if rank_max > rank {
+                            Some(rank_max)
+                        } else {
+                            None
+                        },
+                        participant,
+                        points,
+                    });
+                }
+            }
+        }
+        Some(FinalStandings(standings))
+    }
+
+    /// Checks the standings before they are written: the ranks start from one, follow each
+    /// other without gaps and the participants tied share the same range.
+    pub fn validate(&self) -> Result<()> {
+        let mut next = 1;
+        let mut i = 0;
+        while i < self.0.len() {
+            let standing = &self.0[i];
+            let ranks = standing.ranks();
+            let tied = (ranks.end() - ranks.start() + 1).max(0) as usize;
+            let reason = if *ranks.start() != next {
+                format!("the rank {} follows the rank {}", ranks.start(), next - 1)
+            } else if tied == 0 {
+                format!(
+                    "the ranks from {} to {} are empty",
+                    ranks.start(),
+                    ranks.end()
+                )
+            } else if self.0.len() < i + tied
+                || self.0[i..i + tied].iter().any(|s| s.ranks() != ranks)
+            {
+                format!(
+                    "the ranks from {} to {} are not shared by {} participants",
+                    ranks.start(),
+                    ranks.end(),
+                    tied
+                )
+            } else {
+                next = ranks.end() + 1;
+                i += tied;
+                continue;
+            };
+            return Err(Error::Validation(ValidationError::Invalid(
+                "final_standings".to_owned(),
+                reason,
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stages::{StageNumber, StageType};
+
+    fn stage(number: i64, stage_type: StageType) -> Stage {
+        Stage {
+            number: StageNumber(number),
+            name: format!("Stage {}", number),
+            stage_type,
+            size: 4,
+        }
+    }
+
+    fn duel(stage: u64, round: u64, winner: &str, loser: &str) -> String {
+        format!(
+            r#"{{
+                "id": "{stage}{round}{winner}", "type": "duel", "discipline": "chess",
+                "status": "completed", "tournament_id": "1", "number": 1,
+                "stage_number": {stage}, "group_number": 1, "round_number": {round},
+                "date": "2015-09-06T00:10:00-06:00",
+                "opponents": [
+                    {{ "number": 1, "participant": {{ "id": "{winner}", "name": "{winner}" }},
+                       "result": 1, "forfeit": false }},
+                    {{ "number": 2, "participant": {{ "id": "{loser}", "name": "{loser}" }},
+                       "result": 3, "forfeit": false }}
+                ]
+            }}"#,
+            stage = stage,
+            round = round,
+            winner = winner,
+            loser = loser
+        )
+    }
+
+    fn forfeit(stage: u64, round: u64, winner: &str, loser: &str) -> String {
+        duel(stage, round, winner, loser)
+            .replace(r#""result": 1, "forfeit": false"#, r#""forfeit": false"#)
+            .replace(r#""result": 3, "forfeit": false"#, r#""forfeit": true"#)
+    }
+
+    fn names(standings: &FinalStandings) -> Vec<(i64, Option<i64>, &str)> {
+        standings
+            .0
+            .iter()
+            .map(|s| (s.rank, s.rank_max, s.participant.name.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_compute_final_standings() {
+        let stages = Stages(vec![
+            stage(1, StageType::League),
+            stage(2, StageType::SingleElimination),
+        ]);
+        let matches = [
+            duel(1, 1, "a", "e"),
+            duel(1, 1, "b", "f"),
+            duel(1, 2, "c", "e"),
+            duel(1, 2, "d", "f"),
+            duel(2, 1, "a", "d"),
+            duel(2, 1, "b", "c"),
+            forfeit(2, 2, "b", "a"),
+        ];
+        let matches: Matches = serde_json::from_str(&format!("[{}]", matches.join(","))).unwrap();
+        assert!(matches.0[6].opponents.0.iter().all(|o| o.result.is_none()));
+        let standings = FinalStandings::compute(&stages, &matches, 3, 1, 0).unwrap();
+        assert_eq!(
+            names(&standings),
+            vec![
+                (1, None, "b"),
+                (2, None, "a"),
+                (3, Some(4), "c"),
+                (3, Some(4), "d"),
+                (5, Some(6), "e"),
+                (5, Some(6), "f"),
+            ]
+        );
+        assert_eq!(standings.0[4].points, Some(0));
+        assert!(standings.0[2].is_tied());
+        assert_eq!(standings.0[2].ranks(), 3..=4);
+        assert!(standings.validate().is_ok());
+        // The points are those of the organizer.
+        let standings = FinalStandings::compute(&stages, &matches, 2, 1, 1).unwrap();
+        assert_eq!(standings.0[4].points, Some(2));
+
+        let mut pending = matches;
+        pending.0[6].status = MatchStatus::Pending;
+        assert!(FinalStandings::compute(&stages, &pending, 3, 1, 0).is_none());
+    }
+
+    #[test]
+    fn test_validate_final_standings() {
+        let p = || Participant::create("p");
+        let standings = FinalStandings(vec![
+            FinalStanding::new(1, p()),
+            FinalStanding::new(2, p()).rank_max(3),
+            FinalStanding::new(2, p()).rank_max(3),
+        ]);
+        assert!(standings.validate().is_ok());
+        let gap = FinalStandings(vec![FinalStanding::new(1, p()), FinalStanding::new(3, p())]);
+        assert!(gap.validate().is_err());
+        let alone = FinalStandings(vec![
+            FinalStanding::new(1, p()),
+            FinalStanding::new(2, p()).rank_max(3),
+        ]);
+        assert!(alone.validate().is_err());
+        let empty = FinalStandings(vec![FinalStanding::new(1, p()).rank_max(0)]);
+        assert!(empty.validate().is_err());
+    }
+}
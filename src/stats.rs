@@ -0,0 +1,246 @@
+//! This module parses the statistics of the games, returned with `Include::Stats`. The service
+//! returns them as a raw payload whose shape depends on the discipline: the `DisciplineStats`
+//! implementations of this module parse the payloads of a few popular disciplines into typed
+//! structs, and `serde_json::Value` parses any other.
+//!
+//! # Example
+//!
+//! ```rust
+//! use toornament::*;
+//! use toornament::stats::{AnyStats, CounterStrikeStats};
+//!
+//! let game: Game = serde_json::from_str(r#"{
+//!     "number": 1,
+//!     "status": "completed",
+//!     "opponents": [],
+//!     "stats": [{ "number": 1, "kills": 80, "rounds_won": 16 }]
+//! }"#).unwrap();
+//! let stats: CounterStrikeStats = game.stats().unwrap().unwrap();
+//! assert_eq!(stats.0[0].rounds_won, Some(16));
+//!
+//! let discipline = DisciplineId::from("counterstrike_go");
+//! let stats = AnyStats::parse(&discipline, game.raw_stats.as_ref().unwrap()).unwrap();
+//! assert!(matches!(stats, AnyStats::CounterStrike(_)));
+//! ```
+use std::cmp::Ordering;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::disciplines::ids;
+use crate::{DisciplineId, Result};
+
+/// The statistics of a game as returned by the service, before they are parsed.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RawStats(pub Value);
+// The json values are not ordered, so the statistics are ordered by their written form.
+impl Ord for RawStats {
+    fn cmp(&self, other: &RawStats) -> Ordering {
+        self.0.to_string().cmp(&other.0.to_string())
+    }
+}
+impl PartialOrd for RawStats {
+    fn partial_cmp(&self, other: &RawStats) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The statistics of a game of some disciplines.
+pub trait DisciplineStats: DeserializeOwned {
+    /// The ids of the disciplines whose statistics are parsed, or none if the statistics of any
+    /// discipline are.
+    const DISCIPLINES: &'static [&'static str];
+
+    /// Returns `true` if the statistics of the `discipline` are parsed.
+    fn supports(discipline: &DisciplineId) -> bool {
+        Self::DISCIPLINES.is_empty() || Self::DISCIPLINES.contains(&discipline.0.as_str())
+    }
+
+    /// Parses the raw statistics of a game.
+    fn parse(raw: &RawStats) -> Result<Self> {
+        Ok(serde_json::from_value(raw.0.clone())?)
+    }
+}
+
+impl DisciplineStats for Value {
+    const DISCIPLINES: &'static [&'static str] = &[];
+}
+
+/// The statistics of an opponent in a Counter-Strike game.
+#[derive(
+    Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
+)]
+pub struct CounterStrikeOpponentStats {
+    /// Number of the opponent.
+    pub number: i64,
+    /// Rounds won by the opponent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rounds_won: Option<i64>,
+    /// Kills of the opponent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kills: Option<i64>,
+    /// Deaths of the opponent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deaths: Option<i64>,
+    /// Assists of the opponent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assists: Option<i64>,
+    /// Kills by a headshot of the opponent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub headshots: Option<i64>,
+}
+
+/// The statistics of the opponents of a Counter-Strike game.
+#[derive(
+    Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
+)]
+pub struct CounterStrikeStats(pub Vec<CounterStrikeOpponentStats>);
+impl DisciplineStats for CounterStrikeStats {
+    const DISCIPLINES: &'static [&'static str] = &[ids::COUNTERSTRIKE_GO];
+}
+
+/// The statistics of an opponent in a League of Legends game.
+#[derive(
+    Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
+)]
+pub struct LeagueOfLegendsOpponentStats {
+    /// Number of the opponent.
+    pub number: i64,
+    /// Kills of the opponent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kills: Option<i64>,
+    /// Deaths of the opponent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deaths: Option<i64>,
+    /// Assists of the opponent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assists: Option<i64>,
+    /// Gold earned by the opponent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gold: Option<i64>,
+    /// Towers destroyed by the opponent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub towers: Option<i64>,
+    /// Dragons slain by the opponent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dragons: Option<i64>,
+    /// Barons slain by the opponent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub barons: Option<i64>,
+}
+
+/// The statistics of the opponents of a League of Legends game.
+#[derive(
+    Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
+)]
+pub struct LeagueOfLegendsStats(pub Vec<LeagueOfLegendsOpponentStats>);
+impl DisciplineStats for LeagueOfLegendsStats {
+    const DISCIPLINES: &'static [&'static str] = &[ids::LEAGUE_OF_LEGENDS];
+}
+
+/// The statistics of an opponent in a Dota 2 game.
+#[derive(
+    Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
+)]
+pub struct DotaOpponentStats {
+    /// Number of the opponent.
+    pub number: i64,
+    /// Kills of the opponent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kills: Option<i64>,
+    /// Deaths of the opponent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deaths: Option<i64>,
+    /// Assists of the opponent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assists: Option<i64>,
+    /// Net worth of the opponent at the end of the game.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub net_worth: Option<i64>,
+    /// Towers destroyed by the opponent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub towers: Option<i64>,
+    /// Roshans slain by the opponent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roshans: Option<i64>,
+}
+
+/// The statistics of the opponents of a Dota 2 game.
+#[derive(
+    Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
+)]
+pub struct DotaStats(pub Vec<DotaOpponentStats>);
+impl DisciplineStats for DotaStats {
+    const DISCIPLINES: &'static [&'static str] = &[ids::DOTA2];
+}
+
+/// The statistics of a game, parsed by the implementation supporting its discipline. More
+/// disciplines may be supported by the next versions: a `match` on it needs a wildcard arm.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum AnyStats {
+    /// The statistics of a Counter-Strike game.
+    CounterStrike(CounterStrikeStats),
+    /// The statistics of a League of Legends game.
+    LeagueOfLegends(LeagueOfLegendsStats),
+    /// The statistics of a Dota 2 game.
+    Dota(DotaStats),
+    /// The statistics of a game of another discipline.
+    Other(Value),
+}
+impl AnyStats {
+    /// Parses the raw statistics of a game of the `discipline`.
+    pub fn parse(discipline: &DisciplineId, raw: &RawStats) -> Result<AnyStats> {
+        Ok(if CounterStrikeStats::supports(discipline) {
+            AnyStats::CounterStrike(CounterStrikeStats::parse(raw)?)
+        } else if LeagueOfLegendsStats::supports(discipline) {
+            AnyStats::LeagueOfLegends(LeagueOfLegendsStats::parse(raw)?)
+        } else if DotaStats::supports(discipline) {
+            AnyStats::Dota(DotaStats::parse(raw)?)
+        } else {
+            AnyStats::Other(Value::parse(raw)?)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    #[test]
+    fn test_parse_discipline_stats() {
+        let raw = RawStats(serde_json::json!([
+            { "number": 1, "kills": 12, "deaths": 3, "gold": 15000, "barons": 1 },
+            { "number": 2, "kills": 3, "deaths": 12, "unknown": true }
+        ]));
+        let lol = DisciplineId::from(ids::LEAGUE_OF_LEGENDS);
+        match AnyStats::parse(&lol, &raw).unwrap() {
+            AnyStats::LeagueOfLegends(stats) => {
+                assert_eq!(stats.0[0].gold, Some(15000));
+                assert_eq!(stats.0[1].barons, None);
+            }
+            stats => panic!("unexpected stats: {:?}", stats),
+        }
+        let dota = DisciplineId::from(ids::DOTA2);
+        assert!(matches!(
+            AnyStats::parse(&dota, &raw),
+            Ok(AnyStats::Dota(_))
+        ));
+
+        // The other disciplines keep the payload as it is.
+        let chess = DisciplineId::from("chess");
+        assert!(!CounterStrikeStats::supports(&chess));
+        assert!(Value::supports(&chess));
+        assert_eq!(
+            AnyStats::parse(&chess, &raw).unwrap(),
+            AnyStats::Other(raw.0)
+        );
+
+        let invalid = RawStats(serde_json::json!({ "kills": 12 }));
+        assert!(matches!(
+            CounterStrikeStats::parse(&invalid),
+            Err(Error::Json(_))
+        ));
+    }
+}
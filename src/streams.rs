@@ -8,6 +8,7 @@ pub struct StreamId(pub String);
 #[derive(
     Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
 )]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct Stream {
     /// An hexadecimal unique identifier for this stream.
     /// Example: "56742bc7cc3c17ee608b4567"
@@ -0,0 +1,414 @@
+//! This module generates the pairings of the next round of a Swiss stage, for the organizers
+//! who run the Swiss stages manually through the API.
+//!
+//! The participants are paired from the top of the standings to the bottom, avoiding the
+//! rematches, and the sides (the opponent numbers) are balanced so that every participant
+//! plays as the first opponent as often as the second one.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use toornament::*;
+//! use toornament::swiss::{Standing, Standings, SwissRound};
+//!
+//! let toornament = Toornament::with_application("API_TOKEN",
+//!                                               "CLIENT_ID",
+//!                                               "CLIENT_SECRET").unwrap();
+//! let tournament_id = TournamentId("1".to_owned());
+//! let played = toornament.matches(tournament_id.clone(), None, Includes::none()).unwrap();
+//! let standings = Standings(vec![
+//!     Standing::new(Participant::create("First").id(ParticipantId("1".to_owned())), 3),
+//!     Standing::new(Participant::create("Second").id(ParticipantId("2".to_owned())), 0),
+//! ]);
+//! let round = SwissRound::pair(&standings, &played).unwrap();
+//! ```
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, FixedOffset};
+
+use crate::disciplines::DisciplineId;
+use crate::matches::{Match, MatchId, MatchStatus, MatchType, Matches};
+use crate::opponents::{Opponent, Opponents};
+use crate::participants::Participant;
+use crate::tournaments::TournamentId;
+
+/// A participant's position in the standings.
+#[derive(Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Standing {
+    /// The participant.
+    pub participant: Participant,
+    /// Points of the participant.
+    pub points: i64,
+}
+impl Standing {
+    /// Creates new standing.
+    pub fn new(participant: Participant, points: i64) -> Standing {
+        Standing {
+            participant,
+            points,
+        }
+    }
+}
+
+/// The standings of a Swiss stage.
+#[derive(Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Standings(pub Vec<Standing>);
+impl Standings {
+    /// Computes the standings from the results of the duel matches: every participant gets the
+    /// `win`, `draw` or `loss` points for each match with a result. The standings are sorted by
+    /// the points, the best first; the participants with equal points keep the order in which
+    /// they first appear in the matches.
+    pub fn from_matches(matches: &Matches, win: i64, draw: i64, loss: i64) -> Standings {
+        use crate::common::MatchResultSimple;
+
+        let mut standings: Vec<Standing> = Vec::new();
+        for m in &matches.0 {
+            if m.match_type != MatchType::Duel {
+                continue;
+            }
+            for o in &m.opponents.0 {
+                let participant = match o.participant {
+                    Some(ref participant) => participant,
+                    None => continue,
+                };
+                let points = match o.result {
+                    Some(MatchResultSimple::Win) => win,
+                    Some(MatchResultSimple::Draw) => draw,
+                    Some(MatchResultSimple::Loss) => loss,
+                    None => 0,
+                };
+                match standings
+                    .iter_mut()
+                    .find(|s| key(&s.participant) == key(participant))
+                {
+                    Some(standing) => standing.points += points,
+                    None => standings.push(Standing::new(participant.clone(), points)),
+                }
+            }
+        }
+        standings.sort_by_key(|s| std::cmp::Reverse(s.points));
+        Standings(standings)
+    }
+}
+
+/// Pairings of a Swiss round.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SwissRound {
+    /// The pairs of participants: the first one plays as the opponent number 1.
+    pub pairings: Vec<(Participant, Participant)>,
+    /// The participant who does not play this round when the number of participants is odd.
+    pub bye: Option<Participant>,
+}
+
+/// Identifies a participant by its id or, if there is no id, by its name.
+fn key(participant: &Participant) -> &str {
+    match participant.id {
+        Some(ref id) => &id.0,
+        None => &participant.name,
+    }
+}
+
+/// What is known about a participant from the matches already played.
+#[derive(Default)]
+struct History<'a> {
+    opponents: Vec<&'a str>,
+    first_side: i64,
+    byes: usize,
+}
+
+fn histories(played: &Matches) -> BTreeMap<&str, History<'_>> {
+    let mut histories = BTreeMap::<&str, History>::new();
+    for m in &played.0 {
+        let participants = m
+            .opponents
+            .0
+            .iter()
+            .filter_map(|o| o.participant.as_ref().map(|p| (o.number, key(p))))
+            .collect::<Vec<(i64, &str)>>();
+        if participants.len() == 1 {
+            histories.entry(participants[0].1).or_default().byes += 1;
+        }
+        for &(number, participant) in &participants {
+            let history = histories.entry(participant).or_default();
+            if number == 1 {
+                history.first_side += 1;
+            } else {
+                history.first_side -= 1;
+            }
+            for &(_, other) in &participants {
+                if other != participant {
+                    history.opponents.push(other);
+                }
+            }
+        }
+    }
+    histories
+}
+
+/// Pairs the first unpaired participant with the next possible one, backtracking when the
+/// rest of the participants can not be paired.
+fn pair_rest(
+    order: &[&str],
+    paired: &mut Vec<bool>,
+    histories: &BTreeMap<&str, History>,
+    out: &mut Vec<(usize, usize)>,
+) -> bool {
+    let first = match paired.iter().position(|p| !p) {
+        Some(first) => first,
+        None => return true,
+    };
+    paired[first] = true;
+    for second in first + 1..order.len() {
+        if paired[second] {
+            continue;
+        }
+        let rematch = histories
+            .get(order[first])
+            .map(|h| h.opponents.contains(&order[second]))
+            .unwrap_or(false);
+        if rematch {
+            continue;
+        }
+        paired[second] = true;
+        out.push((first, second));
+        if pair_rest(order, paired, histories, out) {
+            return true;
+        }
+        out.pop();
+        paired[second] = false;
+    }
+    paired[first] = false;
+    false
+}
+
+impl SwissRound {
+    /// Pairs the participants for the next round. Returns `None` if it is impossible to pair
+    /// them without a rematch.
+    pub fn pair(standings: &Standings, played: &Matches) -> Option<SwissRound> {
+        let histories = histories(played);
+        let mut standings = standings.0.iter().collect::<Vec<&Standing>>();
+        standings.sort_by_key(|s| std::cmp::Reverse(s.points));
+
+        let mut bye = None;
+        if standings.len() % 2 == 1 {
+            let byes = |s: &Standing| {
+                histories
+                    .get(key(&s.participant))
+                    .map(|h| h.byes)
+                    .unwrap_or(0)
+            };
+            let fewest = standings.iter().map(|s| byes(s)).min().unwrap_or(0);
+            let position = standings.iter().rposition(|s| byes(s) == fewest)?;
+            bye = Some(standings.remove(position).participant.clone());
+        }
+
+        let order = standings
+            .iter()
+            .map(|s| key(&s.participant))
+            .collect::<Vec<&str>>();
+        let mut paired = vec![false; order.len()];
+        let mut pairs = Vec::new();
+        if !pair_rest(&order, &mut paired, &histories, &mut pairs) {
+            return None;
+        }
+
+        let first_side = |i: usize| histories.get(order[i]).map(|h| h.first_side).unwrap_or(0);
+        let pairings = pairs
+            .into_iter()
+            .map(|(a, b)| {
+                let (a, b) = if first_side(b) < first_side(a) {
+                    (b, a)
+                } else {
+                    (a, b)
+                };
+                (
+                    standings[a].participant.clone(),
+                    standings[b].participant.clone(),
+                )
+            })
+            .collect();
+
+        Some(SwissRound { pairings, bye })
+    }
+
+    /// Creates pending duel matches of the round, numbered from one, ready to be scheduled.
+    pub fn matches(
+        &self,
+        tournament_id: TournamentId,
+        discipline_id: DisciplineId,
+        stage_number: u64,
+        round_number: u64,
+        date: DateTime<FixedOffset>,
+    ) -> Matches {
+        let opponent = |number, participant: &Participant| Opponent {
+            number,
+            participant: Some(participant.clone()),
+            ..Default::default()
+        };
+        self.pairings
+            .iter()
+            .enumerate()
+            .map(|(i, (first, second))| Match {
+                id: MatchId::default(),
+                match_type: MatchType::Duel,
+                discipline_id: discipline_id.clone(),
+                status: MatchStatus::Pending,
+                tournament_id: tournament_id.clone(),
+                number: i as u64 + 1,
+                stage_number,
+                group_number: 1,
+                round_number,
+                date,
+                time_zone: None,
+                match_format: None,
+                opponents: Opponents(vec![opponent(1, first), opponent(2, second)]),
+                games: None,
+                branch: None,
+                position: None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::participants::ParticipantId;
+
+    fn participant(id: &str) -> Participant {
+        Participant::create(id).id(ParticipantId(id.to_owned()))
+    }
+
+    fn played(pairs: &[(&str, &str)]) -> Matches {
+        let date = DateTime::parse_from_rfc3339("2015-09-06T10:00:00+00:00").unwrap();
+        let round = SwissRound {
+            pairings: pairs
+                .iter()
+                .map(|&(a, b)| (participant(a), participant(b)))
+                .collect(),
+            bye: None,
+        };
+        round.matches(
+            TournamentId("1".to_owned()),
+            DisciplineId("chess".to_owned()),
+            1,
+            1,
+            date,
+        )
+    }
+
+    fn names(round: &SwissRound) -> Vec<(String, String)> {
+        round
+            .pairings
+            .iter()
+            .map(|(a, b)| (a.name.clone(), b.name.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_swiss_first_round() {
+        let standings = Standings(vec![
+            Standing::new(participant("a"), 0),
+            Standing::new(participant("b"), 0),
+            Standing::new(participant("c"), 0),
+            Standing::new(participant("d"), 0),
+        ]);
+        let round = SwissRound::pair(&standings, &Matches::default()).unwrap();
+        assert_eq!(
+            names(&round),
+            vec![
+                ("a".to_owned(), "b".to_owned()),
+                ("c".to_owned(), "d".to_owned())
+            ]
+        );
+        assert!(round.bye.is_none());
+    }
+
+    #[test]
+    fn test_swiss_avoids_rematches_and_balances_sides() {
+        let standings = Standings(vec![
+            Standing::new(participant("c"), 0),
+            Standing::new(participant("a"), 3),
+            Standing::new(participant("b"), 0),
+            Standing::new(participant("d"), 3),
+        ]);
+        let round = SwissRound::pair(&standings, &played(&[("a", "b"), ("d", "c")])).unwrap();
+        // "a" and "d" both played as the first opponent: "a" keeps the first side since it is
+        // ranked higher; "c" and "b" had the second side.
+        assert_eq!(
+            names(&round),
+            vec![
+                ("a".to_owned(), "d".to_owned()),
+                ("c".to_owned(), "b".to_owned())
+            ]
+        );
+
+        let round =
+            SwissRound::pair(&standings, &played(&[("a", "b"), ("d", "c"), ("a", "d")])).unwrap();
+        assert_eq!(
+            names(&round),
+            vec![
+                ("c".to_owned(), "a".to_owned()),
+                ("b".to_owned(), "d".to_owned())
+            ]
+        );
+
+        let all = played(&[
+            ("a", "b"),
+            ("a", "c"),
+            ("a", "d"),
+            ("b", "c"),
+            ("b", "d"),
+            ("c", "d"),
+        ]);
+        assert!(SwissRound::pair(&standings, &all).is_none());
+    }
+
+    #[test]
+    fn test_swiss_bye_and_matches() {
+        let standings = Standings(vec![
+            Standing::new(participant("a"), 3),
+            Standing::new(participant("b"), 0),
+            Standing::new(participant("c"), 0),
+        ]);
+        let round = SwissRound::pair(&standings, &Matches::default()).unwrap();
+        assert_eq!(round.bye.as_ref().unwrap().name, "c");
+        assert_eq!(names(&round), vec![("a".to_owned(), "b".to_owned())]);
+
+        let date = DateTime::parse_from_rfc3339("2015-09-06T10:00:00+00:00").unwrap();
+        let matches = round.matches(
+            TournamentId("1".to_owned()),
+            DisciplineId("chess".to_owned()),
+            2,
+            3,
+            date,
+        );
+        assert_eq!(matches.0.len(), 1);
+        let m = &matches.0[0];
+        assert_eq!(m.status, MatchStatus::Pending);
+        assert_eq!(m.stage_number, 2);
+        assert_eq!(m.round_number, 3);
+        assert_eq!(m.opponents.0[0].participant.as_ref().unwrap().name, "a");
+        assert_eq!(m.opponents.0[1].number, 2);
+    }
+
+    #[test]
+    fn test_standings_from_matches() {
+        use crate::common::MatchResultSimple;
+
+        let mut matches = played(&[("a", "b"), ("c", "d")]);
+        matches.0[0].opponents.0[0].result = Some(MatchResultSimple::Loss);
+        matches.0[0].opponents.0[1].result = Some(MatchResultSimple::Win);
+        matches.0[1].opponents.0[0].result = Some(MatchResultSimple::Draw);
+        matches.0[1].opponents.0[1].result = Some(MatchResultSimple::Draw);
+        let standings = Standings::from_matches(&matches, 3, 1, 0);
+        assert_eq!(
+            standings
+                .0
+                .iter()
+                .map(|s| (s.participant.name.as_str(), s.points))
+                .collect::<Vec<_>>(),
+            vec![("b", 3), ("c", 1), ("d", 1), ("a", 0)]
+        );
+    }
+}
@@ -0,0 +1,271 @@
+//! This module mirrors a tournament into a local storage, so the statistics sites may query
+//! the storage instead of the service. A `Mirror` fetches the tournament with all the pages of
+//! its participants, its stages and its matches with their games, compares them with the
+//! previous run and passes only the changes to a user-provided `Storage`.
+//!
+//! The rate limiting is handled by the `RetryPolicy` of the client; a `pause` between the
+//! requests may also be set to stay below the limits.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use std::collections::HashMap;
+//! use toornament::*;
+//! use toornament::sync::{Mirror, Storage};
+//!
+//! #[derive(Default)]
+//! struct Memory {
+//!     matches: HashMap<String, Match>,
+//! }
+//! impl Storage for Memory {
+//!     fn upsert_match(&mut self, m: &Match) -> Result<()> {
+//!         self.matches.insert(m.id.0.clone(), m.clone());
+//!         Ok(())
+//!     }
+//!
+//!     fn delete_match(&mut self, id: &MatchId) -> Result<()> {
+//!         self.matches.remove(&id.0);
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let toornament = Toornament::with_application("API_TOKEN",
+//!                                               "CLIENT_ID",
+//!                                               "CLIENT_SECRET").unwrap()
+//!     .retry(RetryPolicy::new(5, std::time::Duration::from_secs(1)));
+//! let mut storage = Memory::default();
+//! let mut mirror = Mirror::new(&toornament, TournamentId("1".to_owned()));
+//! loop {
+//!     let changes = mirror.run(&mut storage).unwrap();
+//!     println!("{} changes", changes.total());
+//!     std::thread::sleep(std::time::Duration::from_secs(60));
+//! }
+//! ```
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::iter::ClientHandle;
+use crate::progress::{Progress, Steps};
+use crate::snapshot::{Difference, TournamentSnapshot};
+use crate::*;
+
+/// A local storage of a mirrored tournament. All the methods do nothing by default, so only
+/// the objects of interest need to be stored.
+pub trait Storage {
+    /// Stores the tournament.
+    fn upsert_tournament(&mut self, _tournament: &Tournament) -> Result<()> {
+        Ok(())
+    }
+
+    /// Stores a new or a changed participant.
+    fn upsert_participant(&mut self, _participant: &Participant) -> Result<()> {
+        Ok(())
+    }
+
+    /// Removes a participant which no longer exists.
+    fn delete_participant(&mut self, _id: &ParticipantId) -> Result<()> {
+        Ok(())
+    }
+
+    /// Stores a new or a changed stage.
+    fn upsert_stage(&mut self, _stage: &Stage) -> Result<()> {
+        Ok(())
+    }
+
+    /// Removes a stage which no longer exists.
+    fn delete_stage(&mut self, _number: &StageNumber) -> Result<()> {
+        Ok(())
+    }
+
+    /// Stores a new or a changed match with its games.
+    fn upsert_match(&mut self, _match: &Match) -> Result<()> {
+        Ok(())
+    }
+
+    /// Removes a match which no longer exists.
+    fn delete_match(&mut self, _id: &MatchId) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// The number of the changes passed to the storage by a run.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Changes {
+    /// Number of the stored objects.
+    pub upserted: usize,
+    /// Number of the removed objects.
+    pub deleted: usize,
+}
+impl Changes {
+    /// Returns the total number of the changes.
+    pub fn total(&self) -> usize {
+        self.upserted + self.deleted
+    }
+}
+
+/// Passes the differences between the `old` and the `new` snapshots to the storage, see
+/// `TournamentSnapshot::diff`. The participants without an id are never deleted.
+pub fn apply_changes<S: Storage + ?Sized>(
+    old: &TournamentSnapshot,
+    new: &TournamentSnapshot,
+    storage: &mut S,
+) -> Result<Changes> {
+    let diff = old.diff(new);
+    let mut changes = Changes::default();
+    if let Some(tournament) = diff.tournament {
+        storage.upsert_tournament(tournament)?;
+        changes.upserted += 1;
+    }
+    for difference in diff.participants {
+        match difference {
+            Difference::Added(p) | Difference::Changed(_, p) => {
+                storage.upsert_participant(p)?;
+                changes.upserted += 1;
+            }
+            Difference::Removed(p) => {
+                if let Some(ref id) = p.id {
+                    storage.delete_participant(id)?;
+                    changes.deleted += 1;
+                }
+            }
+        }
+    }
+    for difference in diff.stages {
+        match difference {
+            Difference::Added(s) | Difference::Changed(_, s) => {
+                storage.upsert_stage(s)?;
+                changes.upserted += 1;
+            }
+            Difference::Removed(s) => {
+                storage.delete_stage(&s.number)?;
+                changes.deleted += 1;
+            }
+        }
+    }
+    for difference in diff.matches {
+        match difference {
+            Difference::Added(m) | Difference::Changed(_, m) => {
+                storage.upsert_match(m)?;
+                changes.upserted += 1;
+            }
+            Difference::Removed(m) => {
+                storage.delete_match(&m.id)?;
+                changes.deleted += 1;
+            }
+        }
+    }
+    Ok(changes)
+}
+
+/// Mirrors a tournament into a storage incrementally.
+pub struct Mirror<'a> {
+    client: ClientHandle<'a>,
+    tournament_id: TournamentId,
+    snapshot: TournamentSnapshot,
+    pause: Option<Duration>,
+    progress: Option<Arc<dyn Progress>>,
+}
+impl<'a> Mirror<'a> {
+    /// Creates new mirror. The first run stores everything.
+    pub fn new<C: Into<ClientHandle<'a>>>(client: C, tournament_id: TournamentId) -> Mirror<'a> {
+        Mirror {
+            client: client.into(),
+            tournament_id,
+            snapshot: TournamentSnapshot::default(),
+            pause: None,
+            progress: None,
+        }
+    }
+
+    /// Starts from the state which is already in the storage, so the first run only stores
+    /// the changes since the `snapshot`.
+    pub fn snapshot(mut self, snapshot: TournamentSnapshot) -> Mirror<'a> {
+        self.snapshot = snapshot;
+        self
+    }
+
+    /// Sleeps for the `pause` before every request, to stay below the rate limits.
+    pub fn pause(mut self, pause: Duration) -> Mirror<'a> {
+        self.pause = Some(pause);
+        self
+    }
+
+    /// Sets the receiver of the progress of every run. See `Progress`.
+    pub fn progress<P: Progress + 'static>(mut self, progress: P) -> Mirror<'a> {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    /// Returns the state of the tournament as of the last successful run.
+    pub fn current(&self) -> &TournamentSnapshot {
+        &self.snapshot
+    }
+
+    /// Fetches the tournament and passes the changes since the previous run to the storage.
+    /// If the storage fails, the next run passes the same changes again.
+    pub fn run<S: Storage + ?Sized>(&mut self, storage: &mut S) -> Result<Changes> {
+        let pause = self.pause;
+        let mut steps = Steps::new(
+            self.progress.as_deref(),
+            Some(TournamentSnapshot::FETCH_STEPS),
+        );
+        let current = TournamentSnapshot::fetch(
+            &self.client,
+            self.tournament_id.clone(),
+            &mut steps,
+            || {
+                if let Some(pause) = pause {
+                    std::thread::sleep(pause);
+                }
+            },
+        )?;
+        let changes = apply_changes(&self.snapshot, &current, storage)?;
+        self.snapshot = current;
+        steps.finish();
+        Ok(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Log(Vec<String>);
+    impl Storage for Log {
+        fn upsert_participant(&mut self, participant: &Participant) -> Result<()> {
+            self.0.push(format!("+{}", participant.name));
+            Ok(())
+        }
+
+        fn delete_participant(&mut self, id: &ParticipantId) -> Result<()> {
+            self.0.push(format!("-{}", id.0));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_apply_changes() {
+        let participant =
+            |id: &str, name: &str| Participant::create(name).id(ParticipantId(id.to_owned()));
+        let old = TournamentSnapshot {
+            participants: Participants::from(vec![participant("1", "A"), participant("2", "B")]),
+            ..Default::default()
+        };
+        let new = TournamentSnapshot {
+            participants: Participants::from(vec![participant("1", "A"), participant("3", "C")]),
+            ..Default::default()
+        };
+        let mut log = Log::default();
+        let changes = apply_changes(&old, &new, &mut log).unwrap();
+        assert_eq!(log.0, vec!["+C", "-2"]);
+        assert_eq!(
+            changes,
+            Changes {
+                upserted: 1,
+                deleted: 1
+            }
+        );
+        assert_eq!(apply_changes(&new, &new, &mut log).unwrap().total(), 0);
+    }
+}
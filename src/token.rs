@@ -0,0 +1,196 @@
+#[cfg(feature = "client-blocking")]
+use std::sync::Condvar;
+use std::sync::Mutex;
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+use std::sync::MutexGuard;
+
+use crate::{AccessToken, TokenInfo};
+#[cfg(any(feature = "client-blocking", feature = "client-async"))]
+use crate::{Error, Result};
+
+/// The refresh of the token in progress, shared by the threads waiting for it.
+#[cfg(feature = "client-blocking")]
+#[derive(Debug, Default)]
+struct Flight {
+    refreshing: bool,
+    /// Incremented when a refresh ends.
+    landed: u64,
+    /// Whether the last refresh failed.
+    failed: bool,
+    /// Number of the threads waiting for the refresh.
+    waiting: usize,
+}
+
+/// Ends the refresh when dropped, even if the refreshing thread panics, and wakes up the
+/// threads waiting for it.
+#[cfg(feature = "client-blocking")]
+struct Landing<'a> {
+    store: &'a TokenStore,
+    failed: bool,
+}
+#[cfg(feature = "client-blocking")]
+impl Drop for Landing<'_> {
+    fn drop(&mut self) {
+        let mut flight = self.store.flight.lock().unwrap_or_else(|e| e.into_inner());
+        flight.refreshing = false;
+        flight.landed = flight.landed.wrapping_add(1);
+        flight.failed = self.failed;
+        self.store.landed.notify_all();
+    }
+}
+
+/// A store of the access token which can be shared by several `Toornament` objects through an
+/// `Arc`, so that they authenticate and refresh the token only once.
+/// See `Toornament::with_token_store`.
+#[derive(Debug, Default)]
+pub struct TokenStore {
+    token: Mutex<AccessToken>,
+    #[cfg(feature = "client-blocking")]
+    flight: Mutex<Flight>,
+    #[cfg(feature = "client-blocking")]
+    landed: Condvar,
+}
+impl TokenStore {
+    /// Creates new empty store: the first client using it gets the token.
+    pub fn new() -> TokenStore {
+        TokenStore::default()
+    }
+
+    /// Returns information about the stored token, if there is one.
+    pub fn token_info(&self) -> Option<TokenInfo> {
+        match self.token.lock() {
+            Ok(g) if g.expires > 0 => Some(crate::token_info(&g)),
+            _ => None,
+        }
+    }
+
+    #[cfg(any(feature = "client-blocking", feature = "client-async"))]
+    pub(crate) fn lock(&self) -> Result<MutexGuard<'_, AccessToken>> {
+        self.token
+            .lock()
+            .map_err(|_| Error::Rest("Can't get the token"))
+    }
+
+    /// Returns the stored token, refreshing it with `refresh` if it is expired or if `force`
+    /// is set. Only one thread refreshes the token at a time, without locking the token: the
+    /// other threads needing a fresh token wait for the refresh and share its outcome, so a
+    /// failed refresh is not repeated by every waiting thread. The error of `refresh` is
+    /// returned to the refreshing thread only.
+    #[cfg(feature = "client-blocking")]
+    pub(crate) fn fresh<F>(&self, mut force: bool, refresh: F) -> Result<String>
+    where
+        F: FnOnce() -> Result<AccessToken>,
+    {
+        let mut flight = self
+            .flight
+            .lock()
+            .map_err(|_| Error::Rest("Can't get the token"))?;
+        loop {
+            if !force {
+                let token = self.lock()?;
+                if !crate::protocol::expired(&token) {
+                    return Ok(token.access_token.expose().to_owned());
+                }
+            }
+            if !flight.refreshing {
+                break;
+            }
+            let landed = flight.landed;
+            flight.waiting += 1;
+            while flight.refreshing && flight.landed == landed {
+                flight = self
+                    .landed
+                    .wait(flight)
+                    .map_err(|_| Error::Rest("Can't get the token"))?;
+            }
+            flight.waiting -= 1;
+            if flight.failed {
+                return Err(Error::Rest("Could not refresh the token"));
+            }
+            // The token refreshed by another thread is used even if a refresh was forced.
+            force = false;
+        }
+        flight.refreshing = true;
+        drop(flight);
+
+        let mut landing = Landing {
+            store: self,
+            failed: true,
+        };
+        let token = refresh()?;
+        let access_token = token.access_token.expose().to_owned();
+        *self.lock()? = token;
+        landing.failed = false;
+        Ok(access_token)
+    }
+}
+
+#[cfg(all(test, any(feature = "client-blocking", feature = "client-async")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_store() {
+        let store = TokenStore::new();
+        assert!(store.token_info().is_none());
+        let json = r#"{ "access_token": "secret", "expires_in": 3600 }"#;
+        *store.lock().unwrap() = crate::parse_token(json.as_bytes()).unwrap();
+        assert!(!store.token_info().unwrap().is_expired());
+        assert!(!format!("{:?}", store).contains("secret"));
+    }
+
+    #[cfg(feature = "client-blocking")]
+    #[test]
+    fn test_single_flight_refresh() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::{Arc, Barrier};
+
+        let store = Arc::new(TokenStore::new());
+        let refreshes = Arc::new(AtomicUsize::new(0));
+        let concurrently = |succeed: bool| {
+            let barrier = Arc::new(Barrier::new(8));
+            (0..8)
+                .map(|_| {
+                    let (store, refreshes, barrier) =
+                        (store.clone(), refreshes.clone(), barrier.clone());
+                    std::thread::spawn(move || {
+                        barrier.wait();
+                        store.fresh(false, || {
+                            refreshes.fetch_add(1, Ordering::SeqCst);
+                            // The refresh lands only once the 7 other threads wait for it.
+                            while store.flight.lock().unwrap().waiting < 7 {
+                                std::thread::yield_now();
+                            }
+                            if !succeed {
+                                return Err(Error::Rest("The service is down"));
+                            }
+                            let json = r#"{ "access_token": "secret", "expires_in": 3600 }"#;
+                            crate::parse_token(json.as_bytes())
+                        })
+                    })
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .map(|t| t.join().unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        // The waiting threads share the failure instead of refreshing in turn.
+        let failures = concurrently(false);
+        assert!(failures.iter().all(|r| r.is_err()));
+        assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+        assert!(store.token_info().is_none());
+
+        let tokens = concurrently(true);
+        assert!(tokens.iter().all(|r| r.as_deref().ok() == Some("secret")));
+        assert_eq!(refreshes.load(Ordering::SeqCst), 2);
+
+        // A forced refresh gets a new token even if the stored one is valid.
+        let forced = store.fresh(true, || {
+            refreshes.fetch_add(1, Ordering::SeqCst);
+            crate::parse_token(r#"{ "access_token": "new", "expires_in": 3600 }"#.as_bytes())
+        });
+        assert_eq!(forced.unwrap(), "new");
+        assert_eq!(refreshes.load(Ordering::SeqCst), 3);
+    }
+}
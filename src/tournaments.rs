@@ -1,8 +1,14 @@
+use std::marker::PhantomData;
+
+use chrono::{DateTime, Duration, FixedOffset};
+
 use crate::common::Date;
 use crate::disciplines::DisciplineId;
+use crate::fingerprint::{fingerprint, Fingerprint};
 use crate::matches::{MatchFormat, MatchType};
 use crate::participants::ParticipantType;
 use crate::streams::Streams;
+use crate::{Error, Result, ValidationError};
 
 /// A tournament identity.
 #[derive(
@@ -26,6 +32,7 @@ pub enum TournamentStatus {
 
 /// A tournament object.
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct Tournament {
     /// An hexadecimal unique identifier for this tournament.
     /// Example: "5608fd12140ba061298b4569"
@@ -114,6 +121,7 @@ pub struct Tournament {
     /// Enable or disable the participant check-in in the tournament.
     /// Example: true
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[deprecated(since = "3.0.0", note = "use `TournamentSettings::check_in`")]
     pub check_in: Option<bool>,
     /// Enable or disable the participant flag in the tournament.
     /// Example: true
@@ -123,9 +131,48 @@ pub struct Tournament {
     /// Possible values: none, one, home_away, bo3, bo5, bo7, bo9, bo11
     #[serde(skip_serializing_if = "Option::is_none")]
     pub match_format: Option<MatchFormat>,
+    /// Whether the participants are able to register to the tournament.
+    /// Example: true
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[deprecated(
+        since = "3.0.0",
+        note = "use `TournamentSettings::registration_enabled`"
+    )]
+    pub registration_enabled: Option<bool>,
+    /// Date and time when the registrations are opened. This value is represented as an ISO 8601
+    /// date containing the date, the time and the time zone.
+    /// Example: "2015-09-01T00:00:00+00:00"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[deprecated(
+        since = "3.0.0",
+        note = "use `TournamentSettings::registration_opening_datetime`"
+    )]
+    pub registration_opening_datetime: Option<DateTime<FixedOffset>>,
+    /// Date and time when the registrations are closed. This value is represented as an ISO 8601
+    /// date containing the date, the time and the time zone.
+    /// Example: "2015-09-05T00:00:00+00:00"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[deprecated(
+        since = "3.0.0",
+        note = "use `TournamentSettings::registration_closing_datetime`"
+    )]
+    pub registration_closing_datetime: Option<DateTime<FixedOffset>>,
+    /// Whether the organizer is notified by email about the new registrations.
+    /// Example: true
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[deprecated(
+        since = "3.0.0",
+        note = "use `TournamentSettings::registration_notification_enabled`"
+    )]
+    pub registration_notification_enabled: Option<bool>,
+    /// Whether the tournament is archived.
+    /// Example: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
 }
 impl Tournament {
     /// Creates new `Tournament` object.
+    #[allow(deprecated)]
     pub fn new<S: Into<String>>(
         id: Option<TournamentId>,
         discipline: DisciplineId,
@@ -162,11 +209,17 @@ impl Tournament {
             check_in: None,
             participant_nationality: None,
             match_format: None,
+            registration_enabled: None,
+            registration_opening_datetime: None,
+            registration_closing_datetime: None,
+            registration_notification_enabled: None,
+            archived: None,
         }
     }
 
     /// A method which creates `Tournament` object for creation (Toornament::edit_tournament)
     /// purposes.
+    #[allow(deprecated)]
     pub fn create<S: Into<String>>(
         discipline: DisciplineId,
         name: S,
@@ -200,6 +253,11 @@ impl Tournament {
             check_in: None,
             participant_nationality: None,
             match_format: None,
+            registration_enabled: None,
+            registration_opening_datetime: None,
+            registration_closing_datetime: None,
+            registration_notification_enabled: None,
+            archived: None,
         }
     }
 
@@ -226,11 +284,200 @@ impl Tournament {
     builder!(team_size_min, Option<i64>);
     builder!(team_size_max, Option<i64>);
     builder!(streams, Option<Streams>);
-    builder!(check_in, Option<bool>);
+    builder!(
+        #[deprecated(since = "3.0.0", note = "use `TournamentSettings::check_in`")]
+        check_in,
+        Option<bool>
+    );
     builder!(participant_nationality, Option<bool>);
     builder!(match_format, Option<MatchFormat>);
+    builder!(
+        #[deprecated(
+            since = "3.0.0",
+            note = "use `TournamentSettings::registration_enabled`"
+        )]
+        registration_enabled,
+        Option<bool>
+    );
+    builder!(
+        #[deprecated(
+            since = "3.0.0",
+            note = "use `TournamentSettings::registration_opening_datetime`"
+        )]
+        registration_opening_datetime,
+        Option<DateTime<FixedOffset>>
+    );
+    builder!(
+        #[deprecated(
+            since = "3.0.0",
+            note = "use `TournamentSettings::registration_closing_datetime`"
+        )]
+        registration_closing_datetime,
+        Option<DateTime<FixedOffset>>
+    );
+    builder!(
+        #[deprecated(
+            since = "3.0.0",
+            note = "use `TournamentSettings::registration_notification_enabled`"
+        )]
+        registration_notification_enabled,
+        Option<bool>
+    );
+    builder!(archived, Option<bool>);
+}
+
+impl Tournament {
+    /// Maximum length of the name, in characters, as documented for the `name` field.
+    pub const NAME_MAX_LENGTH: usize = 30;
+    /// Maximum length of the full name, in characters, as documented for the `full_name`
+    /// field.
+    pub const FULL_NAME_MAX_LENGTH: usize = 80;
+
+    /// Returns a builder of a tournament for creation (Toornament::edit_tournament) purposes.
+    /// The discipline, the name and the size are required: `TournamentBuilder::build` does not
+    /// compile until they are all set.
+    ///
+    /// ```rust
+    /// use toornament::*;
+    ///
+    /// let tournament = Tournament::builder()
+    ///     .discipline(DisciplineId("my_discipline".to_owned()))
+    ///     .name("My Weekly Tournament")
+    ///     .size(16)
+    ///     .public(true)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(tournament.size, 16);
+    /// ```
+    ///
+    /// ```rust,compile_fail
+    /// use toornament::*;
+    ///
+    /// let tournament = Tournament::builder()
+    ///     .discipline(DisciplineId("my_discipline".to_owned()))
+    ///     .name("My Weekly Tournament")
+    ///     .build();
+    /// ```
+    pub fn builder() -> TournamentBuilder {
+        TournamentBuilder {
+            tournament: Tournament::new(
+                None,
+                DisciplineId(String::new()),
+                "",
+                TournamentStatus::Setup,
+                true,
+                false,
+                0,
+            ),
+            required: PhantomData,
+        }
+    }
+}
+
+/// A builder of a `Tournament`, see `Tournament::builder`. The type parameters are the types
+/// of the required fields (the discipline, the name and the size) once they are set, and `()`
+/// before.
+#[derive(Clone, Debug)]
+pub struct TournamentBuilder<D = (), N = (), S = ()> {
+    tournament: Tournament,
+    required: PhantomData<(D, N, S)>,
+}
+impl<D, N, S> TournamentBuilder<D, N, S> {
+    /// Sets the discipline of the tournament.
+    pub fn discipline(self, discipline: DisciplineId) -> TournamentBuilder<DisciplineId, N, S> {
+        TournamentBuilder {
+            tournament: self.tournament.discipline(discipline),
+            required: PhantomData,
+        }
+    }
+
+    /// Sets the name of the tournament.
+    pub fn name<T: Into<String>>(self, name: T) -> TournamentBuilder<D, String, S> {
+        TournamentBuilder {
+            tournament: self.tournament.name(name),
+            required: PhantomData,
+        }
+    }
+
+    /// Sets the size of the tournament.
+    pub fn size(self, size: i64) -> TournamentBuilder<D, N, i64> {
+        TournamentBuilder {
+            tournament: self.tournament.size(size),
+            required: PhantomData,
+        }
+    }
+
+    /// Sets the complete name of the tournament.
+    pub fn full_name<T: Into<String>>(mut self, full_name: T) -> Self {
+        self.tournament.full_name = Some(full_name.into());
+        self
+    }
+
+    /// Sets the status of the tournament, `TournamentStatus::Setup` by default.
+    pub fn status(mut self, status: TournamentStatus) -> Self {
+        self.tournament.status = status;
+        self
+    }
+
+    /// Sets whether the tournament is played on internet, `true` by default.
+    pub fn online(mut self, online: bool) -> Self {
+        self.tournament.online = online;
+        self
+    }
+
+    /// Sets whether the tournament is public, `false` by default.
+    pub fn public(mut self, public: bool) -> Self {
+        self.tournament.public = public;
+        self
+    }
+
+    /// Sets the type of the participants of the tournament.
+    pub fn participant_type(mut self, participant_type: ParticipantType) -> Self {
+        self.tournament.participant_type = Some(participant_type);
+        self
+    }
+
+    /// Sets the type of the matches of the tournament.
+    pub fn match_type(mut self, match_type: MatchType) -> Self {
+        self.tournament.match_type = Some(match_type);
+        self
+    }
+}
+impl TournamentBuilder<DisciplineId, String, i64> {
+    /// Returns the tournament, or a `ValidationError` if the discipline or the name are empty,
+    /// the names are longer than `Tournament::NAME_MAX_LENGTH` and
+    /// `Tournament::FULL_NAME_MAX_LENGTH` or the size is not positive.
+    pub fn build(self) -> Result<Tournament> {
+        let t = self.tournament;
+        let invalid = |field: &str, reason: &str| {
+            Err(Error::Validation(ValidationError::Invalid(
+                field.to_owned(),
+                reason.to_owned(),
+            )))
+        };
+        if t.discipline.0.trim().is_empty() {
+            return invalid("discipline", "must not be empty");
+        }
+        if t.name.trim().is_empty() {
+            return invalid("name", "must not be empty");
+        }
+        let too_long = |max: usize| format!("must not be longer than {} characters", max);
+        if t.name.chars().count() > Tournament::NAME_MAX_LENGTH {
+            return invalid("name", &too_long(Tournament::NAME_MAX_LENGTH));
+        }
+        if let Some(ref full_name) = t.full_name {
+            if full_name.chars().count() > Tournament::FULL_NAME_MAX_LENGTH {
+                return invalid("full_name", &too_long(Tournament::FULL_NAME_MAX_LENGTH));
+            }
+        }
+        if t.size <= 0 {
+            return invalid("size", "must be positive");
+        }
+        Ok(t)
+    }
 }
 
+#[cfg(feature = "client-blocking")]
 impl Tournament {
     /// Returns iter for the tournament
     pub fn iter<'a>(&self, client: &'a crate::Toornament) -> Option<crate::TournamentIter<'a>> {
@@ -250,11 +497,168 @@ impl Tournament {
     }
 }
 
+impl Tournament {
+    /// Returns the fingerprint of the tournament, which changes with any of its fields. See
+    /// `Fingerprint`. Fails only if the serialization fails.
+    pub fn fingerprint(&self) -> Result<Fingerprint> {
+        fingerprint(self)
+    }
+
+    /// Returns the duration of the tournament (both the starting and the ending days are
+    /// included) if both dates are set.
+    pub fn duration(&self) -> Option<Duration> {
+        match (self.date_start, self.date_end) {
+            (Some(start), Some(end)) if end >= start => Some(end - start + Duration::days(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the tournament takes place at the `now` date: it is running or the
+    /// date lies between the starting and the ending dates.
+    pub fn is_live(&self, now: Date) -> bool {
+        match self.status {
+            TournamentStatus::Running => return true,
+            TournamentStatus::Completed => return false,
+            _ => {}
+        }
+        match (self.date_start, self.date_end) {
+            (Some(start), Some(end)) => start <= now && now <= end,
+            (Some(start), None) => start == now,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the tournament has not started yet at the `now` date and it is going
+    /// to start within the `within` duration.
+    pub fn starts_within(&self, now: Date, within: Duration) -> bool {
+        match self.date_start {
+            Some(start) if self.status == TournamentStatus::Setup => {
+                start >= now && start - now <= within
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the tournament is archived. The archived tournaments are read-only and
+    /// are listed only when asked for with `TournamentFilter::archived`.
+    pub fn is_archived(&self) -> bool {
+        self.archived == Some(true)
+    }
+
+    /// Returns `true` if the tournament itself allows to start it: it is in the setup status
+    /// and is not archived. The participants and the stages are checked by
+    /// `Organizer::start_tournament`.
+    pub fn can_start(&self) -> bool {
+        self.status == TournamentStatus::Setup && !self.is_archived()
+    }
+
+    /// Returns `true` if the tournament has started and is not completed yet. The matches are
+    /// checked by `Organizer::complete_tournament`.
+    pub fn can_complete(&self) -> bool {
+        match self.status {
+            TournamentStatus::Running | TournamentStatus::Pending => true,
+            TournamentStatus::Setup | TournamentStatus::Completed => false,
+        }
+    }
+}
+
+/// The editable settings of a tournament: its registration, check-in and participant options.
+/// The newer versions of the API serve them apart from the tournament, see
+/// `Toornament::tournament_settings`. Only the settings which are set are sent on update.
+#[derive(
+    Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
+)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
+pub struct TournamentSettings {
+    /// Whether the participants are able to register to the tournament.
+    /// Example: true
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registration_enabled: Option<bool>,
+    /// Date and time when the registrations are opened.
+    /// Example: "2015-09-01T00:00:00+00:00"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registration_opening_datetime: Option<DateTime<FixedOffset>>,
+    /// Date and time when the registrations are closed.
+    /// Example: "2015-09-05T00:00:00+00:00"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registration_closing_datetime: Option<DateTime<FixedOffset>>,
+    /// Whether the organizer is notified by email about the new registrations.
+    /// Example: true
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registration_notification_enabled: Option<bool>,
+    /// Enable or disable the participant check-in in the tournament.
+    /// Example: true
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_in: Option<bool>,
+    /// Enable or disable the participant flag in the tournament.
+    /// Example: true
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub participant_nationality: Option<bool>,
+    /// The smallest possible team size, if the participants are teams.
+    /// Example: 5
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team_size_min: Option<i64>,
+    /// The largest possible team size, if the participants are teams.
+    /// Example: 7
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team_size_max: Option<i64>,
+}
+impl TournamentSettings {
+    builder_o!(registration_enabled, bool);
+    builder_o!(registration_opening_datetime, DateTime<FixedOffset>);
+    builder_o!(registration_closing_datetime, DateTime<FixedOffset>);
+    builder_o!(registration_notification_enabled, bool);
+    builder_o!(check_in, bool);
+    builder_o!(participant_nationality, bool);
+    builder_o!(team_size_min, i64);
+    builder_o!(team_size_max, i64);
+
+    /// Checks the settings before they are sent: the registrations do not close before they
+    /// open and the smallest team size is not larger than the largest.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |field: &str, reason: &str| {
+            Err(Error::Validation(ValidationError::Invalid(
+                field.to_owned(),
+                reason.to_owned(),
+            )))
+        };
+        if let (Some(opening), Some(closing)) = (
+            self.registration_opening_datetime,
+            self.registration_closing_datetime,
+        ) {
+            if closing < opening {
+                return invalid(
+                    "registration_closing_datetime",
+                    "must not be before the opening",
+                );
+            }
+        }
+        if let (Some(min), Some(max)) = (self.team_size_min, self.team_size_max) {
+            if min > max {
+                return invalid("team_size_min", "must not be larger than team_size_max");
+            }
+        }
+        Ok(())
+    }
+}
+
 /// A list of `Tournament` objects.
 #[derive(
     Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
 )]
 pub struct Tournaments(pub Vec<Tournament>);
+list_wrapper!(Tournaments, Tournament);
+impl Tournaments {
+    /// Returns the tournament with the identifier.
+    pub fn by_id(&self, id: &TournamentId) -> Option<&Tournament> {
+        self.0.iter().find(|t| t.id.as_ref() == Some(id))
+    }
+
+    /// Returns the first tournament with the name.
+    pub fn by_name(&self, name: &str) -> Option<&Tournament> {
+        self.0.iter().find(|t| t.name == name)
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -278,6 +682,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_tournament_parse() {
         use chrono::Datelike;
 
@@ -313,7 +718,12 @@ mod tests {
             ],
             "check_in": true,
             "participant_nationality": true,
-            "match_format": "bo3"
+            "match_format": "bo3",
+            "registration_enabled": true,
+            "registration_opening_datetime": "2015-09-01T00:00:00+00:00",
+            "registration_closing_datetime": "2015-09-05T12:30:00+02:00",
+            "registration_notification_enabled": false,
+            "archived": false
         }"#;
         let t: Tournament = serde_json::from_str(string).unwrap();
 
@@ -363,5 +773,208 @@ mod tests {
         assert_eq!(t.check_in, Some(true));
         assert_eq!(t.participant_nationality, Some(true));
         assert_eq!(t.match_format, Some(MatchFormat::BestOf3));
+        assert_eq!(t.registration_enabled, Some(true));
+        assert_eq!(
+            t.registration_opening_datetime,
+            Some(DateTime::parse_from_rfc3339("2015-09-01T00:00:00+00:00").unwrap())
+        );
+        assert_eq!(
+            t.registration_closing_datetime,
+            Some(DateTime::parse_from_rfc3339("2015-09-05T12:30:00+02:00").unwrap())
+        );
+        assert_eq!(t.registration_notification_enabled, Some(false));
+        assert_eq!(t.archived, Some(false));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_tournament_registration_serialize() {
+        let opening = DateTime::parse_from_rfc3339("2015-09-01T10:00:00+02:00").unwrap();
+        let t = Tournament::create(
+            DisciplineId("my_discipline".to_owned()),
+            "My Weekly Tournament",
+            16,
+            ParticipantType::Single,
+        )
+        .registration_enabled(Some(true))
+        .registration_opening_datetime(Some(opening));
+        let value = serde_json::to_value(&t).unwrap();
+
+        assert_eq!(value["registration_enabled"], true);
+        assert_eq!(
+            value["registration_opening_datetime"],
+            "2015-09-01T10:00:00+02:00"
+        );
+        assert!(value.get("registration_closing_datetime").is_none());
+        assert!(value.get("archived").is_none());
+    }
+
+    #[test]
+    fn test_tournament_date_helpers() {
+        use chrono::NaiveDate;
+
+        let date = |d| NaiveDate::from_ymd_opt(2015, 9, d).unwrap();
+        let t = Tournament::create(
+            DisciplineId("my_discipline".to_owned()),
+            "My Weekly Tournament",
+            16,
+            ParticipantType::Single,
+        );
+        assert!(t.duration().is_none());
+        assert!(!t.is_live(date(6)));
+        assert!(!t.starts_within(date(1), Duration::days(30)));
+
+        let t = t.date_start(Some(date(6))).date_end(Some(date(7)));
+        assert_eq!(t.duration(), Some(Duration::days(2)));
+        assert!(!t.is_live(date(5)));
+        assert!(t.is_live(date(6)));
+        assert!(t.is_live(date(7)));
+        assert!(!t.is_live(date(8)));
+        assert!(t.starts_within(date(1), Duration::days(5)));
+        assert!(!t.starts_within(date(1), Duration::days(4)));
+        assert!(!t.starts_within(date(7), Duration::days(5)));
+
+        let t = t.status(TournamentStatus::Running);
+        assert!(t.is_live(date(8)));
+        assert!(!t.starts_within(date(1), Duration::days(5)));
+        let t = t.status(TournamentStatus::Completed);
+        assert!(!t.is_live(date(6)));
+    }
+
+    #[test]
+    fn test_tournament_status_guards() {
+        let t = Tournament::create(
+            DisciplineId("my_discipline".to_owned()),
+            "My Weekly Tournament",
+            16,
+            ParticipantType::Single,
+        );
+        assert!(t.can_start());
+        assert!(!t.can_complete());
+        assert!(!t.is_archived());
+        assert!(t.clone().archived(Some(true)).is_archived());
+        assert!(!t.clone().archived(Some(true)).can_start());
+
+        let t = t.status(TournamentStatus::Running);
+        assert!(!t.can_start());
+        assert!(t.can_complete());
+        assert!(!t.status(TournamentStatus::Completed).can_complete());
+    }
+
+    #[test]
+    fn test_tournament_mutators() {
+        let mut t = Tournament::create(
+            DisciplineId("my_discipline".to_owned()),
+            "My Weekly Tournament",
+            16,
+            ParticipantType::Single,
+        );
+        let location = Some("Paris".to_owned());
+        if let Some(ref location) = location {
+            t.set_location(Some(location.clone())).set_online(false);
+        }
+        t.set_name("My Monthly Tournament").set_size(32);
+        assert_eq!(t.name, "My Monthly Tournament");
+        assert_eq!(t.location.as_deref(), Some("Paris"));
+        assert!(!t.online);
+        assert_eq!(t.size, 32);
+    }
+
+    #[test]
+    fn test_tournaments_lookups() {
+        let tournament = |id: &str, name: &str, size| {
+            Tournament::builder()
+                .name(name)
+                .discipline(DisciplineId("my_discipline".to_owned()))
+                .size(size)
+                .build()
+                .unwrap()
+                .id(Some(TournamentId(id.to_owned())))
+        };
+        let tournaments = Tournaments::from(vec![
+            tournament("5608fd12140ba061298b4569", "My Weekly Tournament", 16),
+            tournament("5608fd12140ba061298b4570", "My Monthly Tournament", 8),
+        ]);
+        let id = TournamentId("5608fd12140ba061298b4570".to_owned());
+        assert_eq!(
+            tournaments.by_id(&id).unwrap().name,
+            "My Monthly Tournament"
+        );
+        assert!(tournaments.by_id(&TournamentId("1".to_owned())).is_none());
+        let weekly = tournaments.by_name("My Weekly Tournament").unwrap();
+        assert_eq!(weekly.size, 16);
+        assert!(tournaments.by_name("my weekly tournament").is_none());
+
+        let mut renamed = weekly.clone();
+        assert_eq!(
+            renamed.fingerprint().unwrap(),
+            weekly.fingerprint().unwrap()
+        );
+        renamed.set_name("My Weekly Cup");
+        assert_ne!(
+            renamed.fingerprint().unwrap(),
+            weekly.fingerprint().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_tournament_builder() {
+        let t = Tournament::builder()
+            .size(16)
+            .name("My Weekly Tournament")
+            .discipline(DisciplineId("my_discipline".to_owned()))
+            .online(false)
+            .participant_type(ParticipantType::Team)
+            .build()
+            .unwrap();
+        assert_eq!(t.discipline.0, "my_discipline");
+        assert_eq!(t.name, "My Weekly Tournament");
+        assert_eq!(t.size, 16);
+        assert_eq!(t.status, TournamentStatus::Setup);
+        assert!(!t.online);
+        assert!(!t.public);
+        assert_eq!(t.participant_type, Some(ParticipantType::Team));
+
+        let builder = Tournament::builder()
+            .discipline(DisciplineId("my_discipline".to_owned()))
+            .name("My Weekly Tournament");
+        match builder.clone().size(0).build() {
+            Err(Error::Validation(ValidationError::Invalid(field, _))) => assert_eq!(field, "size"),
+            _ => panic!("Expected an invalid size"),
+        }
+        match builder.name("x".repeat(31)).size(16).build() {
+            Err(Error::Validation(ValidationError::Invalid(field, _))) => assert_eq!(field, "name"),
+            _ => panic!("Expected an invalid name"),
+        }
+    }
+
+    #[test]
+    fn test_tournament_settings() {
+        let settings = TournamentSettings::default()
+            .check_in(true)
+            .team_size_min(5);
+        // Only the settings which are set are sent.
+        assert_eq!(
+            serde_json::to_value(&settings).unwrap(),
+            serde_json::json!({ "check_in": true, "team_size_min": 5 })
+        );
+        assert!(settings.validate().is_ok());
+        match settings.team_size_max(4).validate() {
+            Err(Error::Validation(ValidationError::Invalid(field, _))) => {
+                assert_eq!(field, "team_size_min")
+            }
+            _ => panic!("Expected an invalid team size"),
+        }
+        let day =
+            |d| DateTime::parse_from_rfc3339(&format!("2015-09-0{}T00:00:00+00:00", d)).unwrap();
+        let settings = TournamentSettings::default()
+            .registration_opening_datetime(day(5))
+            .registration_closing_datetime(day(1));
+        match settings.validate() {
+            Err(Error::Validation(ValidationError::Invalid(field, _))) => {
+                assert_eq!(field, "registration_closing_datetime")
+            }
+            _ => panic!("Expected an invalid closing date"),
+        }
     }
 }
@@ -0,0 +1,129 @@
+use crate::participants::{Participant, Participants};
+use crate::tournaments::{Tournament, TournamentId};
+#[cfg(feature = "client-blocking")]
+use crate::{Error, Result, Toornament, TournamentParticipantsFilter};
+
+/// The state of the resources before a destructive call, to recreate them with
+/// `Toornament::undo`. The token may be serialized to undo the call later.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case", tag = "operation")]
+pub enum UndoToken {
+    /// A tournament was deleted with its participants.
+    TournamentDeleted {
+        /// The deleted tournament.
+        tournament: Tournament,
+        /// The participants of the deleted tournament.
+        participants: Participants,
+    },
+    /// A participant was deleted.
+    ParticipantDeleted {
+        /// The tournament of the participant.
+        tournament_id: TournamentId,
+        /// The deleted participant.
+        participant: Participant,
+    },
+    /// The participants of a tournament were replaced.
+    ParticipantsReplaced {
+        /// The tournament of the participants.
+        tournament_id: TournamentId,
+        /// The participants before the replacement.
+        participants: Participants,
+    },
+}
+#[cfg(feature = "client-blocking")]
+impl UndoToken {
+    /// Fetches all the participants of a tournament.
+    pub(crate) fn participants(client: &Toornament, id: TournamentId) -> Result<Participants> {
+        client
+            .tournament_participants_pager(id, TournamentParticipantsFilter::default())
+            .fetch_all()
+            .map(Participants::from)
+    }
+
+    /// Recreates the resources, returning the tournament they belong to.
+    pub(crate) fn undo(self, client: &Toornament) -> Result<TournamentId> {
+        match self {
+            UndoToken::TournamentDeleted {
+                tournament,
+                participants,
+            } => recreate(client, tournament, participants),
+            UndoToken::ParticipantDeleted {
+                tournament_id,
+                mut participant,
+            } => {
+                participant.id = None;
+                let _ = client.create_tournament_participant(tournament_id.clone(), participant)?;
+                Ok(tournament_id)
+            }
+            UndoToken::ParticipantsReplaced {
+                tournament_id,
+                participants,
+            } => {
+                let _ = client.update_tournament_participants(
+                    tournament_id.clone(),
+                    without_ids(participants),
+                )?;
+                Ok(tournament_id)
+            }
+        }
+    }
+}
+
+/// Creates the tournament as a new one with the participants, without their identifiers,
+/// returning the id of the new tournament.
+#[cfg(feature = "client-blocking")]
+pub(crate) fn recreate(
+    client: &Toornament,
+    mut tournament: Tournament,
+    participants: Participants,
+) -> Result<TournamentId> {
+    tournament.id = None;
+    let id = client
+        .edit_tournament(tournament)?
+        .id
+        .ok_or(Error::Rest("The recreated tournament has no id"))?;
+    if !participants.0.is_empty() {
+        let _ = client.update_tournament_participants(id.clone(), without_ids(participants))?;
+    }
+    Ok(id)
+}
+
+/// Removes the identifiers of the participants, which are given by the service on creation.
+#[cfg(feature = "client-blocking")]
+fn without_ids(participants: Participants) -> Participants {
+    participants
+        .0
+        .into_iter()
+        .map(|mut p| {
+            p.id = None;
+            p
+        })
+        .collect()
+}
+
+#[cfg(all(test, feature = "client-blocking"))]
+mod tests {
+    use super::*;
+    use crate::participants::ParticipantId;
+
+    #[test]
+    fn test_undo_token_serialization() {
+        let mut participant = Participant::create("Evil Geniuses");
+        participant.id = Some(ParticipantId("2".to_owned()));
+        let token = UndoToken::ParticipantDeleted {
+            tournament_id: TournamentId("1".to_owned()),
+            participant,
+        };
+        let json = serde_json::to_value(&token).unwrap();
+        assert_eq!(json["operation"], "participant_deleted");
+        assert_eq!(json["participant"]["id"], "2");
+        assert_eq!(serde_json::from_value::<UndoToken>(json).unwrap(), token);
+
+        let restored = without_ids(Participants::from(vec![Participant {
+            id: Some(ParticipantId("3".to_owned())),
+            ..Participant::create("Fnatic")
+        }]));
+        assert_eq!(restored.0[0].id, None);
+        assert_eq!(restored.0[0].name, "Fnatic");
+    }
+}
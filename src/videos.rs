@@ -25,6 +25,7 @@ impl fmt::Display for VideoCategory {
 
 /// A tournament video
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct Video {
     /// Title of the video.
     pub name: String,
@@ -42,6 +43,7 @@ pub struct Video {
 /// A list of tournament videos
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
 pub struct Videos(pub Vec<Video>);
+list_wrapper!(Videos, Video);
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,244 @@
+//! This module emulates the webhooks for the users who can not receive them: a `Watcher`
+//! periodically fetches a tournament with its participants, stages and matches, compares them
+//! with the previous fetch and emits the same `webhooks::Event`s, so the same code may consume
+//! both. The first poll emits the events for everything which exists, as if it was just
+//! created, and the next ones emit the deletions of the participants, stages and matches as
+//! well. The registrations are not watched: the version of the API used by this crate does
+//! not list them.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use toornament::*;
+//! use toornament::watcher::Watcher;
+//!
+//! let toornament = Toornament::with_application("API_TOKEN",
+//!                                               "CLIENT_ID",
+//!                                               "CLIENT_SECRET").unwrap();
+//! let cancellation = Cancellation::with_timeout(Duration::from_secs(3600));
+//! let mut watcher = Watcher::new(&toornament, TournamentId("1".to_owned()))
+//!     .interval(Duration::from_secs(30))
+//!     .cancellation(cancellation);
+//! let _ = watcher.watch(|event| println!("{:?}", event));
+//! ```
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::iter::ClientHandle;
+use crate::progress::{Progress, Steps};
+use crate::snapshot::{Difference, TournamentSnapshot};
+use crate::webhooks::Event;
+use crate::*;
+
+/// Returns the events which turn the `old` snapshot into the `new` one, see
+/// `TournamentSnapshot::diff`. The participants without an id are never deleted.
+pub fn diff(old: &TournamentSnapshot, new: &TournamentSnapshot) -> Vec<Event> {
+    let diff = old.diff(new);
+    let mut events = Vec::new();
+    if let Some(tournament) = diff.tournament {
+        events.push(Event::TournamentUpdated(tournament.clone()));
+    }
+    for difference in diff.participants {
+        match difference {
+            Difference::Added(p) => events.push(Event::ParticipantCreated(p.clone())),
+            Difference::Changed(_, p) => events.push(Event::ParticipantUpdated(p.clone())),
+            Difference::Removed(p) => {
+                if let Some(ref id) = p.id {
+                    events.push(Event::ParticipantDeleted { id: id.clone() });
+                }
+            }
+        }
+    }
+    for difference in diff.stages {
+        match difference {
+            Difference::Added(s) | Difference::Changed(_, s) => {
+                events.push(Event::StageUpdated(s.clone()))
+            }
+            Difference::Removed(s) => events.push(Event::StageDeleted {
+                number: s.number.clone(),
+            }),
+        }
+    }
+    for difference in diff.matches {
+        match difference {
+            Difference::Added(m) | Difference::Changed(_, m) => {
+                events.push(Event::MatchUpdated(m.clone()))
+            }
+            Difference::Removed(m) => events.push(Event::MatchDeleted { id: m.id.clone() }),
+        }
+    }
+    events
+}
+
+/// Polls a tournament and emits the changes as events.
+pub struct Watcher<'a> {
+    client: ClientHandle<'a>,
+    tournament_id: TournamentId,
+    snapshot: TournamentSnapshot,
+    interval: Duration,
+    cancellation: Option<Cancellation>,
+    progress: Option<Arc<dyn Progress>>,
+}
+impl<'a> Watcher<'a> {
+    /// Creates new watcher polling the tournament every minute.
+    pub fn new<C: Into<ClientHandle<'a>>>(client: C, tournament_id: TournamentId) -> Watcher<'a> {
+        Watcher {
+            client: client.into(),
+            tournament_id,
+            snapshot: TournamentSnapshot::default(),
+            interval: Duration::from_secs(60),
+            cancellation: None,
+            progress: None,
+        }
+    }
+
+    /// Sets the interval between the polls.
+    pub fn interval(mut self, interval: Duration) -> Watcher<'a> {
+        self.interval = interval;
+        self
+    }
+
+    /// Makes `watch` stop once the `cancellation` is cancelled.
+    pub fn cancellation(mut self, cancellation: Cancellation) -> Watcher<'a> {
+        self.cancellation = Some(cancellation);
+        self
+    }
+
+    /// Sets the receiver of the progress of every poll. See `Progress`.
+    pub fn progress<P: Progress + 'static>(mut self, progress: P) -> Watcher<'a> {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    /// Starts from the known state instead of an empty one, so the first poll only emits the
+    /// changes since the `snapshot`.
+    pub fn snapshot(mut self, snapshot: TournamentSnapshot) -> Watcher<'a> {
+        self.snapshot = snapshot;
+        self
+    }
+
+    /// Returns the state of the tournament as of the last poll.
+    pub fn current(&self) -> &TournamentSnapshot {
+        &self.snapshot
+    }
+
+    /// Fetches the tournament once and returns the events since the previous poll.
+    pub fn poll(&mut self) -> Result<Vec<Event>> {
+        let mut steps = Steps::new(
+            self.progress.as_deref(),
+            Some(TournamentSnapshot::FETCH_STEPS),
+        );
+        let current =
+            TournamentSnapshot::fetch(&self.client, self.tournament_id.clone(), &mut steps, || {})?;
+        steps.finish();
+        let events = diff(&self.snapshot, &current);
+        self.snapshot = current;
+        Ok(events)
+    }
+
+    /// Polls the tournament until the cancellation or an error and passes every event to
+    /// `f`. Returns `Error::Cancelled` once cancelled.
+    pub fn watch<F: FnMut(Event)>(&mut self, mut f: F) -> Result<()> {
+        loop {
+            self.check()?;
+            for event in self.poll()? {
+                f(event);
+            }
+            let next = Instant::now() + self.interval;
+            while Instant::now() < next {
+                self.check()?;
+                std::thread::sleep(
+                    next.saturating_duration_since(Instant::now())
+                        .min(Duration::from_millis(100)),
+                );
+            }
+        }
+    }
+
+    fn check(&self) -> Result<()> {
+        match self.cancellation {
+            Some(ref cancellation) => cancellation.check(),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_emits_webhook_events() {
+        let participant =
+            |id: &str, name: &str| Participant::create(name).id(ParticipantId(id.to_owned()));
+        let old = TournamentSnapshot {
+            participants: Participants::from(vec![participant("1", "A"), participant("2", "B")]),
+            ..Default::default()
+        };
+        let new = TournamentSnapshot {
+            participants: Participants::from(vec![participant("1", "A2"), participant("3", "C")]),
+            ..Default::default()
+        };
+        assert_eq!(
+            diff(&old, &new),
+            vec![
+                Event::ParticipantUpdated(participant("1", "A2")),
+                Event::ParticipantCreated(participant("3", "C")),
+                Event::ParticipantDeleted {
+                    id: ParticipantId("2".to_owned())
+                },
+            ]
+        );
+        assert!(diff(&new, &new).is_empty());
+
+        let mut replayed = old.clone();
+        for event in diff(&old, &new) {
+            event.apply_to(&mut replayed);
+        }
+        let mut names = replayed
+            .participants
+            .0
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["A2", "C"]);
+
+        let a_match = |id: &str| -> Match {
+            serde_json::from_value(serde_json::json!({
+                "id": id,
+                "type": "duel",
+                "discipline": "my_discipline",
+                "status": "pending",
+                "tournament_id": "1",
+                "number": 1,
+                "stage_number": 1,
+                "group_number": 1,
+                "round_number": 1,
+                "date": "2015-09-06T00:10:00-06:00",
+                "opponents": []
+            }))
+            .unwrap()
+        };
+        let both = TournamentSnapshot {
+            matches: Matches(vec![a_match("1"), a_match("2")]),
+            ..Default::default()
+        };
+        let first = TournamentSnapshot {
+            matches: Matches(vec![a_match("1")]),
+            ..Default::default()
+        };
+        assert_eq!(
+            diff(&both, &first),
+            vec![Event::MatchDeleted {
+                id: MatchId("2".to_owned())
+            }]
+        );
+        let mut replayed = both.clone();
+        for event in diff(&both, &first) {
+            event.apply_to(&mut replayed);
+        }
+        assert_eq!(replayed, first);
+    }
+}
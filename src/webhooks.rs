@@ -0,0 +1,554 @@
+//! This module describes the events delivered by the Toornament webhooks and helps to keep a
+//! consistent local state from them: the deliveries may be repeated and may come out of order,
+//! so an `EventLog` drops the duplicates and applies the events in the order of their
+//! timestamps, skipping those older than the last event applied to the same resource.
+//!
+//! The log remembers the deliveries for a retention period only (a day by default, see
+//! `EventLog::retention`), so it does not grow without bound: the deliveries older than the
+//! retention period before the newest one are dropped, as they can no longer be told apart
+//! from the repeated ones.
+//!
+//! A delivery is a JSON object like:
+//!
+//! ```json
+//! {
+//!     "id": "a1b2c3",
+//!     "timestamp": "2015-09-06T10:00:00+00:00",
+//!     "name": "participant.created",
+//!     "data": { "id": "5617c3acf3df95f2318b4567", "name": "Evil Geniuses" }
+//! }
+//! ```
+//!
+//! # Usage
+//!
+//! ```rust
+//! use toornament::TournamentSnapshot;
+//! use toornament::webhooks::{Delivery, EventLog};
+//!
+//! let body = r#"{
+//!     "id": "a1b2c3",
+//!     "timestamp": "2015-09-06T10:00:00+00:00",
+//!     "name": "participant.created",
+//!     "data": { "id": "1", "name": "Evil Geniuses" }
+//! }"#;
+//! let mut log = EventLog::new();
+//! let mut snapshot = TournamentSnapshot::default();
+//! let delivery: Delivery = serde_json::from_str(body).unwrap();
+//! log.push(delivery.clone());
+//! // A repeated delivery is ignored
+//! log.push(delivery);
+//! log.apply_to(&mut snapshot);
+//! assert_eq!(snapshot.participants.0.len(), 1);
+//! ```
+//!
+//! With the `webhook-http` feature enabled, the deliveries can be taken from the
+//! `http::Request`s of the web frameworks (axum, actix, warp, ...) with
+//! `Delivery::from_request`, which checks their signatures with a `Verifier` before parsing
+//! them: an unsigned request is never trusted.
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Duration, FixedOffset};
+
+use crate::matches::{Match, MatchId, MatchResult};
+use crate::participants::{Participant, ParticipantId};
+use crate::snapshot::TournamentSnapshot;
+use crate::stages::{Stage, StageNumber};
+use crate::tournaments::Tournament;
+
+/// A unique identifier of a delivered event.
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct EventId(pub String);
+
+/// An event which happened to a tournament. The events of the kinds this crate does not know
+/// are kept as `Event::Other`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "name", content = "data")]
+pub enum Event {
+    /// The tournament was updated
+    #[serde(rename = "tournament.updated")]
+    TournamentUpdated(Tournament),
+    /// A participant was created
+    #[serde(rename = "participant.created")]
+    ParticipantCreated(Participant),
+    /// A participant was updated
+    #[serde(rename = "participant.updated")]
+    ParticipantUpdated(Participant),
+    /// A participant was deleted
+    #[serde(rename = "participant.deleted")]
+    ParticipantDeleted {
+        /// Id of the deleted participant
+        id: ParticipantId,
+    },
+    /// A stage was created or updated
+    #[serde(rename = "stage.updated")]
+    StageUpdated(Stage),
+    /// A stage was deleted
+    #[serde(rename = "stage.deleted")]
+    StageDeleted {
+        /// Number of the deleted stage
+        number: StageNumber,
+    },
+    /// A match was created or updated
+    #[serde(rename = "match.updated")]
+    MatchUpdated(Match),
+    /// A match was deleted
+    #[serde(rename = "match.deleted")]
+    MatchDeleted {
+        /// Id of the deleted match
+        id: MatchId,
+    },
+    /// The result of a match was set
+    #[serde(rename = "match.result_updated")]
+    MatchResultUpdated {
+        /// Id of the match
+        match_id: MatchId,
+        /// The new result
+        result: MatchResult,
+    },
+    /// An event of another kind, or one whose data does not match its kind. It changes
+    /// nothing when applied.
+    #[serde(untagged)]
+    Other {
+        /// Name of the event, like "participant.created"
+        name: String,
+        /// Data of the event
+        #[serde(default)]
+        data: serde_json::Value,
+    },
+}
+
+/// The resource an event is about, to tell whether it is older than the last event applied to
+/// the same resource.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum Subject {
+    Tournament,
+    Participant(ParticipantId),
+    Stage(StageNumber),
+    Match(MatchId),
+}
+
+impl Event {
+    fn subject(&self) -> Option<Subject> {
+        match *self {
+            Event::TournamentUpdated(_) => Some(Subject::Tournament),
+            Event::ParticipantCreated(ref p) | Event::ParticipantUpdated(ref p) => {
+                p.id.clone().map(Subject::Participant)
+            }
+            Event::ParticipantDeleted { ref id } => Some(Subject::Participant(id.clone())),
+            Event::StageUpdated(ref s) => Some(Subject::Stage(s.number.clone())),
+            Event::StageDeleted { ref number } => Some(Subject::Stage(number.clone())),
+            Event::MatchUpdated(ref m) => Some(Subject::Match(m.id.clone())),
+            Event::MatchDeleted { ref id } => Some(Subject::Match(id.clone())),
+            Event::MatchResultUpdated { ref match_id, .. } => {
+                Some(Subject::Match(match_id.clone()))
+            }
+            Event::Other { .. } => None,
+        }
+    }
+
+    /// Applies the event to the snapshot.
+    pub fn apply_to(&self, snapshot: &mut TournamentSnapshot) {
+        match *self {
+            Event::TournamentUpdated(ref t) => snapshot.tournament = Some(t.clone()),
+            Event::ParticipantCreated(ref p) | Event::ParticipantUpdated(ref p) => {
+                snapshot.upsert_participant(p.clone())
+            }
+            Event::ParticipantDeleted { ref id } => {
+                snapshot.remove_participant(id);
+            }
+            Event::StageUpdated(ref s) => snapshot.upsert_stage(s.clone()),
+            Event::StageDeleted { ref number } => {
+                snapshot.remove_stage(number);
+            }
+            Event::MatchUpdated(ref m) => snapshot.upsert_match(m.clone()),
+            Event::MatchDeleted { ref id } => {
+                snapshot.remove_match(id);
+            }
+            Event::MatchResultUpdated {
+                ref match_id,
+                ref result,
+            } => {
+                snapshot.set_match_result(match_id, result.clone());
+            }
+            Event::Other { .. } => {}
+        }
+    }
+}
+
+/// A delivery of an event by a webhook.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Delivery {
+    /// Id of the event, the same for the repeated deliveries.
+    pub id: EventId,
+    /// When the event happened.
+    pub timestamp: DateTime<FixedOffset>,
+    /// The event.
+    #[serde(flatten)]
+    pub event: Event,
+}
+
+/// Collects the deliveries, drops the repeated ones and applies the events in the order of
+/// their timestamps.
+#[derive(Clone, Debug)]
+pub struct EventLog {
+    /// The ids of the deliveries of the retention period, with their timestamps.
+    seen: HashMap<EventId, DateTime<FixedOffset>>,
+    /// The timestamps of the last events applied to the resources in the retention period.
+    applied: BTreeMap<Subject, DateTime<FixedOffset>>,
+    newest: Option<DateTime<FixedOffset>>,
+    retention: Duration,
+    pending: Vec<Delivery>,
+}
+impl Default for EventLog {
+    fn default() -> EventLog {
+        EventLog {
+            seen: HashMap::new(),
+            applied: BTreeMap::new(),
+            newest: None,
+            retention: Duration::days(1),
+            pending: Vec::new(),
+        }
+    }
+}
+impl EventLog {
+    /// Creates new empty event log remembering the deliveries for a day.
+    pub fn new() -> EventLog {
+        EventLog::default()
+    }
+
+    /// Sets how long before the newest delivery the deliveries are remembered. It should be
+    /// longer than the service may take to repeat a delivery.
+    pub fn retention(mut self, retention: Duration) -> EventLog {
+        self.retention = retention;
+        self
+    }
+
+    /// Adds a delivery. Returns `false` if an event with the same id was already added, or if
+    /// the delivery is older than the retention period before the newest one.
+    pub fn push(&mut self, delivery: Delivery) -> bool {
+        let timestamp = delivery.timestamp;
+        if self
+            .newest
+            .is_some_and(|newest| timestamp < newest - self.retention)
+        {
+            log::debug!("Dropping the expired delivery {:?}", delivery.id);
+            return false;
+        }
+        if self.seen.contains_key(&delivery.id) {
+            return false;
+        }
+        self.seen.insert(delivery.id.clone(), timestamp);
+        if self.newest.is_none_or(|newest| newest < timestamp) {
+            self.newest = Some(timestamp);
+            let oldest = timestamp - self.retention;
+            self.seen.retain(|_, t| *t >= oldest);
+            self.applied.retain(|_, t| *t >= oldest);
+        }
+        let position = self
+            .pending
+            .iter()
+            .rposition(|d| d.timestamp <= delivery.timestamp)
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        self.pending.insert(position, delivery);
+        true
+    }
+
+    /// Returns the deliveries which are not applied yet, ordered by their timestamps.
+    pub fn pending(&self) -> &[Delivery] {
+        &self.pending
+    }
+
+    /// Applies the pending events to the snapshot in the order of their timestamps, skipping
+    /// those older than the last event applied to the same resource, so a late delivery does
+    /// not overwrite a newer state. The ids of the applied events are remembered, so their
+    /// repeated deliveries are still dropped.
+    pub fn apply_to(&mut self, snapshot: &mut TournamentSnapshot) {
+        for delivery in self.pending.drain(..) {
+            if let Some(subject) = delivery.event.subject() {
+                if self
+                    .applied
+                    .get(&subject)
+                    .is_some_and(|applied| *applied > delivery.timestamp)
+                {
+                    log::debug!("Skipping the late delivery {:?}", delivery.id);
+                    continue;
+                }
+                self.applied.insert(subject, delivery.timestamp);
+            }
+            delivery.event.apply_to(snapshot);
+        }
+    }
+}
+
+#[cfg(feature = "webhook-http")]
+impl Delivery {
+    /// Checks the signature of the request with the `verifier`, then parses the delivery from
+    /// its body. Fails with a `WebhookError` if the signature is missing or does not match.
+    pub fn from_request(
+        request: &http::Request<bytes::Bytes>,
+        verifier: &Verifier,
+    ) -> crate::Result<Delivery> {
+        verifier.verify(request)
+    }
+}
+
+/// The default header with the signature of a delivery, see `Verifier`.
+#[cfg(feature = "webhook-http")]
+pub const SIGNATURE_HEADER: &str = "x-toornament-signature";
+
+/// Checks the signatures of the deliveries: the hex-encoded HMAC-SHA256 of the body with the
+/// secret of the webhook, optionally prefixed with `sha256=`, in the `SIGNATURE_HEADER` header
+/// (or another one set with `Verifier::header`).
+///
+/// # Example
+///
+/// ```rust
+/// use toornament::webhooks::{Delivery, Verifier, SIGNATURE_HEADER};
+///
+/// fn handle(request: http::Request<bytes::Bytes>) -> toornament::Result<()> {
+///     let verifier = Verifier::new("WEBHOOK_SECRET");
+///     let delivery = Delivery::from_request(&request, &verifier)?;
+///     println!("{:?}", delivery.event);
+///     Ok(())
+/// }
+///
+/// let body = r#"{
+///     "id": "a1b2c3",
+///     "timestamp": "2015-09-06T10:00:00+00:00",
+///     "name": "participant.deleted",
+///     "data": { "id": "1" }
+/// }"#;
+/// let signature = Verifier::new("WEBHOOK_SECRET").sign(body.as_bytes());
+/// let request = http::Request::post("/webhook")
+///     .header(SIGNATURE_HEADER, signature)
+///     .body(bytes::Bytes::from(body))
+///     .unwrap();
+/// handle(request).unwrap();
+/// ```
+#[cfg(feature = "webhook-http")]
+#[derive(Clone, Debug)]
+pub struct Verifier {
+    secret: crate::secret::Secret,
+    header: String,
+}
+#[cfg(feature = "webhook-http")]
+impl Verifier {
+    /// Creates new verifier with the secret of the webhook.
+    pub fn new<S: Into<String>>(secret: S) -> Verifier {
+        Verifier {
+            secret: crate::secret::Secret::from(secret.into()),
+            header: SIGNATURE_HEADER.to_owned(),
+        }
+    }
+
+    /// Sets the name of the header with the signature.
+    pub fn header<S: Into<String>>(mut self, name: S) -> Verifier {
+        self.header = name.into();
+        self
+    }
+
+    fn mac(&self) -> hmac::Hmac<sha2::Sha256> {
+        use hmac::Mac;
+
+        hmac::Hmac::new_from_slice(self.secret.expose().as_bytes())
+            .expect("HMAC accepts keys of any length")
+    }
+
+    /// Returns the signature of the body, as it is expected in the header.
+    pub fn sign(&self, body: &[u8]) -> String {
+        use hmac::Mac;
+
+        let mut mac = self.mac();
+        mac.update(body);
+        mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Checks the signature of the request and parses the delivery from its body.
+    pub fn verify(&self, request: &http::Request<bytes::Bytes>) -> crate::Result<Delivery> {
+        use crate::error::{Error, WebhookError};
+        use hmac::Mac;
+
+        let header = request
+            .headers()
+            .get(self.header.as_str())
+            .and_then(|v| v.to_str().ok())
+            .ok_or(Error::Webhook(WebhookError::MissingSignature))?;
+        let hex = header.trim().trim_start_matches("sha256=");
+        let signature = (0..hex.len())
+            .step_by(2)
+            .map(|i| {
+                hex.get(i..i + 2)
+                    .and_then(|b| u8::from_str_radix(b, 16).ok())
+            })
+            .collect::<Option<Vec<u8>>>()
+            .ok_or(Error::Webhook(WebhookError::InvalidSignature))?;
+        let mut mac = self.mac();
+        mac.update(request.body());
+        mac.verify_slice(&signature)
+            .map_err(|_| Error::Webhook(WebhookError::InvalidSignature))?;
+        Ok(serde_json::from_slice(request.body())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delivery(id: &str, timestamp: &str, event: &str) -> Delivery {
+        serde_json::from_str(&format!(
+            r#"{{ "id": "{}", "timestamp": "{}", {} }}"#,
+            id, timestamp, event
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_event_log_orders_and_deduplicates() {
+        let created = delivery(
+            "1",
+            "2015-09-06T10:00:00+00:00",
+            r#""name": "participant.created", "data": { "id": "p", "name": "Old" }"#,
+        );
+        let updated = delivery(
+            "2",
+            "2015-09-06T11:00:00+00:00",
+            r#""name": "participant.updated", "data": { "id": "p", "name": "New" }"#,
+        );
+        let deleted = delivery(
+            "3",
+            "2015-09-06T12:00:00+00:00",
+            r#""name": "participant.deleted", "data": { "id": "p" }"#,
+        );
+
+        let mut log = EventLog::new();
+        let mut snapshot = TournamentSnapshot::default();
+        assert!(log.push(updated.clone()));
+        assert!(log.push(created.clone()));
+        assert!(!log.push(updated));
+        assert_eq!(log.pending()[0].id, EventId("1".to_owned()));
+        log.apply_to(&mut snapshot);
+        assert_eq!(snapshot.participants.0.len(), 1);
+        assert_eq!(snapshot.participants.0[0].name, "New");
+
+        assert!(!log.push(created));
+        assert!(log.push(deleted));
+        log.apply_to(&mut snapshot);
+        assert!(snapshot.participants.0.is_empty());
+    }
+
+    #[test]
+    fn test_event_log_skips_late_deliveries() {
+        let participant = |id: &str, timestamp: &str, name: &str| {
+            delivery(
+                id,
+                timestamp,
+                &format!(
+                    r#""name": "participant.updated", "data": {{ "id": "p", "name": "{}" }}"#,
+                    name
+                ),
+            )
+        };
+        let mut log = EventLog::new();
+        let mut snapshot = TournamentSnapshot::default();
+        log.push(participant("2", "2015-09-06T11:00:00+00:00", "New"));
+        log.apply_to(&mut snapshot);
+        // An older update of the same participant delivered late is not applied.
+        assert!(log.push(participant("1", "2015-09-06T12:00:00+02:00", "Old")));
+        log.apply_to(&mut snapshot);
+        assert_eq!(snapshot.participants.0[0].name, "New");
+
+        // The deliveries are remembered for the retention period only.
+        let mut log = EventLog::new().retention(Duration::hours(1));
+        assert!(log.push(participant("1", "2015-09-06T10:00:00+00:00", "A")));
+        assert!(log.push(participant("2", "2015-09-06T12:00:00+00:00", "B")));
+        assert_eq!(log.seen.len(), 1);
+        assert!(!log.push(participant("3", "2015-09-06T10:30:00+00:00", "C")));
+        assert!(log.push(participant("4", "2015-09-06T11:30:00+00:00", "D")));
+    }
+
+    #[test]
+    fn test_unknown_event() {
+        let unknown = delivery(
+            "1",
+            "2015-09-06T10:00:00+00:00",
+            r#""name": "registration.created", "data": { "id": "r" }"#,
+        );
+        assert_eq!(
+            unknown.event,
+            Event::Other {
+                name: "registration.created".to_owned(),
+                data: serde_json::json!({ "id": "r" }),
+            }
+        );
+        let json = serde_json::to_value(&unknown).unwrap();
+        assert_eq!(json["name"], "registration.created");
+        assert_eq!(json["data"]["id"], "r");
+
+        let mut log = EventLog::new();
+        let mut snapshot = TournamentSnapshot::default();
+        assert!(log.push(unknown));
+        log.apply_to(&mut snapshot);
+        assert_eq!(snapshot, TournamentSnapshot::default());
+    }
+
+    #[cfg(feature = "webhook-http")]
+    #[test]
+    fn test_verifier() {
+        use crate::error::{Error, WebhookError};
+
+        let body = r#"{
+            "id": "a1b2c3",
+            "timestamp": "2015-09-06T10:00:00+00:00",
+            "name": "participant.deleted",
+            "data": { "id": "1" }
+        }"#;
+        let verifier = Verifier::new("secret");
+        let request = |signature: Option<String>| {
+            let mut builder = http::Request::post("/webhook");
+            if let Some(signature) = signature {
+                builder = builder.header(SIGNATURE_HEADER, signature);
+            }
+            builder.body(bytes::Bytes::from(body)).unwrap()
+        };
+
+        let signature = verifier.sign(body.as_bytes());
+        let delivery = verifier.verify(&request(Some(signature.clone()))).unwrap();
+        assert_eq!(delivery.id, EventId("a1b2c3".to_owned()));
+        let prefixed = request(Some(format!("sha256={}", signature)));
+        assert!(verifier.verify(&prefixed).is_ok());
+        assert!(matches!(
+            verifier.verify(&request(None)),
+            Err(Error::Webhook(WebhookError::MissingSignature))
+        ));
+        assert!(matches!(
+            Verifier::new("other").verify(&prefixed),
+            Err(Error::Webhook(WebhookError::InvalidSignature))
+        ));
+        // An unsigned request is never parsed.
+        assert!(matches!(
+            Delivery::from_request(&request(None), &verifier),
+            Err(Error::Webhook(WebhookError::MissingSignature))
+        ));
+        let signed = request(Some(signature));
+        assert!(matches!(
+            Delivery::from_request(&signed, &verifier).map(|d| d.event),
+            Ok(Event::ParticipantDeleted { .. })
+        ));
+    }
+}
@@ -0,0 +1,37 @@
+//! The restoration of an archive bundle against the mocked service of the examples.
+#[path = "../examples/harness/mod.rs"]
+mod harness;
+
+use toornament::archive::{self, RestoreOptions};
+use toornament::*;
+
+#[test]
+fn test_restore_invites_only_when_asked() {
+    let service = harness::MockService::start();
+    let toornament = service.client().unwrap();
+    let bundle = archive::snapshot(&toornament, TournamentId("1".to_owned()), None).unwrap();
+    assert!(!bundle.permissions.0.is_empty());
+    let invitations = || {
+        service
+            .requests()
+            .into_iter()
+            .filter(|r| r.starts_with("POST ") && r.contains("/permissions"))
+            .count()
+    };
+
+    let restored =
+        archive::restore(&toornament, bundle.clone(), RestoreOptions::default()).unwrap();
+    assert_eq!(invitations(), 0);
+    assert_eq!(restored.permissions, bundle.permissions);
+    assert_eq!(restored.stages, bundle.snapshot.stages);
+    assert_eq!(restored.videos, bundle.videos);
+    assert!(service
+        .requests()
+        .iter()
+        .any(|r| r.starts_with("PUT ") && r.contains("/participants")));
+
+    let options = RestoreOptions::default().permissions(true);
+    let restored = archive::restore(&toornament, bundle.clone(), options).unwrap();
+    assert_eq!(invitations(), bundle.permissions.0.len());
+    assert!(restored.permissions.0.is_empty());
+}
@@ -0,0 +1,38 @@
+//! The cancellation of the pagers, fetching the pages of the mocked service of the examples.
+#[path = "../examples/harness/mod.rs"]
+mod harness;
+
+use toornament::*;
+
+#[test]
+fn test_cancelled_pager_returns_partial_results() {
+    let service = harness::MockService::start();
+    let toornament = service.client().unwrap();
+    let cancellation = Cancellation::new();
+    let pager = toornament
+        .tournament_participants_pager(
+            TournamentId("1".to_owned()),
+            TournamentParticipantsFilter::default(),
+        )
+        .cancellation(cancellation.clone());
+    let all = pager.fetch_all().unwrap();
+    assert!(!all.is_empty());
+
+    cancellation.cancel();
+    for result in [pager.fetch_all(), pager.fetch_all_parallel(4)] {
+        match result {
+            Err(Error::Cancelled(partial)) => {
+                assert_eq!(partial.into_results::<Vec<Participant>>(), Some(Vec::new()));
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+    assert_eq!(
+        service
+            .requests()
+            .iter()
+            .filter(|r| r.contains("/participants"))
+            .count(),
+        harness::PAGES
+    );
+}
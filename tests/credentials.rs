@@ -0,0 +1,42 @@
+//! The rotation of the credentials of the client, refused by the mocked service of the
+//! examples once revoked.
+#[path = "../examples/harness/mod.rs"]
+mod harness;
+
+fn token_requests(service: &harness::MockService) -> usize {
+    service
+        .requests()
+        .iter()
+        .filter(|r| r.as_str() == "POST /oauth/v2/token")
+        .count()
+}
+
+#[test]
+fn test_secondary_credentials_used_once_current_revoked() {
+    let service = harness::MockService::start();
+    let toornament = service.client().unwrap();
+    service.revoke("CLIENT_ID");
+    assert!(!toornament.refresh());
+
+    toornament
+        .secondary_credentials("NEW_CLIENT_ID", "NEW_CLIENT_SECRET")
+        .unwrap();
+    let before = token_requests(&service);
+    assert!(toornament.refresh());
+    // The current credentials are refused, then the secondary ones get the token.
+    assert_eq!(token_requests(&service), before + 2);
+    // The secondary credentials are the current ones from now on.
+    assert!(toornament.clone().refresh());
+    assert_eq!(token_requests(&service), before + 3);
+}
+
+#[test]
+fn test_rotation_to_revoked_credentials_keeps_current() {
+    let service = harness::MockService::start();
+    let toornament = service.client().unwrap();
+    service.revoke("REVOKED_ID");
+    assert!(toornament
+        .rotate_credentials("REVOKED_ID", "REVOKED_SECRET")
+        .is_err());
+    assert!(toornament.refresh());
+}
@@ -0,0 +1,64 @@
+//! The headers added to the requests of the client, received by the mocked service of the
+//! examples.
+#[path = "../examples/harness/mod.rs"]
+mod harness;
+
+#[test]
+fn test_headers_sent_with_token_requests() {
+    let service = harness::MockService::start();
+    let toornament = service
+        .client()
+        .unwrap()
+        .user_agent("tournament-bot/1.0")
+        .unwrap()
+        .header("X-Partner-Application", "tournament-bot")
+        .unwrap();
+    assert!(toornament.refresh());
+    toornament.disciplines(None).unwrap();
+
+    let agents = service.requests_with_header("User-Agent");
+    let partners = service.requests_with_header("X-Partner-Application");
+    // The constructor gets the first token before the headers are added.
+    assert_eq!(agents[0].0, "POST /oauth/v2/token");
+    assert_eq!(partners[0].1, None);
+    let sent = agents[1..]
+        .iter()
+        .zip(&partners[1..])
+        .map(|((request, agent), (_, partner))| {
+            (request.as_str(), agent.as_deref(), partner.as_deref())
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        sent,
+        vec![
+            (
+                "POST /oauth/v2/token",
+                Some("tournament-bot/1.0"),
+                Some("tournament-bot")
+            ),
+            (
+                "GET /v1/disciplines",
+                Some("tournament-bot/1.0"),
+                Some("tournament-bot")
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_invalid_header_value_not_echoed() {
+    let service = harness::MockService::start();
+    let error = service
+        .client()
+        .unwrap()
+        .header("X-Partner-Key", "secret\nvalue")
+        .unwrap_err();
+    let message = error.to_string();
+    assert!(message.contains("X-Partner-Key"));
+    assert!(!message.contains("secret"));
+    assert!(service
+        .client()
+        .unwrap()
+        .header("Authorization", "Bearer token")
+        .is_err());
+}
@@ -0,0 +1,16 @@
+//! The health checks against the mocked service of the examples.
+#[path = "../examples/harness/mod.rs"]
+mod harness;
+
+#[test]
+fn test_ping_fetches_a_single_discipline() {
+    let service = harness::MockService::start();
+    let toornament = service.client().unwrap();
+    toornament.ping().unwrap();
+    let requests = service
+        .requests()
+        .into_iter()
+        .filter(|r| r.starts_with("GET "))
+        .collect::<Vec<String>>();
+    assert_eq!(requests, ["GET /v1/disciplines?per_page=1"]);
+}
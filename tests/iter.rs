@@ -0,0 +1,50 @@
+//! The pages requested by the iterators, received by the mocked service of the examples.
+#[path = "../examples/harness/mod.rs"]
+mod harness;
+
+use toornament::*;
+
+#[test]
+fn test_paged_iterators_request_the_page() {
+    let service = harness::MockService::start();
+    let toornament = service.client().unwrap();
+    toornament
+        .tournaments_iter()
+        .all()
+        .page(2)
+        .per_page(5)
+        .collect::<Tournaments>()
+        .unwrap();
+    let tournament = || {
+        toornament
+            .tournaments_iter()
+            .with_id(TournamentId("1".to_owned()))
+    };
+    tournament().matches().page(3).collect::<Matches>().unwrap();
+    tournament()
+        .stages()
+        .with_number(StageNumber(1))
+        .matches()
+        .per_page(10)
+        .collect::<Matches>()
+        .unwrap();
+    tournament()
+        .participants()
+        .with_id(ParticipantId("2".to_owned()))
+        .matches()
+        .page(2)
+        .per_page(10)
+        .collect::<Matches>()
+        .unwrap();
+
+    let requests = service.requests();
+    assert_eq!(
+        &requests[requests.len() - 4..],
+        [
+            "GET /v1/tournaments?with_streams=0&page=2&per_page=5",
+            "GET /v1/tournaments/1/matches?with_games=0&page=3",
+            "GET /v1/tournaments/1/matches?stage_number=1&with_games=0&per_page=10",
+            "GET /v1/tournaments/1/matches?participant_id=2&with_games=0&page=2&per_page=10",
+        ]
+    );
+}
@@ -0,0 +1,81 @@
+//! The rescheduling of the matches against the mocked service of the examples.
+#[path = "../examples/harness/mod.rs"]
+mod harness;
+
+use chrono::DateTime;
+use toornament::*;
+
+#[test]
+fn test_shift_matches_reports_every_update() {
+    let service = harness::MockService::start();
+    // The update of the completed match answers no match.
+    service.replace("/v1/tournaments/1/matches/5617bb3af3df95f2318b4567", "null");
+    let toornament = service.client().unwrap();
+    let report = toornament
+        .shift_matches(
+            TournamentId("1".to_owned()),
+            |_| true,
+            chrono::Duration::hours(1),
+        )
+        .unwrap();
+    // The two matches of every page.
+    assert_eq!(report.len(), 2 * harness::PAGES);
+
+    for pair in report.chunks(2) {
+        let (failed, result) = &pair[0];
+        assert_eq!(failed.0, "5617bb3af3df95f2318b4567");
+        assert!(result.is_err());
+        let (shifted, result) = &pair[1];
+        assert_eq!(shifted.0, "5617bb3af3df95f2318b4568");
+        assert_eq!(
+            result.as_ref().unwrap().date,
+            DateTime::parse_from_rfc3339("2015-09-06T03:10:00-06:00").unwrap()
+        );
+    }
+
+    let updates = service
+        .requests()
+        .into_iter()
+        .filter(|r| r.starts_with("PATCH"))
+        .collect::<Vec<String>>();
+    assert_eq!(
+        updates,
+        [
+            "PATCH /v1/tournaments/1/matches/5617bb3af3df95f2318b4567",
+            "PATCH /v1/tournaments/1/matches/5617bb3af3df95f2318b4568",
+        ]
+        .repeat(harness::PAGES)
+    );
+}
+
+#[test]
+fn test_shift_matches_updates_the_date_of_the_selected_matches() {
+    let service = harness::MockService::start();
+    let toornament = service.client().unwrap();
+    let report = toornament
+        .shift_matches(
+            TournamentId("1".to_owned()),
+            |m| m.status == MatchStatus::Pending,
+            chrono::Duration::hours(1),
+        )
+        .unwrap();
+    assert_eq!(report.len(), harness::PAGES);
+    assert!(report
+        .iter()
+        .all(|(id, _)| id.0 == "5617bb3af3df95f2318b4568"));
+
+    // Only the date is sent, the opponents and the results are left as they are.
+    let updates = service
+        .requests_with_body()
+        .into_iter()
+        .filter(|(r, _)| r.starts_with("PATCH"))
+        .map(|(_, body)| serde_json::from_str::<serde_json::Value>(&body).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(updates.len(), harness::PAGES);
+    for update in updates {
+        assert_eq!(
+            update,
+            serde_json::json!({ "date": "2015-09-06T03:10:00-06:00" })
+        );
+    }
+}
@@ -0,0 +1,32 @@
+//! The connection metrics of the client, counted on the responses of the mocked service of the
+//! examples.
+#[path = "../examples/harness/mod.rs"]
+mod harness;
+
+use toornament::*;
+
+#[test]
+fn test_connection_reuse_metrics() {
+    let service = harness::MockService::start();
+    let toornament = service
+        .client()
+        .unwrap()
+        .connection_pool(ConnectionPool::new().max_idle(1))
+        .unwrap();
+    for _ in 0..3 {
+        toornament.disciplines(None).unwrap();
+    }
+    let metrics = toornament.metrics_snapshot();
+    assert_eq!(metrics.new_connection_requests, 1);
+    assert_eq!(metrics.reused_connection_requests, 2);
+
+    // A client without a connection pool opens a connection for every request.
+    let toornament = toornament
+        .connection_pool(ConnectionPool::new().max_idle(0))
+        .unwrap();
+    toornament.disciplines(None).unwrap();
+    toornament.disciplines(None).unwrap();
+    let metrics = toornament.metrics_snapshot();
+    assert_eq!(metrics.new_connection_requests, 3);
+    assert_eq!(metrics.reused_connection_requests, 2);
+}
@@ -0,0 +1,173 @@
+//! The organization workflow against the mocked service of the examples.
+#[path = "../examples/harness/mod.rs"]
+mod harness;
+
+use toornament::*;
+
+/// Starts the mocked service with the tournament "1" in the setup status.
+fn setup_tournament() -> harness::MockService {
+    let service = harness::MockService::start();
+    service.replace(
+        "/v1/tournaments/1",
+        &fixtures::TOURNAMENT.replace(r#""running""#, r#""setup""#),
+    );
+    service
+}
+
+fn id() -> TournamentId {
+    TournamentId("1".to_owned())
+}
+
+#[test]
+fn test_open_registrations_updates_the_settings() {
+    let service = setup_tournament();
+    let toornament = service.client().unwrap();
+    let settings = toornament.organizer().open_registrations(id()).unwrap();
+    assert_eq!(settings.registration_enabled, Some(true));
+    assert!(service
+        .requests()
+        .contains(&"PATCH /v1/tournaments/1/settings".to_owned()));
+}
+
+#[test]
+fn test_open_registrations_of_a_running_tournament() {
+    let service = harness::MockService::start();
+    let toornament = service.client().unwrap();
+    match toornament.organizer().open_registrations(id()) {
+        Err(Error::Organizer(OrganizerError::InvalidStatus(_, TournamentStatus::Running))) => {}
+        other => panic!("Unexpected outcome: {:?}", other),
+    }
+    assert!(service.requests().iter().all(|r| !r.starts_with("PATCH")));
+}
+
+#[test]
+fn test_close_and_seed_orders_the_participants() {
+    let service = setup_tournament();
+    let toornament = service.client().unwrap();
+    let participants = toornament
+        .organizer()
+        .close_and_seed(id(), |a, b| b.name.cmp(&a.name))
+        .unwrap();
+    let names = participants
+        .0
+        .iter()
+        .map(|p| p.name.as_str())
+        .collect::<Vec<&str>>();
+    // The participants of every page are re-created, none is dropped.
+    let mut expected = vec!["Fnatic"; harness::PAGES];
+    expected.extend(vec!["Evil Geniuses"; harness::PAGES]);
+    assert_eq!(names, expected);
+
+    let requests = service.requests();
+    let closed = requests
+        .iter()
+        .position(|r| r == "PATCH /v1/tournaments/1/settings")
+        .unwrap();
+    let seeded = requests
+        .iter()
+        .position(|r| r == "PUT /v1/tournaments/1/participants")
+        .unwrap();
+    assert!(closed < seeded);
+    let pages = requests
+        .iter()
+        .filter(|r| r.starts_with("GET /v1/tournaments/1/participants?"))
+        .count();
+    assert_eq!(pages, harness::PAGES);
+}
+
+#[test]
+fn test_close_and_seed_without_enough_participants() {
+    let service = setup_tournament();
+    service.replace("/v1/tournaments/1/participants", "[]");
+    let toornament = service.client().unwrap();
+    match toornament
+        .organizer()
+        .close_and_seed(id(), |a, b| a.name.cmp(&b.name))
+    {
+        Err(Error::Organizer(OrganizerError::NotEnoughParticipants(_, 0))) => {}
+        other => panic!("Unexpected outcome: {:?}", other),
+    }
+    assert!(service.requests().iter().all(|r| !r.starts_with("PATCH")));
+}
+
+#[test]
+fn test_start_stage_returns_its_matches() {
+    let service = harness::MockService::start();
+    let toornament = service.client().unwrap();
+    let matches = toornament
+        .organizer()
+        .start_stage(id(), StageNumber(1))
+        .unwrap();
+    assert_eq!(matches.0.len(), 2 * harness::PAGES);
+    assert!(matches.0.iter().all(|m| m.stage_number == 1));
+    // The tournament is running already.
+    assert!(!service
+        .requests()
+        .contains(&"PATCH /v1/tournaments/1".to_owned()));
+
+    match toornament.organizer().start_stage(id(), StageNumber(3)) {
+        Err(Error::Organizer(OrganizerError::NoSuchStage(_, StageNumber(3)))) => {}
+        other => panic!("Unexpected outcome: {:?}", other),
+    }
+}
+
+#[test]
+fn test_start_tournament_marks_it_running() {
+    let service = setup_tournament();
+    let toornament = service.client().unwrap();
+    let tournament = toornament.organizer().start_tournament(id()).unwrap();
+    assert_eq!(tournament.status, TournamentStatus::Running);
+    assert!(service
+        .requests()
+        .contains(&"PATCH /v1/tournaments/1".to_owned()));
+}
+
+#[test]
+fn test_start_tournament_counts_the_participants_of_every_page() {
+    let service = setup_tournament();
+    // A participant on each of the pages.
+    service.replace(
+        "/v1/tournaments/1/participants",
+        &format!("[{}]", fixtures::PARTICIPANT),
+    );
+    let toornament = service.client().unwrap();
+    let tournament = toornament.organizer().start_tournament(id()).unwrap();
+    assert_eq!(tournament.status, TournamentStatus::Running);
+}
+
+#[test]
+fn test_start_tournament_of_a_running_tournament() {
+    let service = harness::MockService::start();
+    let toornament = service.client().unwrap();
+    match toornament.organizer().start_tournament(id()) {
+        Err(Error::Organizer(OrganizerError::InvalidStatus(_, TournamentStatus::Running))) => {}
+        other => panic!("Unexpected outcome: {:?}", other),
+    }
+    assert!(service.requests().iter().all(|r| !r.starts_with("PATCH")));
+}
+
+#[test]
+fn test_start_tournament_without_stages() {
+    let service = setup_tournament();
+    service.replace("/v1/tournaments/1/stages", "[]");
+    let toornament = service.client().unwrap();
+    match toornament.organizer().start_tournament(id()) {
+        Err(Error::Organizer(OrganizerError::NoStages(_))) => {}
+        other => panic!("Unexpected outcome: {:?}", other),
+    }
+    assert!(service.requests().iter().all(|r| !r.starts_with("PATCH")));
+}
+
+#[test]
+fn test_complete_tournament_with_unfinished_matches() {
+    let service = harness::MockService::start();
+    let toornament = service.client().unwrap();
+    match toornament.organizer().complete_tournament(id()) {
+        // The pending match of every page.
+        Err(Error::Organizer(OrganizerError::UnfinishedMatches(_, matches))) => {
+            assert_eq!(matches.len(), harness::PAGES)
+        }
+        other => panic!("Unexpected outcome: {:?}", other),
+    }
+    assert!(service.requests().iter().all(|r| !r.starts_with("PATCH")));
+}
@@ -0,0 +1,39 @@
+//! The review of a plan against the mocked service of the examples.
+#[path = "../examples/harness/mod.rs"]
+mod harness;
+
+use toornament::planning::{Plan, PlannedMatch, PlannedStage};
+use toornament::*;
+
+#[test]
+fn test_plan_review_fetches_all_the_matches() {
+    let service = harness::MockService::start();
+    let toornament = service.client().unwrap();
+    let participant =
+        |id: &str, name: &str| Participant::create(name).id(ParticipantId(id.to_owned()));
+    let plan = Plan::new(TournamentId("1".to_owned()))
+        .stage(PlannedStage::new(
+            StageNumber(1),
+            "Groups",
+            StageType::Group,
+            4,
+        ))
+        .add_match(
+            PlannedMatch::new(1, 1, 2, 2)
+                .opponent(1, participant("378426939508809728", "Evil Geniuses"))
+                .opponent(2, participant("378426939508809729", "Fnatic")),
+        );
+    let changes = plan.review(&toornament).unwrap();
+    assert_eq!(changes.placements.len(), 1);
+    assert_eq!(
+        changes.placements[0].0,
+        MatchId("5617bb3af3df95f2318b4568".to_owned())
+    );
+
+    let pages = service
+        .requests()
+        .into_iter()
+        .filter(|r| r.starts_with("GET /v1/tournaments/1/matches?"))
+        .count();
+    assert_eq!(pages, harness::PAGES);
+}
@@ -0,0 +1,41 @@
+//! The progress of an archive snapshot against the mocked service of the examples.
+#[path = "../examples/harness/mod.rs"]
+mod harness;
+
+use std::sync::Mutex;
+
+use toornament::*;
+
+#[test]
+fn test_snapshot_reports_every_page() {
+    let service = harness::MockService::start();
+    let toornament = service.client().unwrap();
+    let updates = Mutex::new(Vec::new());
+    let progress = |update: &ProgressUpdate| updates.lock().unwrap().push(update.clone());
+    archive::snapshot(&toornament, TournamentId("1".to_owned()), Some(&progress)).unwrap();
+
+    let updates = updates.into_inner().unwrap();
+    let pages = |resource: &str| {
+        updates
+            .iter()
+            .filter_map(|u| u.endpoint.as_deref())
+            .filter(|e| e.starts_with("page ") && e.ends_with(resource))
+            .count()
+    };
+    assert_eq!(pages("the participants of tournament 1"), harness::PAGES);
+    assert_eq!(pages("the matches of tournament 1"), harness::PAGES);
+    // The tournament, the stages, the permissions and the videos are one request each.
+    let requests = 2 * harness::PAGES + 4;
+    assert_eq!(updates.len(), requests + 1);
+    assert!(updates
+        .windows(2)
+        .all(|w| w[1].done > w[0].done && w[1].total >= w[0].total));
+    assert_eq!(
+        updates.last(),
+        Some(&ProgressUpdate {
+            done: requests,
+            total: Some(requests),
+            endpoint: None,
+        })
+    );
+}